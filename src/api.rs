@@ -0,0 +1,1439 @@
+//! Library API for embedding Ora in other Rust programs
+//!
+//! The `ora` binary is a thin wrapper over [`Ora`]: every operation here returns
+//! structured results instead of printing them, so callers decide how to report
+//! progress and failures.
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::config::global::{InstallMode, TrustLevel};
+use crate::config::installed::{InstalledPackage, ARCHIVE_CHECKSUM_KEY, FILE_CHECKSUM_PREFIX};
+use crate::config::local_metadata::LocalMetadata;
+use crate::config::lockfile::LockedPackage;
+use crate::config::manifest::ManifestEntry;
+use crate::config::repo::{ChecksumAlgorithm, RepoConfig};
+use crate::config::{GlobalConfig, SecurityConfig};
+use crate::error::OraError;
+use crate::installer::deployer::{validate_extra_download_dst, CURRENT_LINK_NAME};
+use crate::installer::extractor::ExtractionManifest;
+use crate::installer::post_install::check_script_policy;
+use crate::installer::shell_env::{self, Shell};
+use crate::installer::{run_post_install, Deployer, Downloader, Extractor, Verifier};
+use crate::providers::{
+    create_provider, latest_version, list_versions_cached, resolve_version, Version,
+    VersionProvider,
+};
+use crate::registry::RegistryManager;
+use crate::security::checksum::compute_checksum;
+use crate::security::{verify_checksum, AuditLogger, SecurityWarningManager};
+use crate::storage::cache::Cache;
+use crate::storage::database::{load_global_config, load_installed_db, save_installed_db};
+use crate::storage::paths::Paths;
+use crate::utils::http::{is_offline_mode, HttpClient};
+use crate::utils::platform::{default_arch_mapping, default_os_mapping, Platform};
+use crate::utils::templating::resolve_template_safe;
+
+/// `InstalledPackage::metadata` key holding the target OS of a foreign-platform install
+pub const TARGET_OS_KEY: &str = "target_os";
+/// `InstalledPackage::metadata` key holding the target arch of a foreign-platform install
+pub const TARGET_ARCH_KEY: &str = "target_arch";
+/// `InstalledPackage::metadata` key holding the package's environment snippet
+pub const ENV_SNIPPET_KEY: &str = "env_snippet";
+
+/// What to install and how
+#[derive(Debug, Clone, Default)]
+pub struct InstallSpec {
+    /// Package name, optionally suffixed with `@registry`
+    pub package: String,
+    /// Exact version to install (latest stable release when `None`)
+    pub version: Option<String>,
+    /// Path or HTTPS URL of a `.repo` file to use instead of the registries
+    pub repo: Option<String>,
+    /// Install system-wide or in the user's home; `install.default_mode` from
+    /// the global config when `None`
+    pub mode: Option<InstallMode>,
+    /// Proceed even if the package fails security checks
+    pub allow_insecure: bool,
+    /// Accept alpha/beta/rc versions
+    pub allow_prerelease: bool,
+    /// Path to a local archive to install from
+    pub local: Option<String>,
+    /// Metadata file describing a local archive (required with `local`)
+    pub metadata: Option<String>,
+    /// Target OS instead of the detected one (e.g. `linux`, `macos`)
+    pub os: Option<String>,
+    /// Target architecture instead of the detected one (e.g. `x86_64`, `aarch64`)
+    pub arch: Option<String>,
+    /// Download and verify the archive, then stop before extracting or deploying it
+    pub verify_only: bool,
+    /// Proceed without checksum verification, keeping every other security check
+    pub skip_checksum: bool,
+    /// Install exactly this lockfile entry: its version, URL and checksum are used
+    /// without asking the provider, and the checksum is enforced even with
+    /// `allow_insecure` or `skip_checksum`
+    pub locked: Option<LockedPackage>,
+    /// Installed entry this install replaces (an update uninstalls it first);
+    /// reinstalling its version must yield the archive checksum it recorded
+    pub replaces: Option<InstalledPackage>,
+}
+
+impl InstallSpec {
+    /// Creates a spec installing the latest version of `package` in the
+    /// configured default mode
+    #[allow(dead_code)]
+    pub fn new(package: impl Into<String>) -> Self {
+        Self {
+            package: package.into(),
+            ..Default::default()
+        }
+    }
+}
+
+/// Result of [`Ora::install`]
+#[derive(Debug, Clone)]
+pub enum InstallOutcome {
+    /// The package was installed
+    Installed {
+        package: InstalledPackage,
+        /// Downloaded archive left in the cache (when downloads are kept)
+        kept_archive: Option<PathBuf>,
+        /// Problems worth telling the user about that didn't stop the install
+        warnings: Vec<String>,
+    },
+    /// A package with the same name was already installed; nothing was changed
+    AlreadyInstalled(InstalledPackage),
+    /// The archive was downloaded and verified but not installed (`verify_only`)
+    Verified(VerificationReport),
+}
+
+/// Checks performed on an archive by a verify-only install
+#[derive(Debug, Clone)]
+pub struct VerificationReport {
+    pub package: String,
+    pub version: String,
+    /// URL the archive was downloaded from
+    pub url: String,
+    /// Verified archive in the download cache
+    pub archive: PathBuf,
+    /// Archive digest, computed with the package's checksum algorithm (SHA-256 if none)
+    pub checksum: String,
+    pub checksum_algorithm: ChecksumAlgorithm,
+    /// Whether the digest matched the checksum published for the release
+    pub checksum_verified: bool,
+    /// Whether a GPG signature (of the archive or its checksum file) was verified
+    pub signature_verified: bool,
+}
+
+/// Entry point for driving Ora as a library
+///
+/// Paths and settings are resolved the same way as for the CLI, including the
+/// `ORA_CONFIG_DIR`, `ORA_DATA_DIR` and `ORA_CACHE_DIR` overrides.
+#[derive(Debug, Clone, Default)]
+pub struct Ora;
+
+impl Ora {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Installs a package from a registry, a `.repo` file or a local archive
+    ///
+    /// Failed installs are recorded in the audit log as well.
+    pub async fn install(&self, spec: &InstallSpec) -> Result<InstallOutcome> {
+        let result = if spec.local.is_some() {
+            if spec.verify_only {
+                anyhow::bail!(
+                    "Verify-only mode checks downloads; it can't be used with a local archive"
+                );
+            }
+            self.install_local(spec).await
+        } else {
+            self.install_remote(spec).await
+        };
+
+        if result.is_err() && !spec.verify_only {
+            let version = spec.version.as_deref().unwrap_or("latest");
+            if let Err(e) = AuditLogger::new()?
+                .log_install(
+                    &spec.package,
+                    version,
+                    &requested_source(spec),
+                    spec.allow_insecure,
+                    false,
+                )
+                .await
+            {
+                log::warn!("Failed to record failed install in audit log: {}", e);
+            }
+        }
+        result
+    }
+
+    async fn install_remote(&self, spec: &InstallSpec) -> Result<InstallOutcome> {
+        log::info!("Installing package: {}", spec.package);
+        let started = Instant::now();
+
+        // Parse package name and optional registry (package@registry syntax)
+        let (package_name, registry_name) = if spec.package.contains('@') {
+            let parts: Vec<&str> = spec.package.splitn(2, '@').collect();
+            (parts[0].to_string(), Some(parts[1].to_string()))
+        } else {
+            (spec.package.clone(), None)
+        };
+
+        // Check if already installed (vetting an installed package is fine)
+        let mut db = load_installed_db().await?;
+        if let Some(existing) = db.packages.get(&package_name) {
+            if !spec.verify_only {
+                log::warn!("Package '{}' is already installed", package_name);
+                return Ok(InstallOutcome::AlreadyInstalled(existing.clone()));
+            }
+        }
+
+        // Load repo config and track registry source
+        let (mut repo_config, registry_source) = if let Some(repo) = &spec.repo {
+            load_repo_argument(repo).await?
+        } else if let Some(registry) = registry_name {
+            // Find in specific registry
+            let (config, reg_name) =
+                RegistryManager::find_package_in_specific_registry(&package_name, &registry)
+                    .await?;
+            (config, format!("registry:{}", reg_name))
+        } else {
+            // Find in any registry
+            let (config, reg_name) = RegistryManager::find_package(&package_name).await?;
+            (config, format!("registry:{}", reg_name))
+        };
+
+        // Refuse .repo files written for a newer Ora than this one
+        repo_config.check_ora_version(env!("CARGO_PKG_VERSION"))?;
+
+        // Check security warnings BEFORE starting installation
+        // SECURITY: Do NOT silently ignore config load failures - they could be permission issues
+        // that prevent security settings from being applied
+        let global_config = match load_global_config().await {
+            Ok(config) => config,
+            Err(e) => {
+                log::warn!(
+                    "Could not load configuration file ({}); using default configuration, \
+                     which may affect security settings",
+                    e
+                );
+                GlobalConfig::default()
+            }
+        };
+        let install_mode = install_mode(spec, &global_config);
+        let security_config = SecurityConfig::load().unwrap_or_default();
+        SecurityWarningManager::check_and_warn(&repo_config, spec.allow_insecure, &global_config)?;
+
+        // Detect platform, honoring explicit --os/--arch overrides
+        let platform = Platform::with_overrides(spec.os.as_deref(), spec.arch.as_deref());
+        let foreign_platform = !platform.is_host();
+        if foreign_platform {
+            log::info!(
+                "Installing for {}/{}: binaries won't be linked or executed",
+                platform.os,
+                platform.arch
+            );
+        }
+
+        // Pick glibc or musl builds on Linux
+        if let Some(libc) = platform.libc() {
+            repo_config.apply_libc(libc);
+        }
+
+        // Refuse a blocked post-install script or environment exports before
+        // downloading anything
+        let from_public_registry = registry_source
+            .strip_prefix("registry:")
+            .and_then(|name| global_config.registries.iter().find(|r| r.name == name))
+            .is_some_and(|registry| matches!(registry.trust_level, TrustLevel::Public));
+        if !foreign_platform && !spec.verify_only {
+            let scripts_config = &security_config.scripts;
+            if let Some(script) = &repo_config.install.post_install {
+                check_script_policy(
+                    &package_name,
+                    script,
+                    from_public_registry,
+                    spec.allow_insecure,
+                    scripts_config,
+                )?;
+            }
+            shell_env::check_exports_policy(
+                &package_name,
+                &repo_config.install.env_exports,
+                from_public_registry,
+                spec.allow_insecure,
+                scripts_config,
+            )?;
+        }
+
+        let (os_candidates, arch_candidates) = platform_candidates(&repo_config, &platform);
+
+        log::info!(
+            "Platform: {} ({}), Arch: {} ({})",
+            platform.os,
+            os_candidates.join("|"),
+            platform.arch,
+            arch_candidates.join("|")
+        );
+
+        // Create provider and get version
+        let provider = create_provider(&repo_config)?;
+
+        let (version, download_url, mapped_os, mapped_arch) = if let Some(locked) = &spec.locked {
+            // The lockfile pins the artifact; the provider isn't asked for anything
+            if locked.os != platform.os || locked.arch != platform.arch {
+                anyhow::bail!(
+                    "'{}' was locked for {}/{}, not {}/{}; run 'ora lock' on this platform",
+                    package_name,
+                    locked.os,
+                    locked.arch,
+                    platform.os,
+                    platform.arch
+                );
+            }
+            (
+                locked.version.clone(),
+                locked.url.clone(),
+                os_candidates
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| platform.os.clone()),
+                arch_candidates
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| platform.arch.clone()),
+            )
+        } else {
+            // Prereleases need an explicit opt-in (flag, registry or global config)
+            let allow_prerelease = spec.allow_prerelease
+                || global_config.allow_prerelease_for(registry_source.strip_prefix("registry:"));
+            let selected = resolve_version(
+                provider.as_ref(),
+                &package_name,
+                spec.version.as_deref(),
+                allow_prerelease,
+            )
+            .await?;
+
+            // Get download URL, trying each platform name candidate
+            let (download_url, mapped_os, mapped_arch) = resolve_download_url(
+                provider.as_ref(),
+                &repo_config,
+                &platform,
+                &selected,
+                &os_candidates,
+                &arch_candidates,
+            )
+            .await?;
+            (selected.tag, download_url, mapped_os, mapped_arch)
+        };
+
+        log::debug!("Installing version: {}", version);
+
+        // Archive layout of this version, before anything is extracted or deployed
+        if let Some(version_req) = repo_config.install.apply_version_override(&version)? {
+            log::info!(
+                "Using install.overrides entry '{}' for version {}",
+                version_req,
+                version
+            );
+        }
+
+        log::debug!("Download URL: {}", download_url);
+
+        // Download
+        let filename = download_filename(&download_url)?;
+
+        // A known checksum keys the download cache, so identical archives are
+        // fetched once; otherwise archives are cached by file name
+        let verifier = Verifier::new()?;
+        let expected_checksum = if let Some(locked) = &spec.locked {
+            Some(locked.expected_checksum()?)
+        } else if spec.allow_insecure || spec.skip_checksum {
+            None
+        } else {
+            verifier
+                .expected_checksum(
+                    &download_url,
+                    filename,
+                    &repo_config,
+                    provider.as_ref(),
+                    &version,
+                    &mapped_os,
+                    &mapped_arch,
+                )
+                .await?
+        };
+        let download_path = match &expected_checksum {
+            Some(expected) => Cache::checksum_download_path(&expected.hash, filename)?,
+            None => Cache::download_path(filename)?,
+        };
+
+        let cache_hit = match &expected_checksum {
+            Some(expected) if download_path.exists() => {
+                verify_checksum(&download_path, &expected.hash, &expected.algorithm)
+                    .await
+                    .is_ok()
+            }
+            _ => false,
+        };
+
+        if cache_hit {
+            log::info!("Using cached archive {}", download_path.display());
+            if let Err(e) = Cache::touch(&download_path) {
+                log::debug!("Failed to update cached archive mtime: {}", e);
+            }
+        } else if is_offline_mode() && download_path.exists() {
+            log::info!(
+                "Offline mode: using cached archive {}",
+                download_path.display()
+            );
+        } else {
+            let downloader = Downloader::new()?;
+            downloader.download(&download_url, &download_path).await?;
+            enforce_cache_budget(&download_path);
+        }
+
+        // Fail early if the server returned something other than the expected archive
+        if let Err(e) = Extractor::check_archive_signature(&download_path) {
+            if !Cache::keep_downloads() {
+                let _ = std::fs::remove_file(&download_path);
+            }
+            return Err(e);
+        }
+        let download_bytes = std::fs::metadata(&download_path).ok().map(|m| m.len());
+
+        // Verified here as well, since `verify` skips checksums when insecure
+        if let (Some(_), Some(expected)) = (&spec.locked, &expected_checksum) {
+            verify_checksum(&download_path, &expected.hash, &expected.algorithm)
+                .await
+                .context(format!(
+                    "{} doesn't match the checksum in the lockfile",
+                    filename
+                ))?;
+        }
+
+        // Verify (`verify` checks the checksum unless insecure or skipped)
+        let verification = verifier
+            .verify(
+                &download_path,
+                &repo_config,
+                expected_checksum.as_ref(),
+                &version,
+                &mapped_os,
+                &mapped_arch,
+                spec.allow_insecure,
+                spec.skip_checksum,
+            )
+            .await?;
+        let checksum_verified =
+            verification.checksum || (spec.locked.is_some() && expected_checksum.is_some());
+
+        // A version installed before must come with the same archive; anything
+        // else means the release was re-tagged or tampered with
+        let pinned = spec
+            .replaces
+            .as_ref()
+            .or(db.packages.get(&package_name))
+            .filter(|previous| previous.version == version)
+            .and_then(InstalledPackage::archive_checksum)
+            .map(str::to_string);
+        let mut warnings = Vec::new();
+        if let Some(pinned) = pinned {
+            warnings.extend(
+                check_pinned_checksum(&package_name, &version, &download_path, &pinned).await,
+            );
+        }
+
+        // Auxiliary files go through the same download and checksum pipeline
+        let extra_files =
+            fetch_extra_downloads(&repo_config, &version, &mapped_os, &mapped_arch, spec).await?;
+
+        if spec.verify_only {
+            let checksum_algorithm = expected_checksum
+                .as_ref()
+                .map(|expected| expected.algorithm.clone())
+                .unwrap_or(ChecksumAlgorithm::Sha256);
+            let checksum = compute_checksum(&download_path, &checksum_algorithm).await?;
+
+            return Ok(InstallOutcome::Verified(VerificationReport {
+                package: package_name,
+                version,
+                url: download_url,
+                archive: download_path,
+                checksum,
+                checksum_algorithm,
+                checksum_verified,
+                signature_verified: verification.signature,
+            }));
+        }
+
+        // Extract
+        let extract_dir = Cache::download_path(&format!("{}_extract", spec.package))?;
+        let mut manifest = Extractor::extract_only(
+            &download_path,
+            &extract_dir,
+            &repo_config.install.extract_only,
+        )?;
+        if repo_config.install.strip_single_dir {
+            match Extractor::unwrap_single_dir(&extract_dir, &mut manifest)? {
+                Some(dir) => log::debug!("Installing the contents of {}/", dir),
+                None => log::debug!("No single top-level directory to strip"),
+            }
+        }
+        for (file, dst) in &extra_files {
+            Deployer::stage_extra_download(&extract_dir, file, dst)?;
+        }
+        // Hashed after staging, so a member replaced by an extra download is
+        // recorded as installed
+        manifest.hash_files(&extract_dir)?;
+        log::info!(
+            "Extracted {} files ({} bytes)",
+            manifest.files.len(),
+            manifest.total_bytes()
+        );
+
+        // Deploy
+        let deployment = Deployer::deploy(
+            &extract_dir,
+            &repo_config.install,
+            install_mode.clone(),
+            &global_config.install,
+            &security_config.extraction,
+            &spec.package,
+            &version,
+            !foreign_platform,
+        )?;
+        warnings.extend(deployment.warnings);
+
+        // Run post-install script if configured (it can't run foreign binaries)
+        if foreign_platform && repo_config.install.post_install.is_some() {
+            log::warn!(
+                "Skipping post-install script for {}/{} install",
+                platform.os,
+                platform.arch
+            );
+        } else if let Some(post_install_script) = &repo_config.install.post_install {
+            let install_dir = PathBuf::from(&deployment.install_dir);
+            run_post_install(
+                &package_name,
+                post_install_script,
+                &install_dir,
+                &version,
+                &repo_config.install.env,
+                from_public_registry,
+                spec.allow_insecure,
+            )
+            .await?;
+        }
+
+        // Record the target platform of foreign installs
+        let mut metadata = std::collections::HashMap::new();
+        if foreign_platform {
+            metadata.insert(TARGET_OS_KEY.to_string(), platform.os.clone());
+            metadata.insert(TARGET_ARCH_KEY.to_string(), platform.arch.clone());
+        } else {
+            // PATH and environment additions, for the user's shell to source
+            let base_dir = deployment
+                .current_path
+                .clone()
+                .unwrap_or_else(|| deployment.install_dir.clone());
+            if let Some(snippet) = shell_env::write_snippet(
+                &package_name,
+                Path::new(&base_dir),
+                &repo_config.install,
+                Shell::detect(),
+            )? {
+                metadata.insert(ENV_SNIPPET_KEY.to_string(), snippet.display().to_string());
+            }
+        }
+
+        // Update installed database
+        let installed_package = InstalledPackage {
+            schema_version: crate::config::migrations::CURRENT_SCHEMA_VERSION.to_string(),
+            name: package_name.clone(),
+            version: version.clone(),
+            installed_at: Utc::now(),
+            install_mode: format!("{:?}", install_mode).to_lowercase(),
+            install_dir: deployment.install_dir.clone(),
+            files: deployment.files,
+            symlinks: deployment.symlinks,
+            current_path: deployment.current_path,
+            registry_source: registry_source.clone(),
+            checksums: {
+                let mut checksums = file_checksums(&manifest);
+                if let Some(expected) = expected_checksum.as_ref().filter(|_| checksum_verified) {
+                    checksums.insert(
+                        ARCHIVE_CHECKSUM_KEY.to_string(),
+                        format!("{}:{}", expected.algorithm.as_str(), expected.hash),
+                    );
+                }
+                checksums
+            },
+            allow_insecure: spec.allow_insecure,
+            download_bytes,
+            duration_ms: Some(started.elapsed().as_millis() as u64),
+            metadata,
+        };
+
+        db.packages
+            .insert(package_name.clone(), installed_package.clone());
+        save_installed_db(&db).await?;
+
+        // Audit log
+        AuditLogger::new()?
+            .log_install(
+                &package_name,
+                &version,
+                &registry_source,
+                spec.allow_insecure,
+                true,
+            )
+            .await?;
+
+        Ok(InstallOutcome::Installed {
+            package: installed_package,
+            kept_archive: Cache::keep_downloads().then_some(download_path),
+            warnings,
+        })
+    }
+
+    async fn install_local(&self, spec: &InstallSpec) -> Result<InstallOutcome> {
+        let started = Instant::now();
+        let archive_path = spec
+            .local
+            .as_ref()
+            .context("Local archive path is required but was not provided")?;
+
+        // Require metadata file
+        let metadata_path = spec
+            .metadata
+            .as_ref()
+            .context("--metadata is required for local installations")?;
+
+        log::debug!("Installing from local archive: {}", archive_path);
+
+        // Load and validate metadata
+        let metadata_content = tokio::fs::read_to_string(metadata_path)
+            .await
+            .context("Failed to read metadata file")?;
+        let metadata: LocalMetadata =
+            toml::from_str(&metadata_content).context("Failed to parse metadata file")?;
+        metadata.validate()?;
+
+        let global_config = load_global_config().await.unwrap_or_else(|e| {
+            log::warn!("Failed to load global configuration: {}", e);
+            GlobalConfig::default()
+        });
+        let install_mode = install_mode(spec, &global_config);
+        let security_config = SecurityConfig::load().unwrap_or_default();
+
+        // Check if already installed
+        let mut db = load_installed_db().await?;
+        if let Some(existing) = db.packages.get(&metadata.name) {
+            log::warn!("Package '{}' is already installed", metadata.name);
+            return Ok(InstallOutcome::AlreadyInstalled(existing.clone()));
+        }
+
+        // Copy archive to cache and extract
+        let archive_path = Path::new(archive_path);
+        if !archive_path.exists() {
+            anyhow::bail!("Archive file not found: {}", archive_path.display());
+        }
+
+        let cache_archive_path = Cache::download_path(&format!(
+            "{}-{}-local.tar.gz",
+            metadata.name, metadata.version
+        ))?;
+        tokio::fs::copy(archive_path, &cache_archive_path)
+            .await
+            .context("Failed to copy archive to cache")?;
+
+        // Extract archive
+        let extract_dir = Cache::download_path(&format!("{}_extract", metadata.name))?;
+        let mut manifest = Extractor::extract(&cache_archive_path, &extract_dir)?;
+        manifest.hash_files(&extract_dir)?;
+
+        // Deploy package
+        let install_config = crate::config::repo::InstallConfig {
+            mode: None, // Mode is passed separately to Deployer::deploy
+            binaries: metadata.binaries.clone(),
+            extract_only: vec![],
+            files: vec![],
+            strip_single_dir: false,
+            extra_downloads: vec![],
+            post_install: None,
+            env: Default::default(),
+            path_additions: vec![],
+            env_exports: Default::default(),
+            overrides: vec![],
+        };
+
+        let deployment = Deployer::deploy(
+            &extract_dir,
+            &install_config,
+            install_mode.clone(),
+            &global_config.install,
+            &security_config.extraction,
+            &metadata.name,
+            &metadata.version,
+            true,
+        )?;
+
+        // Update installed database
+        let installed_package = InstalledPackage {
+            schema_version: crate::config::migrations::CURRENT_SCHEMA_VERSION.to_string(),
+            name: metadata.name.clone(),
+            version: metadata.version.clone(),
+            installed_at: Utc::now(),
+            install_mode: format!("{:?}", install_mode).to_lowercase(),
+            install_dir: deployment.install_dir.clone(),
+            files: deployment.files,
+            symlinks: deployment.symlinks,
+            current_path: deployment.current_path,
+            registry_source: format!("local:{}", archive_path.display()),
+            checksums: file_checksums(&manifest),
+            allow_insecure: spec.allow_insecure,
+            download_bytes: None,
+            duration_ms: Some(started.elapsed().as_millis() as u64),
+            metadata: Default::default(),
+        };
+
+        db.packages
+            .insert(metadata.name.clone(), installed_package.clone());
+        save_installed_db(&db).await?;
+
+        // Audit log
+        AuditLogger::new()?
+            .log_install(&metadata.name, &metadata.version, "local", false, true)
+            .await?;
+
+        Ok(InstallOutcome::Installed {
+            package: installed_package,
+            kept_archive: None,
+            warnings: deployment.warnings,
+        })
+    }
+
+    /// Looks up an installed package, optionally requiring a specific version
+    pub async fn installed(&self, name: &str, version: Option<&str>) -> Result<InstalledPackage> {
+        let db = load_installed_db().await?;
+
+        let installed = db
+            .packages
+            .get(name)
+            .ok_or_else(|| OraError::PackageNotInstalled(name.to_string()))
+            .context(format!("Package '{}' is not installed", name))?
+            .clone();
+
+        if let Some(ver) = version {
+            if installed.version != ver {
+                anyhow::bail!(
+                    "Package '{}' version {} is not installed (installed: {})",
+                    name,
+                    ver,
+                    installed.version
+                );
+            }
+        }
+
+        Ok(installed)
+    }
+
+    /// Removes an installed package and returns the record that was removed
+    pub async fn uninstall(&self, name: &str, version: Option<&str>) -> Result<InstalledPackage> {
+        log::info!("Uninstalling package: {}", name);
+
+        let installed = self.installed(name, version).await?;
+
+        // Audit failures too, so a batch uninstall leaves a record per package
+        if let Err(e) = Self::remove_installed(name, &installed).await {
+            if let Ok(logger) = AuditLogger::new() {
+                let _ = logger.log_uninstall(name, &installed.version, false).await;
+            }
+            return Err(e);
+        }
+
+        AuditLogger::new()?
+            .log_uninstall(name, &installed.version, true)
+            .await?;
+
+        Ok(installed)
+    }
+
+    /// Removes an installed package's links, files and database entry
+    async fn remove_installed(name: &str, installed: &InstalledPackage) -> Result<()> {
+        log::debug!("Removing files...");
+
+        // The database is only trusted as far as it names paths an install
+        // creates: files under `<packages>/<name>/<version>`, and links in the
+        // bin directory or into the package
+        let install_dir = Path::new(&installed.install_dir);
+        let package_dir = install_dir
+            .parent()
+            .filter(|dir| dir.file_name() == Some(std::ffi::OsStr::new(name)))
+            .filter(|_| install_dir.file_name() == Some(std::ffi::OsStr::new(&installed.version)));
+        let Some(package_dir) = package_dir else {
+            return Err(OraError::SecurityPolicyViolation(format!(
+                "refusing to uninstall '{}': {} is not a '{}/{}' install directory",
+                name, installed.install_dir, name, installed.version
+            ))
+            .into());
+        };
+        let mode = if installed.install_mode == InstallMode::System.as_str() {
+            InstallMode::System
+        } else {
+            InstallMode::Userland
+        };
+        let settings = load_global_config()
+            .await
+            .map(|config| config.install)
+            .unwrap_or_default();
+        let bin_dir = Paths::bin_dir(mode, &settings)?;
+
+        // Remove symlinks first
+        for symlink in &installed.symlinks {
+            let path = Path::new(symlink);
+            if !(path.exists() || path.is_symlink()) {
+                log::warn!("Symlink already missing: {}", symlink);
+            } else if !is_within(path, &bin_dir) && !links_into(path, package_dir) {
+                log::warn!(
+                    "Not removing {}: it is outside {} and doesn't link into {}",
+                    symlink,
+                    bin_dir.display(),
+                    package_dir.display()
+                );
+            } else {
+                std::fs::remove_file(symlink)
+                    .context(format!("Failed to remove symlink: {}", symlink))?;
+                log::debug!("Removed symlink: {}", symlink);
+            }
+        }
+
+        // Remove recorded files, tolerating ones that were already deleted
+        for file in &installed.files {
+            let path = Path::new(file);
+            if !(path.exists() || path.is_symlink()) {
+                log::warn!("File already missing: {}", file);
+            } else if !is_within(path, install_dir) {
+                log::warn!("Not removing {}: it is outside the install directory", file);
+            } else {
+                std::fs::remove_file(path).context(format!("Failed to remove file: {}", file))?;
+                log::debug!("Removed file: {}", file);
+            }
+        }
+
+        shell_env::remove_snippets(name)?;
+
+        // Drop the `current` link if it still points at the removed version
+        if let Some(current) = installed
+            .current_path
+            .as_ref()
+            .filter(|current| Path::new(current) == package_dir.join(CURRENT_LINK_NAME))
+        {
+            let current = Path::new(current);
+            let points_here = std::fs::read_link(current)
+                .map(|target| target == Path::new(&installed.version))
+                .unwrap_or(false);
+            if points_here {
+                std::fs::remove_file(current)
+                    .context(format!("Failed to remove symlink: {}", current.display()))?;
+                log::debug!("Removed symlink: {}", current.display());
+            }
+        }
+
+        // Remove the versioned install directory (and anything generated after install)
+        if install_dir.exists() {
+            std::fs::remove_dir_all(install_dir).context(format!(
+                "Failed to remove directory: {}",
+                installed.install_dir
+            ))?;
+            log::debug!("Removed directory: {}", installed.install_dir);
+
+            // Clean up parent directory if it's empty
+            if let Some(parent_dir) = install_dir.parent() {
+                if parent_dir.exists() {
+                    match std::fs::read_dir(parent_dir) {
+                        Ok(mut entries) => {
+                            // Check if directory is empty
+                            if entries.next().is_none() {
+                                if let Err(e) = std::fs::remove_dir(parent_dir) {
+                                    log::debug!(
+                                        "Could not remove empty parent directory {:?}: {}",
+                                        parent_dir,
+                                        e
+                                    );
+                                } else {
+                                    log::debug!("Removed empty parent directory: {:?}", parent_dir);
+                                }
+                            }
+                        }
+                        Err(e) => {
+                            log::debug!("Could not check parent directory {:?}: {}", parent_dir, e);
+                        }
+                    }
+                }
+            }
+        }
+
+        // Remove from database
+        let mut db = load_installed_db().await?;
+        db.packages.remove(name);
+        save_installed_db(&db).await
+    }
+
+    /// Lists installed packages, sorted by name
+    pub async fn list(&self) -> Result<Vec<InstalledPackage>> {
+        let db = load_installed_db().await?;
+
+        let mut packages: Vec<InstalledPackage> = db.packages.into_values().collect();
+        packages.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Ok(packages)
+    }
+
+    /// Latest version of an installed package available from the source it was
+    /// installed from, honoring the prerelease policy of its registry
+    ///
+    /// Returns `None` for local archive installs, which have no source to ask.
+    pub async fn latest_version(&self, package: &InstalledPackage) -> Result<Option<Version>> {
+        let Some(entry) = ManifestEntry::from_installed(package) else {
+            return Ok(None);
+        };
+        let repo_config = match (&entry.repo, &entry.registry) {
+            (Some(repo), _) => load_repo_argument(repo).await?.0,
+            (None, Some(registry)) => {
+                RegistryManager::find_package_in_specific_registry(&entry.name, registry)
+                    .await?
+                    .0
+            }
+            (None, None) => return Ok(None),
+        };
+
+        let allow_prerelease = load_global_config()
+            .await?
+            .allow_prerelease_for(entry.registry.as_deref());
+        let provider = create_provider(&repo_config)?;
+        let versions = list_versions_cached(provider.as_ref(), &entry.name).await?;
+        Ok(latest_version(&versions, allow_prerelease).cloned())
+    }
+}
+
+/// Whether `path` lies under `root` once both are resolved
+///
+/// The parent directory is canonicalized rather than `path` itself, so a link
+/// is judged by where it sits and not by where it points.
+fn is_within(path: &Path, root: &Path) -> bool {
+    let (Some(parent), Some(name)) = (path.parent(), path.file_name()) else {
+        return false;
+    };
+    match (parent.canonicalize(), root.canonicalize()) {
+        (Ok(parent), Ok(root)) => parent.join(name).starts_with(root),
+        _ => false,
+    }
+}
+
+/// Whether `link` is a symlink whose target lies under `root`
+fn links_into(link: &Path, root: &Path) -> bool {
+    match (std::fs::read_link(link), link.parent()) {
+        (Ok(target), Some(parent)) => is_within(&parent.join(target), root),
+        _ => false,
+    }
+}
+
+/// `InstalledPackage::checksums` entries for the hashed files of an extraction
+///
+/// The install directory mirrors the extraction directory, so the paths are
+/// relative to `install_dir` as well.
+fn file_checksums(manifest: &ExtractionManifest) -> std::collections::HashMap<String, String> {
+    manifest
+        .files
+        .iter()
+        .filter_map(|file| {
+            let hash = file.sha256.as_ref()?;
+            Some((
+                format!("{}{}", FILE_CHECKSUM_PREFIX, file.path.display()),
+                format!("sha256:{}", hash),
+            ))
+        })
+        .collect()
+}
+
+/// Downloads and verifies `install.extra_downloads`, returning each cached file
+/// with its destination
+///
+/// Checksums follow the archive's policy: a missing one is only accepted with
+/// `security.allow_insecure`, and `--insecure` / `--skip-checksum` skip them.
+async fn fetch_extra_downloads(
+    repo_config: &RepoConfig,
+    version: &str,
+    os: &str,
+    arch: &str,
+    spec: &InstallSpec,
+) -> Result<Vec<(PathBuf, String)>> {
+    if repo_config.install.extra_downloads.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut vars = std::collections::HashMap::new();
+    vars.insert("version".to_string(), version.to_string());
+    vars.insert("os".to_string(), os.to_string());
+    vars.insert("arch".to_string(), arch.to_string());
+
+    // Each file is fetched by its own task; the download scheduler bounds how
+    // many run at once
+    let check = !(spec.allow_insecure || spec.skip_checksum);
+    let mut jobs = tokio::task::JoinSet::new();
+    // Cache path of every job, with its URL: two jobs must never write one file
+    let mut fetched: std::collections::HashMap<PathBuf, String> = std::collections::HashMap::new();
+    let mut files = Vec::new();
+    for (index, extra) in repo_config.install.extra_downloads.iter().enumerate() {
+        validate_extra_download_dst(&extra.dst)?;
+        let url = resolve_template_safe(&extra.url, &vars)
+            .context("Failed to resolve extra download URL template")?;
+        let filename = url
+            .trim_end_matches('/')
+            .rsplit('/')
+            .next()
+            .filter(|name| !name.is_empty())
+            .context(format!("Invalid extra download URL: {}", url))?;
+
+        let expected = extra.checksum.as_deref().map(|checksum| {
+            let checksum = checksum.trim();
+            checksum
+                .strip_prefix("sha256:")
+                .unwrap_or(checksum)
+                .to_lowercase()
+        });
+        if check && expected.is_none() && !repo_config.security.allow_insecure {
+            anyhow::bail!(
+                "No checksum configured for extra download {} and allow_insecure is false",
+                url
+            );
+        }
+
+        let mut path = match &expected {
+            Some(hash) => Cache::checksum_download_path(hash, filename)?,
+            None => Cache::download_path(filename)?,
+        };
+        let dst = extra.dst.clone();
+        match fetched.get(&path) {
+            // Same checksum and name, or the same URL: one download serves both
+            Some(other_url) if expected.is_some() || *other_url == url => {
+                files.push((index, path, dst));
+                continue;
+            }
+            // Different unverified files sharing a name
+            Some(_) => path = Cache::download_path(&format!("{}-{}", index, filename))?,
+            None => {}
+        }
+        fetched.insert(path.clone(), url.clone());
+        jobs.spawn(async move {
+            fetch_extra_download(&url, &path, expected.as_deref(), check).await?;
+            Ok::<_, anyhow::Error>((index, path, dst))
+        });
+    }
+
+    while let Some(result) = jobs.join_next().await {
+        files.push(result.context("Extra download task failed")??);
+    }
+    files.sort_by_key(|(index, _, _)| *index);
+    Ok(files
+        .into_iter()
+        .map(|(_, path, dst)| (path, dst))
+        .collect())
+}
+
+/// Downloads one extra file unless a verified copy is cached, then checks it
+async fn fetch_extra_download(
+    url: &str,
+    path: &Path,
+    expected: Option<&str>,
+    check: bool,
+) -> Result<()> {
+    let cache_hit = match expected {
+        Some(hash) if path.exists() => verify_checksum(path, hash, &ChecksumAlgorithm::Sha256)
+            .await
+            .is_ok(),
+        _ => false,
+    };
+    if cache_hit || (is_offline_mode() && path.exists()) {
+        log::info!("Using cached extra download {}", path.display());
+    } else {
+        Downloader::new()?.download(url, path).await?;
+    }
+
+    match expected {
+        Some(hash) if check => verify_checksum(path, hash, &ChecksumAlgorithm::Sha256)
+            .await
+            .context(format!("Extra download {} failed verification", url))?,
+        _ if check => log::warn!(
+            "No checksum configured for extra download {} and allow_insecure is true",
+            url
+        ),
+        _ => log::warn!("Skipping checksum verification of extra download {}", url),
+    }
+    Ok(())
+}
+
+/// Warning for when `archive` doesn't match the checksum an earlier install
+/// of the same version recorded
+async fn check_pinned_checksum(
+    package: &str,
+    version: &str,
+    archive: &Path,
+    pinned: &str,
+) -> Option<String> {
+    let Some((algorithm, expected)) = pinned
+        .split_once(':')
+        .and_then(|(name, hash)| Some((ChecksumAlgorithm::from_name(name)?, hash)))
+    else {
+        log::debug!("Ignoring unrecognized recorded checksum {}", pinned);
+        return None;
+    };
+
+    match compute_checksum(archive, &algorithm).await {
+        Ok(actual) if actual.eq_ignore_ascii_case(expected) => {
+            log::debug!("{} {} matches its recorded checksum", package, version);
+            None
+        }
+        Ok(actual) => Some(format!(
+            "The archive of {} {} differs from the one installed before \
+             (recorded {}, now {}:{}); a published version should never change, \
+             so the release may have been re-tagged or tampered with",
+            package,
+            version,
+            pinned,
+            algorithm.as_str(),
+            actual
+        )),
+        Err(e) => {
+            log::warn!(
+                "Could not compare {} {} with its recorded checksum: {}",
+                package,
+                version,
+                e
+            );
+            None
+        }
+    }
+}
+
+/// Evicts old downloads once the cache outgrows `resources.max_cache_size_bytes`
+///
+/// Failures are only logged; the install itself doesn't depend on them.
+fn enforce_cache_budget(just_downloaded: &Path) {
+    if Cache::keep_downloads() {
+        return;
+    }
+    let resources = SecurityConfig::load().unwrap_or_default().resources;
+    if !resources.enabled || resources.max_cache_size_bytes == 0 {
+        return;
+    }
+
+    match Cache::enforce_download_budget(resources.max_cache_size_bytes, just_downloaded) {
+        Ok(evicted) => {
+            for (path, size) in evicted {
+                log::info!(
+                    "Evicted cached download {} ({} bytes) to stay within resources.max_cache_size_bytes",
+                    path.display(),
+                    size
+                );
+            }
+        }
+        Err(e) => log::warn!("Failed to enforce download cache size limit: {}", e),
+    }
+}
+
+/// Loads the `.repo` given with `--repo`: a local path or an `https://` URL
+///
+/// Returns the config and the `registry_source` to record for it
+/// (`file:<path>` or `url:<url>`), which `update` uses to load it again.
+/// Remote files go through the usual URL validation and are capped at
+/// `validation.max_toml_size`.
+pub async fn load_repo_argument(repo: &str) -> Result<(RepoConfig, String)> {
+    if !is_repo_url(repo) {
+        let content = tokio::fs::read_to_string(repo)
+            .await
+            .context("Failed to read .repo file")?;
+        let config =
+            toml::from_str::<RepoConfig>(&content).context("Failed to parse .repo file")?;
+        return Ok((config, format!("file:{}", repo)));
+    }
+
+    if !repo.starts_with("https://") {
+        anyhow::bail!("Remote .repo files must be fetched over HTTPS: {}", repo);
+    }
+
+    log::debug!("Fetching .repo file from {}", repo);
+    let max_toml_size = SecurityConfig::load()
+        .unwrap_or_default()
+        .validation
+        .max_toml_size;
+    let content = HttpClient::new()?
+        .get_text_limited(repo, max_toml_size)
+        .await
+        .context(format!("Failed to fetch .repo file from {}", repo))?;
+    let config = toml::from_str::<RepoConfig>(&content).context("Failed to parse .repo file")?;
+    Ok((config, format!("url:{}", repo)))
+}
+
+/// Whether a `--repo` value names a remote file rather than a local path
+fn is_repo_url(repo: &str) -> bool {
+    repo.starts_with("https://") || repo.starts_with("http://")
+}
+
+/// Source recorded for an install that failed before its package was resolved
+fn requested_source(spec: &InstallSpec) -> String {
+    if spec.local.is_some() {
+        "local".to_string()
+    } else if let Some(repo) = &spec.repo {
+        if is_repo_url(repo) {
+            format!("url:{}", repo)
+        } else {
+            format!("file:{}", repo)
+        }
+    } else {
+        match spec.package.split_once('@') {
+            Some((_, registry)) => format!("registry:{}", registry),
+            None => "registry".to_string(),
+        }
+    }
+}
+
+/// The spec's install mode, or the configured default
+fn install_mode(spec: &InstallSpec, config: &GlobalConfig) -> InstallMode {
+    spec.mode
+        .clone()
+        .unwrap_or_else(|| config.install.default_mode.clone())
+}
+
+/// OS and arch names to try for `platform`, using the `.repo` mappings (or
+/// the defaults) followed by its fallbacks
+pub(crate) fn platform_candidates(
+    repo_config: &RepoConfig,
+    platform: &Platform,
+) -> (Vec<String>, Vec<String>) {
+    // Apply mappings (use helper methods for v1/v2 compatibility)
+    let os_mapping = {
+        let map = repo_config.get_os_map();
+        if map.is_empty() {
+            default_os_mapping()
+        } else {
+            map
+        }
+    };
+
+    let arch_mapping = {
+        let map = repo_config.get_arch_map();
+        if map.is_empty() {
+            default_arch_mapping()
+        } else {
+            map
+        }
+    };
+
+    (
+        platform.map_os(&os_mapping, &repo_config.get_os_fallbacks()),
+        platform.map_arch(&arch_mapping, &repo_config.get_arch_fallbacks()),
+    )
+}
+
+/// Archive file name of a download URL (its last path segment)
+fn download_filename(download_url: &str) -> Result<&str> {
+    // Strip trailing slashes from URL before extracting filename
+    let filename = download_url
+        .trim_end_matches('/')
+        .split('/')
+        .next_back()
+        .context("Invalid download URL")?;
+
+    // Validate that we got a non-empty filename
+    if filename.is_empty() {
+        anyhow::bail!(
+            "Failed to extract filename from download URL: {}\n\
+             The URL appears to be invalid. Please check the .repo file's download URL template.",
+            download_url
+        );
+    }
+    Ok(filename)
+}
+
+/// URLs the latest version of a `.repo` resolves to on this machine
+#[derive(Debug, Clone)]
+pub struct ResolvedUrls {
+    pub version: String,
+    pub os: String,
+    pub arch: String,
+    pub download_url: String,
+    /// Checksum file, when the `.repo` names one by URL or file name pattern
+    pub checksum_url: Option<String>,
+}
+
+/// Resolves the latest version and expands the download and checksum URL
+/// templates for the host platform, as `ora install` would, without
+/// downloading anything
+pub async fn resolve_urls(
+    repo_config: &RepoConfig,
+    allow_prerelease: bool,
+) -> Result<ResolvedUrls> {
+    let mut repo_config = repo_config.clone();
+    let platform = Platform::with_overrides(None, None);
+    if let Some(libc) = platform.libc() {
+        repo_config.apply_libc(libc);
+    }
+    let (os_candidates, arch_candidates) = platform_candidates(&repo_config, &platform);
+
+    let provider = create_provider(&repo_config)?;
+    let selected =
+        resolve_version(provider.as_ref(), &repo_config.name, None, allow_prerelease).await?;
+    let (download_url, os, arch) = resolve_download_url(
+        provider.as_ref(),
+        &repo_config,
+        &platform,
+        &selected,
+        &os_candidates,
+        &arch_candidates,
+    )
+    .await?;
+
+    let checksum_url = match &repo_config.security.checksum {
+        Some(checksum)
+            if checksum.from_release_notes.is_none()
+                && (!checksum.url.is_empty() || checksum.filename_pattern.is_some()) =>
+        {
+            Some(Verifier::checksum_url(
+                checksum,
+                &download_url,
+                download_filename(&download_url)?,
+                &selected.tag,
+                &os,
+                &arch,
+            )?)
+        }
+        _ => None,
+    };
+
+    Ok(ResolvedUrls {
+        version: selected.tag,
+        os,
+        arch,
+        download_url,
+        checksum_url,
+    })
+}
+
+/// Resolves the download URL for the first platform name candidates that match
+///
+/// With a single (os, arch) candidate the URL is used as-is. With several, the
+/// first one naming an asset of the release listing wins; otherwise each
+/// resolved URL is probed and the first existing asset wins. If none can be
+/// confirmed (e.g. offline), the first resolvable URL is used.
+async fn resolve_download_url(
+    provider: &dyn VersionProvider,
+    repo_config: &RepoConfig,
+    platform: &Platform,
+    version: &Version,
+    os_candidates: &[String],
+    arch_candidates: &[String],
+) -> Result<(String, String, String)> {
+    let mut resolved = Vec::new();
+    let mut last_error = None;
+
+    for os in os_candidates {
+        for arch in arch_candidates {
+            match provider.download_url_for(version, os, arch).await {
+                Ok(url) => resolved.push((url, os.clone(), arch.clone())),
+                Err(e) => {
+                    log::debug!("No download URL for {}/{}: {}", os, arch, e);
+                    last_error = Some(e);
+                }
+            }
+        }
+    }
+
+    if resolved.len() > 1 && !version.assets.is_empty() {
+        let listed = resolved.iter().find(|(url, _, _)| {
+            version.assets.iter().any(|asset| {
+                asset.url == *url
+                    || url
+                        .trim_end_matches('/')
+                        .ends_with(&format!("/{}", asset.name))
+            })
+        });
+        if let Some((url, os, arch)) = listed {
+            log::debug!("Selected listed asset for {}/{}: {}", os, arch, url);
+            return Ok((url.clone(), os.clone(), arch.clone()));
+        }
+    }
+
+    if resolved.len() > 1 && !is_offline_mode() {
+        let client = HttpClient::new()?;
+        for (url, os, arch) in &resolved {
+            if client.url_exists(url).await {
+                log::debug!("Selected platform candidate {}/{}: {}", os, arch, url);
+                return Ok((url.clone(), os.clone(), arch.clone()));
+            }
+            log::debug!("Asset not found for {}/{}: {}", os, arch, url);
+        }
+        log::warn!("No platform candidate could be confirmed, using the first one");
+    }
+
+    match resolved.into_iter().next() {
+        Some(candidate) => Ok(candidate),
+        None => {
+            let error = last_error.unwrap_or_else(|| anyhow::anyhow!("No download URL found"));
+            let available = available_assets(provider, repo_config, version).await;
+            Err(error.context(format!(
+                "No download for detected platform {}/{} (tried OS names: {}; arch names: {})\n\
+                 Available: {}\n\
+                 Map these names with platform.os_map/arch_map, or list candidates in \
+                 platform.os_fallbacks/arch_fallbacks in the .repo file",
+                platform.os,
+                platform.arch,
+                os_candidates.join(", "),
+                arch_candidates.join(", "),
+                if available.is_empty() {
+                    "unknown".to_string()
+                } else {
+                    available.join(", ")
+                }
+            )))
+        }
+    }
+}
+
+/// Asset names (or `download.urls` platform keys) published for `version`
+async fn available_assets(
+    provider: &dyn VersionProvider,
+    repo_config: &RepoConfig,
+    version: &Version,
+) -> Vec<String> {
+    if let Some(urls) = repo_config
+        .source
+        .download
+        .as_ref()
+        .and_then(|download| download.urls.as_ref())
+    {
+        let mut keys: Vec<String> = urls.keys().cloned().collect();
+        keys.sort();
+        return keys;
+    }
+
+    if !version.assets.is_empty() {
+        return version.assets.iter().map(|a| a.name.clone()).collect();
+    }
+
+    provider
+        .available_assets(&version.tag)
+        .await
+        .unwrap_or_else(|e| {
+            log::debug!("Could not list release assets: {}", e);
+            Vec::new()
+        })
+}
@@ -1,5 +1,9 @@
 use clap::{Parser, Subcommand};
 
+use crate::config::manifest::ManifestFormat;
+use crate::security::AuditFormat;
+use crate::utils::logging::LogFormat;
+
 #[derive(Parser)]
 #[command(name = "ora")]
 #[command(about = "Omni Repository for Archives - Decentralized package manager")]
@@ -15,6 +19,56 @@ pub struct Cli {
     /// Enable debug output (DEBUG level logs, implies --verbose)
     #[arg(long, global = true)]
     pub debug: bool,
+
+    /// Never access the network; rely on local registry clones and caches only
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Keep downloaded archives instead of deleting them on error or interrupt
+    #[arg(long, global = true)]
+    pub keep_downloads: bool,
+
+    /// Maximum parallel downloads for this run, overriding
+    /// resources.max_concurrent_downloads from security.toml
+    #[arg(
+        long,
+        global = true,
+        value_name = "N",
+        value_parser = clap::builder::RangedU64ValueParser::<usize>::new().range(1..)
+    )]
+    pub concurrency: Option<usize>,
+
+    /// Don't run the hooks configured in config.toml
+    #[arg(long, global = true)]
+    pub no_hooks: bool,
+
+    /// Answer yes to every confirmation prompt (for scripts and CI)
+    #[arg(short = 'y', long, global = true, visible_alias = "assume-yes")]
+    pub yes: bool,
+
+    /// Use plain ASCII instead of emoji and box-drawing characters, without colors
+    #[arg(long, global = true, visible_alias = "no-emoji")]
+    pub plain: bool,
+
+    /// Global config file to use instead of config.toml in the config directory
+    /// (takes precedence over ORA_CONFIG_DIR)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<std::path::PathBuf>,
+
+    /// Security config file to use instead of security.toml in the config directory
+    /// (takes precedence over ORA_CONFIG_DIR)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub security_config: Option<std::path::PathBuf>,
+
+    /// Log record format; `json` emits one JSON object per line
+    #[arg(
+        long,
+        global = true,
+        value_enum,
+        env = "ORA_LOG_FORMAT",
+        default_value_t = LogFormat::Human
+    )]
+    pub log_format: LogFormat,
 }
 
 #[derive(Subcommand)]
@@ -22,7 +76,7 @@ pub enum Commands {
     /// Install a package
     Install(InstallArgs),
 
-    /// Uninstall a package
+    /// Uninstall packages
     Uninstall(UninstallArgs),
 
     /// Update package(s)
@@ -48,15 +102,39 @@ pub enum Commands {
 
     /// Manage configuration files
     Config(ConfigArgs),
+
+    /// Inspect the download and registry cache
+    Cache(CacheArgs),
+
+    /// Update Ora itself to the latest release
+    SelfUpdate(SelfUpdateArgs),
+
+    /// Write the installed packages to a manifest
+    Export(ExportArgs),
+
+    /// Install the packages listed in a manifest
+    Import(ImportArgs),
+
+    /// Pin the version, download URL and checksum of installed packages in a lockfile
+    Lock(LockArgs),
+
+    /// Manage short names for packages
+    Alias(AliasArgs),
+
+    /// Show the detected platform and how a .repo file maps it
+    Platform(PlatformArgs),
 }
 
-#[derive(clap::Args)]
+#[derive(clap::Args, Clone)]
 pub struct InstallArgs {
-    pub package: String,
+    /// Package to install (every lockfile entry with --locked if omitted)
+    #[arg(required_unless_present = "locked")]
+    pub package: Option<String>,
 
     #[arg(short, long)]
     pub version: Option<String>,
 
+    /// Install from a .repo file (local path or HTTPS URL) instead of the registries
     #[arg(long)]
     pub repo: Option<String>,
 
@@ -69,6 +147,10 @@ pub struct InstallArgs {
     #[arg(long, name = "allow-insecure")]
     pub allow_insecure: bool,
 
+    /// Accept alpha/beta/rc versions
+    #[arg(long)]
+    pub allow_prerelease: bool,
+
     /// Install from a local tar.gz archive
     #[arg(long)]
     pub local: Option<String>,
@@ -76,23 +158,81 @@ pub struct InstallArgs {
     /// Metadata file for local installation (.toml)
     #[arg(long)]
     pub metadata: Option<String>,
+
+    /// Target OS instead of the current one (e.g. linux, macos); binaries are not linked
+    #[arg(long, conflicts_with = "local")]
+    pub os: Option<String>,
+
+    /// Target architecture instead of the current one (e.g. x86_64, aarch64); binaries are not linked
+    #[arg(long, conflicts_with = "local")]
+    pub arch: Option<String>,
+
+    /// Download and verify the archive (checksum, signature) without installing it
+    #[arg(long, conflicts_with = "local")]
+    pub verify_only: bool,
+
+    /// Download and verify the archive, then write it and its checksum to
+    /// --output-dir instead of installing (e.g. to stage an offline machine)
+    #[arg(long, conflicts_with_all = ["local", "verify_only"])]
+    pub download_only: bool,
+
+    /// Directory --download-only writes to [default: current directory]
+    #[arg(long, requires = "download_only", value_name = "DIR")]
+    pub output_dir: Option<String>,
+
+    /// Skip only checksum verification, e.g. when upstream publishes no checksums
+    /// (unlike --allow-insecure, all other security checks still apply)
+    #[arg(long, visible_alias = "no-verify")]
+    pub skip_checksum: bool,
+
+    /// Install exactly the version, URL and checksum pinned in the lockfile,
+    /// without resolving versions from the provider
+    #[arg(
+        long,
+        conflicts_with_all = ["version", "repo", "local", "os", "arch", "skip_checksum", "verify_only"]
+    )]
+    pub locked: bool,
+
+    /// Lockfile used by --locked [default: ora.lock]
+    #[arg(long, requires = "locked", value_name = "FILE")]
+    pub lockfile: Option<String>,
 }
 
 #[derive(clap::Args)]
 pub struct ValidateArgs {
     /// Path to the .repo file to validate
     pub repo_file: String,
+
+    /// Resolve the latest version and check that its download and checksum
+    /// URLs exist for this platform (HEAD requests; needs network access)
+    #[arg(long)]
+    pub check_urls: bool,
+
+    /// Accept alpha/beta/rc versions as the latest one with --check-urls
+    #[arg(long, requires = "check_urls")]
+    pub allow_prerelease: bool,
 }
 
 #[derive(clap::Args)]
 pub struct UninstallArgs {
-    pub package: String,
+    /// Packages to uninstall
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    pub packages: Vec<String>,
 
+    /// Version to uninstall (single package only)
     #[arg(short, long)]
     pub version: Option<String>,
 
     #[arg(long)]
     pub purge: bool,
+
+    /// Show the files and symlinks that would be removed without deleting anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Uninstall every installed package
+    #[arg(long, conflicts_with = "version")]
+    pub all: bool,
 }
 
 #[derive(clap::Args)]
@@ -101,12 +241,40 @@ pub struct UpdateArgs {
 
     #[arg(long)]
     pub all: bool,
+
+    /// Accept alpha/beta/rc versions
+    #[arg(long)]
+    pub allow_prerelease: bool,
+
+    /// Only update packages installed below their metadata.security_fixed_in version
+    #[arg(long)]
+    pub only_security: bool,
+
+    /// Resume an interrupted `update --all`, skipping the packages it already updated
+    #[arg(long = "continue", requires = "all")]
+    pub resume: bool,
 }
 
 #[derive(clap::Args)]
 pub struct ListArgs {
     #[arg(short, long)]
     pub verbose: bool,
+
+    /// Show every version directory on disk with its size
+    #[arg(long, visible_alias = "sizes")]
+    pub tree: bool,
+
+    /// Only packages with a newer version available (asks each package's source)
+    #[arg(long)]
+    pub outdated: bool,
+
+    /// Only packages installed with --allow-insecure
+    #[arg(long)]
+    pub insecure: bool,
+
+    /// Only packages installed from this registry
+    #[arg(long, value_name = "NAME")]
+    pub registry: Option<String>,
 }
 
 #[derive(clap::Args)]
@@ -117,6 +285,33 @@ pub struct SearchArgs {
 #[derive(clap::Args)]
 pub struct InfoArgs {
     pub package: String,
+
+    /// List available versions with their release dates
+    #[arg(long)]
+    pub versions: bool,
+
+    /// List the files installed by the package
+    #[arg(long)]
+    pub files: bool,
+
+    /// Print the resolved .repo definition from the registry (secrets omitted)
+    #[arg(long)]
+    pub remote: bool,
+}
+
+#[derive(clap::Args)]
+pub struct PlatformArgs {
+    /// .repo file (path or https:// URL) whose OS/arch names to show
+    #[arg(long)]
+    pub repo: Option<String>,
+
+    /// OS to map instead of the current one (e.g. linux, macos)
+    #[arg(long)]
+    pub os: Option<String>,
+
+    /// Architecture to map instead of the current one (e.g. x86_64, aarch64)
+    #[arg(long)]
+    pub arch: Option<String>,
 }
 
 #[derive(clap::Args)]
@@ -142,10 +337,21 @@ pub enum RegistryCommand {
         /// Registry directory name within the repository (optional, defaults to "ora-registry")
         #[arg(long)]
         dir: Option<String>,
+        /// Expected SHA-256 of the .repo file for Direct URL registries
+        #[arg(long)]
+        index_checksum: Option<String>,
+        /// URL of a prebuilt package index for Git registries; the registry is
+        /// then synced by downloading the index instead of cloning it
+        #[arg(long)]
+        index_url: Option<String>,
     },
     List {
         #[arg(short, long)]
         verbose: bool,
+
+        /// Print registries as JSON, with last sync time and package count
+        #[arg(long, conflicts_with = "verbose")]
+        json: bool,
     },
     Remove {
         name: String,
@@ -154,13 +360,45 @@ pub enum RegistryCommand {
     Sync {
         /// Optional registry name to sync (syncs all if not specified)
         name: Option<String>,
+
+        /// Download only the registry's package index (its index_url) instead
+        /// of cloning it; falls back to a clone when it has no index_url
+        #[arg(long)]
+        index_only: bool,
     },
     Verify {
         name: String,
+
+        /// Parse and lint every .repo file (fields, regexes, templates)
+        #[arg(long)]
+        deep: bool,
+    },
+    /// Show a registry's settings, last sync time and package count
+    Info {
+        name: String,
+    },
+    /// Trust a registry's signing key (first use or explicit key rotation)
+    Trust {
+        name: String,
+
+        /// Trust without prompting if the key has this fingerprint
+        #[arg(long)]
+        fingerprint: Option<String>,
     },
     UpdatePin {
         name: String,
     },
+    /// Point a registry at a new URL, keeping its settings and local clone
+    SetUrl {
+        name: String,
+        url: String,
+    },
+    /// Change a registry's trust level
+    SetTrust {
+        name: String,
+        /// `public` or `private`
+        trust_level: String,
+    },
 }
 
 #[derive(clap::Args)]
@@ -179,6 +417,24 @@ pub enum SecurityCommand {
 
     /// Reset security configuration to defaults
     Reset,
+
+    /// Report install, uninstall and update events from the audit log
+    Audit {
+        /// Only include events newer than this (e.g. 30d, 12h, 2w or 2026-01-31)
+        #[arg(long)]
+        since: Option<String>,
+
+        /// Report format
+        #[arg(long, value_enum, default_value_t = AuditFormat::Text)]
+        format: AuditFormat,
+    },
+
+    /// Check installed packages against the security policy; fails on high-severity findings
+    Scan {
+        /// Report format
+        #[arg(long, value_enum, default_value_t = AuditFormat::Text)]
+        format: AuditFormat,
+    },
 }
 
 #[derive(clap::Args)]
@@ -193,8 +449,82 @@ pub enum ConfigCommand {
     Show,
 
     /// Verify all configuration files are valid
-    Verify,
+    Verify {
+        /// Print the checks, errors and warnings as JSON
+        #[arg(long)]
+        json: bool,
+    },
 
     /// Initialize all configuration files with defaults
     Init,
+
+    /// Upgrade the config and installed database to the current format
+    Migrate {
+        /// Only report what would change, without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(clap::Args)]
+pub struct SelfUpdateArgs {
+    /// Only check whether a newer release is available
+    #[arg(long)]
+    pub check: bool,
+}
+
+#[derive(clap::Args)]
+pub struct ExportArgs {
+    /// Manifest format (defaults to the output file's extension, else toml)
+    #[arg(long, value_enum)]
+    pub format: Option<ManifestFormat>,
+
+    /// Write the manifest to this file instead of stdout
+    #[arg(short, long)]
+    pub output: Option<String>,
+}
+
+#[derive(clap::Args)]
+pub struct ImportArgs {
+    /// Manifest written by 'ora export'
+    pub file: String,
+
+    /// Manifest format (defaults to the file's extension, else brewfile)
+    #[arg(long, value_enum)]
+    pub format: Option<ManifestFormat>,
+}
+
+#[derive(clap::Args)]
+pub struct LockArgs {
+    /// Lockfile to write
+    #[arg(short, long, default_value = crate::config::lockfile::DEFAULT_LOCKFILE)]
+    pub output: String,
+}
+
+#[derive(clap::Args)]
+pub struct AliasArgs {
+    #[command(subcommand)]
+    pub command: AliasCommand,
+}
+
+#[derive(Subcommand)]
+pub enum AliasCommand {
+    /// Make `alias` stand for `package` in install, info and update
+    Add { alias: String, package: String },
+    /// Remove an alias
+    Remove { alias: String },
+    /// List aliases
+    List,
+}
+
+#[derive(clap::Args)]
+pub struct CacheArgs {
+    #[command(subcommand)]
+    pub command: CacheCommand,
+}
+
+#[derive(Subcommand)]
+pub enum CacheCommand {
+    /// Show cache size with a per-category breakdown
+    Info,
 }
@@ -0,0 +1,91 @@
+use anyhow::Result;
+
+use crate::cli::args::{AliasArgs, AliasCommand};
+use crate::storage::database::{load_global_config, save_global_config};
+
+pub async fn execute(args: AliasArgs) -> Result<()> {
+    match args.command {
+        AliasCommand::Add { alias, package } => add_alias(alias, package).await,
+        AliasCommand::Remove { alias } => remove_alias(alias).await,
+        AliasCommand::List => list_aliases().await,
+    }
+}
+
+/// Aliases and targets are plain package names: no registry or path parts
+fn validate_name(kind: &str, name: &str) -> Result<()> {
+    if name.is_empty()
+        || name.contains('@')
+        || name.contains('/')
+        || name.contains("..")
+        || name.chars().any(char::is_whitespace)
+    {
+        anyhow::bail!("Invalid {} '{}': use a plain package name", kind, name);
+    }
+    Ok(())
+}
+
+async fn add_alias(alias: String, package: String) -> Result<()> {
+    validate_name("alias", &alias)?;
+    validate_name("package name", &package)?;
+    if alias == package {
+        anyhow::bail!("An alias can't point to itself");
+    }
+
+    let mut config = load_global_config().await?;
+    if config.aliases.contains_key(&package) {
+        anyhow::bail!(
+            "'{}' is itself an alias; aliases can't point to other aliases",
+            package
+        );
+    }
+    if let Some((other, _)) = config.aliases.iter().find(|(_, target)| **target == alias) {
+        anyhow::bail!(
+            "'{}' is the target of alias '{}'; aliases can't point to other aliases",
+            alias,
+            other
+        );
+    }
+
+    let previous = config.aliases.insert(alias.clone(), package.clone());
+    save_global_config(&config).await?;
+    match previous {
+        Some(previous) if previous != package => {
+            println!(
+                "✅ Alias '{}' now points to '{}' (was '{}')",
+                alias, package, previous
+            )
+        }
+        _ => println!("✅ Alias '{}' points to '{}'", alias, package),
+    }
+    Ok(())
+}
+
+async fn remove_alias(alias: String) -> Result<()> {
+    let mut config = load_global_config().await?;
+    let Some(package) = config.aliases.remove(&alias) else {
+        anyhow::bail!("No alias named '{}'", alias);
+    };
+    save_global_config(&config).await?;
+    println!("✅ Removed alias '{}' (was '{}')", alias, package);
+    Ok(())
+}
+
+async fn list_aliases() -> Result<()> {
+    let config = load_global_config().await?;
+    if config.aliases.is_empty() {
+        println!("No aliases defined. Add one with: ora alias add <alias> <package>");
+        return Ok(());
+    }
+
+    let mut aliases: Vec<_> = config.aliases.iter().collect();
+    aliases.sort();
+    let width = aliases
+        .iter()
+        .map(|(alias, _)| alias.len())
+        .max()
+        .unwrap_or(0);
+    for (alias, package) in aliases {
+        println!("{:<width$} → {}", alias, package, width = width);
+    }
+    Ok(())
+}
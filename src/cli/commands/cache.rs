@@ -0,0 +1,120 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::cli::args::{CacheArgs, CacheCommand};
+use crate::config::SecurityConfig;
+use crate::storage::cache::Cache;
+use crate::storage::paths::Paths;
+use crate::utils::ui::format_size;
+
+pub async fn execute(args: CacheArgs) -> Result<()> {
+    match args.command {
+        CacheCommand::Info => show_info().await,
+    }
+}
+
+/// Size and file count of one cache category
+struct CacheUsage {
+    bytes: u64,
+    files: usize,
+}
+
+impl CacheUsage {
+    fn empty() -> Self {
+        Self { bytes: 0, files: 0 }
+    }
+
+    fn add(&mut self, (bytes, files): (u64, usize)) {
+        self.bytes += bytes;
+        self.files += files;
+    }
+}
+
+/// Show total cache usage with a per-category breakdown
+async fn show_info() -> Result<()> {
+    let cache_dir = Paths::cache_dir()?;
+
+    // Downloads and extraction directories share the downloads folder;
+    // extraction directories are named "<package>_extract"
+    let mut downloads = CacheUsage::empty();
+    let mut extracts = CacheUsage::empty();
+    let downloads_dir = cache_dir.join("downloads");
+    if downloads_dir.is_dir() {
+        for entry in std::fs::read_dir(&downloads_dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            let is_extract = entry.file_type()?.is_dir()
+                && entry.file_name().to_string_lossy().ends_with("_extract");
+
+            if is_extract {
+                extracts.add(Cache::dir_usage(&path)?);
+            } else if entry.file_type()?.is_dir() {
+                downloads.add(Cache::dir_usage(&path)?);
+            } else {
+                downloads.add((entry.metadata()?.len(), 1));
+            }
+        }
+    }
+
+    let registries = usage_of(&Paths::registries_cache_dir()?)?;
+    let scrapers = usage_of(&cache_dir.join("scrapers"))?;
+    let versions = usage_of(&cache_dir.join("versions"))?;
+    let total = usage_of(&cache_dir)?;
+
+    let categorized = [&downloads, &extracts, &registries, &scrapers, &versions];
+    let other = CacheUsage {
+        bytes: total
+            .bytes
+            .saturating_sub(categorized.iter().map(|u| u.bytes).sum()),
+        files: total
+            .files
+            .saturating_sub(categorized.iter().map(|u| u.files).sum()),
+    };
+
+    println!("📦 Cache usage ({})\n", cache_dir.display());
+    print_row("Downloads", &downloads);
+    print_row("Extracts", &extracts);
+    print_row("Registry clones", &registries);
+    print_row("Scraper caches", &scrapers);
+    print_row("Version caches", &versions);
+    if other.files > 0 {
+        print_row("Other", &other);
+    }
+    println!();
+    print_row("Total", &total);
+
+    let resources = SecurityConfig::load().unwrap_or_default().resources;
+    if resources.enabled && resources.max_cache_size_bytes > 0 {
+        let percent = total.bytes as f64 / resources.max_cache_size_bytes as f64 * 100.0;
+        println!(
+            "  {:<18} {:>12}  ({:.1}% used)",
+            "Budget",
+            format_size(resources.max_cache_size_bytes),
+            percent
+        );
+
+        if total.bytes > resources.max_cache_size_bytes {
+            println!(
+                "\n⚠️  Cache exceeds its budget by {} (resources.max_cache_size_bytes)",
+                format_size(total.bytes - resources.max_cache_size_bytes)
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn usage_of(path: &Path) -> Result<CacheUsage> {
+    let (bytes, files) = Cache::dir_usage(path)?;
+    Ok(CacheUsage { bytes, files })
+}
+
+fn print_row(label: &str, usage: &CacheUsage) {
+    println!(
+        "  {:<18} {:>12}  ({} file{})",
+        label,
+        format_size(usage.bytes),
+        usage.files,
+        if usage.files == 1 { "" } else { "s" }
+    );
+}
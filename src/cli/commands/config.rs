@@ -1,52 +1,65 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::Serialize;
 use std::path::{Path, PathBuf};
 
 use crate::cli::args::ConfigArgs;
-use crate::config::{GlobalConfig, SecurityConfig};
-use crate::storage::database::{load_global_config, load_installed_db};
+use crate::config::migrations::{
+    added_fields, migrate_global_config, migrate_installed_database, CURRENT_CONFIG_VERSION,
+    CURRENT_SCHEMA_VERSION,
+};
+use crate::config::{GlobalConfig, InstalledDatabase, SecurityConfig};
+use crate::registry::sync::RegistryType;
+use crate::storage::cache::Cache;
+use crate::storage::database::{
+    config_frozen, load_global_config, load_installed_db, save_global_config, save_installed_db,
+};
 use crate::storage::paths::Paths;
+use crate::ui_println;
+use crate::utils::io_errors::read_file_user_friendly_async;
+use crate::utils::ui::format_size;
 
 pub async fn execute(args: ConfigArgs) -> Result<()> {
     match args.command {
         crate::cli::args::ConfigCommand::Show => show_config().await,
-        crate::cli::args::ConfigCommand::Verify => verify_config().await,
+        crate::cli::args::ConfigCommand::Verify { json } => verify_config(json).await,
         crate::cli::args::ConfigCommand::Init => init_config().await,
+        crate::cli::args::ConfigCommand::Migrate { dry_run } => migrate_config(dry_run).await,
     }
 }
 
 /// Show current configuration paths and status
 async fn show_config() -> Result<()> {
-    println!("📋 Ora Configuration Status\n");
+    ui_println!("📋 Ora Configuration Status\n");
 
     // Show environment variable overrides
-    println!("🔧 Environment Variables:");
-    if let Ok(config_dir) = std::env::var("ORA_CONFIG_DIR") {
-        println!("   ORA_CONFIG_DIR = {} (override active)", config_dir);
-    } else {
-        println!("   ORA_CONFIG_DIR = (not set, using default)");
+    ui_println!("🔧 Environment Variables:");
+    // Empty values are ignored, like in `Paths`
+    for var in ["ORA_CONFIG_DIR", "ORA_DATA_DIR", "ORA_CACHE_DIR"] {
+        match std::env::var(var).ok().filter(|dir| !dir.is_empty()) {
+            Some(dir) => ui_println!("   {:<14} = {} (override active)", var, dir),
+            None => ui_println!("   {:<14} = (not set, using platform default)", var),
+        }
     }
-    if let Ok(data_dir) = std::env::var("ORA_DATA_DIR") {
-        println!("   ORA_DATA_DIR   = {} (override active)", data_dir);
-    } else {
-        println!("   ORA_DATA_DIR   = (not set, using default)");
+    if let Ok(registry_dirs) = std::env::var("ORA_REGISTRY_CACHE_DIRS") {
+        ui_println!(
+            "   ORA_REGISTRY_CACHE_DIRS = {} (shared registry caches)",
+            registry_dirs
+        );
     }
-    if let Ok(cache_dir) = std::env::var("ORA_CACHE_DIR") {
-        println!("   ORA_CACHE_DIR  = {} (override active)", cache_dir);
-    } else {
-        println!("   ORA_CACHE_DIR  = (not set, using default)");
+    if config_frozen()? {
+        ui_println!("   Configuration is frozen: ora won't modify config.toml or security.toml");
     }
-    println!();
+    ui_println!();
 
     // Show configuration file paths
-    println!("📁 Configuration Files:");
+    ui_println!("📁 Configuration Files:");
 
     // Global config
     let global_config_path = Paths::config_file()?;
     print_file_status("Global Config", &global_config_path);
 
     // Security config
-    if let Ok(config_dir) = Paths::config_dir() {
-        let security_config_path = config_dir.join("security.toml");
+    if let Ok(security_config_path) = Paths::security_config_file() {
         print_file_status("Security Config", &security_config_path);
     }
 
@@ -54,10 +67,10 @@ async fn show_config() -> Result<()> {
     let installed_db_path = Paths::installed_db_file()?;
     print_file_status("Installed Packages DB", &installed_db_path);
 
-    println!();
+    ui_println!();
 
     // Show data directories
-    println!("📂 Data Directories:");
+    ui_println!("📂 Data Directories:");
     if let Ok(config_dir) = Paths::config_dir() {
         print_dir_status("Config", &config_dir);
     }
@@ -67,253 +80,461 @@ async fn show_config() -> Result<()> {
     if let Ok(cache_dir) = Paths::cache_dir() {
         print_dir_status("Cache", &cache_dir);
     }
-    println!();
+    ui_println!();
 
     // Try to load configs and show status
-    println!("📊 Configuration Status:");
+    ui_println!("📊 Configuration Status:");
 
     // Global config
     match load_global_config().await {
         Ok(config) => {
-            println!("   ✅ Global Config: Loaded successfully");
-            println!("     ├─ Registries: {}", config.registries.len());
+            ui_println!("   ✅ Global Config: Loaded successfully");
+            ui_println!("     ├─ Registries: {}", config.registries.len());
             let enabled_count = config.registries.iter().filter(|r| r.enabled).count();
-            println!("     └─ Enabled: {}", enabled_count);
+            ui_println!("     ├─ Enabled: {}", enabled_count);
+            ui_println!(
+                "     └─ Default install mode: {}",
+                config.install.default_mode.as_str()
+            );
         }
         Err(e) => {
-            println!("   ❌ Global Config: Failed to load");
-            println!("     └─ Error: {}", e);
+            ui_println!("   ❌ Global Config: Failed to load");
+            ui_println!("     └─ Error: {}", e);
         }
     }
 
     // Security config
     match SecurityConfig::load() {
         Ok(_config) => {
-            println!("   ✅ Security Config: Loaded successfully");
+            ui_println!("   ✅ Security Config: Loaded successfully");
         }
         Err(e) => {
-            println!("   ❌ Security Config: Failed to load");
-            println!("     └─ Error: {}", e);
+            ui_println!("   ❌ Security Config: Failed to load");
+            ui_println!("     └─ Error: {}", e);
         }
     }
 
     // Installed database
     match load_installed_db().await {
         Ok(db) => {
-            println!("   ✅ Installed Packages: {} package(s)", db.packages.len());
+            ui_println!("   ✅ Installed Packages: {} package(s)", db.packages.len());
         }
         Err(e) => {
-            println!("   ❌ Installed Packages: Failed to load");
-            println!("     └─ Error: {}", e);
+            ui_println!("   ❌ Installed Packages: Failed to load");
+            ui_println!("     └─ Error: {}", e);
         }
     }
 
-    println!();
-    println!("💡 Tip: Run 'ora config verify' to check for issues");
+    ui_println!();
+    ui_println!("💡 Tip: Run 'ora config verify' to check for issues");
 
     Ok(())
 }
 
+/// Outcome of one `config verify` check
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum CheckStatus {
+    Pass,
+    Warn,
+    Fail,
+}
+
+#[derive(Debug, Serialize)]
+struct Check {
+    name: String,
+    status: CheckStatus,
+}
+
+/// Everything `config verify` found, printed as-is with `--json`
+#[derive(Debug, Default, Serialize)]
+struct VerifyReport {
+    errors: Vec<String>,
+    warnings: Vec<String>,
+    checks: Vec<Check>,
+}
+
+impl VerifyReport {
+    /// Records `status` for check `name`, keeping the worst status seen for it
+    fn record(&mut self, name: &str, status: CheckStatus) {
+        match self.checks.iter_mut().find(|c| c.name == name) {
+            Some(check) => check.status = check.status.max(status),
+            None => self.checks.push(Check {
+                name: name.to_string(),
+                status,
+            }),
+        }
+    }
+
+    fn pass(&mut self, name: &str) {
+        self.record(name, CheckStatus::Pass);
+    }
+
+    fn warn(&mut self, name: &str, message: String) {
+        self.warnings.push(message);
+        self.record(name, CheckStatus::Warn);
+    }
+
+    fn fail(&mut self, name: &str, message: String) {
+        self.errors.push(message);
+        self.record(name, CheckStatus::Fail);
+    }
+}
+
 /// Verify all configuration files are readable and valid
-async fn verify_config() -> Result<()> {
-    println!("🔍 Verifying Ora Configuration...\n");
-
-    let mut errors = Vec::new();
-    let mut warnings = Vec::new();
-
-    // Check environment variables
-    println!("Checking environment variables...");
-    if let Ok(config_dir) = std::env::var("ORA_CONFIG_DIR") {
-        let path = PathBuf::from(&config_dir);
-        if !path.exists() {
-            warnings.push(format!(
+async fn verify_config(json: bool) -> Result<()> {
+    let report = run_checks().await;
+
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&report).context("Failed to serialize report")?
+        );
+    } else {
+        print_report(&report);
+    }
+
+    if !report.errors.is_empty() {
+        anyhow::bail!(
+            "Configuration verification failed with {} error(s)",
+            report.errors.len()
+        );
+    }
+    Ok(())
+}
+
+/// Runs every `config verify` check without printing anything
+async fn run_checks() -> VerifyReport {
+    let mut report = VerifyReport::default();
+
+    // Environment variables
+    match std::env::var("ORA_CONFIG_DIR")
+        .ok()
+        .filter(|dir| !dir.is_empty())
+    {
+        Some(config_dir) if !Path::new(&config_dir).exists() => report.warn(
+            "environment",
+            format!(
                 "ORA_CONFIG_DIR points to non-existent directory: {}",
                 config_dir
-            ));
-        } else if !path.is_dir() {
-            errors.push(format!("ORA_CONFIG_DIR is not a directory: {}", config_dir));
-        }
+            ),
+        ),
+        Some(config_dir) if !Path::new(&config_dir).is_dir() => report.fail(
+            "environment",
+            format!("ORA_CONFIG_DIR is not a directory: {}", config_dir),
+        ),
+        _ => report.pass("environment"),
     }
-    println!("   ✅ Environment variables checked\n");
 
-    // Verify global config
-    println!("Verifying global configuration...");
-    let global_config_path = Paths::config_file()?;
+    // Global config
+    let mut registries = Vec::new();
+    let mut shared_dirs = Vec::new();
     match load_global_config().await {
         Ok(config) => {
-            println!("   ✅ Global config is valid");
+            report.pass("global_config");
 
-            // Check for empty registries
             if config.registries.is_empty() {
-                warnings.push(
+                report.warn(
+                    "global_config",
                     "No registries configured. Add one with: ora registry add <name> <url>"
                         .to_string(),
                 );
             }
 
-            // Check for disabled registries
             let disabled_count = config.registries.iter().filter(|r| !r.enabled).count();
             if disabled_count > 0 && disabled_count == config.registries.len() {
-                warnings.push("All registries are disabled".to_string());
-            }
-        }
-        Err(e) => {
-            if global_config_path.exists() {
-                errors.push(format!("Global config is invalid: {}", e));
-            } else {
-                warnings.push("Global config doesn't exist (will use defaults)".to_string());
+                report.warn("global_config", "All registries are disabled".to_string());
             }
+
+            registries = config.registries;
+            shared_dirs = config.registry_cache_dirs;
         }
+        Err(e) => match Paths::config_file() {
+            Ok(path) if !path.exists() => report.warn(
+                "global_config",
+                "Global config doesn't exist (will use defaults)".to_string(),
+            ),
+            _ => report.fail("global_config", format!("Global config is invalid: {}", e)),
+        },
     }
-    println!();
 
-    // Verify security config
-    println!("Verifying security configuration...");
-    if let Ok(config_dir) = Paths::config_dir() {
-        let security_config_path = config_dir.join("security.toml");
-        match SecurityConfig::load() {
-            Ok(_) => {
-                println!("   ✅ Security config is valid");
-            }
-            Err(e) => {
-                if security_config_path.exists() {
-                    errors.push(format!("Security config is invalid: {}", e));
-                } else {
-                    warnings.push(
-                        "Security config doesn't exist (using defaults). Run: ora security init"
-                            .to_string(),
-                    );
-                }
-            }
-        }
+    // Security config
+    match SecurityConfig::load() {
+        Ok(_) => report.pass("security_config"),
+        Err(e) => match Paths::security_config_file() {
+            Ok(path) if !path.exists() => report.warn(
+                "security_config",
+                "Security config doesn't exist (using defaults). Run: ora security init"
+                    .to_string(),
+            ),
+            _ => report.fail(
+                "security_config",
+                format!("Security config is invalid: {}", e),
+            ),
+        },
     }
-    println!();
 
-    // Verify installed database
-    println!("Verifying installed packages database...");
-    let installed_db_path = Paths::installed_db_file()?;
+    // Installed database (a missing one just means nothing is installed yet)
     match load_installed_db().await {
-        Ok(_) => {
-            println!("   ✅ Installed packages database is valid");
-        }
-        Err(e) => {
-            if installed_db_path.exists() {
-                errors.push(format!("Installed packages database is invalid: {}", e));
-            } else {
-                println!("   ℹ No packages installed yet");
-            }
-        }
+        Ok(_) => report.pass("installed_db"),
+        Err(e) => match Paths::installed_db_file() {
+            Ok(path) if !path.exists() => report.pass("installed_db"),
+            _ => report.fail(
+                "installed_db",
+                format!("Installed packages database is invalid: {}", e),
+            ),
+        },
     }
-    println!();
 
-    // Check directory permissions
-    println!("Checking directory permissions...");
-    if let Ok(config_dir) = Paths::config_dir() {
-        check_directory_writable(&config_dir, "Config directory", &mut errors);
+    // Directory permissions
+    let directories = [
+        ("config_dir", "Config directory", Paths::config_dir()),
+        ("data_dir", "Data directory", Paths::data_dir()),
+        ("cache_dir", "Cache directory", Paths::cache_dir()),
+    ];
+    for (name, label, dir) in directories {
+        let result = dir
+            .map_err(|e| format!("{} cannot be determined: {}", label, e))
+            .and_then(|dir| check_directory_writable(&dir, label));
+        match result {
+            Ok(()) => report.pass(name),
+            Err(message) => report.fail(name, message),
+        }
     }
-    if let Ok(data_dir) = Paths::data_dir() {
-        check_directory_writable(&data_dir, "Data directory", &mut errors);
+
+    // Registry sync status (Direct URL registries are fetched on demand)
+    for registry in registries.iter().filter(|r| r.enabled) {
+        if RegistryType::from_url(&registry.url) == RegistryType::DirectUrl {
+            continue;
+        }
+        let name = format!("registry:{}", registry.name);
+        let clone = Cache::find_registry_clone(&registry.name, &shared_dirs)
+            .ok()
+            .filter(|path| path.join(".git").exists());
+        match clone {
+            Some(_) => report.pass(&name),
+            None => report.warn(
+                &name,
+                format!(
+                    "Registry '{}' has never been synced. Run: ora registry sync {}",
+                    registry.name, registry.name
+                ),
+            ),
+        }
     }
-    if let Ok(cache_dir) = Paths::cache_dir() {
-        check_directory_writable(&cache_dir, "Cache directory", &mut errors);
+
+    report
+}
+
+fn print_report(report: &VerifyReport) {
+    ui_println!("🔍 Verifying Ora Configuration...\n");
+
+    for check in &report.checks {
+        let icon = match check.status {
+            CheckStatus::Pass => "✅",
+            CheckStatus::Warn => "⚠️ ",
+            CheckStatus::Fail => "❌",
+        };
+        ui_println!("   {} {}", icon, check.name);
     }
-    println!("   ✅ Directory permissions checked\n");
+    ui_println!();
 
-    // Print summary
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    if errors.is_empty() && warnings.is_empty() {
-        println!("✅ All checks passed! Configuration is healthy.");
+    ui_println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    if report.errors.is_empty() && report.warnings.is_empty() {
+        ui_println!("✅ All checks passed! Configuration is healthy.");
     } else {
-        if !errors.is_empty() {
-            println!("❌ Found {} error(s):", errors.len());
-            for error in &errors {
-                println!("   • {}", error);
+        if !report.errors.is_empty() {
+            ui_println!("❌ Found {} error(s):", report.errors.len());
+            for error in &report.errors {
+                ui_println!("   • {}", error);
             }
-            println!();
+            ui_println!();
         }
 
-        if !warnings.is_empty() {
-            println!("⚠️  Found {} warning(s):", warnings.len());
-            for warning in &warnings {
-                println!("   • {}", warning);
+        if !report.warnings.is_empty() {
+            ui_println!("⚠️  Found {} warning(s):", report.warnings.len());
+            for warning in &report.warnings {
+                ui_println!("   • {}", warning);
             }
-            println!();
-        }
-
-        if !errors.is_empty() {
-            anyhow::bail!(
-                "Configuration verification failed with {} error(s)",
-                errors.len()
-            );
+            ui_println!();
         }
     }
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-
-    Ok(())
+    ui_println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 }
 
 /// Initialize all configuration files with defaults
 async fn init_config() -> Result<()> {
-    println!("🚀 Initializing Ora Configuration...\n");
+    ui_println!("🚀 Initializing Ora Configuration...\n");
 
     // Ensure directories exist
-    println!("Creating directories...");
+    ui_println!("Creating directories...");
     Paths::ensure_directories()?;
-    println!("   ✅ Directories created\n");
+    ui_println!("   ✅ Directories created\n");
 
     // Initialize global config
-    println!("Initializing global configuration...");
+    ui_println!("Initializing global configuration...");
     let global_config_path = Paths::config_file()?;
     if global_config_path.exists() {
-        println!(
+        ui_println!(
             "   ℹ Global config already exists at: {}",
             global_config_path.display()
         );
-        println!("   (not overwriting)");
+        ui_println!("   (not overwriting)");
     } else {
         let default_config = GlobalConfig::default();
-        crate::storage::database::save_global_config(&default_config).await?;
-        println!("   ✅ Created: {}", global_config_path.display());
+        save_global_config(&default_config).await?;
+        ui_println!("   ✅ Created: {}", global_config_path.display());
     }
-    println!();
+    ui_println!();
 
     // Initialize security config
-    println!("Initializing security configuration...");
+    ui_println!("Initializing security configuration...");
     if let Ok(config_dir) = Paths::config_dir() {
         let security_config_path = config_dir.join("security.toml");
         if security_config_path.exists() {
-            println!(
+            ui_println!(
                 "   ℹ Security config already exists at: {}",
                 security_config_path.display()
             );
-            println!("   (not overwriting)");
-            println!("   To reset, use: ora security reset");
+            ui_println!("   (not overwriting)");
+            ui_println!("   To reset, use: ora security reset");
         } else {
             let default_config = SecurityConfig::default();
             default_config.save()?;
-            println!("   ✅ Created: {}", security_config_path.display());
+            ui_println!("   ✅ Created: {}", security_config_path.display());
         }
     }
-    println!();
+    ui_println!();
 
     // Summary
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
-    println!("✅ Configuration initialized successfully!");
-    println!();
-    println!("📋 Next steps:");
-    println!("   1. Create a .repo file for your package");
-    println!("      See: docs/CREATING_REPO_FILES.md");
-    println!();
-    println!("   2. Install packages:");
-    println!("      ora install --repo ./package.repo");
-    println!();
-    println!("   3. View your configuration:");
-    println!("      ora config show");
-    println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    ui_println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+    ui_println!("✅ Configuration initialized successfully!");
+    ui_println!();
+    ui_println!("📋 Next steps:");
+    ui_println!("   1. Create a .repo file for your package");
+    ui_println!("      See: docs/CREATING_REPO_FILES.md");
+    ui_println!();
+    ui_println!("   2. Install packages:");
+    ui_println!("      ora install --repo ./package.repo");
+    ui_println!();
+    ui_println!("   3. View your configuration:");
+    ui_println!("      ora config show");
+    ui_println!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
+
+    Ok(())
+}
+
+/// Runs the config and installed database migrations, reporting what changes
+///
+/// Both files are read as-is rather than through `load_global_config` and
+/// `load_installed_db`, which would migrate them silently first.
+async fn migrate_config(dry_run: bool) -> Result<()> {
+    ui_println!("🔄 Ora Migrations\n");
+
+    let config_path = Paths::config_file()?;
+    let config_changed = match read_file_user_friendly_async(&config_path).await? {
+        None => {
+            ui_println!("Global config: not created yet - {}", config_path.display());
+            false
+        }
+        Some(content) => {
+            let original: toml::Value = toml::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+            let mut config: GlobalConfig = toml::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+            let old_version = version_label(&config.config_version);
+            migrate_global_config(&mut config)?;
+
+            ui_println!("Global config: {}", config_path.display());
+            let changed = config.config_version != old_version;
+            if changed {
+                ui_println!("   Version: {} → {}", old_version, config.config_version);
+                print_added_fields(&original, &toml::Value::try_from(&config)?);
+                if !dry_run {
+                    save_global_config(&config).await?;
+                }
+            } else {
+                ui_println!("   Version: {} (up to date)", CURRENT_CONFIG_VERSION);
+            }
+            changed
+        }
+    };
+    ui_println!();
+
+    let db_path = Paths::installed_db_file()?;
+    let db_changed = match read_file_user_friendly_async(&db_path).await? {
+        None => {
+            ui_println!(
+                "Installed packages: not created yet - {}",
+                db_path.display()
+            );
+            false
+        }
+        Some(content) => {
+            let original: toml::Value = toml::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", db_path.display()))?;
+            let mut db: InstalledDatabase = toml::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", db_path.display()))?;
+            let mut old_versions: Vec<(String, String)> = db
+                .packages
+                .iter()
+                .map(|(name, p)| (name.clone(), version_label(&p.schema_version)))
+                .collect();
+            old_versions.sort();
+            migrate_installed_database(&mut db)?;
+
+            ui_println!("Installed packages: {}", db_path.display());
+            let mut migrated = 0;
+            for (name, old_version) in &old_versions {
+                let new_version = &db.packages[name].schema_version;
+                if new_version != old_version {
+                    ui_println!("   {}: schema {} → {}", name, old_version, new_version);
+                    migrated += 1;
+                }
+            }
+            if migrated > 0 {
+                print_added_fields(&original, &toml::Value::try_from(&db)?);
+                if !dry_run {
+                    save_installed_db(&db).await?;
+                }
+            } else {
+                ui_println!(
+                    "   Schema: {} for {} package(s) (up to date)",
+                    CURRENT_SCHEMA_VERSION,
+                    old_versions.len()
+                );
+            }
+            migrated > 0
+        }
+    };
+    ui_println!();
 
+    if !config_changed && !db_changed {
+        ui_println!("✅ Nothing to migrate");
+    } else if dry_run {
+        ui_println!("ℹ Dry run: nothing was written. Run 'ora config migrate' to apply.");
+    } else {
+        ui_println!("✅ Migration complete");
+    }
     Ok(())
 }
 
+/// Version as shown by `config migrate`; unversioned files are `0.0`
+fn version_label(version: &str) -> String {
+    if version.is_empty() {
+        "0.0".to_string()
+    } else {
+        version.to_string()
+    }
+}
+
+fn print_added_fields(before: &toml::Value, after: &toml::Value) {
+    let added = added_fields(before, after);
+    if !added.is_empty() {
+        ui_println!("   Fields added: {}", added.join(", "));
+    }
+}
+
 /// Helper function to print file status
 fn print_file_status(label: &str, path: &PathBuf) {
     let status = if path.exists() {
@@ -330,7 +551,7 @@ fn print_file_status(label: &str, path: &PathBuf) {
         "ℹ not created yet (will use defaults)".to_string()
     };
 
-    println!("   {:20} {} - {}", label, status, path.display());
+    ui_println!("   {:20} {} - {}", label, status, path.display());
 }
 
 /// Helper function to print directory status
@@ -345,17 +566,15 @@ fn print_dir_status(label: &str, path: &Path) {
         "ℹ not created yet".to_string()
     };
 
-    println!("   {:20} {} - {}", label, status, path.display());
+    ui_println!("   {:20} {} - {}", label, status, path.display());
 }
 
 /// Helper function to check if directory is writable
-fn check_directory_writable(path: &PathBuf, label: &str, errors: &mut Vec<String>) {
+fn check_directory_writable(path: &Path, label: &str) -> Result<(), String> {
     if !path.exists() {
         // Try to create it
-        if let Err(e) = std::fs::create_dir_all(path) {
-            errors.push(format!("{} cannot be created: {}", label, e));
-        }
-        return;
+        return std::fs::create_dir_all(path)
+            .map_err(|e| format!("{} cannot be created: {}", label, e));
     }
 
     // Test write by creating a temp file
@@ -363,23 +582,8 @@ fn check_directory_writable(path: &PathBuf, label: &str, errors: &mut Vec<String
     match std::fs::write(&test_file, b"test") {
         Ok(_) => {
             let _ = std::fs::remove_file(&test_file);
+            Ok(())
         }
-        Err(e) => {
-            errors.push(format!("{} is not writable: {}", label, e));
-        }
-    }
-}
-
-/// Format bytes as human-readable size
-fn format_size(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = 1024 * KB;
-
-    if bytes < KB {
-        format!("{} B", bytes)
-    } else if bytes < MB {
-        format!("{:.1} KB", bytes as f64 / KB as f64)
-    } else {
-        format!("{:.1} MB", bytes as f64 / MB as f64)
+        Err(e) => Err(format!("{} is not writable: {}", label, e)),
     }
 }
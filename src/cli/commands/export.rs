@@ -0,0 +1,41 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::api::Ora;
+use crate::cli::args::ExportArgs;
+use crate::config::manifest::{Manifest, ManifestEntry, ManifestFormat};
+
+pub async fn execute(args: ExportArgs) -> Result<()> {
+    let mut manifest = Manifest::default();
+    for package in Ora::new().list().await? {
+        match ManifestEntry::from_installed(&package) {
+            Some(entry) => manifest.packages.push(entry),
+            None => eprintln!(
+                "⚠️  Skipping {}: installed from a local archive",
+                package.name
+            ),
+        }
+    }
+
+    let format = args.format.unwrap_or_else(|| {
+        args.output
+            .as_deref()
+            .map(|path| ManifestFormat::from_path(Path::new(path)))
+            .unwrap_or(ManifestFormat::Toml)
+    });
+    let content = manifest.serialize(format)?;
+
+    match args.output {
+        Some(path) => {
+            std::fs::write(&path, content).context(format!("Failed to write {}", path))?;
+            println!(
+                "✅ Exported {} package(s) to {}",
+                manifest.packages.len(),
+                path
+            );
+        }
+        None => print!("{}", content),
+    }
+
+    Ok(())
+}
@@ -0,0 +1,73 @@
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::api::Ora;
+use crate::cli::args::{ImportArgs, InstallArgs};
+use crate::cli::commands::install;
+use crate::config::manifest::{Manifest, ManifestFormat};
+use crate::utils::ui;
+
+pub async fn execute(args: ImportArgs) -> Result<()> {
+    let path = Path::new(&args.file);
+    let format = args
+        .format
+        .unwrap_or_else(|| ManifestFormat::from_path(path));
+    let content = std::fs::read_to_string(path).context(format!("Failed to read {}", args.file))?;
+    let manifest = Manifest::parse(&content, format)?;
+
+    let installed = Ora::new().list().await?;
+    let mut failed = Vec::new();
+
+    for entry in manifest.packages {
+        if let Some(existing) = installed.iter().find(|p| p.name == entry.name) {
+            if existing.version == entry.version {
+                println!("⏭️  {} {} is already installed", entry.name, entry.version);
+            } else {
+                println!(
+                    "⏭️  {} is installed at {} (manifest: {}); use 'ora update' to change it",
+                    entry.name, existing.version, entry.version
+                );
+            }
+            continue;
+        }
+
+        let package = match &entry.registry {
+            Some(registry) => format!("{}@{}", entry.name, registry),
+            None => entry.name.clone(),
+        };
+        let install_args = InstallArgs {
+            package: Some(package),
+            version: Some(entry.version),
+            repo: entry.repo,
+            userland: false,
+            system: false,
+            allow_insecure: false,
+            allow_prerelease: false,
+            local: None,
+            metadata: None,
+            os: None,
+            arch: None,
+            verify_only: false,
+            download_only: false,
+            output_dir: None,
+            skip_checksum: false,
+            locked: false,
+            lockfile: None,
+        };
+
+        if let Err(e) = install::execute(install_args).await {
+            ui::error(&format!("Failed to install {}: {}", entry.name, e));
+            failed.push(entry.name);
+        }
+    }
+
+    if !failed.is_empty() {
+        anyhow::bail!(
+            "Failed to import {} package(s): {}",
+            failed.len(),
+            failed.join(", ")
+        );
+    }
+
+    Ok(())
+}
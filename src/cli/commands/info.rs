@@ -1,27 +1,48 @@
 use anyhow::Result;
+use chrono::Utc;
+use indicatif::HumanBytes;
 
 use crate::cli::args::InfoArgs;
+use crate::config::installed::InstalledPackage;
+use crate::config::repo::RepoConfig;
+use crate::providers::{create_provider, list_versions_cached, Version};
 use crate::registry::RegistryManager;
-use crate::storage::database::load_installed_db;
+use crate::storage::database::{load_global_config, load_installed_db};
 
 pub async fn execute(args: InfoArgs) -> Result<()> {
-    log::debug!("Getting info for: {}", args.package);
+    let package = load_global_config().await?.resolve_alias(&args.package);
+    log::debug!("Getting info for: {}", package);
 
     // Check if installed
     let db = load_installed_db().await?;
-    if let Some(installed) = db.packages.get(&args.package) {
-        println!("Package: {}", args.package);
+    if let Some(installed) = db.packages.get(&package) {
+        println!("Package: {}", package);
         println!("Status: Installed");
         println!("Version: {}", installed.version);
         println!("Installed: {}", installed.installed_at);
+        if let Some(bytes) = installed.download_bytes {
+            println!("Download size: {}", HumanBytes(bytes));
+        }
+        if let Some(ms) = installed.duration_ms {
+            println!("Install time: {:.1}s", ms as f64 / 1000.0);
+        }
         println!("Mode: {}", installed.install_mode);
         println!("Directory: {}", installed.install_dir);
         println!("Symlinks: {}", installed.symlinks.len());
+        if args.files {
+            print_files(installed);
+        }
+        println!();
+    } else if args.files {
+        println!(
+            "'{}' is not installed; its file list is only known once the archive has been extracted.",
+            package
+        );
         println!();
     }
 
     // Get info from registry
-    match RegistryManager::find_package(&args.package).await {
+    match RegistryManager::find_package(&package).await {
         Ok((repo_config, registry_name)) => {
             println!("Package: {}", repo_config.name);
             println!("Description: {}", repo_config.description);
@@ -41,7 +62,29 @@ pub async fn execute(args: InfoArgs) -> Result<()> {
                     println!("Tags: {}", metadata.tags.join(", "));
                 }
             }
+
+            // The binaries globs are what decides which files get linked
+            if args.files && !db.packages.contains_key(&package) {
+                let binaries: Vec<String> = repo_config
+                    .install
+                    .binaries
+                    .iter()
+                    .map(|b| b.to_string())
+                    .collect();
+                println!("Binaries: {}", binaries.join(", "));
+            }
+
+            if args.versions {
+                print_versions(&repo_config).await?;
+            }
+
+            if args.remote {
+                println!();
+                println!("Definition ({}):", registry_name);
+                print!("{}", toml::to_string_pretty(&repo_config.redacted())?);
+            }
         }
+        Err(e) if args.remote => return Err(e),
         Err(e) => {
             log::warn!("Could not find package in registry: {}", e);
         }
@@ -49,3 +92,73 @@ pub async fn execute(args: InfoArgs) -> Result<()> {
 
     Ok(())
 }
+
+/// Prints the files and binary symlinks recorded at install time
+fn print_files(installed: &InstalledPackage) {
+    println!();
+    println!("Files:");
+    if installed.files.is_empty() {
+        println!("  (none recorded)");
+    }
+    for file in &installed.files {
+        println!("  {}", file);
+    }
+
+    if !installed.symlinks.is_empty() {
+        println!();
+        println!("Linked binaries:");
+        for symlink in &installed.symlinks {
+            println!("  {}", symlink);
+        }
+    }
+}
+
+/// Prints available versions, newest first, with release date and age
+async fn print_versions(repo_config: &RepoConfig) -> Result<()> {
+    let provider = create_provider(repo_config)?;
+    let mut versions = list_versions_cached(provider.as_ref(), &repo_config.name).await?;
+
+    // Newest first: by release date when known, then by semver / tag
+    versions.sort_by(|a, b| {
+        b.published_date().cmp(&a.published_date()).then_with(|| {
+            match (
+                semver::Version::parse(b.tag.trim_start_matches('v')),
+                semver::Version::parse(a.tag.trim_start_matches('v')),
+            ) {
+                (Ok(v_b), Ok(v_a)) => v_b.cmp(&v_a),
+                _ => b.tag.cmp(&a.tag),
+            }
+        })
+    });
+
+    println!();
+    println!("Versions:");
+    if versions.is_empty() {
+        println!("  (none available)");
+    }
+    for version in &versions {
+        let prerelease = if version.prerelease {
+            " (prerelease)"
+        } else {
+            ""
+        };
+        println!(
+            "  {:<20} {}{}",
+            version.tag,
+            release_age(version),
+            prerelease
+        );
+    }
+
+    Ok(())
+}
+
+fn release_age(version: &Version) -> String {
+    match version.published_date() {
+        Some(date) => {
+            let days = (Utc::now() - date).num_days().max(0);
+            format!("{} ({} days ago)", date.format("%Y-%m-%d"), days)
+        }
+        None => "release date unknown".to_string(),
+    }
+}
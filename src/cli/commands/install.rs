@@ -1,351 +1,240 @@
 use anyhow::{Context, Result};
-use chrono::Utc;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
+use crate::api::{
+    InstallOutcome, InstallSpec, Ora, VerificationReport, ENV_SNIPPET_KEY, TARGET_ARCH_KEY,
+    TARGET_OS_KEY,
+};
 use crate::cli::args::InstallArgs;
 use crate::config::global::InstallMode;
 use crate::config::installed::InstalledPackage;
-use crate::config::local_metadata::LocalMetadata;
-use crate::config::repo::RepoConfig;
-use crate::config::GlobalConfig;
-use crate::installer::{run_post_install, Deployer, Downloader, Extractor, Verifier};
-use crate::providers::create_provider;
-use crate::registry::RegistryManager;
-use crate::security::{AuditLogger, SecurityWarningManager};
-use crate::storage::cache::Cache;
-use crate::storage::database::{load_global_config, load_installed_db, save_installed_db};
-use crate::utils::platform::{default_arch_mapping, default_os_mapping, Platform};
+use crate::config::lockfile::{Lockfile, DEFAULT_LOCKFILE};
+use crate::config::repo::ChecksumAlgorithm;
+use crate::installer::hooks::{run_hook, HookEvent};
+use crate::installer::shell_env::{self, Shell};
+use crate::storage::database::load_global_config;
+use crate::utils::ui;
 
 pub async fn execute(args: InstallArgs) -> Result<()> {
-    // Check if this is a local installation
-    if args.local.is_some() {
-        return execute_local_install(args).await;
+    if args.locked && args.package.is_none() {
+        return install_all_locked(args).await;
     }
 
-    log::info!("Installing package: {}", args.package);
-
-    // Parse package name and optional registry (package@registry syntax)
-    let (package_name, registry_name) = if args.package.contains('@') {
-        let parts: Vec<&str> = args.package.splitn(2, '@').collect();
-        (parts[0].to_string(), Some(parts[1].to_string()))
-    } else {
-        (args.package.clone(), None)
-    };
-
-    // Determine install mode
-    let install_mode = if args.system {
-        InstallMode::System
-    } else {
-        InstallMode::Userland
-    };
-
-    // Check if already installed
-    let mut db = load_installed_db().await?;
-    if db.packages.contains_key(&package_name) {
-        log::warn!("Package '{}' is already installed", package_name);
-        println!(
-            "Package '{}' is already installed. Use 'ora update' to upgrade.",
-            package_name
-        );
-        return Ok(());
+    if let Some(package) = run(args, None).await? {
+        run_hook(HookEvent::Install, &package.name, &package.version).await;
     }
+    Ok(())
+}
 
-    // Load repo config and track registry source
-    let (repo_config, registry_source) = if let Some(repo_file) = &args.repo {
-        // Load from file
-        let content = tokio::fs::read_to_string(repo_file)
-            .await
-            .context("Failed to read .repo file")?;
-        let config =
-            toml::from_str::<RepoConfig>(&content).context("Failed to parse .repo file")?;
-        (config, format!("file:{}", repo_file))
-    } else if let Some(registry) = registry_name {
-        // Find in specific registry
-        let (config, reg_name) =
-            RegistryManager::find_package_in_specific_registry(&package_name, &registry).await?;
-        (config, format!("registry:{}", reg_name))
-    } else {
-        // Find in any registry
-        let (config, reg_name) = RegistryManager::find_package(&package_name).await?;
-        (config, format!("registry:{}", reg_name))
-    };
-
-    // Check security warnings BEFORE starting installation
-    // SECURITY: Do NOT silently ignore config load failures - they could be permission issues
-    // that prevent security settings from being applied
-    let global_config = match load_global_config().await {
-        Ok(config) => config,
-        Err(e) => {
-            log::warn!("Failed to load global configuration: {}", e);
-            eprintln!("⚠️  Warning: Could not load configuration file");
-            eprintln!("   {}", e);
-            eprintln!("   Using default configuration (this may affect security settings)");
-            eprintln!();
-            GlobalConfig::default()
-        }
-    };
-    SecurityWarningManager::check_and_warn(&repo_config, args.allow_insecure, &global_config)?;
-
-    // Detect platform
-    let platform = Platform::detect();
-
-    // Apply mappings (use helper methods for v1/v2 compatibility)
-    let os_mapping = {
-        let map = repo_config.get_os_map();
-        if map.is_empty() {
-            default_os_mapping()
-        } else {
-            map
-        }
-    };
-
-    let arch_mapping = {
-        let map = repo_config.get_arch_map();
-        if map.is_empty() {
-            default_arch_mapping()
-        } else {
-            map
+/// Installs every package of the lockfile, continuing past failures
+async fn install_all_locked(args: InstallArgs) -> Result<()> {
+    let lockfile = load_lockfile(&args)?;
+    let mut failed = Vec::new();
+
+    for locked in &lockfile.packages {
+        let package_args = InstallArgs {
+            package: Some(locked.name.clone()),
+            ..args.clone()
+        };
+        match run(package_args, None).await {
+            Ok(Some(package)) => {
+                run_hook(HookEvent::Install, &package.name, &package.version).await;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                ui::error(&format!("Failed to install {}: {:#}", locked.name, e));
+                failed.push(locked.name.as_str());
+            }
         }
-    };
-
-    let mapped_os = platform.map_os(&os_mapping);
-    let mapped_arch = platform.map_arch(&arch_mapping);
-
-    log::info!(
-        "Platform: {} ({}), Arch: {} ({})",
-        platform.os,
-        mapped_os,
-        platform.arch,
-        mapped_arch
-    );
-
-    // Create provider and get version
-    let provider = create_provider(&repo_config)?;
-
-    let version = if let Some(v) = &args.version {
-        v.clone()
-    } else {
-        // Get latest non-prerelease version
-        let versions = provider.list_versions().await?;
-        let latest = versions
-            .iter()
-            .filter(|v| !v.prerelease)
-            .max_by(|a, b| {
-                // Use semver comparison if possible, fallback to string comparison
-                match (
-                    semver::Version::parse(&a.tag),
-                    semver::Version::parse(&b.tag),
-                ) {
-                    (Ok(v_a), Ok(v_b)) => v_a.cmp(&v_b),
-                    _ => a.tag.cmp(&b.tag),
-                }
-            })
-            .context("No versions available")?;
-        latest.tag.clone()
-    };
-
-    log::debug!("Installing version: {}", version);
-
-    // Get download URL
-    let download_url = provider
-        .get_download_url(&version, &mapped_os, &mapped_arch)
-        .await?;
-
-    log::debug!("Download URL: {}", download_url);
-
-    // Download
-    // Strip trailing slashes from URL before extracting filename
-    let url_without_trailing_slash = download_url.trim_end_matches('/');
-    let filename = url_without_trailing_slash
-        .split('/')
-        .next_back()
-        .context("Invalid download URL")?;
+    }
 
-    // Validate that we got a non-empty filename
-    if filename.is_empty() {
+    if !failed.is_empty() {
         anyhow::bail!(
-            "Failed to extract filename from download URL: {}\n\
-             The URL appears to be invalid. Please check the .repo file's download URL template.",
-            download_url
+            "Failed to install {} locked package(s): {}",
+            failed.len(),
+            failed.join(", ")
         );
     }
-
-    let download_path = Cache::download_path(filename)?;
-
-    let downloader = Downloader::new()?;
-    downloader.download(&download_url, &download_path).await?;
-
-    // Verify
-    let verifier = Verifier::new()?;
-    verifier
-        .verify(
-            &download_path,
-            &repo_config,
-            &version,
-            &mapped_os,
-            &mapped_arch,
-            args.allow_insecure,
-        )
-        .await?;
-
-    // Extract
-    let extract_dir = Cache::download_path(&format!("{}_extract", args.package))?;
-    Extractor::extract(&download_path, &extract_dir)?;
-
-    // Deploy
-    let deployment = Deployer::deploy(
-        &extract_dir,
-        &repo_config.install,
-        install_mode.clone(),
-        &args.package,
-        &version,
-    )?;
-
-    // Run post-install script if configured
-    if let Some(post_install_script) = &repo_config.install.post_install {
-        let install_dir = std::path::PathBuf::from(&deployment.install_dir);
-        run_post_install(
-            post_install_script,
-            &install_dir,
-            &version,
-            &repo_config.install.env,
-            args.allow_insecure,
-        )
-        .await?;
-    }
-
-    // Update installed database
-    let installed_package = InstalledPackage {
-        schema_version: crate::config::migrations::CURRENT_SCHEMA_VERSION.to_string(),
-        name: package_name.clone(),
-        version: version.clone(),
-        installed_at: Utc::now(),
-        install_mode: format!("{:?}", install_mode).to_lowercase(),
-        install_dir: deployment.install_dir.clone(),
-        files: deployment.files,
-        symlinks: deployment.symlinks,
-        registry_source: registry_source.clone(),
-        checksums: Default::default(),
-        allow_insecure: args.allow_insecure,
-        metadata: Default::default(),
-    };
-
-    db.packages.insert(package_name.clone(), installed_package);
-    save_installed_db(&db).await?;
-
-    // Audit log
-    AuditLogger::new()?
-        .log_install(&package_name, &version, &registry_source, true)
-        .await?;
-
-    println!("✅ Successfully installed {} {}", package_name, version);
-
     Ok(())
 }
 
-async fn execute_local_install(args: InstallArgs) -> Result<()> {
-    let archive_path = args
-        .local
-        .as_ref()
-        .context("Local archive path is required but was not provided")?;
-
-    // Require metadata file
-    let metadata_path = args
-        .metadata
-        .as_ref()
-        .context("--metadata is required for local installations")?;
-
-    log::debug!("Installing from local archive: {}", archive_path);
+fn load_lockfile(args: &InstallArgs) -> Result<Lockfile> {
+    Lockfile::load(Path::new(
+        args.lockfile.as_deref().unwrap_or(DEFAULT_LOCKFILE),
+    ))
+}
 
-    // Load and validate metadata
-    let metadata_content = tokio::fs::read_to_string(metadata_path)
-        .await
-        .context("Failed to read metadata file")?;
-    let metadata: LocalMetadata =
-        toml::from_str(&metadata_content).context("Failed to parse metadata file")?;
-    metadata.validate()?;
+/// Installs and reports the package, without running hooks
+///
+/// `replaces` is the installed entry an update removed first. Returns the
+/// newly installed package, or `None` if it was already installed.
+pub async fn run(
+    args: InstallArgs,
+    replaces: Option<InstalledPackage>,
+) -> Result<Option<InstalledPackage>> {
+    let config = load_global_config().await?;
+    let mut package = args.package.clone().context("No package given")?;
+    if args.local.is_none() {
+        package = config.resolve_alias(&package);
+    }
+    // Explicit flags win over the configured default mode, applied by the API
+    let mode = if args.system {
+        Some(InstallMode::System)
+    } else if args.userland {
+        Some(InstallMode::Userland)
+    } else {
+        None
+    };
 
-    // Determine install mode
-    let install_mode = if args.system {
-        InstallMode::System
+    // With --locked, the lockfile decides where the package comes from
+    let (package, repo, locked) = if args.locked {
+        let name = package.split('@').next().unwrap_or_default();
+        let locked = load_lockfile(&args)?.get(name)?.clone();
+        (locked.package_spec(), locked.repo.clone(), Some(locked))
     } else {
-        InstallMode::Userland
+        (package, args.repo, None)
     };
 
-    // Check if already installed
-    let mut db = load_installed_db().await?;
-    if db.packages.contains_key(&metadata.name) {
-        log::warn!("Package '{}' is already installed", metadata.name);
-        println!(
-            "Package '{}' is already installed. Uninstall it first or use a different name.",
-            metadata.name
-        );
-        return Ok(());
-    }
+    let spec = InstallSpec {
+        package,
+        version: args.version,
+        repo,
+        mode,
+        allow_insecure: args.allow_insecure,
+        allow_prerelease: args.allow_prerelease,
+        local: args.local,
+        metadata: args.metadata,
+        os: args.os,
+        arch: args.arch,
+        verify_only: args.verify_only || args.download_only,
+        skip_checksum: args.skip_checksum,
+        locked,
+        replaces,
+    };
 
-    // Copy archive to cache and extract
-    let archive_path = Path::new(archive_path);
-    if !archive_path.exists() {
-        anyhow::bail!("Archive file not found: {}", archive_path.display());
-    }
+    let installed = match Ora::new().install(&spec).await? {
+        InstallOutcome::AlreadyInstalled(existing) => {
+            if spec.local.is_some() {
+                println!(
+                    "Package '{}' is already installed. Uninstall it first or use a different name.",
+                    existing.name
+                );
+            } else {
+                println!(
+                    "Package '{}' is already installed. Use 'ora update' to upgrade.",
+                    existing.name
+                );
+            }
+            None
+        }
+        InstallOutcome::Verified(report) => {
+            print_verification(&report);
+            if args.download_only {
+                let output_dir = Path::new(args.output_dir.as_deref().unwrap_or("."));
+                let (archive, checksum_file) = write_download(&report, output_dir)?;
+                println!("📦 Archive written to {}", archive.display());
+                println!("   Checksum written to {}", checksum_file.display());
+            }
+            None
+        }
+        InstallOutcome::Installed {
+            package,
+            kept_archive,
+            warnings,
+        } => {
+            for warning in &warnings {
+                ui::warning(warning);
+            }
+            if let Some(path) = kept_archive {
+                println!("📦 Archive kept at {}", path.display());
+            }
+            if spec.local.is_some() {
+                println!(
+                    "✅ Successfully installed {} {} from local archive",
+                    package.name, package.version
+                );
+            } else {
+                println!(
+                    "✅ Successfully installed {} {}",
+                    package.name, package.version
+                );
+            }
+            if let Some(snippet) = package.metadata.get(ENV_SNIPPET_KEY) {
+                println!("   Environment settings written to {}", snippet);
+                println!(
+                    "   Load them from your shell profile with: {}",
+                    shell_env::profile_line(Shell::detect())?
+                );
+            }
+            if let (Some(os), Some(arch)) = (
+                package.metadata.get(TARGET_OS_KEY),
+                package.metadata.get(TARGET_ARCH_KEY),
+            ) {
+                println!(
+                    "   Binaries for {}/{} were not linked; they are in {}",
+                    os, arch, package.install_dir
+                );
+            }
+            Some(package)
+        }
+    };
 
-    let cache_archive_path = Cache::download_path(&format!(
-        "{}-{}-local.tar.gz",
-        metadata.name, metadata.version
-    ))?;
-    tokio::fs::copy(archive_path, &cache_archive_path)
-        .await
-        .context("Failed to copy archive to cache")?;
+    Ok(installed)
+}
 
-    // Extract archive
-    let extract_dir = Cache::download_path(&format!("{}_extract", metadata.name))?;
-    Extractor::extract(&cache_archive_path, &extract_dir)?;
+/// Copies a verified archive into `output_dir`, next to a `<archive>.<algorithm>`
+/// file in `sha256sum` format
+fn write_download(report: &VerificationReport, output_dir: &Path) -> Result<(PathBuf, PathBuf)> {
+    std::fs::create_dir_all(output_dir)
+        .with_context(|| format!("Failed to create {}", output_dir.display()))?;
+    let filename = report
+        .archive
+        .file_name()
+        .context("Invalid archive path")?
+        .to_string_lossy()
+        .into_owned();
+
+    let archive = output_dir.join(&filename);
+    std::fs::copy(&report.archive, &archive)
+        .with_context(|| format!("Failed to write {}", archive.display()))?;
+
+    let checksum_file = output_dir.join(format!(
+        "{}.{}",
+        filename,
+        report.checksum_algorithm.as_str()
+    ));
+    std::fs::write(
+        &checksum_file,
+        format!("{}  {}\n", report.checksum, filename),
+    )
+    .with_context(|| format!("Failed to write {}", checksum_file.display()))?;
+
+    Ok((archive, checksum_file))
+}
 
-    // Deploy package
-    let install_config = crate::config::repo::InstallConfig {
-        mode: None, // Mode is passed separately to Deployer::deploy
-        binaries: metadata.binaries.clone(),
-        files: vec![],
-        post_install: None,
-        env: Default::default(),
+fn print_verification(report: &VerificationReport) {
+    let algorithm = match report.checksum_algorithm {
+        ChecksumAlgorithm::Sha256 => "sha256",
+        ChecksumAlgorithm::Sha512 => "sha512",
     };
-
-    let deployment = Deployer::deploy(
-        &extract_dir,
-        &install_config,
-        install_mode.clone(),
-        &metadata.name,
-        &metadata.version,
-    )?;
-
-    // Update installed database
-    let installed_package = InstalledPackage {
-        schema_version: crate::config::migrations::CURRENT_SCHEMA_VERSION.to_string(),
-        name: metadata.name.clone(),
-        version: metadata.version.clone(),
-        installed_at: Utc::now(),
-        install_mode: format!("{:?}", install_mode).to_lowercase(),
-        install_dir: deployment.install_dir.clone(),
-        files: deployment.files,
-        symlinks: deployment.symlinks,
-        registry_source: format!("local:{}", archive_path.display()),
-        checksums: Default::default(),
-        allow_insecure: args.allow_insecure,
-        metadata: Default::default(),
+    let checksum_status = if report.checksum_verified {
+        "matches published checksum"
+    } else {
+        "not checked against a published checksum"
+    };
+    let signature_status = if report.signature_verified {
+        "verified"
+    } else {
+        "not checked"
     };
 
-    db.packages.insert(metadata.name.clone(), installed_package);
-    save_installed_db(&db).await?;
-
-    // Audit log
-    AuditLogger::new()?
-        .log_install(&metadata.name, &metadata.version, "local", true)
-        .await?;
-
+    println!("✅ Verified {} {}", report.package, report.version);
+    println!("   URL:       {}", report.url);
+    println!("   Archive:   {}", report.archive.display());
     println!(
-        "✅ Successfully installed {} {} from local archive",
-        metadata.name, metadata.version
+        "   {}:    {} ({})",
+        algorithm, report.checksum, checksum_status
     );
-
-    Ok(())
+    println!("   Signature: {}", signature_status);
+    println!("   Nothing was installed");
 }
@@ -1,32 +1,190 @@
 use anyhow::Result;
+use indicatif::HumanBytes;
+use std::collections::HashMap;
+use std::path::Path;
 
+use crate::api::Ora;
 use crate::cli::args::ListArgs;
-use crate::storage::database::load_installed_db;
+use crate::config::installed::InstalledPackage;
+use crate::installer::deployer::CURRENT_LINK_NAME;
+use crate::providers::{is_newer_version, Version};
+use crate::storage::cache::Cache;
+use crate::ui_println;
+use crate::utils::table::Table;
+use crate::utils::ui;
 
 pub async fn execute(args: ListArgs) -> Result<()> {
-    let db = load_installed_db().await?;
+    let ora = Ora::new();
+    let packages = ora.list().await?;
 
-    if db.packages.is_empty() {
+    if packages.is_empty() {
         println!("No packages installed");
         return Ok(());
     }
 
-    println!("Installed packages:");
-    println!();
+    let filtered = args.outdated || args.insecure || args.registry.is_some();
+    let registry_source = args
+        .registry
+        .as_ref()
+        .map(|name| format!("registry:{}", name));
+    let packages: Vec<InstalledPackage> = packages
+        .into_iter()
+        .filter(|pkg| !args.insecure || pkg.allow_insecure)
+        .filter(|pkg| {
+            registry_source
+                .as_ref()
+                .is_none_or(|source| pkg.registry_source == *source)
+        })
+        .collect();
 
-    for (name, pkg) in &db.packages {
-        if args.verbose {
-            println!("Package: {}", name);
-            println!("  Version: {}", pkg.version);
-            println!("  Installed: {}", pkg.installed_at);
-            println!("  Mode: {}", pkg.install_mode);
-            println!("  Directory: {}", pkg.install_dir);
-            println!("  Symlinks: {}", pkg.symlinks.len());
-            println!();
+    // Only the packages left by the cheap filters are looked up
+    let mut latest = HashMap::new();
+    if args.outdated {
+        for pkg in &packages {
+            match ora.latest_version(pkg).await {
+                Ok(Some(version)) if is_newer_version(&version.tag, &pkg.version) => {
+                    latest.insert(pkg.name.clone(), version);
+                }
+                Ok(_) => {}
+                Err(e) => ui::warning(&format!(
+                    "Could not check {} for updates: {:#}",
+                    pkg.name, e
+                )),
+            }
+        }
+    }
+    let packages: Vec<InstalledPackage> = packages
+        .into_iter()
+        .filter(|pkg| !args.outdated || latest.contains_key(&pkg.name))
+        .collect();
+
+    if packages.is_empty() {
+        if args.outdated {
+            println!("All matching packages are up to date");
         } else {
-            println!("  {} @ {} ({})", name, pkg.version, pkg.install_mode);
+            println!("No installed packages match the filters");
+        }
+        return Ok(());
+    }
+
+    if filtered {
+        println!("Installed packages ({} matching):", packages.len());
+    } else {
+        println!("Installed packages:");
+    }
+    println!();
+
+    if args.tree {
+        return print_tree(&packages);
+    }
+
+    if !args.verbose {
+        let mut headers = vec!["NAME", "VERSION", "REGISTRY", "MODE"];
+        if args.outdated {
+            headers.push("AVAILABLE");
+        }
+        let mut table = Table::new(&headers);
+        for pkg in &packages {
+            let mut row = vec![
+                pkg.name.clone(),
+                pkg.version.clone(),
+                source_label(&pkg.registry_source).to_string(),
+                pkg.install_mode.clone(),
+            ];
+            row.extend(latest.get(&pkg.name).map(describe_update));
+            table.add_row(row);
+        }
+        table.print();
+        return Ok(());
+    }
+
+    for pkg in &packages {
+        println!("Package: {}", pkg.name);
+        println!("  Version: {}", pkg.version);
+        if let Some(version) = latest.get(&pkg.name) {
+            println!("  Available: {}", describe_update(version));
+        }
+        println!("  Installed: {}", pkg.installed_at);
+        println!("  Mode: {}", pkg.install_mode);
+        println!("  Source: {}", pkg.registry_source);
+        println!("  Directory: {}", pkg.install_dir);
+        println!("  Symlinks: {}", pkg.symlinks.len());
+        println!();
+    }
+
+    Ok(())
+}
+
+/// Registry name for packages installed from a registry, else the raw source
+/// (`file:<path>`, `url:<url>`, `local`)
+fn source_label(registry_source: &str) -> &str {
+    registry_source
+        .strip_prefix("registry:")
+        .unwrap_or(registry_source)
+}
+
+/// Newer version with its release date, when the provider reported one
+fn describe_update(version: &Version) -> String {
+    match version.published_date() {
+        Some(date) => format!("{} (released {})", version.tag, date.format("%Y-%m-%d")),
+        None => version.tag.clone(),
+    }
+}
+
+/// Prints each package's version directories with their disk usage, so stale
+/// versions left next to the active one stand out
+fn print_tree(packages: &[InstalledPackage]) -> Result<()> {
+    let mut grand_total = 0;
+
+    for pkg in packages {
+        let Some(package_dir) = Path::new(&pkg.install_dir).parent() else {
+            continue;
+        };
+
+        let mut versions = Vec::new();
+        if package_dir.is_dir() {
+            for entry in std::fs::read_dir(package_dir)? {
+                let entry = entry?;
+                let name = entry.file_name().to_string_lossy().into_owned();
+                if name == CURRENT_LINK_NAME || name.starts_with('.') {
+                    continue;
+                }
+                if entry.file_type()?.is_dir() {
+                    versions.push((name, Cache::dir_size(&entry.path())?));
+                }
+            }
+        }
+        versions.sort();
+
+        let total: u64 = versions.iter().map(|(_, size)| size).sum();
+        grand_total += total;
+
+        ui_println!("{} ({}, {})", pkg.name, pkg.install_mode, HumanBytes(total));
+        if versions.is_empty() {
+            ui_println!("└─ (missing: {})", package_dir.display());
+        }
+        for (i, (version, size)) in versions.iter().enumerate() {
+            let branch = if i + 1 == versions.len() {
+                "└─"
+            } else {
+                "├─"
+            };
+            let status = if *version == pkg.version {
+                "active"
+            } else {
+                "stale"
+            };
+            ui_println!(
+                "{} {:<20} {:>10}  {}",
+                branch,
+                version,
+                HumanBytes(*size).to_string(),
+                status
+            );
         }
+        println!();
     }
 
+    println!("Total: {}", HumanBytes(grand_total));
     Ok(())
 }
@@ -0,0 +1,114 @@
+use anyhow::Result;
+use std::path::Path;
+
+use crate::api::{InstallOutcome, InstallSpec, Ora, TARGET_ARCH_KEY, TARGET_OS_KEY};
+use crate::cli::args::LockArgs;
+use crate::config::global::InstallMode;
+use crate::config::installed::InstalledPackage;
+use crate::config::lockfile::{LockedPackage, Lockfile};
+use crate::config::manifest::ManifestEntry;
+use crate::utils::platform::Platform;
+use crate::utils::ui;
+
+pub async fn execute(args: LockArgs) -> Result<()> {
+    let ora = Ora::new();
+    let mut lockfile = Lockfile::default();
+    let mut failed = Vec::new();
+
+    for package in ora.list().await? {
+        let Some(entry) = ManifestEntry::from_installed(&package) else {
+            eprintln!(
+                "⚠️  Skipping {}: installed from a local archive",
+                package.name
+            );
+            continue;
+        };
+
+        match lock_package(&ora, &package, entry).await {
+            Ok(locked) => {
+                println!(
+                    "🔒 {} {} ({})",
+                    locked.name, locked.version, locked.checksum
+                );
+                lockfile.packages.push(locked);
+            }
+            Err(e) => {
+                ui::error(&format!("Failed to lock {}: {:#}", package.name, e));
+                failed.push(package.name);
+            }
+        }
+    }
+
+    // A partial lockfile would silently drop packages from every machine using it
+    if !failed.is_empty() {
+        anyhow::bail!(
+            "Failed to lock {} package(s): {}; {} was not written",
+            failed.len(),
+            failed.join(", "),
+            args.output
+        );
+    }
+
+    lockfile.save(Path::new(&args.output))?;
+    println!(
+        "✅ Locked {} package(s) in {}",
+        lockfile.packages.len(),
+        args.output
+    );
+    Ok(())
+}
+
+/// Resolves and verifies the archive of the installed version, as a lockfile entry
+async fn lock_package(
+    ora: &Ora,
+    package: &InstalledPackage,
+    entry: ManifestEntry,
+) -> Result<LockedPackage> {
+    let os = package.metadata.get(TARGET_OS_KEY).cloned();
+    let arch = package.metadata.get(TARGET_ARCH_KEY).cloned();
+    let platform = Platform::with_overrides(os.as_deref(), arch.as_deref());
+
+    let spec = InstallSpec {
+        package: match &entry.registry {
+            Some(registry) => format!("{}@{}", entry.name, registry),
+            None => entry.name.clone(),
+        },
+        version: Some(entry.version.clone()),
+        repo: entry.repo.clone(),
+        mode: Some(if package.install_mode == InstallMode::System.as_str() {
+            InstallMode::System
+        } else {
+            InstallMode::Userland
+        }),
+        allow_insecure: package.allow_insecure,
+        allow_prerelease: true,
+        os,
+        arch,
+        verify_only: true,
+        ..Default::default()
+    };
+
+    let report = match ora.install(&spec).await? {
+        InstallOutcome::Verified(report) => report,
+        _ => anyhow::bail!("Unexpected install outcome while locking"),
+    };
+    let algorithm = report.checksum_algorithm.as_str();
+    if !report.checksum_verified {
+        log::warn!(
+            "{} {} has no published checksum; locking the digest of the downloaded archive",
+            entry.name,
+            entry.version
+        );
+    }
+
+    Ok(LockedPackage {
+        name: entry.name,
+        version: entry.version,
+        registry: entry.registry,
+        repo: entry.repo,
+        os: platform.os,
+        arch: platform.arch,
+        url: report.url,
+        checksum: format!("{}:{}", algorithm, report.checksum),
+    })
+}
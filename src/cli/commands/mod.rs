@@ -1,10 +1,17 @@
+pub mod alias;
+pub mod cache;
 pub mod config;
+pub mod export;
+pub mod import;
 pub mod info;
 pub mod install;
 pub mod list;
+pub mod lock;
+pub mod platform;
 pub mod registry;
 pub mod search;
 pub mod security;
+pub mod self_update;
 pub mod uninstall;
 pub mod update;
 pub mod validate;
@@ -0,0 +1,118 @@
+use anyhow::Result;
+use std::collections::HashMap;
+
+use crate::api::{load_repo_argument, platform_candidates};
+use crate::cli::args::PlatformArgs;
+use crate::utils::platform::{default_arch_mapping, default_os_mapping, Platform};
+
+pub async fn execute(args: PlatformArgs) -> Result<()> {
+    let detected = Platform::detect();
+    let platform = Platform::with_overrides(args.os.as_deref(), args.arch.as_deref());
+
+    println!("🖥️  Platform");
+    println!(
+        "  OS:    {}{}",
+        platform.os,
+        overridden(&platform.os, &detected.os)
+    );
+    println!(
+        "  Arch:  {}{}",
+        platform.arch,
+        overridden(&platform.arch, &detected.arch)
+    );
+    if let Some(libc) = platform.libc() {
+        println!("  libc:  {}", libc);
+    }
+
+    println!("\n🗺️  Default mappings (for .repo files without platform.os_map/arch_map)");
+    println!("  OS:    {}", format_mapping(&default_os_mapping()));
+    println!("  Arch:  {}", format_mapping(&default_arch_mapping()));
+
+    let Some(repo) = &args.repo else {
+        return Ok(());
+    };
+    let (mut repo_config, _) = load_repo_argument(repo).await?;
+    if let Some(libc) = platform.libc() {
+        repo_config.apply_libc(libc);
+    }
+    let (os_candidates, arch_candidates) = platform_candidates(&repo_config, &platform);
+    let mapping_source = |map: &HashMap<String, Vec<String>>, name: &str| {
+        if map.is_empty() {
+            "default mapping".to_string()
+        } else {
+            format!("platform.{}", name)
+        }
+    };
+
+    println!("\n📦 {} ({})", repo_config.name, repo);
+    println!(
+        "  OS names:    {} (from {})",
+        os_candidates.join(", "),
+        mapping_source(&repo_config.get_os_map(), "os_map")
+    );
+    println!(
+        "  Arch names:  {} (from {})",
+        arch_candidates.join(", "),
+        mapping_source(&repo_config.get_arch_map(), "arch_map")
+    );
+
+    // The order install tries them in, with what the .repo file declares for each
+    let urls = repo_config
+        .source
+        .download
+        .as_ref()
+        .and_then(|download| download.urls.as_ref());
+    println!("  Platform keys, in the order they are tried:");
+    for os in &os_candidates {
+        for arch in &arch_candidates {
+            let key = format!("{}_{}", os, arch);
+            let mut notes = Vec::new();
+            if let Some(filter) = repo_config.get_url_filter(os, arch) {
+                notes.push(format!("url filter '{}'", filter));
+            }
+            if let Some(url) = urls.and_then(|urls| urls.get(&key)) {
+                notes.push(format!("download URL {}", url));
+            }
+            if notes.is_empty() {
+                println!("    {}", key);
+            } else {
+                println!("    {}  → {}", key, notes.join(", "));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Marks a value given with --os/--arch, showing the detected one
+fn overridden(value: &str, detected: &str) -> String {
+    if value == detected {
+        String::new()
+    } else {
+        format!(" (override; detected {})", detected)
+    }
+}
+
+/// `detected → mapped` entries, sorted by detected name
+fn format_mapping(mapping: &HashMap<String, Vec<String>>) -> String {
+    let mut entries: Vec<String> = mapping
+        .iter()
+        .map(|(detected, names)| format!("{} → {}", detected, names.join(", ")))
+        .collect();
+    entries.sort();
+    entries.join("; ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_mapping() {
+        assert_eq!(
+            format_mapping(&default_arch_mapping()),
+            "aarch64 → arm64; x86_64 → amd64"
+        );
+        assert_eq!(overridden("linux", "linux"), "");
+        assert_eq!(overridden("macos", "linux"), " (override; detected linux)");
+    }
+}
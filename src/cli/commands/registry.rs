@@ -13,21 +13,45 @@ pub async fn execute(args: RegistryArgs) -> Result<()> {
             pin_cert,
             branch,
             dir,
+            index_checksum,
+            index_url,
         } => {
-            RegistryManager::add_registry(name, url, trust_level, ca_cert, pin_cert, branch, dir)
-                .await?;
+            RegistryManager::add_registry(
+                name,
+                url,
+                trust_level,
+                ca_cert,
+                pin_cert,
+                branch,
+                dir,
+                index_checksum,
+                index_url,
+            )
+            .await?;
         }
-        RegistryCommand::List { verbose } => {
-            RegistryManager::list_registries(verbose).await?;
+        RegistryCommand::List { verbose, json } => {
+            RegistryManager::list_registries(verbose, json).await?;
         }
         RegistryCommand::Remove { name } => {
             RegistryManager::remove_registry(name).await?;
         }
-        RegistryCommand::Sync { name } => {
-            RegistryManager::sync_registries(name).await?;
+        RegistryCommand::Sync { name, index_only } => {
+            RegistryManager::sync_registries(name, index_only).await?;
         }
-        RegistryCommand::Verify { name } => {
-            RegistryManager::verify_registry(name).await?;
+        RegistryCommand::Verify { name, deep } => {
+            RegistryManager::verify_registry(name, deep).await?;
+        }
+        RegistryCommand::Info { name } => {
+            RegistryManager::registry_info(name).await?;
+        }
+        RegistryCommand::Trust { name, fingerprint } => {
+            RegistryManager::trust_registry_key(name, fingerprint).await?;
+        }
+        RegistryCommand::SetUrl { name, url } => {
+            RegistryManager::set_registry_url(name, url).await?;
+        }
+        RegistryCommand::SetTrust { name, trust_level } => {
+            RegistryManager::set_registry_trust(name, trust_level).await?;
         }
         RegistryCommand::UpdatePin { name } => {
             log::warn!("Certificate pinning update not yet fully implemented");
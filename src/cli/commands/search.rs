@@ -3,6 +3,7 @@ use anyhow::Result;
 use crate::cli::args::SearchArgs;
 use crate::registry::RegistryIndex;
 use crate::storage::database::load_global_config;
+use crate::utils::table::Table;
 
 pub async fn execute(args: SearchArgs) -> Result<()> {
     log::debug!("Searching for: {}", args.query);
@@ -14,6 +15,7 @@ pub async fn execute(args: SearchArgs) -> Result<()> {
         return Ok(());
     }
 
+    let mut table = Table::new(&["NAME", "REGISTRY"]);
     let mut found_any = false;
 
     for registry in &config.registries {
@@ -21,14 +23,18 @@ pub async fn execute(args: SearchArgs) -> Result<()> {
             continue;
         }
 
-        match RegistryIndex::search_packages(&registry.name, &args.query).await {
-            Ok(packages) => {
-                if !packages.is_empty() {
+        match RegistryIndex::search_packages(
+            &registry.name,
+            &args.query,
+            &config.registry_cache_dirs,
+        )
+        .await
+        {
+            Ok(mut packages) => {
+                packages.sort();
+                for pkg in packages {
                     found_any = true;
-                    println!("\nRegistry: {}", registry.name);
-                    for pkg in packages {
-                        println!("  - {}", pkg);
-                    }
+                    table.add_row(vec![pkg, registry.name.clone()]);
                 }
             }
             Err(e) => {
@@ -37,7 +43,9 @@ pub async fn execute(args: SearchArgs) -> Result<()> {
         }
     }
 
-    if !found_any {
+    if found_any {
+        table.print();
+    } else {
         println!("No packages found matching '{}'", args.query);
     }
 
@@ -1,5 +1,13 @@
 use crate::cli::args::{SecurityArgs, SecurityCommand};
 use crate::config::SecurityConfig;
+use crate::error::OraError;
+use crate::security::audit::{self, AuditAction, AuditOutcome};
+use crate::security::scan::{scan_package, Finding, Severity};
+use crate::security::{AuditFormat, AuditSummary};
+use crate::storage::database::{load_global_config, load_installed_db};
+use crate::storage::paths::Paths;
+use crate::ui_println;
+use crate::utils::ui::{self, format_size};
 use anyhow::Result;
 
 pub async fn execute(args: SecurityArgs) -> Result<()> {
@@ -7,6 +15,8 @@ pub async fn execute(args: SecurityArgs) -> Result<()> {
         SecurityCommand::Init => init_config().await,
         SecurityCommand::Show => show_config().await,
         SecurityCommand::Reset => reset_config().await,
+        SecurityCommand::Audit { since, format } => audit_report(since.as_deref(), format),
+        SecurityCommand::Scan { format } => scan_installed(format).await,
     }
 }
 
@@ -15,30 +25,30 @@ async fn init_config() -> Result<()> {
     let config = SecurityConfig::default();
 
     // Check if config already exists
-    if let Ok(config_path) = crate::storage::paths::Paths::config_dir() {
-        let security_config_path = config_path.join("security.toml");
+    if let Ok(security_config_path) = Paths::security_config_file() {
         if security_config_path.exists() {
-            println!("⚠️  Security configuration already exists at:");
-            println!("   {}", security_config_path.display());
-            println!("\nUse 'ora security reset' to overwrite with defaults.");
+            ui_println!("⚠️  Security configuration already exists at:");
+            ui_println!("   {}", security_config_path.display());
+            ui_println!("\nUse 'ora security reset' to overwrite with defaults.");
             return Ok(());
         }
     }
 
     config.save()?;
 
-    let config_path = crate::storage::paths::Paths::config_dir()?;
-    let security_config_path = config_path.join("security.toml");
+    let security_config_path = Paths::security_config_file()?;
 
-    println!("✅ Created security configuration at:");
-    println!("   {}", security_config_path.display());
-    println!("\nYou can now customize security settings by editing this file.");
-    println!("\nKey settings to review:");
-    println!("  • network.https_only: Enforce HTTPS-only downloads");
-    println!("  • scripts.require_confirmation: Require approval for post-install scripts");
-    println!("  • registries.require_checksums_public: Require checksums from public registries");
-    println!("  • validation.templates.url_encode_variables: Prevent template injection");
-    println!("\nRun 'ora security show' to view current settings.");
+    ui_println!("✅ Created security configuration at:");
+    ui_println!("   {}", security_config_path.display());
+    ui_println!("\nYou can now customize security settings by editing this file.");
+    ui_println!("\nKey settings to review:");
+    ui_println!("  • network.https_only: Enforce HTTPS-only downloads");
+    ui_println!("  • scripts.require_confirmation: Require approval for post-install scripts");
+    ui_println!(
+        "  • registries.require_checksums_public: Require checksums from public registries"
+    );
+    ui_println!("  • validation.templates.url_encode_variables: Prevent template injection");
+    ui_println!("\nRun 'ora security show' to view current settings.");
 
     Ok(())
 }
@@ -47,195 +57,298 @@ async fn init_config() -> Result<()> {
 async fn show_config() -> Result<()> {
     let config = SecurityConfig::load()?;
 
-    println!("📋 Current Security Configuration\n");
-    println!("═══════════════════════════════════════════════════════════");
+    ui_println!("📋 Current Security Configuration\n");
+    ui_println!("═══════════════════════════════════════════════════════════");
 
     // Network Security
-    println!("\n🌐 Network Security:");
-    println!(
+    ui_println!("\n🌐 Network Security:");
+    ui_println!(
         "  HTTPS Only:              {}",
         format_bool(config.network.https_only)
     );
-    println!(
+    ui_println!(
         "  Allow Redirects:         {}",
         format_bool(config.network.allow_redirects)
     );
-    println!(
+    ui_println!(
         "  Block Private IPs:       {}",
         format_bool(config.network.block_private_ips)
     );
-    println!(
+    ui_println!(
         "  Block Localhost:         {}",
         format_bool(config.network.block_localhost)
     );
-    println!(
+    ui_println!(
         "  Validate DNS:            {}",
         format_bool(config.network.validate_dns_resolution)
     );
-    println!(
+    ui_println!(
         "  Max Download:            {}",
         format_size(config.network.max_download_size)
     );
-    println!(
+    ui_println!(
         "  Timeout:                 {}s",
         config.network.timeout_seconds
     );
+    ui_println!(
+        "  User-Agent:              {}",
+        config.network.effective_user_agent()
+    );
+    ui_println!(
+        "  Proxy:                   {}",
+        config
+            .network
+            .proxy
+            .display_url()
+            .unwrap_or_else(|| "from environment".to_string())
+    );
 
     // Git Security
-    println!("\n  Git Protocol:");
-    println!(
+    ui_println!("\n  Git Protocol:");
+    ui_println!(
         "    HTTPS Only:            {}",
         format_bool(config.network.git.https_only)
     );
-    println!(
+    ui_println!(
         "    Max Repo Size:         {}",
         format_size(config.network.git.max_repo_size)
     );
-    println!(
+    ui_println!(
         "    Allow Force Checkout:  {}",
         format_bool(config.network.git.allow_force_checkout)
     );
 
     // Extraction Security
-    println!("\n📦 Extraction Security:");
-    println!(
+    ui_println!("\n📦 Extraction Security:");
+    ui_println!(
         "  Max File Size:           {}",
         format_size(config.extraction.max_file_size)
     );
-    println!(
+    ui_println!(
         "  Max Total Size:          {}",
         format_size(config.extraction.max_total_size)
     );
-    println!(
+    ui_println!(
         "  Max File Count:          {}",
         config.extraction.max_file_count
     );
-    println!(
+    ui_println!(
         "  Max Directory Depth:     {}",
         config.extraction.max_directory_depth
     );
-    println!(
+    ui_println!(
         "  Block Symlinks:          {}",
         format_bool(config.extraction.block_symlinks)
     );
-    println!(
+    ui_println!(
         "  Strip SETUID Bits:       {}",
         format_bool(config.extraction.strip_setuid_bits)
     );
+    ui_println!(
+        "  Reject SETUID Binaries:  {}",
+        format_bool(config.extraction.reject_setuid_binaries)
+    );
 
     // Script Security
-    println!("\n📜 Script Security:");
-    println!(
+    ui_println!("\n📜 Script Security:");
+    ui_println!(
         "  Scripts Enabled:         {}",
         format_bool(config.scripts.enabled)
     );
-    println!(
+    ui_println!(
         "  Require Confirmation:    {}",
         format_bool(config.scripts.require_confirmation)
     );
-    println!(
+    ui_println!(
         "  Show Script Content:     {}",
         format_bool(config.scripts.show_script_content)
     );
-    println!(
+    ui_println!(
         "  Static Analysis:         {}",
         format_bool(config.scripts.static_analysis)
     );
-    println!(
+    ui_println!(
         "  Block Public Scripts:    {}",
         format_bool(config.scripts.block_public_registry_scripts)
     );
-    println!(
+    ui_println!(
+        "  Approved Scripts:        {}",
+        config.scripts.approved_scripts.len()
+    );
+    ui_println!(
         "  Timeout:                 {}s",
         config.scripts.timeout_seconds
     );
 
     // Registry Security
-    println!("\n🗃️  Registry Security:");
-    println!(
+    ui_println!("\n🗃️  Registry Security:");
+    ui_println!(
         "  Enforce Trust Levels:    {}",
         format_bool(config.registries.enforce_trust_levels)
     );
-    println!(
+    ui_println!(
         "  Require Checksums (Pub): {}",
         format_bool(config.registries.require_checksums_public)
     );
-    println!(
+    ui_println!(
         "  Require GPG Signatures:  {}",
         format_bool(config.registries.require_gpg_signatures)
     );
-    println!(
+    ui_println!(
         "  Allow Pkg Shadowing:     {}",
         format_bool(config.registries.allow_package_shadowing)
     );
-    println!(
+    ui_println!(
         "  Max Registry Size:       {}",
         format_size(config.registries.max_registry_size)
     );
 
     // Validation Security
-    println!("\n✅ Input Validation:");
-    println!(
+    ui_println!("\n📝 Input Validation:");
+    ui_println!(
         "  Max TOML Size:           {}",
         format_size(config.validation.max_toml_size)
     );
-    println!(
+    ui_println!(
         "  Max JSON Size:           {}",
         format_size(config.validation.max_json_size)
     );
-    println!(
+    ui_println!(
         "  URL Encode Templates:    {}",
         format_bool(config.validation.templates.url_encode_variables)
     );
-    println!(
+    ui_println!(
         "  Block Path Traversal:    {}",
         format_bool(config.validation.templates.block_path_traversal)
     );
-    println!(
+    ui_println!(
         "  Max Regex Size:          {}",
         format_size(config.validation.regex.max_compiled_size as u64)
     );
 
     // Resource Limits
-    println!("\n⚙️  Resource Limits:");
-    println!(
+    ui_println!("\n⚙️  Resource Limits:");
+    ui_println!(
         "  Limits Enabled:          {}",
         format_bool(config.resources.enabled)
     );
-    println!(
+    ui_println!(
         "  Max Concurrent DLs:      {}",
         config.resources.max_concurrent_downloads
     );
-    println!(
+    ui_println!(
         "  Max Cache Size:          {}",
         format_size(config.resources.max_cache_size_bytes)
     );
+    ui_println!(
+        "  Keep Downloads:          {}",
+        format_bool(config.resources.keep_downloads)
+    );
 
-    println!("\n═══════════════════════════════════════════════════════════");
+    ui_println!("\n═══════════════════════════════════════════════════════════");
 
-    let config_path = crate::storage::paths::Paths::config_dir()?;
-    let security_config_path = config_path.join("security.toml");
-    println!(
+    let security_config_path = Paths::security_config_file()?;
+    ui_println!(
         "\n📁 Configuration file: {}",
         security_config_path.display()
     );
-    println!("   Edit this file to customize security settings.");
+    ui_println!("   Edit this file to customize security settings.");
 
     Ok(())
 }
 
 /// Reset security configuration to defaults
 async fn reset_config() -> Result<()> {
+    let security_config_path = Paths::security_config_file()?;
+    if security_config_path.exists()
+        && !ui::confirm(&format!(
+            "❓ Overwrite {} with the default settings? [y/N]",
+            security_config_path.display()
+        ))?
+    {
+        ui_println!("Reset cancelled");
+        return Ok(());
+    }
+
     let config = SecurityConfig::default();
     config.save()?;
 
-    let config_path = crate::storage::paths::Paths::config_dir()?;
-    let security_config_path = config_path.join("security.toml");
+    ui_println!("✅ Reset security configuration to defaults:");
+    ui_println!("   {}", security_config_path.display());
+    ui_println!("\nRun 'ora security show' to view current settings.");
 
-    println!("✅ Reset security configuration to defaults:");
-    println!("   {}", security_config_path.display());
-    println!("\nRun 'ora security show' to view current settings.");
+    Ok(())
+}
+
+/// Print audit log events, optionally only the recent ones, with a summary
+fn audit_report(since: Option<&str>, format: AuditFormat) -> Result<()> {
+    let since = since
+        .map(|value| audit::parse_since(value, chrono::Utc::now()))
+        .transpose()?;
+    let mut events = audit::read_events(&Paths::audit_log_file()?)?;
+    if let Some(since) = since {
+        events.retain(|event| event.timestamp >= since);
+    }
+    let summary = AuditSummary::from_events(&events);
+
+    if format == AuditFormat::Json {
+        let report = serde_json::json!({
+            "since": since.map(|s| s.to_rfc3339()),
+            "events": events,
+            "summary": summary,
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    if events.is_empty() {
+        println!("No audit events recorded");
+        return Ok(());
+    }
 
+    for event in &events {
+        let action = match event.action {
+            AuditAction::Install => "install",
+            AuditAction::Uninstall => "uninstall",
+            AuditAction::Update => "update",
+            AuditAction::Security => "security",
+        };
+        let version = match &event.previous_version {
+            Some(previous) => format!("{} -> {}", previous, event.version),
+            None => event.version.clone(),
+        };
+        let mut line = format!(
+            "{}  {:<9} {} {}",
+            event.timestamp.format("%Y-%m-%d %H:%M"),
+            action,
+            event.package,
+            version
+        );
+        if let Some(source) = &event.source {
+            line.push_str(&format!(" from {}", source));
+        }
+        if let Some(user) = &event.user {
+            line.push_str(&format!(" by {}", user));
+        }
+        if event.outcome == AuditOutcome::Failure {
+            line.push_str(" [failed]");
+        }
+        if event.allow_insecure {
+            line.push_str(" [insecure]");
+        }
+        println!("{}", line);
+    }
+
+    println!();
+    println!(
+        "{} events: {} installs, {} uninstalls, {} updates, {} failed, {} insecure",
+        summary.total,
+        summary.installs,
+        summary.uninstalls,
+        summary.updates,
+        summary.failures,
+        summary.insecure
+    );
     Ok(())
 }
 
@@ -248,18 +361,65 @@ fn format_bool(value: bool) -> String {
     }
 }
 
-fn format_size(bytes: u64) -> String {
-    const KB: u64 = 1024;
-    const MB: u64 = 1024 * KB;
-    const GB: u64 = 1024 * MB;
-
-    if bytes >= GB {
-        format!("{:.2} GB", bytes as f64 / GB as f64)
-    } else if bytes >= MB {
-        format!("{:.2} MB", bytes as f64 / MB as f64)
-    } else if bytes >= KB {
-        format!("{:.2} KB", bytes as f64 / KB as f64)
+/// Report policy violations of every installed package
+async fn scan_installed(format: AuditFormat) -> Result<()> {
+    let registries = load_global_config().await?.registries;
+    let db = load_installed_db().await?;
+
+    let mut packages: Vec<_> = db.packages.values().collect();
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    let mut findings: Vec<Finding> = packages
+        .iter()
+        .flat_map(|package| scan_package(package, &registries))
+        .collect();
+    findings.sort_by(|a, b| b.severity.cmp(&a.severity).then(a.package.cmp(&b.package)));
+
+    let count = |severity| findings.iter().filter(|f| f.severity == severity).count();
+    let (high, medium, low, unknown) = (
+        count(Severity::High),
+        count(Severity::Medium),
+        count(Severity::Low),
+        count(Severity::Unknown),
+    );
+
+    if format == AuditFormat::Json {
+        let report = serde_json::json!({
+            "packages": packages.len(),
+            "findings": findings,
+            "summary": { "high": high, "medium": medium, "low": low, "unknown": unknown },
+        });
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else if findings.is_empty() {
+        ui_println!(
+            "✅ No issues found in {} installed package(s)",
+            packages.len()
+        );
     } else {
-        format!("{} bytes", bytes)
+        for finding in &findings {
+            ui_println!(
+                "[{:<7}] {}: {}",
+                finding.severity.label(),
+                finding.package,
+                finding.message
+            );
+        }
+        ui_println!();
+        ui_println!(
+            "{} installed package(s) scanned: {} high, {} medium, {} low, {} unknown",
+            packages.len(),
+            high,
+            medium,
+            low,
+            unknown
+        );
+    }
+
+    if high > 0 {
+        return Err(OraError::SecurityPolicyViolation(format!(
+            "{} high-severity issue(s) found in installed packages",
+            high
+        ))
+        .into());
     }
+    Ok(())
 }
@@ -0,0 +1,245 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::cli::args::SelfUpdateArgs;
+use crate::config::repo::ChecksumAlgorithm;
+use crate::error::OraError;
+use crate::installer::{Downloader, Extractor};
+use crate::security::{parse_checksum_file, verify_checksum};
+use crate::storage::cache::Cache;
+use crate::utils::http::HttpClient;
+
+/// GitHub repository Ora itself is released from
+const ORA_REPO: &str = "Altagen/Ora";
+
+/// Consolidated checksum file published with every release
+const CHECKSUMS_ASSET: &str = "SHA256SUMS.txt";
+
+#[derive(Debug, Deserialize)]
+struct Release {
+    tag_name: String,
+    assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReleaseAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+impl Release {
+    fn asset_url(&self, name: &str) -> Option<&str> {
+        self.assets
+            .iter()
+            .find(|a| a.name == name)
+            .map(|a| a.browser_download_url.as_str())
+    }
+}
+
+pub async fn execute(args: SelfUpdateArgs) -> Result<()> {
+    let current = semver::Version::parse(env!("CARGO_PKG_VERSION"))
+        .context("Invalid compiled-in Ora version")?;
+
+    println!("🔍 Checking for Ora updates...");
+    let client = HttpClient::new()?;
+    let release: Release = client
+        .get_json(&format!(
+            "https://api.github.com/repos/{}/releases/latest",
+            ORA_REPO
+        ))
+        .await
+        .context("Failed to fetch the latest Ora release")?;
+
+    let latest_str = release.tag_name.trim_start_matches('v');
+    let latest = semver::Version::parse(latest_str).context(format!(
+        "Latest release tag '{}' is not a valid version",
+        release.tag_name
+    ))?;
+
+    if latest <= current {
+        println!("✅ Ora {} is up to date", current);
+        return Ok(());
+    }
+
+    println!("📦 Ora {} is available (installed: {})", latest, current);
+    if args.check {
+        println!("   Run 'ora self-update' to install it");
+        return Ok(());
+    }
+
+    let exe = std::env::current_exe()
+        .and_then(|p| p.canonicalize())
+        .context("Failed to locate the running Ora executable")?;
+    let exe_dir = exe
+        .parent()
+        .context("Ora executable has no parent directory")?;
+    ensure_writable(&exe, exe_dir)?;
+
+    // Release archives are named ora-<version>-<os>-<amd64|arm64>.tar.gz and
+    // contain a single binary named like the archive without the extension
+    let binary_name = format!("ora-{}-{}", latest_str, release_platform()?);
+    let archive_name = format!("{}.tar.gz", binary_name);
+
+    let archive_url = release.asset_url(&archive_name).context(format!(
+        "Release {} has no build for this platform ({})",
+        release.tag_name, archive_name
+    ))?;
+    let checksums_url = release.asset_url(CHECKSUMS_ASSET).context(format!(
+        "Release {} has no {}; refusing to install an unverified binary",
+        release.tag_name, CHECKSUMS_ASSET
+    ))?;
+
+    let downloader = Downloader::new()?;
+    let checksums = downloader
+        .download_text(checksums_url)
+        .await
+        .context("Failed to download release checksums")?;
+    let expected_hash = parse_checksum_file(&checksums, &archive_name).context(format!(
+        "{} has no entry for {}",
+        CHECKSUMS_ASSET, archive_name
+    ))?;
+
+    println!("⬇️  Downloading {}...", archive_name);
+    let archive_path = Cache::download_path(&archive_name)?;
+    downloader.download(archive_url, &archive_path).await?;
+
+    let result =
+        replace_executable(&exe, exe_dir, &archive_path, &binary_name, &expected_hash).await;
+    if !Cache::keep_downloads() {
+        let _ = std::fs::remove_file(&archive_path);
+    }
+    result?;
+
+    println!("✅ Updated Ora {} → {}", current, latest);
+    Ok(())
+}
+
+/// Verifies and unpacks the release archive, then swaps it in for `exe`
+async fn replace_executable(
+    exe: &Path,
+    exe_dir: &Path,
+    archive_path: &Path,
+    binary_name: &str,
+    expected_hash: &str,
+) -> Result<()> {
+    verify_checksum(archive_path, expected_hash, &ChecksumAlgorithm::Sha256)
+        .await
+        .context("Downloaded Ora release failed checksum verification")?;
+
+    let extract_dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+    Extractor::extract(archive_path, extract_dir.path())?;
+    let new_binary = extract_dir.path().join(binary_name);
+    if !new_binary.is_file() {
+        anyhow::bail!("Release archive does not contain {}", binary_name);
+    }
+
+    // Stage the new binary next to the current one so the final rename stays
+    // on one filesystem and is atomic
+    let staged = tempfile::Builder::new()
+        .prefix(".ora-update-")
+        .tempfile_in(exe_dir)
+        .context(format!(
+            "Failed to create temporary file in {}",
+            exe_dir.display()
+        ))?;
+    std::fs::copy(&new_binary, staged.path()).context("Failed to stage the new Ora binary")?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(staged.path(), std::fs::Permissions::from_mode(0o755))?;
+    }
+
+    staged
+        .persist(exe)
+        .map_err(|e| e.error)
+        .context(format!("Failed to replace {}", exe.display()))?;
+
+    log::info!("Replaced {}", exe.display());
+    Ok(())
+}
+
+/// Platform suffix used in Ora release asset names
+fn release_platform() -> Result<String> {
+    let os = match std::env::consts::OS {
+        os @ ("linux" | "macos") => os,
+        other => anyhow::bail!("No Ora release builds are published for {}", other),
+    };
+    let arch = match std::env::consts::ARCH {
+        "x86_64" => "amd64",
+        "aarch64" => "arm64",
+        other => anyhow::bail!("No Ora release builds are published for {}", other),
+    };
+    Ok(format!("{}-{}", os, arch))
+}
+
+/// Fails with a pointer to the right update mechanism if `exe` can't be replaced
+fn ensure_writable(exe: &Path, exe_dir: &Path) -> Result<()> {
+    let probe = tempfile::Builder::new()
+        .prefix(".ora-update-")
+        .tempfile_in(exe_dir);
+
+    match probe {
+        Ok(_) => Ok(()),
+        Err(e) if e.kind() == ErrorKind::PermissionDenied => {
+            let hint = if is_package_manager_path(exe) {
+                "It looks like Ora was installed by a package manager (.deb, .rpm or .pkg); \
+                 update it the same way you installed it."
+            } else {
+                "Re-run with sufficient permissions or reinstall Ora in a writable location."
+            };
+            Err(
+                OraError::PermissionDenied(format!("cannot replace {}. {}", exe.display(), hint))
+                    .into(),
+            )
+        }
+        Err(e) => Err(e).context(format!("Cannot write to {}", exe_dir.display())),
+    }
+}
+
+/// Locations used by the .deb/.rpm (`/usr/bin`) and macOS .pkg (`/usr/local/bin`) installers
+fn is_package_manager_path(exe: &Path) -> bool {
+    [PathBuf::from("/usr/bin"), PathBuf::from("/usr/local/bin")]
+        .iter()
+        .any(|dir| exe.parent() == Some(dir.as_path()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_release_asset_lookup() {
+        let release: Release = serde_json::from_str(
+            r#"{
+                "tag_name": "v0.3.0",
+                "assets": [
+                    {"name": "ora-0.3.0-linux-amd64.tar.gz", "browser_download_url": "https://example.com/a"},
+                    {"name": "SHA256SUMS.txt", "browser_download_url": "https://example.com/sums"}
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            release.asset_url("ora-0.3.0-linux-amd64.tar.gz"),
+            Some("https://example.com/a")
+        );
+        assert_eq!(
+            release.asset_url(CHECKSUMS_ASSET),
+            Some("https://example.com/sums")
+        );
+        assert_eq!(release.asset_url("ora-0.3.0-macos-arm64.tar.gz"), None);
+    }
+
+    #[test]
+    fn test_package_manager_paths() {
+        assert!(is_package_manager_path(Path::new("/usr/bin/ora")));
+        assert!(is_package_manager_path(Path::new("/usr/local/bin/ora")));
+        assert!(!is_package_manager_path(Path::new(
+            "/home/user/.local/bin/ora"
+        )));
+    }
+}
@@ -1,95 +1,149 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use std::path::Path;
 
+use crate::api::Ora;
 use crate::cli::args::UninstallArgs;
-use crate::security::AuditLogger;
-use crate::storage::database::{load_installed_db, save_installed_db};
+use crate::config::installed::InstalledPackage;
+use crate::installer::hooks::{run_hook, HookEvent};
+use crate::utils::ui;
 
 pub async fn execute(args: UninstallArgs) -> Result<()> {
-    log::info!("Uninstalling package: {}", args.package);
-
-    // Load installed database
-    let mut db = load_installed_db().await?;
-
-    // Find installed package
-    let installed = db
-        .packages
-        .get(&args.package)
-        .context(format!("Package '{}' is not installed", args.package))?
-        .clone();
-
-    // If version specified, check it matches
-    if let Some(ver) = &args.version {
-        if &installed.version != ver {
-            anyhow::bail!(
-                "Package '{}' version {} is not installed (installed: {})",
-                args.package,
-                ver,
-                installed.version
-            );
-        }
+    if args.all || args.packages.len() > 1 {
+        return run_many(args).await;
     }
 
-    log::debug!("Removing files...");
+    if let Some(removed) = run(&args.packages[0], args.version.as_deref(), args.dry_run).await? {
+        run_hook(HookEvent::Uninstall, &removed.name, &removed.version).await;
+    }
+    Ok(())
+}
 
-    // Remove symlinks first
-    for symlink in &installed.symlinks {
-        let path = Path::new(symlink);
-        if path.exists() || path.is_symlink() {
-            std::fs::remove_file(symlink)
-                .context(format!("Failed to remove symlink: {}", symlink))?;
-            log::debug!("Removed symlink: {}", symlink);
+/// Uninstalls and reports the package, without running hooks
+///
+/// Returns the removed package, or `None` for a dry run.
+pub async fn run(
+    package: &str,
+    version: Option<&str>,
+    dry_run: bool,
+) -> Result<Option<InstalledPackage>> {
+    let ora = Ora::new();
+
+    if dry_run {
+        let installed = ora.installed(package, version).await?;
+        print_dry_run(&installed);
+        return Ok(None);
+    }
+
+    let removed = ora.uninstall(package, version).await?;
+
+    println!(
+        "✅ Successfully uninstalled {} {}",
+        removed.name, removed.version
+    );
+
+    Ok(Some(removed))
+}
+
+/// Uninstalls several packages (every installed one with `--all`), continuing
+/// past failures and summarizing at the end
+async fn run_many(args: UninstallArgs) -> Result<()> {
+    if args.version.is_some() {
+        anyhow::bail!("--version can only be used when uninstalling a single package");
+    }
+
+    let packages: Vec<String> = if args.all {
+        Ora::new()
+            .list()
+            .await?
+            .into_iter()
+            .map(|package| package.name)
+            .collect()
+    } else {
+        args.packages
+    };
+
+    if packages.is_empty() {
+        println!("No packages installed");
+        return Ok(());
+    }
+
+    if args.all && !args.dry_run {
+        println!(
+            "This will uninstall {} package{}: {}",
+            packages.len(),
+            if packages.len() == 1 { "" } else { "s" },
+            packages.join(", ")
+        );
+        if !ui::confirm("\n❓ Continue? [y/N]")? {
+            println!("Uninstall cancelled");
+            return Ok(());
         }
     }
 
-    // Remove install directory
-    let install_dir = Path::new(&installed.install_dir);
-    if install_dir.exists() {
-        std::fs::remove_dir_all(install_dir).context(format!(
-            "Failed to remove directory: {}",
-            installed.install_dir
-        ))?;
-        log::debug!("Removed directory: {}", installed.install_dir);
-
-        // Clean up parent directory if it's empty
-        if let Some(parent_dir) = install_dir.parent() {
-            if parent_dir.exists() {
-                match std::fs::read_dir(parent_dir) {
-                    Ok(mut entries) => {
-                        // Check if directory is empty
-                        if entries.next().is_none() {
-                            if let Err(e) = std::fs::remove_dir(parent_dir) {
-                                log::debug!(
-                                    "Could not remove empty parent directory {:?}: {}",
-                                    parent_dir,
-                                    e
-                                );
-                            } else {
-                                log::debug!("Removed empty parent directory: {:?}", parent_dir);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        log::debug!("Could not check parent directory {:?}: {}", parent_dir, e);
-                    }
-                }
+    let mut removed = 0;
+    let mut failed = Vec::new();
+    for package in &packages {
+        match run(package, None, args.dry_run).await {
+            Ok(Some(package)) => {
+                removed += 1;
+                run_hook(HookEvent::Uninstall, &package.name, &package.version).await;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("❌ Failed to uninstall {}: {:#}", package, e);
+                failed.push(package.as_str());
             }
         }
+        if args.dry_run {
+            println!();
+        }
     }
 
-    // Remove from database
-    db.packages.remove(&args.package);
-    save_installed_db(&db).await?;
+    if !args.dry_run {
+        println!();
+        println!(
+            "Uninstalled {} of {} package{}",
+            removed,
+            packages.len(),
+            if packages.len() == 1 { "" } else { "s" }
+        );
+    }
+    if !failed.is_empty() {
+        anyhow::bail!("Failed to uninstall: {}", failed.join(", "));
+    }
+    Ok(())
+}
 
-    // Audit log
-    AuditLogger::new()?
-        .log_uninstall(&args.package, &installed.version, true)
-        .await?;
+/// Prints everything a real uninstall would remove
+fn print_dry_run(installed: &InstalledPackage) {
+    let status = |path: &str| {
+        let path = Path::new(path);
+        if path.exists() || path.is_symlink() {
+            ""
+        } else {
+            " (already missing)"
+        }
+    };
 
     println!(
-        "✅ Successfully uninstalled {} {}",
-        args.package, installed.version
+        "Would uninstall {} {} (dry run, nothing will be removed)",
+        installed.name, installed.version
     );
 
-    Ok(())
+    println!("\nSymlinks ({}):", installed.symlinks.len());
+    for symlink in &installed.symlinks {
+        println!("  {}{}", symlink, status(symlink));
+    }
+
+    println!("\nFiles ({}):", installed.files.len());
+    for file in &installed.files {
+        println!("  {}{}", file, status(file));
+    }
+
+    println!("\nDirectory:");
+    println!(
+        "  {}{}",
+        installed.install_dir,
+        status(&installed.install_dir)
+    );
 }
@@ -1,145 +1,416 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
 
+use crate::api::{load_repo_argument, TARGET_ARCH_KEY, TARGET_OS_KEY};
 use crate::cli::args::{InstallArgs, UpdateArgs};
 use crate::cli::commands::{install, uninstall};
+use crate::config::installed::InstalledPackage;
+use crate::config::InstalledDatabase;
+use crate::error::OraError;
+use crate::installer::hooks::{run_hook, HookEvent};
 use crate::registry::RegistryManager;
-use crate::storage::database::load_installed_db;
+use crate::security::{AuditLogger, SecurityWarningManager};
+use crate::storage::database::{load_global_config, load_installed_db};
+use crate::storage::paths::Paths;
+use crate::utils::ui;
 
 pub async fn execute(args: UpdateArgs) -> Result<()> {
     let db = load_installed_db().await?;
 
-    let packages_to_update: Vec<String> = if args.all {
-        db.packages.keys().cloned().collect()
-    } else if let Some(pkg) = args.package {
-        vec![pkg]
+    // Without --continue, a new batch starts over
+    let mut progress = if args.resume {
+        UpdateProgress::load()?
     } else {
-        anyhow::bail!("Specify a package name or use --all");
+        if args.all {
+            UpdateProgress::clear()?;
+        }
+        UpdateProgress::default()
     };
 
-    for package_name in packages_to_update {
-        log::debug!("Checking updates for {}...", package_name);
+    let packages_to_update: Vec<String> = if args.all && !progress.packages.is_empty() {
+        // Resume the saved batch: a package whose update failed after its old
+        // version was uninstalled is no longer in the database
+        progress.packages.clone()
+    } else if args.all {
+        let mut packages: Vec<String> = db.packages.keys().cloned().collect();
+        packages.sort();
+        progress.packages = packages.clone();
+        progress.save()?;
+        packages
+    } else if let Some(pkg) = &args.package {
+        vec![load_global_config().await?.resolve_alias(pkg)]
+    } else {
+        anyhow::bail!("Specify a package name or use --all");
+    };
 
-        let installed = match db.packages.get(&package_name) {
-            Some(pkg) => pkg,
-            None => {
-                // If updating a specific package (not --all), fail
-                if !args.all {
-                    anyhow::bail!("Package '{}' not installed", package_name);
+    let total = packages_to_update.len();
+    for (done, package_name) in packages_to_update.into_iter().enumerate() {
+        if progress.completed.contains(&package_name) {
+            println!("Skipping {}: already updated", package_name);
+            continue;
+        }
+        if let Err(e) = update_package(&args, &db, &mut progress, package_name.clone()).await {
+            if args.all {
+                eprintln!(
+                    "\n❌ Update stopped at {} ({} of {} packages done)",
+                    package_name, done, total
+                );
+                eprintln!("   Resume with:         {}", retry_command(&args, None));
+                if progress.uninstalled.contains_key(&package_name) {
+                    eprintln!(
+                        "   {} was uninstalled for the update; resuming installs it again",
+                        package_name
+                    );
+                } else {
+                    eprintln!(
+                        "   Retry only this one: {}",
+                        retry_command(&args, Some(&package_name))
+                    );
                 }
-                // For --all, just skip packages that aren't installed
-                log::warn!("Package '{}' not installed, skipping", package_name);
-                continue;
             }
+            return Err(e);
+        }
+        if args.all {
+            if progress.uninstalled.contains_key(&package_name) {
+                // Skipped before the new version was installed
+                ui::warning(&format!(
+                    "{} is still uninstalled; install it again with: {}",
+                    package_name,
+                    retry_command(&args, None)
+                ));
+            } else {
+                progress.completed.insert(package_name);
+            }
+            progress.save()?;
+        }
+    }
+
+    if args.all && progress.uninstalled.is_empty() {
+        UpdateProgress::clear()?;
+    }
+    Ok(())
+}
+
+/// Checks one installed package for an update and installs it
+///
+/// With `--all`, the entry of a package is kept in `progress` while its old
+/// version is uninstalled, so a failed install can be resumed.
+async fn update_package(
+    args: &UpdateArgs,
+    db: &InstalledDatabase,
+    progress: &mut UpdateProgress,
+    package_name: String,
+) -> Result<()> {
+    log::debug!("Checking updates for {}...", package_name);
+
+    // An interrupted batch may have uninstalled the package already
+    let uninstalled = !db.packages.contains_key(&package_name);
+    let installed = match db
+        .packages
+        .get(&package_name)
+        .or_else(|| progress.uninstalled.get(&package_name))
+    {
+        Some(pkg) => pkg.clone(),
+        None => {
+            // If updating a specific package (not --all), fail
+            if !args.all {
+                return Err(anyhow::Error::new(OraError::PackageNotInstalled(
+                    package_name.clone(),
+                ))
+                .context(format!("Package '{}' not installed", package_name)));
+            }
+            // For --all, just skip packages that aren't installed
+            log::warn!("Package '{}' not installed, skipping", package_name);
+            return Ok(());
+        }
+    };
+
+    // Parse registry_source to determine how to load the .repo file
+    let (repo_file_path, registry_name) =
+        if let Some(file_path) = installed.registry_source.strip_prefix("file:") {
+            // Package was installed from a local .repo file
+            log::debug!(
+                "Package '{}' was installed from local file: {}",
+                package_name,
+                file_path
+            );
+            (Some(file_path.to_string()), None)
+        } else if let Some(url) = installed.registry_source.strip_prefix("url:") {
+            // Package was installed from a remote .repo file
+            log::debug!("Package '{}' was installed from URL: {}", package_name, url);
+            (Some(url.to_string()), None)
+        } else if let Some(reg_name) = installed.registry_source.strip_prefix("registry:") {
+            // Package was installed from a registry
+            log::debug!(
+                "Package '{}' was installed from registry: {}",
+                package_name,
+                reg_name
+            );
+            (None, Some(reg_name.to_string()))
+        } else {
+            log::warn!(
+                "Unknown registry_source format for '{}': {}",
+                package_name,
+                installed.registry_source
+            );
+            log::warn!("Attempting to find package in registries...");
+            (None, None)
         };
 
-        // Parse registry_source to determine how to load the .repo file
-        let (repo_file_path, registry_name) =
-            if let Some(file_path) = installed.registry_source.strip_prefix("file:") {
-                // Package was installed from a local .repo file
-                log::debug!(
-                    "Package '{}' was installed from local file: {}",
+    // Load repo config to check allow_insecure flag
+    let (repo_config, _) = if let Some(repo_file) = &repo_file_path {
+        // Load from the local file or URL the package was installed from
+        match load_repo_argument(repo_file).await {
+            Ok(result) => result,
+            Err(e) => {
+                log::warn!(
+                    "Could not load .repo file '{}' for package '{}': {:#}",
+                    repo_file,
+                    package_name,
+                    e
+                );
+                log::warn!("The original .repo file may have been moved or deleted.");
+                log::warn!("Skipping update for '{}'", package_name);
+                return Ok(());
+            }
+        }
+    } else if let Some(reg_name) = &registry_name {
+        // Find in specific registry
+        match RegistryManager::find_package_in_specific_registry(&package_name, reg_name).await {
+            Ok(result) => result,
+            Err(e) => {
+                log::warn!(
+                    "Could not find package '{}' in registry '{}': {}",
                     package_name,
-                    file_path
+                    reg_name,
+                    e
                 );
-                (Some(file_path.to_string()), None)
-            } else if let Some(reg_name) = installed.registry_source.strip_prefix("registry:") {
-                // Package was installed from a registry
+                return Ok(());
+            }
+        }
+    } else {
+        // Fallback: search in all registries
+        match RegistryManager::find_package(&package_name).await {
+            Ok(result) => result,
+            Err(e) => {
+                log::warn!("Could not find package '{}': {}", package_name, e);
+                return Ok(());
+            }
+        }
+    };
+
+    if args.only_security {
+        let fixed_in = repo_config
+            .metadata
+            .as_ref()
+            .and_then(|m| m.security_fixed_in.as_deref());
+        let Some(fixed_in) = fixed_in else {
+            log::debug!("No security advisory for '{}', skipping", package_name);
+            return Ok(());
+        };
+        match below_security_fix(&installed.version, fixed_in) {
+            Some(true) => println!(
+                "Security update for {}: {} is below fixed version {}",
+                package_name, installed.version, fixed_in
+            ),
+            Some(false) => {
                 log::debug!(
-                    "Package '{}' was installed from registry: {}",
+                    "'{}' {} already includes the fix from {}, skipping",
                     package_name,
-                    reg_name
+                    installed.version,
+                    fixed_in
                 );
-                (None, Some(reg_name.to_string()))
-            } else {
+                return Ok(());
+            }
+            None => {
                 log::warn!(
-                    "Unknown registry_source format for '{}': {}",
+                    "Cannot compare '{}' version {} with security_fixed_in {}, skipping",
                     package_name,
-                    installed.registry_source
+                    installed.version,
+                    fixed_in
                 );
-                log::warn!("Attempting to find package in registries...");
-                (None, None)
-            };
-
-        // Load repo config to check allow_insecure flag
-        let (repo_config, _) = if let Some(repo_file) = &repo_file_path {
-            // Load from local file
-            let content = match tokio::fs::read_to_string(repo_file).await {
-                Ok(c) => c,
-                Err(e) => {
-                    log::warn!(
-                        "Could not read .repo file '{}' for package '{}': {}",
-                        repo_file,
-                        package_name,
-                        e
-                    );
-                    log::warn!("The original .repo file may have been moved or deleted.");
-                    log::warn!("Skipping update for '{}'", package_name);
-                    continue;
-                }
-            };
-            let config = match toml::from_str(&content) {
-                Ok(c) => c,
-                Err(e) => {
-                    log::warn!("Could not parse .repo file for '{}': {}", package_name, e);
-                    continue;
-                }
-            };
-            (config, installed.registry_source.clone())
-        } else if let Some(reg_name) = &registry_name {
-            // Find in specific registry
-            match RegistryManager::find_package_in_specific_registry(&package_name, reg_name).await
-            {
-                Ok(result) => result,
-                Err(e) => {
-                    log::warn!(
-                        "Could not find package '{}' in registry '{}': {}",
-                        package_name,
-                        reg_name,
-                        e
-                    );
-                    continue;
-                }
-            }
-        } else {
-            // Fallback: search in all registries
-            match RegistryManager::find_package(&package_name).await {
-                Ok(result) => result,
-                Err(e) => {
-                    log::warn!("Could not find package '{}': {}", package_name, e);
-                    continue;
-                }
+                return Ok(());
             }
-        };
+        }
+    }
 
-        // Determine if we should use allow_insecure
-        // Use the flag from the installed package if it was set during installation
-        // Otherwise, fall back to the repo config
-        let allow_insecure = installed.allow_insecure || repo_config.security.allow_insecure;
+    // Determine if we should use allow_insecure
+    // Use the flag from the installed package if it was set during installation
+    // Otherwise, fall back to the repo config
+    let allow_insecure = installed.allow_insecure || repo_config.security.allow_insecure;
 
-        // Get latest version (simplified - would need provider logic)
-        println!("Updating {} (current: {})", package_name, installed.version);
+    // Get latest version (simplified - would need provider logic)
+    println!("Updating {} (current: {})", package_name, installed.version);
+    for notice in SecurityWarningManager::update_notices(&installed, &repo_config) {
+        ui::warning(&notice);
+    }
 
-        // Uninstall old version
-        let uninstall_args = crate::cli::args::UninstallArgs {
-            package: package_name.clone(),
-            version: None,
-            purge: false,
-        };
-        uninstall::execute(uninstall_args).await?;
-
-        // Install new version
-        let install_args = InstallArgs {
-            package: package_name.clone(),
-            version: None, // Latest
-            repo: repo_file_path,
-            userland: installed.install_mode == "userland",
-            system: installed.install_mode == "system",
-            allow_insecure,
-            local: None,
-            metadata: None,
-        };
-        install::execute(install_args).await?;
+    // Uninstall old version, remembering it until the new one is installed
+    if !uninstalled {
+        if args.all {
+            progress
+                .uninstalled
+                .insert(package_name.clone(), installed.clone());
+            progress.save()?;
+        }
+        uninstall::run(&package_name, None, false).await?;
+    }
+
+    // Install new version
+    let install_args = InstallArgs {
+        package: Some(package_name.clone()),
+        version: None, // Latest
+        repo: repo_file_path,
+        userland: installed.install_mode == "userland",
+        system: installed.install_mode == "system",
+        allow_insecure,
+        allow_prerelease: args.allow_prerelease,
+        local: None,
+        metadata: None,
+        os: installed.metadata.get(TARGET_OS_KEY).cloned(),
+        arch: installed.metadata.get(TARGET_ARCH_KEY).cloned(),
+        verify_only: false,
+        download_only: false,
+        output_dir: None,
+        skip_checksum: false,
+        locked: false,
+        lockfile: None,
+    };
+    let result = install::run(install_args, Some(installed.clone())).await;
+    let audit = AuditLogger::new()?;
+    match &result {
+        Ok(Some(updated)) => {
+            audit
+                .log_update(
+                    &package_name,
+                    &installed.version,
+                    &updated.version,
+                    allow_insecure,
+                    true,
+                )
+                .await?
+        }
+        Ok(None) => {}
+        Err(_) => {
+            audit
+                .log_update(
+                    &package_name,
+                    &installed.version,
+                    "latest",
+                    allow_insecure,
+                    false,
+                )
+                .await?
+        }
+    }
+    let updated = result?;
+    progress.uninstalled.remove(&package_name);
+    if let Some(updated) = updated {
+        run_hook(HookEvent::Update, &updated.name, &updated.version).await;
     }
 
     Ok(())
 }
+
+/// State of an interrupted `update --all`, read back by
+/// `update --all --continue`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct UpdateProgress {
+    /// Packages of the batch, in update order
+    #[serde(default)]
+    packages: Vec<String>,
+    #[serde(default)]
+    completed: BTreeSet<String>,
+    /// Entries of packages uninstalled for an update that didn't finish
+    #[serde(default)]
+    uninstalled: BTreeMap<String, InstalledPackage>,
+}
+
+impl UpdateProgress {
+    fn load() -> Result<Self> {
+        let path = Paths::update_progress_file()?;
+        match std::fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    fn save(&self) -> Result<()> {
+        let path = Paths::update_progress_file()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content =
+            toml::to_string_pretty(self).context("Failed to serialize update progress")?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    fn clear() -> Result<()> {
+        let path = Paths::update_progress_file()?;
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to remove {}", path.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The `ora update` command resuming the batch, or retrying just `package`
+fn retry_command(args: &UpdateArgs, package: Option<&str>) -> String {
+    let mut command = match package {
+        Some(package) => format!("ora update {}", package),
+        None => "ora update --all --continue".to_string(),
+    };
+    if args.allow_prerelease {
+        command.push_str(" --allow-prerelease");
+    }
+    if args.only_security {
+        command.push_str(" --only-security");
+    }
+    command
+}
+
+/// Whether `installed` predates the `fixed_in` security release (semver,
+/// ignoring a leading `v`); `None` when either version isn't semver
+fn below_security_fix(installed: &str, fixed_in: &str) -> Option<bool> {
+    let parse = |v: &str| semver::Version::parse(v.trim().trim_start_matches('v')).ok();
+    Some(parse(installed)? < parse(fixed_in)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_below_security_fix() {
+        assert_eq!(below_security_fix("1.2.3", "1.2.4"), Some(true));
+        assert_eq!(below_security_fix("v1.2.4", "1.2.4"), Some(false));
+        assert_eq!(below_security_fix("2.0.0", "v1.9.0"), Some(false));
+        assert_eq!(below_security_fix("1.3.0-rc.1", "1.3.0"), Some(true));
+        assert_eq!(below_security_fix("nightly", "1.0.0"), None);
+    }
+
+    #[test]
+    fn test_retry_command() {
+        let args = UpdateArgs {
+            package: None,
+            all: true,
+            allow_prerelease: true,
+            only_security: false,
+            resume: false,
+        };
+        assert_eq!(
+            retry_command(&args, None),
+            "ora update --all --continue --allow-prerelease"
+        );
+        assert_eq!(
+            retry_command(&args, Some("ripgrep")),
+            "ora update ripgrep --allow-prerelease"
+        );
+    }
+}
@@ -1,8 +1,15 @@
+use crate::api::resolve_urls;
 use crate::cli::args::ValidateArgs;
 use crate::config::repo::RepoConfig;
+use crate::config::repo_lint::lint_repo;
+use crate::utils::http::{is_offline_mode, HttpClient};
 use anyhow::{Context, Result};
 
 pub async fn execute(args: ValidateArgs) -> Result<()> {
+    if args.check_urls && is_offline_mode() {
+        anyhow::bail!("--check-urls needs network access; remove --offline");
+    }
+
     println!("🔍 Validating .repo file: {}", args.repo_file);
 
     // Read file
@@ -18,44 +25,32 @@ pub async fn execute(args: ValidateArgs) -> Result<()> {
     println!("✅ Package name: {}", repo_config.name);
     println!("✅ Description: {}", repo_config.description);
 
-    // Check required fields
-    if repo_config.name.is_empty() {
-        anyhow::bail!("Package name cannot be empty");
-    }
-
-    if repo_config.description.is_empty() {
-        anyhow::bail!("Description cannot be empty");
-    }
-
     // Check source config
     println!("✅ Source type: {:?}", repo_config.source.provider_type);
 
     // Check binaries
-    if repo_config.install.binaries.is_empty() {
-        println!("⚠️   Warning: No binaries specified");
-    } else {
-        println!("✅ Binaries: {:?}", repo_config.install.binaries);
-    }
-
-    // Check security
-    if repo_config.security.allow_insecure {
-        println!("⚠️   Warning: Package allows insecure installation");
+    if !repo_config.install.binaries.is_empty() {
+        let binaries: Vec<String> = repo_config
+            .install
+            .binaries
+            .iter()
+            .map(|b| b.to_string())
+            .collect();
+        println!("✅ Binaries: {}", binaries.join(", "));
     }
 
     if let Some(checksum) = &repo_config.security.checksum {
         println!("✅ Checksum algorithm: {:?}", checksum.algorithm);
         println!("✅ Checksum format: {:?}", checksum.format);
-    } else if !repo_config.security.allow_insecure {
-        anyhow::bail!("No checksum configured and allow_insecure is false");
     }
 
     // Check platform config
     if let Some(platform) = &repo_config.platform {
         if !platform.os_map.is_empty() {
-            println!("✅ OS mappings: {:?}", platform.os_map);
+            println!("✅ OS mappings: {:?}", repo_config.get_os_map());
         }
         if !platform.arch_map.is_empty() {
-            println!("✅ Arch mappings: {:?}", platform.arch_map);
+            println!("✅ Arch mappings: {:?}", repo_config.get_arch_map());
         }
     }
 
@@ -66,7 +61,69 @@ pub async fn execute(args: ValidateArgs) -> Result<()> {
         println!("  - URL: {}", version_config.discovery_url);
     }
 
+    // Required fields, provider settings, regexes and templates
+    let report = lint_repo(&repo_config);
+    for warning in &report.warnings {
+        println!("⚠️   Warning: {}", warning);
+    }
+    if !report.is_ok() {
+        for error in &report.errors {
+            println!("❌ {}", error);
+        }
+        anyhow::bail!(
+            "{} problem{} found in {}",
+            report.errors.len(),
+            if report.errors.len() == 1 { "" } else { "s" },
+            args.repo_file
+        );
+    }
+
+    if args.check_urls {
+        check_urls(&repo_config, args.allow_prerelease).await?;
+    }
+
     println!("\n✅ Validation successful! The .repo file is valid.");
 
     Ok(())
 }
+
+/// Expands the download and checksum URLs of the latest version for this
+/// platform and checks that each one answers a HEAD request with success
+async fn check_urls(repo_config: &RepoConfig, allow_prerelease: bool) -> Result<()> {
+    let resolved = resolve_urls(repo_config, allow_prerelease)
+        .await
+        .context("Failed to resolve the download URL")?;
+    println!(
+        "\n🌐 Checking URLs of {} for {}/{}",
+        resolved.version, resolved.os, resolved.arch
+    );
+
+    let client = HttpClient::new()?;
+    let mut urls = vec![("Download", resolved.download_url)];
+    urls.extend(resolved.checksum_url.map(|url| ("Checksum", url)));
+
+    let mut failed = 0;
+    for (kind, url) in &urls {
+        match client.head_status(url).await {
+            Ok(status) if status.is_success() => println!("✅ {}: {} ({})", kind, url, status),
+            Ok(status) => {
+                println!("❌ {}: {} ({})", kind, url, status);
+                failed += 1;
+            }
+            Err(e) => {
+                println!("❌ {}: {} ({:#})", kind, url, e);
+                failed += 1;
+            }
+        }
+    }
+
+    if failed > 0 {
+        anyhow::bail!(
+            "{} of {} URL{} can't be downloaded; check the URL templates in the .repo file",
+            failed,
+            urls.len(),
+            if urls.len() == 1 { "" } else { "s" }
+        );
+    }
+    Ok(())
+}
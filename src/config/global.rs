@@ -7,6 +7,11 @@ pub struct GlobalConfig {
     #[serde(default = "default_config_version")]
     pub config_version: String,
 
+    /// Managed configuration: commands that would modify config.toml or
+    /// security.toml refuse to run (see `ORA_FROZEN_CONFIG`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub frozen: bool,
+
     #[serde(default)]
     pub registries: Vec<Registry>,
     #[serde(default)]
@@ -21,12 +26,70 @@ pub struct GlobalConfig {
     /// Package aliases (e.g., "k" -> "kubectl")
     #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
     pub aliases: std::collections::HashMap<String, String>,
+
+    /// Extra read-only directories searched for registry clones (e.g. a shared,
+    /// preseeded cache), checked in order after the user's own registry cache
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub registry_cache_dirs: Vec<String>,
+
+    /// User commands run after successful operations
+    #[serde(default, skip_serializing_if = "HooksConfig::is_empty")]
+    pub hooks: HooksConfig,
+}
+
+/// Commands run through `sh -c` after a successful install, uninstall or update
+///
+/// Each receives the package name and version as `$1` and `$2`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+pub struct HooksConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_install: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_uninstall: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_update: Option<String>,
+}
+
+impl HooksConfig {
+    pub fn is_empty(&self) -> bool {
+        self.post_install.is_none() && self.post_uninstall.is_none() && self.post_update.is_none()
+    }
 }
 
 fn default_config_version() -> String {
     "0.1".to_string()
 }
 
+impl GlobalConfig {
+    /// Package an alias stands for, or `name` itself when it isn't an alias
+    ///
+    /// A `name@registry` argument keeps its registry. Aliases are not chained.
+    pub fn resolve_alias(&self, name: &str) -> String {
+        let (base, registry) = match name.split_once('@') {
+            Some((base, registry)) => (base, Some(registry)),
+            None => (name, None),
+        };
+        let Some(target) = self.aliases.get(base) else {
+            return name.to_string();
+        };
+        log::info!("'{}' is an alias for '{}'", base, target);
+        match registry {
+            Some(registry) => format!("{}@{}", target, registry),
+            None => target.clone(),
+        }
+    }
+
+    /// Whether prereleases are accepted by default for packages from `registry`
+    ///
+    /// A registry's own `allow_prerelease` wins over `install.allow_prerelease`.
+    pub fn allow_prerelease_for(&self, registry: Option<&str>) -> bool {
+        registry
+            .and_then(|name| self.registries.iter().find(|r| r.name == name))
+            .and_then(|r| r.allow_prerelease)
+            .unwrap_or(self.install.allow_prerelease)
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Registry {
     pub name: String,
@@ -47,10 +110,24 @@ pub struct Registry {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub registry_dir: Option<String>,
 
+    /// Expected SHA-256 of the .repo file served by a Direct URL registry
+    /// (hex, optionally prefixed with "sha256:")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub index_checksum: Option<String>,
+
+    /// URL of a prebuilt package index (index.json) published by a Git
+    /// registry, used by `ora registry sync --index-only` instead of a clone
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub index_url: Option<String>,
+
     /// Registry priority for conflict resolution (lower = higher priority)
     /// Planned for v0.2.3
     #[serde(skip_serializing_if = "Option::is_none")]
     pub priority: Option<u8>,
+
+    /// Accept alpha/beta/rc versions from this registry (overrides install.allow_prerelease)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allow_prerelease: Option<bool>,
 }
 
 impl Registry {
@@ -89,21 +166,40 @@ pub struct TlsConfig {
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct InstallSettings {
+    /// Mode used by `ora install` when neither `--system` nor `--userland` is given
     #[serde(default = "default_install_mode")]
     pub default_mode: InstallMode,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub userland_dir: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub system_dir: Option<String>,
+    /// Accept alpha/beta/rc versions by default
+    #[serde(default)]
+    pub allow_prerelease: bool,
+    /// Where userland installs link binaries (default: `~/.local/bin`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bin_dir: Option<String>,
+    /// Where userland installs keep package files (default: `<data dir>/packages`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub packages_dir: Option<String>,
 }
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub enum InstallMode {
     Userland,
     System,
 }
 
+impl InstallMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InstallMode::Userland => "userland",
+            InstallMode::System => "system",
+        }
+    }
+}
+
 fn default_install_mode() -> InstallMode {
     InstallMode::Userland
 }
@@ -114,6 +210,9 @@ impl Default for InstallSettings {
             default_mode: InstallMode::Userland,
             userland_dir: None,
             system_dir: None,
+            allow_prerelease: false,
+            bin_dir: None,
+            packages_dir: None,
         }
     }
 }
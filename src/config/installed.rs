@@ -22,18 +22,48 @@ pub struct InstalledPackage {
     pub install_dir: String,
     pub files: Vec<String>,
     pub symlinks: Vec<String>,
+    /// Stable `packages/<name>/current` link pointing at this version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub current_path: Option<String>,
     pub registry_source: String,
+    /// `<algorithm>:<hex>` digests: the verified archive, keyed by `archive`,
+    /// and each extracted file, keyed by `file:<path in install_dir>`
     #[serde(default)]
     pub checksums: HashMap<String, String>,
     /// Whether the package was installed with --allow-insecure flag
     #[serde(default)]
     pub allow_insecure: bool,
+    /// Size of the downloaded archive in bytes (unknown for older entries)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_bytes: Option<u64>,
+    /// Wall-clock time the install took, from lookup to database update
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<u64>,
 
     /// Additional metadata (extensible for future features)
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub metadata: HashMap<String, String>,
 }
 
+/// `checksums` key of the verified download archive
+pub const ARCHIVE_CHECKSUM_KEY: &str = "archive";
+
+/// Prefix of `checksums` keys holding an installed file's hash
+pub const FILE_CHECKSUM_PREFIX: &str = "file:";
+
+impl InstalledPackage {
+    /// Verified checksum of the downloaded archive, as `<algorithm>:<hex>`
+    pub fn archive_checksum(&self) -> Option<&str> {
+        self.checksums.get(ARCHIVE_CHECKSUM_KEY).map(String::as_str)
+    }
+
+    /// Whether the checksum of the downloaded archive was recorded, as opposed
+    /// to only the hashes of the extracted files
+    pub fn has_archive_checksum(&self) -> bool {
+        self.checksums.contains_key(ARCHIVE_CHECKSUM_KEY)
+    }
+}
+
 fn default_schema_version() -> String {
     "0.1".to_string()
 }
@@ -54,9 +84,12 @@ mod tests {
             install_dir: "/test/dir".to_string(),
             files: vec!["file1".to_string()],
             symlinks: vec!["link1".to_string()],
+            current_path: None,
             registry_source: "test-registry".to_string(),
             checksums: HashMap::new(),
             allow_insecure: true,
+            download_bytes: None,
+            duration_ms: None,
             metadata: HashMap::new(),
         };
 
@@ -119,4 +152,29 @@ mod tests {
         let package: InstalledPackage = toml::from_str(toml_str).unwrap();
         assert!(package.allow_insecure);
     }
+
+    #[test]
+    fn test_archive_checksum() {
+        let toml_str = r#"
+            name = "test-package"
+            version = "1.0.0"
+            installed_at = "2024-01-01T00:00:00Z"
+            install_mode = "userland"
+            install_dir = "/test/dir"
+            files = ["file1"]
+            symlinks = ["link1"]
+            registry_source = "test-registry"
+
+            [checksums]
+            "file:bin/tool" = "sha256:1111"
+        "#;
+        let mut package: InstalledPackage = toml::from_str(toml_str).unwrap();
+        assert_eq!(package.archive_checksum(), None);
+        assert!(!package.has_archive_checksum());
+
+        package
+            .checksums
+            .insert(ARCHIVE_CHECKSUM_KEY.to_string(), "sha256:3333".to_string());
+        assert_eq!(package.archive_checksum(), Some("sha256:3333"));
+    }
 }
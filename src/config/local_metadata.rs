@@ -1,11 +1,13 @@
 use serde::{Deserialize, Serialize};
 
+use crate::config::repo::BinarySpec;
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct LocalMetadata {
     pub name: String,
     pub version: String,
     #[serde(default)]
-    pub binaries: Vec<String>,
+    pub binaries: Vec<BinarySpec>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub description: Option<String>,
 }
@@ -0,0 +1,142 @@
+//! Lockfiles written by `ora lock` and installed from with `ora install --locked`
+//!
+//! Unlike a manifest, a lockfile pins the exact artifact of each package: its
+//! download URL and archive checksum, for the platform it was locked on.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::config::repo::ChecksumAlgorithm;
+use crate::installer::verifier::ExpectedChecksum;
+
+/// Lockfile used when no path is given
+pub const DEFAULT_LOCKFILE: &str = "ora.lock";
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    /// Registry the package was installed from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+    /// `.repo` file (path or URL) the package was installed from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+    /// Platform the artifact was resolved for
+    pub os: String,
+    pub arch: String,
+    pub url: String,
+    /// Archive digest as `<algorithm>:<hex>` (`sha256` or `sha512`)
+    pub checksum: String,
+}
+
+impl LockedPackage {
+    /// Package argument for an install: `name@registry`, or just the name
+    pub fn package_spec(&self) -> String {
+        match &self.registry {
+            Some(registry) => format!("{}@{}", self.name, registry),
+            None => self.name.clone(),
+        }
+    }
+
+    pub fn expected_checksum(&self) -> Result<ExpectedChecksum> {
+        let (algorithm, hash) = self
+            .checksum
+            .split_once(':')
+            .context(format!("Invalid checksum for '{}' in lockfile", self.name))?;
+        let algorithm = ChecksumAlgorithm::from_name(algorithm).context(format!(
+            "Unsupported checksum algorithm '{}' for '{}' in lockfile",
+            algorithm, self.name
+        ))?;
+        if hash.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            anyhow::bail!("Invalid checksum for '{}' in lockfile", self.name);
+        }
+        Ok(ExpectedChecksum {
+            hash: hash.to_lowercase(),
+            algorithm,
+            signed: false,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Lockfile {
+    #[serde(default)]
+    pub packages: Vec<LockedPackage>,
+}
+
+impl Lockfile {
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read lockfile {}", path.display()))?;
+        toml::from_str(&content)
+            .with_context(|| format!("Failed to parse lockfile {}", path.display()))
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let content = format!(
+            "# Written by 'ora lock'; install with 'ora install --locked'\n\n{}",
+            toml::to_string_pretty(self).context("Failed to serialize lockfile")?
+        );
+        std::fs::write(path, content)
+            .with_context(|| format!("Failed to write lockfile {}", path.display()))
+    }
+
+    pub fn get(&self, name: &str) -> Result<&LockedPackage> {
+        self.packages
+            .iter()
+            .find(|package| package.name == name)
+            .context(format!(
+                "Package '{}' is not in the lockfile; run 'ora lock' after installing it",
+                name
+            ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locked(checksum: &str) -> LockedPackage {
+        LockedPackage {
+            name: "ripgrep".to_string(),
+            version: "14.1.0".to_string(),
+            registry: Some("official".to_string()),
+            repo: None,
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+            url: "https://example.com/ripgrep-14.1.0.tar.gz".to_string(),
+            checksum: checksum.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(DEFAULT_LOCKFILE);
+        let lockfile = Lockfile {
+            packages: vec![locked("sha256:ABCD")],
+        };
+        lockfile.save(&path).unwrap();
+
+        let loaded = Lockfile::load(&path).unwrap();
+        assert_eq!(loaded, lockfile);
+        assert_eq!(
+            loaded.get("ripgrep").unwrap().package_spec(),
+            "ripgrep@official"
+        );
+        assert!(loaded.get("fd").is_err());
+    }
+
+    #[test]
+    fn test_expected_checksum() {
+        let expected = locked("sha256:ABCD").expected_checksum().unwrap();
+        assert_eq!(expected.hash, "abcd");
+        assert!(matches!(expected.algorithm, ChecksumAlgorithm::Sha256));
+
+        assert!(locked("abcd").expected_checksum().is_err());
+        assert!(locked("md5:abcd").expected_checksum().is_err());
+        assert!(locked("sha256:not-hex").expected_checksum().is_err());
+    }
+}
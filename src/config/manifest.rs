@@ -0,0 +1,272 @@
+//! Package manifests written by `ora export` and read by `ora import`
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::config::installed::InstalledPackage;
+
+/// On-disk format of a package manifest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ManifestFormat {
+    /// `[[packages]]` tables (default)
+    Toml,
+    /// JSON object with a `packages` array
+    Json,
+    /// One `ora "name", version: "x.y.z"` line per package
+    Brewfile,
+}
+
+impl ManifestFormat {
+    /// Guesses the format from a file extension, defaulting to the Brewfile style
+    pub fn from_path(path: &Path) -> Self {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Self::Toml,
+            Some("json") => Self::Json,
+            _ => Self::Brewfile,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct ManifestEntry {
+    pub name: String,
+    pub version: String,
+    /// Registry the package was installed from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<String>,
+    /// `.repo` file (path or URL) the package was installed from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo: Option<String>,
+}
+
+impl ManifestEntry {
+    /// Builds an entry for an installed package, or `None` for local archive
+    /// installs, which can't be reproduced from a manifest
+    pub fn from_installed(package: &InstalledPackage) -> Option<Self> {
+        let (registry, repo) = if let Some(name) = package.registry_source.strip_prefix("registry:")
+        {
+            (Some(name.to_string()), None)
+        } else if let Some(path) = package
+            .registry_source
+            .strip_prefix("file:")
+            .or_else(|| package.registry_source.strip_prefix("url:"))
+        {
+            (None, Some(path.to_string()))
+        } else {
+            return None;
+        };
+
+        Some(Self {
+            name: package.name.clone(),
+            version: package.version.clone(),
+            registry,
+            repo,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Manifest {
+    #[serde(default)]
+    pub packages: Vec<ManifestEntry>,
+}
+
+impl Manifest {
+    pub fn serialize(&self, format: ManifestFormat) -> Result<String> {
+        match format {
+            ManifestFormat::Toml => {
+                toml::to_string_pretty(self).context("Failed to serialize manifest")
+            }
+            ManifestFormat::Json => {
+                serde_json::to_string_pretty(self).context("Failed to serialize manifest")
+            }
+            ManifestFormat::Brewfile => Ok(self
+                .packages
+                .iter()
+                .map(|entry| format!("{}\n", brewfile_line(entry)))
+                .collect()),
+        }
+    }
+
+    pub fn parse(content: &str, format: ManifestFormat) -> Result<Self> {
+        match format {
+            ManifestFormat::Toml => toml::from_str(content).context("Failed to parse manifest"),
+            ManifestFormat::Json => {
+                serde_json::from_str(content).context("Failed to parse manifest")
+            }
+            ManifestFormat::Brewfile => {
+                let mut packages = Vec::new();
+                for (index, line) in content.lines().enumerate() {
+                    let line = line.trim();
+                    if line.is_empty() || line.starts_with('#') {
+                        continue;
+                    }
+                    packages.push(
+                        parse_brewfile_line(line)
+                            .context(format!("Invalid manifest line {}", index + 1))?,
+                    );
+                }
+                Ok(Self { packages })
+            }
+        }
+    }
+}
+
+fn brewfile_line(entry: &ManifestEntry) -> String {
+    let mut line = format!(
+        "ora {}, version: {}",
+        quote(&entry.name),
+        quote(&entry.version)
+    );
+    if let Some(registry) = &entry.registry {
+        line.push_str(&format!(", registry: {}", quote(registry)));
+    }
+    if let Some(repo) = &entry.repo {
+        line.push_str(&format!(", repo: {}", quote(repo)));
+    }
+    line
+}
+
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Parses `ora "name", key: "value", ...`
+fn parse_brewfile_line(line: &str) -> Result<ManifestEntry> {
+    let rest = line
+        .strip_prefix("ora ")
+        .context("Expected a line starting with 'ora'")?;
+    let (name, mut rest) = parse_quoted(rest.trim_start())?;
+
+    let mut version = None;
+    let mut registry = None;
+    let mut repo = None;
+    loop {
+        rest = rest.trim_start();
+        if rest.is_empty() {
+            break;
+        }
+        rest = rest
+            .strip_prefix(',')
+            .context("Expected ',' between fields")?
+            .trim_start();
+        let (key, after_key) = rest.split_once(':').context("Expected 'key: \"value\"'")?;
+        let (value, after_value) = parse_quoted(after_key.trim_start())?;
+        rest = after_value;
+
+        match key.trim() {
+            "version" => version = Some(value),
+            "registry" => registry = Some(value),
+            "repo" => repo = Some(value),
+            other => anyhow::bail!("Unknown field '{}'", other),
+        }
+    }
+
+    Ok(ManifestEntry {
+        name,
+        version: version.context("Missing 'version'")?,
+        registry,
+        repo,
+    })
+}
+
+/// Reads a double-quoted string, returning it and the remaining input
+fn parse_quoted(input: &str) -> Result<(String, &str)> {
+    let mut chars = input
+        .strip_prefix('"')
+        .context("Expected a double-quoted string")?
+        .char_indices();
+    let mut value = String::new();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return Ok((value, &input[i + 2..])),
+            '\\' => match chars.next() {
+                Some((_, escaped)) => value.push(escaped),
+                None => break,
+            },
+            c => value.push(c),
+        }
+    }
+
+    anyhow::bail!("Unterminated string")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Manifest {
+        Manifest {
+            packages: vec![
+                ManifestEntry {
+                    name: "ripgrep".to_string(),
+                    version: "13.0.0".to_string(),
+                    registry: Some("official".to_string()),
+                    repo: None,
+                },
+                ManifestEntry {
+                    name: "tool".to_string(),
+                    version: "1.0.0".to_string(),
+                    registry: None,
+                    repo: Some("/home/me/dotfiles/\"tool\".repo".to_string()),
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_brewfile_round_trip() {
+        let text = sample().serialize(ManifestFormat::Brewfile).unwrap();
+        assert!(text.starts_with("ora \"ripgrep\", version: \"13.0.0\", registry: \"official\"\n"));
+
+        let parsed = Manifest::parse(&text, ManifestFormat::Brewfile).unwrap();
+        assert_eq!(parsed, sample());
+    }
+
+    #[test]
+    fn test_structured_formats_round_trip() {
+        for format in [ManifestFormat::Toml, ManifestFormat::Json] {
+            let text = sample().serialize(format).unwrap();
+            assert_eq!(Manifest::parse(&text, format).unwrap(), sample());
+        }
+    }
+
+    #[test]
+    fn test_brewfile_parsing() {
+        let parsed = Manifest::parse(
+            "# tools\n\nora \"fd\",version:\"9.0.0\"  \n",
+            ManifestFormat::Brewfile,
+        )
+        .unwrap();
+        assert_eq!(parsed.packages[0].name, "fd");
+        assert_eq!(parsed.packages[0].version, "9.0.0");
+
+        for invalid in [
+            "brew \"fd\"",
+            "ora \"fd\"",
+            "ora \"fd\", version: \"1.0",
+            "ora \"fd\", version: \"1.0\", arch: \"x86_64\"",
+        ] {
+            let err = Manifest::parse(invalid, ManifestFormat::Brewfile).unwrap_err();
+            assert!(format!("{:#}", err).contains("line 1"), "{}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_format_from_path() {
+        assert_eq!(
+            ManifestFormat::from_path(Path::new("ora.toml")),
+            ManifestFormat::Toml
+        );
+        assert_eq!(
+            ManifestFormat::from_path(Path::new("ora.json")),
+            ManifestFormat::Json
+        );
+        assert_eq!(
+            ManifestFormat::from_path(Path::new("Orafile")),
+            ManifestFormat::Brewfile
+        );
+    }
+}
@@ -185,6 +185,34 @@ pub fn migrate_installed_database(db: &mut InstalledDatabase) -> Result<()> {
     Ok(())
 }
 
+/// Dotted paths of the keys present in `after` but missing from `before`
+///
+/// Used by `ora config migrate` to report the fields a migration writes. Only
+/// tables are walked; a new key is reported once, not with its children.
+pub fn added_fields(before: &toml::Value, after: &toml::Value) -> Vec<String> {
+    fn walk(prefix: &str, before: &toml::Value, after: &toml::Value, added: &mut Vec<String>) {
+        let (Some(before), Some(after)) = (before.as_table(), after.as_table()) else {
+            return;
+        };
+        for (key, value) in after {
+            let path = if prefix.is_empty() {
+                key.clone()
+            } else {
+                format!("{}.{}", prefix, key)
+            };
+            match before.get(key) {
+                Some(previous) => walk(&path, previous, value, added),
+                None => added.push(path),
+            }
+        }
+    }
+
+    let mut added = Vec::new();
+    walk("", before, after, &mut added);
+    added.sort();
+    added
+}
+
 /// Check if version `a` is newer than version `b`
 ///
 /// Compares versions in "x.y" format.
@@ -245,9 +273,12 @@ mod tests {
             install_dir: "/test/dir".to_string(),
             files: vec![],
             symlinks: vec![],
+            current_path: None,
             registry_source: "test".to_string(),
             checksums: HashMap::new(),
             allow_insecure: false,
+            download_bytes: None,
+            duration_ms: None,
             metadata: HashMap::new(),
         };
 
@@ -271,9 +302,12 @@ mod tests {
             install_dir: "/test".to_string(),
             files: vec![],
             symlinks: vec![],
+            current_path: None,
             registry_source: "test".to_string(),
             checksums: HashMap::new(),
             allow_insecure: false,
+            download_bytes: None,
+            duration_ms: None,
             metadata: HashMap::new(),
         };
 
@@ -310,9 +344,12 @@ mod tests {
             install_dir: "/test/dir".to_string(),
             files: vec![],
             symlinks: vec![],
+            current_path: None,
             registry_source: "test".to_string(),
             checksums: HashMap::new(),
             allow_insecure: false,
+            download_bytes: None,
+            duration_ms: None,
             metadata: HashMap::new(),
         };
 
@@ -323,6 +360,36 @@ mod tests {
         assert!(error_msg.contains("is newer than supported"));
     }
 
+    #[test]
+    fn test_added_fields() {
+        let before: toml::Value = toml::from_str(
+            r#"
+            [install]
+            allow_prerelease = true
+            "#,
+        )
+        .unwrap();
+        let after: toml::Value = toml::from_str(
+            r#"
+            config_version = "0.1"
+
+            [install]
+            allow_prerelease = true
+            default_mode = "userland"
+
+            [security]
+            require_checksums = true
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            added_fields(&before, &after),
+            vec!["config_version", "install.default_mode", "security"]
+        );
+        assert!(added_fields(&after, &after).is_empty());
+    }
+
     #[test]
     fn test_is_version_newer() {
         // Newer versions
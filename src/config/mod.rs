@@ -1,8 +1,11 @@
 pub mod global;
 pub mod installed;
 pub mod local_metadata;
+pub mod lockfile;
+pub mod manifest;
 pub mod migrations;
 pub mod repo;
+pub mod repo_lint;
 pub mod security_config;
 pub mod security_limits;
 
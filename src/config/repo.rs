@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use crate::error::OraError;
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct RepoConfig {
     pub name: String,
@@ -26,10 +28,29 @@ pub struct SourceConfig {
     pub repo: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub instance: Option<String>,
+    /// Numeric GitLab project ID (alternative to `repo`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<u64>,
+    /// Environment variable holding a GitLab access token (sent as PRIVATE-TOKEN)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_env: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub api_format: Option<String>,
+    /// HTTP method for custom-api requests (`GET` or `POST`, default `GET`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub method: Option<String>,
+    /// Extra headers for custom-api requests; `${ORA_*}` references are read
+    /// from the environment
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub headers: HashMap<String, String>,
+    /// Request body for custom-api requests (typically JSON with `method = "POST"`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    /// Send custom-api `headers` over plain HTTP too (HTTPS only by default)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub allow_insecure_headers: bool,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub download: Option<DownloadConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -52,6 +73,10 @@ pub struct DownloadConfig {
     pub url: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub urls: Option<HashMap<String, String>>,
+    /// custom-api only: `url` is an API endpoint and the archive URL is read
+    /// from its JSON response at this path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json_path: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -59,13 +84,91 @@ pub struct InstallConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mode: Option<InstallMode>,
     #[serde(default)]
-    pub binaries: Vec<String>,
+    pub binaries: Vec<BinarySpec>,
+    /// Glob patterns of archive members to extract; everything when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extract_only: Vec<String>,
     #[serde(default)]
     pub files: Vec<AdditionalFile>,
+    /// When the archive holds a single top-level directory, install its
+    /// contents instead, so `binaries` and `files` paths are relative to it
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub strip_single_dir: bool,
+    /// Auxiliary files (e.g. a default config) fetched from their own URLs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extra_downloads: Vec<ExtraDownload>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub post_install: Option<String>,
     #[serde(default)]
     pub env: HashMap<String, String>,
+    /// Package directories to add to PATH (e.g. `libexec`), relative to the package
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub path_additions: Vec<String>,
+    /// Environment variables the tool needs; `{install_dir}` expands to the package directory
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub env_exports: HashMap<String, String>,
+    /// Archive layouts of older or newer versions; the first entry whose
+    /// `version_req` matches the installed version replaces the fields it sets
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub overrides: Vec<InstallOverride>,
+}
+
+/// `install.binaries`, `files` and `extract_only` for the versions matching
+/// a semver requirement (e.g. `"<2.0.0"`)
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct InstallOverride {
+    pub version_req: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub binaries: Option<Vec<BinarySpec>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<AdditionalFile>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extract_only: Option<Vec<String>>,
+}
+
+impl InstallConfig {
+    /// Applies the first override matching `version` (semver, ignoring a
+    /// leading `v`), returning its `version_req`
+    ///
+    /// Versions that aren't semver keep the default layout.
+    pub fn apply_version_override(&mut self, version: &str) -> anyhow::Result<Option<String>> {
+        if self.overrides.is_empty() {
+            return Ok(None);
+        }
+        let Ok(parsed) = semver::Version::parse(version.trim().trim_start_matches('v')) else {
+            log::warn!(
+                "Version {} is not semver; ignoring install.overrides",
+                version
+            );
+            return Ok(None);
+        };
+
+        for install_override in &self.overrides {
+            let req = semver::VersionReq::parse(&install_override.version_req).map_err(|e| {
+                anyhow::anyhow!(
+                    "Invalid install.overrides version_req '{}': {}",
+                    install_override.version_req,
+                    e
+                )
+            })?;
+            if !req.matches(&parsed) {
+                continue;
+            }
+
+            let install_override = install_override.clone();
+            if let Some(binaries) = install_override.binaries {
+                self.binaries = binaries;
+            }
+            if let Some(files) = install_override.files {
+                self.files = files;
+            }
+            if let Some(extract_only) = install_override.extract_only {
+                self.extract_only = extract_only;
+            }
+            return Ok(Some(install_override.version_req));
+        }
+        Ok(None)
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -75,12 +178,64 @@ pub enum InstallMode {
     System,
 }
 
+/// An `install.binaries` entry: a path or glob relative to the archive root,
+/// linked under its own file name or, in the table form, under `name`
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum BinarySpec {
+    Path(String),
+    Renamed { src: String, name: String },
+}
+
+impl BinarySpec {
+    /// Path or glob of the binary inside the package
+    pub fn src(&self) -> &str {
+        match self {
+            Self::Path(src) | Self::Renamed { src, .. } => src,
+        }
+    }
+
+    /// Name of the link in the bin directory, when it differs from the file name
+    pub fn link_name(&self) -> Option<&str> {
+        match self {
+            Self::Path(_) => None,
+            Self::Renamed { name, .. } => Some(name),
+        }
+    }
+}
+
+impl From<&str> for BinarySpec {
+    fn from(src: &str) -> Self {
+        Self::Path(src.to_string())
+    }
+}
+
+impl std::fmt::Display for BinarySpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Path(src) => write!(f, "{}", src),
+            Self::Renamed { src, name } => write!(f, "{} as {}", src, name),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct AdditionalFile {
     pub src: String,
     pub dst: String,
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExtraDownload {
+    /// URL template; `{version}`, `{os}` and `{arch}` are substituted
+    pub url: String,
+    /// Destination, relative to the package directory
+    pub dst: String,
+    /// Expected SHA-256 (hex, optionally prefixed with "sha256:")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub checksum: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct SecurityConfig {
     #[serde(default)]
@@ -95,12 +250,27 @@ pub struct SecurityConfig {
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ChecksumConfig {
+    /// Checksum file URL template (not needed with `from_release_notes`)
+    #[serde(default)]
     pub url: String,
     pub algorithm: ChecksumAlgorithm,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub filename_pattern: Option<String>,
     #[serde(default = "default_checksum_format")]
     pub format: ChecksumFormat,
+    /// Detached GPG signature of the checksum file itself (e.g. `SHA256SUMS.asc`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature_url: Option<String>,
+    /// Key the checksum file signature must verify against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    /// Full fingerprint `public_key` must have
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+    /// Regex extracting the hash (first capture group) from the release notes
+    /// instead of a checksum file; `{filename}` matches the archive name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from_release_notes: Option<String>,
 }
 
 fn default_checksum_format() -> ChecksumFormat {
@@ -121,6 +291,25 @@ pub enum ChecksumAlgorithm {
     Sha512,
 }
 
+impl ChecksumAlgorithm {
+    /// Name used in `<algorithm>:<hex>` digests
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChecksumAlgorithm::Sha256 => "sha256",
+            ChecksumAlgorithm::Sha512 => "sha512",
+        }
+    }
+
+    /// Inverse of [`ChecksumAlgorithm::as_str`]
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sha256" => Some(ChecksumAlgorithm::Sha256),
+            "sha512" => Some(ChecksumAlgorithm::Sha512),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct GpgConfig {
     pub signature_url: String,
@@ -153,6 +342,13 @@ pub struct MetadataConfig {
     pub min_version: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_version: Option<String>,
+    /// Oldest Ora release able to install this package correctly
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub min_ora_version: Option<String>,
+    /// First version fixing the latest security advisory; installs below it
+    /// are picked up by `ora update --only-security`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub security_fixed_in: Option<String>,
     #[serde(default)]
     pub dependencies: Vec<SystemDependency>,
 }
@@ -177,12 +373,45 @@ pub struct RegistrySignature {
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct PlatformConfig {
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
-    pub os_map: HashMap<String, String>,
+    pub os_map: HashMap<String, PlatformAlias>,
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
-    pub arch_map: HashMap<String, String>,
-    /// URL filters for webpage-scraping provider: maps "os_arch" to URL substring
+    pub arch_map: HashMap<String, PlatformAlias>,
+    /// URL filters: maps "os_arch" to a URL substring selecting the download
+    /// among scraped URLs (webpage-scraping) or release assets (GitHub/GitLab)
     #[serde(default, skip_serializing_if = "HashMap::is_empty")]
     pub url_filters: HashMap<String, String>,
+    /// Linux C library (`gnu`/`musl`) → name used for `{libc}` in templates
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub libc_map: HashMap<String, String>,
+    /// OS names to try after the mapped and detected ones (e.g. `["linux"]` on BSDs)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub os_fallbacks: Vec<String>,
+    /// Arch names to try after the mapped and detected ones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arch_fallbacks: Vec<String>,
+}
+
+/// Platform name mapping: a single name or candidate names tried in order
+///
+/// ```toml
+/// x86_64 = "amd64"
+/// aarch64 = ["arm64", "aarch64"]
+/// ```
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum PlatformAlias {
+    Single(String),
+    Multiple(Vec<String>),
+}
+
+impl PlatformAlias {
+    /// Candidate names in order of preference
+    pub fn candidates(&self) -> Vec<String> {
+        match self {
+            PlatformAlias::Single(name) => vec![name.clone()],
+            PlatformAlias::Multiple(names) => names.clone(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -199,6 +428,9 @@ pub struct VersionDiscoveryConfig {
     /// Regex pattern to extract version from URL (for webpage-scraping)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version_pattern: Option<String>,
+    /// Path to each version's release date, parallel to `json_path` (json discovery)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub date_json_path: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -226,21 +458,147 @@ fn default_true() -> bool {
 // ========== Helper Methods ==========
 
 impl RepoConfig {
-    /// Get OS mapping from platform config
-    pub fn get_os_map(&self) -> HashMap<String, String> {
+    /// Get OS mapping (detected OS → candidate names) from platform config
+    pub fn get_os_map(&self) -> HashMap<String, Vec<String>> {
+        self.platform
+            .as_ref()
+            .map(|p| Self::expand_aliases(&p.os_map))
+            .unwrap_or_default()
+    }
+
+    /// Get arch mapping (detected arch → candidate names) from platform config
+    pub fn get_arch_map(&self) -> HashMap<String, Vec<String>> {
+        self.platform
+            .as_ref()
+            .map(|p| Self::expand_aliases(&p.arch_map))
+            .unwrap_or_default()
+    }
+
+    /// Declared fallback OS names, tried after the mapped and detected ones
+    pub fn get_os_fallbacks(&self) -> Vec<String> {
         self.platform
             .as_ref()
-            .map(|p| p.os_map.clone())
+            .map(|p| p.os_fallbacks.clone())
             .unwrap_or_default()
     }
 
-    /// Get arch mapping from platform config
-    pub fn get_arch_map(&self) -> HashMap<String, String> {
+    /// Asset filter declared for a platform in `platform.url_filters`
+    pub fn get_url_filter(&self, os: &str, arch: &str) -> Option<&str> {
+        self.platform
+            .as_ref()?
+            .url_filters
+            .get(&format!("{}_{}", os, arch))
+            .map(String::as_str)
+    }
+
+    /// Declared fallback arch names, tried after the mapped and detected ones
+    pub fn get_arch_fallbacks(&self) -> Vec<String> {
         self.platform
             .as_ref()
-            .map(|p| p.arch_map.clone())
+            .map(|p| p.arch_fallbacks.clone())
             .unwrap_or_default()
     }
+
+    /// Routes downloads to the build for the given Linux C library (`gnu` or `musl`)
+    ///
+    /// Fills `{libc}` in download, checksum and signature templates (through
+    /// `platform.libc_map`), and lets `<os>_<arch>_<libc>` entries of
+    /// `download.urls` and `platform.url_filters` replace the `<os>_<arch>` ones.
+    pub fn apply_libc(&mut self, libc: &str) {
+        let name = self
+            .platform
+            .as_ref()
+            .and_then(|p| p.libc_map.get(libc))
+            .cloned()
+            .unwrap_or_else(|| libc.to_string());
+        let fill = |template: &mut String| *template = template.replace("{libc}", &name);
+
+        if let Some(download) = &mut self.source.download {
+            if let Some(url) = &mut download.url {
+                fill(url);
+            }
+            if let Some(urls) = &mut download.urls {
+                Self::select_libc_entries(urls, libc);
+                urls.values_mut().for_each(fill);
+            }
+        }
+        if let Some(checksum) = &mut self.security.checksum {
+            fill(&mut checksum.url);
+            if let Some(pattern) = &mut checksum.filename_pattern {
+                fill(pattern);
+            }
+            if let Some(signature_url) = &mut checksum.signature_url {
+                fill(signature_url);
+            }
+        }
+        if let Some(gpg) = &mut self.security.gpg {
+            fill(&mut gpg.signature_url);
+        }
+        if let Some(platform) = &mut self.platform {
+            Self::select_libc_entries(&mut platform.url_filters, libc);
+        }
+    }
+
+    /// Copies `<key>_<libc>` entries over `<key>`
+    fn select_libc_entries(map: &mut HashMap<String, String>, libc: &str) {
+        let suffix = format!("_{}", libc);
+        let overrides: Vec<(String, String)> = map
+            .iter()
+            .filter_map(|(key, value)| {
+                key.strip_suffix(&suffix)
+                    .map(|base| (base.to_string(), value.clone()))
+            })
+            .collect();
+        map.extend(overrides);
+    }
+
+    fn expand_aliases(map: &HashMap<String, PlatformAlias>) -> HashMap<String, Vec<String>> {
+        map.iter()
+            .map(|(key, alias)| (key.clone(), alias.candidates()))
+            .collect()
+    }
+
+    /// Fails if `metadata.min_ora_version` is newer than `ora_version`
+    pub fn check_ora_version(&self, ora_version: &str) -> Result<(), OraError> {
+        let Some(required) = self
+            .metadata
+            .as_ref()
+            .and_then(|m| m.min_ora_version.as_deref())
+        else {
+            return Ok(());
+        };
+
+        let parse = |v: &str| semver::Version::parse(v.trim().trim_start_matches('v'));
+        let required_version = parse(required).map_err(|_| {
+            OraError::InvalidRepoFormat(format!(
+                "metadata.min_ora_version '{}' is not a valid version",
+                required
+            ))
+        })?;
+        let current = parse(ora_version).map_err(|e| {
+            OraError::ConfigError(format!("Invalid Ora version '{}': {}", ora_version, e))
+        })?;
+
+        if current < required_version {
+            return Err(OraError::ConfigError(format!(
+                "package '{}' requires Ora {} or newer, but this is Ora {}. \
+                 Upgrade Ora (e.g. with 'ora self-update') and try again.",
+                self.name, required, ora_version
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Copy safe to display: custom-api header values, which may hold
+    /// literal tokens, are replaced with `<redacted>`
+    pub fn redacted(&self) -> RepoConfig {
+        let mut config = self.clone();
+        for value in config.source.headers.values_mut() {
+            *value = "<redacted>".to_string();
+        }
+        config
+    }
 }
 
 impl ChecksumConfig {
@@ -249,3 +607,207 @@ impl ChecksumConfig {
         matches!(self.format, ChecksumFormat::SingleHash)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_binaries_plain_and_renamed() {
+        let install: InstallConfig = toml::from_str(
+            r#"
+binaries = ["bin/tool", { src = "bin/myapp-cli", name = "myapp" }]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(install.binaries[0], BinarySpec::from("bin/tool"));
+        assert_eq!(install.binaries[0].link_name(), None);
+        assert_eq!(install.binaries[1].src(), "bin/myapp-cli");
+        assert_eq!(install.binaries[1].link_name(), Some("myapp"));
+        assert_eq!(install.binaries[1].to_string(), "bin/myapp-cli as myapp");
+    }
+
+    #[test]
+    fn test_redacted_hides_header_values() {
+        let repo: RepoConfig = toml::from_str(
+            r#"
+name = "tool"
+description = "A tool"
+
+[source]
+type = "custom-api"
+api_url = "https://api.example.com/releases"
+
+[source.headers]
+Authorization = "Bearer s3cr3t"
+
+[install]
+binaries = ["tool"]
+
+[security]
+"#,
+        )
+        .unwrap();
+
+        let rendered = toml::to_string_pretty(&repo.redacted()).unwrap();
+        assert!(!rendered.contains("s3cr3t"));
+        assert!(rendered.contains("Authorization = \"<redacted>\""));
+        assert!(rendered.contains("custom-api"));
+    }
+
+    fn repo_with_min_ora(min_ora_version: &str) -> RepoConfig {
+        toml::from_str(&format!(
+            r#"
+name = "tool"
+description = "A tool"
+
+[source]
+type = "github-releases"
+repo = "owner/tool"
+
+[install]
+binaries = ["tool"]
+
+[security]
+
+[metadata]
+min_ora_version = "{}"
+"#,
+            min_ora_version
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_check_ora_version() {
+        assert!(repo_with_min_ora("0.2.0")
+            .check_ora_version("0.2.0")
+            .is_ok());
+        assert!(repo_with_min_ora("v0.1.5")
+            .check_ora_version("0.2.0")
+            .is_ok());
+
+        let err = repo_with_min_ora("1.0.0")
+            .check_ora_version("0.2.0")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("requires Ora 1.0.0 or newer"));
+        assert!(err.contains("Upgrade Ora"));
+
+        assert!(repo_with_min_ora("soon")
+            .check_ora_version("0.2.0")
+            .is_err());
+    }
+
+    #[test]
+    fn test_apply_libc() {
+        let mut config: RepoConfig = toml::from_str(
+            r#"
+name = "tool"
+description = "A tool"
+
+[source]
+type = "github-releases"
+repo = "owner/tool"
+
+[source.download]
+url = "https://example.com/tool-{version}-{arch}-unknown-linux-{libc}.tar.gz"
+
+[platform.libc_map]
+gnu = "gnu"
+musl = "musl-static"
+
+[install]
+binaries = ["tool"]
+
+[security.checksum]
+url = "https://example.com/tool-{version}-{libc}.sha256"
+algorithm = "sha256"
+"#,
+        )
+        .unwrap();
+
+        config.apply_libc("musl");
+
+        assert_eq!(
+            config.source.download.as_ref().unwrap().url.as_deref(),
+            Some("https://example.com/tool-{version}-{arch}-unknown-linux-musl-static.tar.gz")
+        );
+        assert_eq!(
+            config.security.checksum.as_ref().unwrap().url,
+            "https://example.com/tool-{version}-musl-static.sha256"
+        );
+    }
+
+    #[test]
+    fn test_apply_libc_selects_platform_entries() {
+        let mut config: RepoConfig = toml::from_str(
+            r#"
+name = "tool"
+description = "A tool"
+
+[source]
+type = "direct-url"
+
+[source.download.urls]
+linux_x86_64 = "https://example.com/tool-linux-gnu.tar.gz"
+linux_x86_64_musl = "https://example.com/tool-linux-musl.tar.gz"
+
+[install]
+binaries = ["tool"]
+
+[security]
+"#,
+        )
+        .unwrap();
+
+        config.apply_libc("musl");
+
+        let urls = config.source.download.unwrap().urls.unwrap();
+        assert_eq!(
+            urls["linux_x86_64"],
+            "https://example.com/tool-linux-musl.tar.gz"
+        );
+    }
+
+    #[test]
+    fn test_apply_version_override() {
+        let install: InstallConfig = toml::from_str(
+            r#"
+binaries = ["tool-*/bin/tool"]
+
+[[overrides]]
+version_req = "<2.0.0"
+binaries = ["tool"]
+
+[[overrides]]
+version_req = "<3.0.0"
+files = [{ src = "doc/tool.1", dst = "share/man/man1/tool.1" }]
+"#,
+        )
+        .unwrap();
+
+        let mut old = install.clone();
+        assert_eq!(
+            old.apply_version_override("v1.4.0").unwrap().as_deref(),
+            Some("<2.0.0")
+        );
+        assert_eq!(old.binaries, vec![BinarySpec::from("tool")]);
+        assert!(old.files.is_empty());
+
+        // Only the first matching entry applies; unset fields keep the default
+        let mut middle = install.clone();
+        assert_eq!(
+            middle.apply_version_override("2.1.0").unwrap().as_deref(),
+            Some("<3.0.0")
+        );
+        assert_eq!(middle.binaries, vec![BinarySpec::from("tool-*/bin/tool")]);
+        assert_eq!(middle.files.len(), 1);
+
+        let mut latest = install.clone();
+        assert_eq!(latest.apply_version_override("3.0.0").unwrap(), None);
+        assert_eq!(latest.apply_version_override("nightly").unwrap(), None);
+        assert_eq!(latest.binaries, vec![BinarySpec::from("tool-*/bin/tool")]);
+    }
+}
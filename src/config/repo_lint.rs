@@ -0,0 +1,600 @@
+//! Structural checks for `.repo` files, shared by `ora validate` and
+//! `ora registry verify --deep`
+
+use crate::config::repo::{ProviderType, RepoConfig};
+use crate::security::gpg::is_valid_fingerprint;
+use crate::utils::regex::build_safe_regex;
+
+/// Placeholders URL templates may use
+const TEMPLATE_VARIABLES: &[&str] = &["name", "version", "os", "arch", "libc", "repo", "commit"];
+
+/// Problems found in a `.repo` file
+#[derive(Debug, Default)]
+pub struct LintReport {
+    /// Issues that make the package uninstallable
+    pub errors: Vec<String>,
+    /// Suspicious but installable configuration
+    pub warnings: Vec<String>,
+}
+
+impl LintReport {
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Checks required fields, provider settings, regexes and URL templates
+pub fn lint_repo(repo: &RepoConfig) -> LintReport {
+    let mut report = LintReport::default();
+
+    if repo.name.trim().is_empty() {
+        report
+            .errors
+            .push("Package name cannot be empty".to_string());
+    }
+    if repo.description.trim().is_empty() {
+        report
+            .errors
+            .push("Description cannot be empty".to_string());
+    }
+
+    check_provider_fields(repo, &mut report);
+    check_security(repo, &mut report);
+    check_regexes(repo, &mut report);
+    check_templates(repo, &mut report);
+
+    if repo.install.binaries.is_empty() {
+        report.warnings.push(
+            "No binaries specified; the archive must contain a single executable".to_string(),
+        );
+    }
+    for binary in &repo.install.binaries {
+        if let Err(e) = glob::Pattern::new(binary.src()) {
+            report.errors.push(format!(
+                "Invalid binaries pattern '{}': {}",
+                binary.src(),
+                e
+            ));
+        }
+        if let Some(name) = binary.link_name() {
+            if let Err(e) = crate::installer::deployer::validate_link_name(name) {
+                report.errors.push(format!("{:#}", e));
+            }
+        }
+    }
+    for install_override in &repo.install.overrides {
+        if let Err(e) = semver::VersionReq::parse(&install_override.version_req) {
+            report.errors.push(format!(
+                "Invalid install.overrides version_req '{}': {}",
+                install_override.version_req, e
+            ));
+        }
+        for binary in install_override.binaries.iter().flatten() {
+            if let Err(e) = glob::Pattern::new(binary.src()) {
+                report.errors.push(format!(
+                    "Invalid binaries pattern '{}' in install.overrides: {}",
+                    binary.src(),
+                    e
+                ));
+            }
+        }
+        if install_override.binaries.is_none()
+            && install_override.files.is_none()
+            && install_override.extract_only.is_none()
+        {
+            report.warnings.push(format!(
+                "install.overrides entry '{}' changes nothing",
+                install_override.version_req
+            ));
+        }
+    }
+    for extra in &repo.install.extra_downloads {
+        if let Err(e) = crate::installer::deployer::validate_extra_download_dst(&extra.dst) {
+            report.errors.push(format!("{:#}", e));
+        }
+        if extra.checksum.is_none() && !repo.security.allow_insecure {
+            report.errors.push(format!(
+                "No checksum configured for extra download {} and allow_insecure is false",
+                extra.url
+            ));
+        }
+    }
+
+    report
+}
+
+fn check_provider_fields(repo: &RepoConfig, report: &mut LintReport) {
+    let source = &repo.source;
+    let download = source.download.as_ref();
+    let has_download = download.is_some_and(|d| d.url.is_some() || d.urls.is_some());
+
+    match source.provider_type {
+        ProviderType::GithubReleases => {
+            if source.repo.is_none() {
+                report
+                    .errors
+                    .push("github-releases requires source.repo".to_string());
+            }
+        }
+        ProviderType::GitlabReleases => {
+            if source.repo.is_none() && source.project_id.is_none() {
+                report
+                    .errors
+                    .push("gitlab-releases requires source.repo or source.project_id".to_string());
+            }
+        }
+        ProviderType::CustomApi => {
+            if source.api_url.is_none() {
+                report
+                    .errors
+                    .push("custom-api requires source.api_url".to_string());
+            }
+            if source.version.is_none() {
+                report.warnings.push(
+                    "custom-api without [source.version] discovery lists no versions".to_string(),
+                );
+            }
+        }
+        ProviderType::DirectUrl => {}
+        ProviderType::WebpageScraping => {
+            if source.version.is_none() {
+                report
+                    .errors
+                    .push("webpage-scraping requires [source.version]".to_string());
+            }
+            if repo
+                .platform
+                .as_ref()
+                .is_none_or(|p| p.url_filters.is_empty())
+            {
+                report
+                    .errors
+                    .push("webpage-scraping requires platform.url_filters".to_string());
+            }
+        }
+    }
+
+    // Release providers can pick their download among the assets instead
+    let has_url_filters = repo
+        .platform
+        .as_ref()
+        .is_some_and(|p| !p.url_filters.is_empty());
+    let filters_select_download = match source.provider_type {
+        ProviderType::WebpageScraping => true,
+        ProviderType::GithubReleases | ProviderType::GitlabReleases => has_url_filters,
+        _ => false,
+    };
+    if !filters_select_download && !has_download {
+        report
+            .errors
+            .push("[source.download] needs a url or urls".to_string());
+    }
+}
+
+fn check_security(repo: &RepoConfig, report: &mut LintReport) {
+    if repo.security.allow_insecure {
+        report
+            .warnings
+            .push("Package allows insecure installation".to_string());
+    }
+
+    match &repo.security.checksum {
+        Some(checksum) => {
+            if checksum.url.is_empty()
+                && checksum.filename_pattern.is_none()
+                && checksum.from_release_notes.is_none()
+            {
+                report.errors.push(
+                    "security.checksum needs url, filename_pattern or from_release_notes"
+                        .to_string(),
+                );
+            }
+            if checksum.signature_url.is_some() && checksum.public_key.is_none() {
+                report.errors.push(
+                    "security.checksum.signature_url needs public_key to verify the signature"
+                        .to_string(),
+                );
+            }
+            if let Some(fingerprint) = &checksum.fingerprint {
+                if !is_valid_fingerprint(fingerprint) {
+                    report.errors.push(format!(
+                        "security.checksum.fingerprint '{}' must be a full 40 or 64 hex digit \
+                         fingerprint",
+                        fingerprint
+                    ));
+                }
+            }
+        }
+        None if !repo.security.allow_insecure => report
+            .errors
+            .push("No checksum configured and allow_insecure is false".to_string()),
+        None => {}
+    }
+
+    if let Some(gpg) = &repo.security.gpg {
+        if gpg.public_key.is_none() && gpg.public_key_url.is_none() {
+            report
+                .errors
+                .push("security.gpg needs public_key or public_key_url".to_string());
+        }
+        match &gpg.fingerprint {
+            Some(fingerprint) if !is_valid_fingerprint(fingerprint) => report.errors.push(format!(
+                "security.gpg.fingerprint '{}' must be a full 40 or 64 hex digit fingerprint",
+                fingerprint
+            )),
+            None if gpg.public_key_url.is_some() => report.warnings.push(
+                "security.gpg.public_key_url is set without a fingerprint; \
+                 a replaced key would go unnoticed"
+                    .to_string(),
+            ),
+            _ => {}
+        }
+    }
+
+    let signed = repo.security.gpg.is_some()
+        || repo
+            .security
+            .checksum
+            .as_ref()
+            .is_some_and(|checksum| checksum.signature_url.is_some());
+    if signed && !cfg!(feature = "gpg") {
+        report.warnings.push(
+            "this build of Ora has no GPG support (the 'gpg' feature), \
+             so installs of this package will fail signature verification"
+                .to_string(),
+        );
+    }
+}
+
+fn check_regexes(repo: &RepoConfig, report: &mut LintReport) {
+    let mut patterns = Vec::new();
+    if let Some(version) = &repo.source.version {
+        patterns.extend([
+            ("source.version.regex", version.regex.clone()),
+            ("source.version.url_pattern", version.url_pattern.clone()),
+            (
+                "source.version.version_pattern",
+                version.version_pattern.clone(),
+            ),
+        ]);
+    }
+    if let Some(checksum) = &repo.security.checksum {
+        patterns.push((
+            "security.checksum.from_release_notes",
+            checksum
+                .from_release_notes
+                .as_ref()
+                .map(|p| p.replace("{filename}", "archive\\.tar\\.gz")),
+        ));
+    }
+
+    for (field, pattern) in patterns
+        .into_iter()
+        .filter_map(|(field, pattern)| Some((field, pattern?)))
+    {
+        if let Err(e) = build_safe_regex(&pattern) {
+            report.errors.push(format!("Invalid {}: {:#}", field, e));
+        }
+    }
+}
+
+fn check_templates(repo: &RepoConfig, report: &mut LintReport) {
+    let mut templates: Vec<(String, &str)> = Vec::new();
+    if let Some(download) = &repo.source.download {
+        if let Some(url) = &download.url {
+            templates.push(("source.download.url".to_string(), url));
+        }
+        for (key, url) in download.urls.iter().flatten() {
+            templates.push((format!("source.download.urls.{}", key), url));
+        }
+    }
+    if let Some(checksum) = &repo.security.checksum {
+        templates.push(("security.checksum.url".to_string(), &checksum.url));
+        if let Some(url) = &checksum.signature_url {
+            templates.push(("security.checksum.signature_url".to_string(), url));
+        }
+    }
+    if let Some(gpg) = &repo.security.gpg {
+        templates.push(("security.gpg.signature_url".to_string(), &gpg.signature_url));
+    }
+    for (i, extra) in repo.install.extra_downloads.iter().enumerate() {
+        templates.push((format!("install.extra_downloads[{}].url", i), &extra.url));
+    }
+
+    for (field, template) in templates {
+        for placeholder in placeholders(template) {
+            if !TEMPLATE_VARIABLES.contains(&placeholder) {
+                report.errors.push(format!(
+                    "Unknown template variable {{{}}} in {}",
+                    placeholder, field
+                ));
+            }
+        }
+    }
+
+    // Checksum file names may also refer to the archive they cover
+    if let Some(pattern) = repo
+        .security
+        .checksum
+        .as_ref()
+        .and_then(|checksum| checksum.filename_pattern.as_deref())
+    {
+        for placeholder in placeholders(pattern) {
+            if placeholder != "filename" && !TEMPLATE_VARIABLES.contains(&placeholder) {
+                report.errors.push(format!(
+                    "Unknown template variable {{{}}} in security.checksum.filename_pattern",
+                    placeholder
+                ));
+            }
+        }
+    }
+}
+
+/// Names of the `{placeholder}`s in a template
+fn placeholders(template: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let after = &rest[start + 1..];
+        match after.find('}') {
+            Some(end) => {
+                names.push(&after[..end]);
+                rest = &after[end + 1..];
+            }
+            None => break,
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(extra: &str) -> RepoConfig {
+        toml::from_str(&format!(
+            r#"
+name = "tool"
+description = "A tool"
+
+[install]
+binaries = ["tool"]
+
+{}
+"#,
+            extra
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_valid_repo_passes() {
+        let repo = parse(
+            r#"
+[source]
+type = "github-releases"
+repo = "owner/tool"
+
+[source.download]
+url = "https://github.com/owner/tool/releases/download/v{version}/tool-{os}-{arch}.tar.gz"
+
+[security.checksum]
+url = "https://github.com/owner/tool/releases/download/v{version}/SHA256SUMS"
+algorithm = "sha256"
+"#,
+        );
+        let report = lint_repo(&repo);
+        assert!(report.is_ok(), "{:?}", report.errors);
+    }
+
+    #[test]
+    fn test_reports_every_problem() {
+        let repo = parse(
+            r#"
+[source]
+type = "github-releases"
+
+[source.download]
+url = "https://example.com/{versoin}/tool.tar.gz"
+
+[source.version]
+discovery_url = "https://example.com/releases"
+discovery_type = "text"
+regex = "v(\\d+"
+
+[security]
+"#,
+        );
+        let errors = lint_repo(&repo).errors;
+
+        assert!(errors.iter().any(|e| e.contains("source.repo")));
+        assert!(errors.iter().any(|e| e.contains("{versoin}")));
+        assert!(errors.iter().any(|e| e.contains("source.version.regex")));
+        assert!(errors.iter().any(|e| e.contains("No checksum configured")));
+    }
+
+    #[test]
+    fn test_checks_extra_downloads() {
+        let repo = parse(
+            r#"
+[source]
+type = "direct-url"
+
+[source.download]
+url = "https://example.com/tool-{version}.tar.gz"
+
+[security]
+allow_insecure = false
+
+[[install.extra_downloads]]
+url = "https://example.com/{versoin}/config.toml"
+dst = "../config.toml"
+"#,
+        );
+        let errors = lint_repo(&repo).errors;
+
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("install.extra_downloads[0].url")));
+        assert!(errors.iter().any(|e| e.contains("'../config.toml'")));
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("No checksum configured for extra download")));
+    }
+
+    #[test]
+    fn test_url_filters_select_release_assets() {
+        let repo = parse(
+            r#"
+[source]
+type = "github-releases"
+repo = "owner/tool"
+
+[platform.url_filters]
+linux_x86_64 = "linux_amd64"
+
+[security.checksum]
+url = "https://github.com/owner/tool/releases/download/v{version}/SHA256SUMS"
+algorithm = "sha256"
+"#,
+        );
+        let report = lint_repo(&repo);
+        assert!(report.is_ok(), "{:?}", report.errors);
+        assert_eq!(repo.get_url_filter("linux", "x86_64"), Some("linux_amd64"));
+        assert_eq!(repo.get_url_filter("darwin", "x86_64"), None);
+    }
+
+    #[test]
+    fn test_checks_gpg_fingerprint() {
+        let gpg = |fingerprint: &str| {
+            parse(&format!(
+                r#"
+[source]
+type = "direct-url"
+
+[source.download]
+url = "https://example.com/tool-{{version}}.tar.gz"
+
+[security]
+allow_insecure = true
+
+[security.gpg]
+signature_url = "https://example.com/tool-{{version}}.tar.gz.asc"
+public_key_url = "https://example.com/key.asc"
+{}
+"#,
+                fingerprint
+            ))
+        };
+
+        let pinned = lint_repo(&gpg(
+            "fingerprint = \"1234 5678 9ABC DEF0 1234 5678 9ABC DEF0 1234 5678\"",
+        ));
+        assert!(pinned.is_ok(), "{:?}", pinned.errors);
+        assert!(!pinned.warnings.iter().any(|w| w.contains("fingerprint")));
+
+        let short = lint_repo(&gpg("fingerprint = \"ABCD1234\""));
+        assert!(short.errors.iter().any(|e| e.contains("'ABCD1234'")));
+
+        let unpinned = lint_repo(&gpg(""));
+        assert!(unpinned.is_ok());
+        assert!(unpinned
+            .warnings
+            .iter()
+            .any(|w| w.contains("without a fingerprint")));
+        assert_eq!(
+            unpinned
+                .warnings
+                .iter()
+                .any(|w| w.contains("'gpg' feature")),
+            !cfg!(feature = "gpg")
+        );
+    }
+
+    #[test]
+    fn test_checks_checksum_key_fingerprint() {
+        let repo = parse(
+            r#"
+[source]
+type = "direct-url"
+
+[source.download]
+url = "https://example.com/tool-{version}.tar.gz"
+
+[security.checksum]
+url = "https://example.com/SHA256SUMS"
+algorithm = "sha256"
+signature_url = "https://example.com/SHA256SUMS.asc"
+public_key = "-----BEGIN PGP PUBLIC KEY BLOCK-----"
+fingerprint = "ABCD1234"
+"#,
+        );
+        let report = lint_repo(&repo);
+        assert!(report
+            .errors
+            .iter()
+            .any(|e| e.contains("security.checksum.fingerprint 'ABCD1234'")));
+    }
+
+    #[test]
+    fn test_signatures_need_a_key() {
+        let repo = parse(
+            r#"
+[source]
+type = "direct-url"
+
+[source.download]
+url = "https://example.com/tool-{version}.tar.gz"
+
+[security.checksum]
+url = "https://example.com/SHA256SUMS"
+algorithm = "sha256"
+signature_url = "https://example.com/SHA256SUMS.asc"
+
+[security.gpg]
+signature_url = "https://example.com/tool-{version}.tar.gz.asc"
+"#,
+        );
+        let errors = lint_repo(&repo).errors;
+
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("security.checksum.signature_url needs public_key")));
+        assert!(errors
+            .iter()
+            .any(|e| e.contains("security.gpg needs public_key or public_key_url")));
+    }
+
+    #[test]
+    fn test_checks_install_overrides() {
+        let report = lint_repo(&parse(
+            r#"
+[[install.overrides]]
+version_req = "<2.0.0"
+binaries = ["tool-*/tool"]
+
+[[install.overrides]]
+version_req = "not a requirement"
+binaries = ["bin/tool"]
+
+[[install.overrides]]
+version_req = ">=5"
+
+[source]
+type = "direct-url"
+
+[source.download]
+url = "https://example.com/tool-{version}.tar.gz"
+
+[security]
+allow_insecure = true
+"#,
+        ));
+        assert_eq!(report.errors.len(), 1, "{:?}", report.errors);
+        assert!(report.errors[0].contains("'not a requirement'"));
+        assert!(report
+            .warnings
+            .iter()
+            .any(|w| w.contains("'>=5' changes nothing")));
+    }
+}
@@ -1,5 +1,33 @@
 use thiserror::Error;
 
+/// Process exit codes reported by the `ora` binary.
+///
+/// Scripts wrapping Ora can use these to react to specific failure categories.
+/// Code 2 is reserved for command-line usage errors (reported by clap).
+pub mod exit_code {
+    /// Unclassified failure
+    pub const GENERAL: i32 = 1;
+    /// Invalid command-line usage (emitted by clap)
+    #[allow(dead_code)]
+    pub const USAGE: i32 = 2;
+    /// Network failure (connection, DNS, HTTP error)
+    pub const NETWORK: i32 = 3;
+    /// Package, version or registry not found
+    pub const NOT_FOUND: i32 = 4;
+    /// Checksum, signature or certificate verification failed
+    pub const VERIFICATION_FAILED: i32 = 5;
+    /// Permission denied on a file or directory
+    pub const PERMISSION_DENIED: i32 = 6;
+    /// Operation refused by security policy
+    pub const SECURITY_POLICY: i32 = 7;
+    /// Invalid configuration or .repo file
+    pub const CONFIG: i32 = 8;
+    /// Current platform is not supported by the package
+    pub const PLATFORM_UNSUPPORTED: i32 = 9;
+    /// Terminated by SIGINT/SIGTERM
+    pub const INTERRUPTED: i32 = 130;
+}
+
 // Allow dead_code for error variants planned for future use
 #[allow(dead_code)]
 #[derive(Error, Debug)]
@@ -7,6 +35,9 @@ pub enum OraError {
     #[error("Package not found: {0}")]
     PackageNotFound(String),
 
+    #[error("Package is not installed: {0}")]
+    PackageNotInstalled(String),
+
     #[error("Version not found: {package}@{version}")]
     VersionNotFound { package: String, version: String },
 
@@ -34,9 +65,105 @@ pub enum OraError {
     #[error("IO error: {0}")]
     IoError(#[from] std::io::Error),
 
+    #[error("Permission denied: {0}")]
+    PermissionDenied(String),
+
     #[error("Configuration error: {0}")]
     ConfigError(String),
 
     #[error("Package is insecure (no checksum verification available)")]
     InsecurePackage,
+
+    #[error("Security policy violation: {0}")]
+    SecurityPolicyViolation(String),
+
+    #[error("Interrupted")]
+    Interrupted,
+}
+
+impl OraError {
+    /// Exit code associated with this error category
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            OraError::PackageNotFound(_)
+            | OraError::PackageNotInstalled(_)
+            | OraError::VersionNotFound { .. }
+            | OraError::RegistryNotFound(_) => exit_code::NOT_FOUND,
+            OraError::PlatformNotSupported { .. } => exit_code::PLATFORM_UNSUPPORTED,
+            OraError::ChecksumMismatch
+            | OraError::SignatureInvalid
+            | OraError::CertificatePinMismatch => exit_code::VERIFICATION_FAILED,
+            OraError::InvalidRepoFormat(_) | OraError::ConfigError(_) => exit_code::CONFIG,
+            OraError::NetworkError(_) => exit_code::NETWORK,
+            OraError::IoError(e) if e.kind() == std::io::ErrorKind::PermissionDenied => {
+                exit_code::PERMISSION_DENIED
+            }
+            OraError::IoError(_) => exit_code::GENERAL,
+            OraError::PermissionDenied(_) => exit_code::PERMISSION_DENIED,
+            OraError::InsecurePackage | OraError::SecurityPolicyViolation(_) => {
+                exit_code::SECURITY_POLICY
+            }
+            OraError::Interrupted => exit_code::INTERRUPTED,
+        }
+    }
+}
+
+/// Determine the process exit code for an error returned by a command
+///
+/// Walks the error chain (outermost first) and returns the code of the first
+/// recognized cause. Unknown errors map to `exit_code::GENERAL`.
+pub fn exit_code_for(err: &anyhow::Error) -> i32 {
+    for cause in err.chain() {
+        if let Some(ora_err) = cause.downcast_ref::<OraError>() {
+            return ora_err.exit_code();
+        }
+        if let Some(io_err) = cause.downcast_ref::<std::io::Error>() {
+            if io_err.kind() == std::io::ErrorKind::PermissionDenied {
+                return exit_code::PERMISSION_DENIED;
+            }
+        }
+        if cause.downcast_ref::<reqwest::Error>().is_some() {
+            return exit_code::NETWORK;
+        }
+        if cause.downcast_ref::<toml::de::Error>().is_some() {
+            return exit_code::CONFIG;
+        }
+    }
+
+    exit_code::GENERAL
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn test_exit_code_for_ora_error() {
+        let err = anyhow::Error::from(OraError::ChecksumMismatch);
+        assert_eq!(exit_code_for(&err), exit_code::VERIFICATION_FAILED);
+
+        let err = anyhow::Error::from(OraError::InsecurePackage);
+        assert_eq!(exit_code_for(&err), exit_code::SECURITY_POLICY);
+    }
+
+    #[test]
+    fn test_exit_code_for_wrapped_error() {
+        let result: Result<(), OraError> = Err(OraError::PackageNotFound("jq".to_string()));
+        let err = result.context("Failed to install jq").unwrap_err();
+        assert_eq!(exit_code_for(&err), exit_code::NOT_FOUND);
+    }
+
+    #[test]
+    fn test_exit_code_for_permission_denied_io_error() {
+        let io_err = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+        let err = anyhow::Error::from(io_err).context("Failed to write file");
+        assert_eq!(exit_code_for(&err), exit_code::PERMISSION_DENIED);
+    }
+
+    #[test]
+    fn test_exit_code_for_unknown_error() {
+        let err = anyhow::anyhow!("something went wrong");
+        assert_eq!(exit_code_for(&err), exit_code::GENERAL);
+    }
 }
@@ -2,31 +2,68 @@ use anyhow::{Context, Result};
 use std::path::{Path, PathBuf};
 use walkdir::WalkDir;
 
-use crate::config::global::InstallMode;
-use crate::config::repo::InstallConfig;
+use crate::config::global::{InstallMode, InstallSettings};
+use crate::config::repo::{BinarySpec, InstallConfig};
+use crate::config::security_config::ExtractionSecurityConfig;
 use crate::config::security_limits::MAX_PATH_LENGTH;
+use crate::error::OraError;
 use crate::storage::paths::Paths;
+use crate::utils::shutdown::{check_shutdown, CleanupGuard};
+
+/// Name of the per-package symlink pointing at the active version directory
+pub const CURRENT_LINK_NAME: &str = "current";
 
 pub struct Deployer;
 
 impl Deployer {
+    /// Copies the extracted package into its versioned install directory
+    ///
+    /// With `link_binaries` unset (e.g. binaries built for another platform),
+    /// binaries are still validated but no symlinks are created in the bin directory.
+    /// Without `install.binaries`, the archive's only executable is linked.
+    #[allow(clippy::too_many_arguments)]
     pub fn deploy(
         extract_dir: &Path,
         install_config: &InstallConfig,
         mode: InstallMode,
+        settings: &InstallSettings,
+        extraction: &ExtractionSecurityConfig,
         package_name: &str,
         version: &str,
+        link_binaries: bool,
     ) -> Result<DeploymentResult> {
         log::debug!("Deploying package to install directory");
 
-        let install_dir = Paths::packages_dir(mode.clone())?
-            .join(package_name)
-            .join(version);
-        let bin_dir = Paths::bin_dir(mode.clone())?;
-
-        // Ensure directories exist
+        let inferred;
+        let install_config = if install_config.binaries.is_empty() {
+            inferred = InstallConfig {
+                binaries: vec![BinarySpec::Path(Self::infer_binary(extract_dir)?)],
+                ..install_config.clone()
+            };
+            &inferred
+        } else {
+            install_config
+        };
+
+        // Fail before the copy if a binaries pattern is wrong
+        Self::validate_binaries(extract_dir, install_config)?;
+
+        let packages_dir = Paths::packages_dir(mode.clone(), settings)?;
+        let package_dir = packages_dir.join(package_name);
+        let install_dir = package_dir.join(version);
+        let bin_dir = Paths::bin_dir(mode.clone(), settings)?;
+
+        // Ensure directories exist and are writable before copying anything
+        Paths::ensure_writable_dir(&packages_dir, "Packages directory")?;
+        Paths::ensure_writable_dir(&bin_dir, "Bin directory")?;
+
+        // Undone on error and on Ctrl+C, so no half-deployed version is left
+        // behind; only what this deploy creates is removed
+        let cleanup = CleanupGuard::new();
+        if !install_dir.exists() {
+            cleanup.remove_on_abort(&install_dir);
+        }
         std::fs::create_dir_all(&install_dir)?;
-        std::fs::create_dir_all(&bin_dir)?;
 
         // Canonicalize paths for security validation
         let canonical_install_dir = install_dir
@@ -39,32 +76,62 @@ impl Deployer {
         // Copy all files from extract_dir to install_dir
         Self::copy_directory(extract_dir, &install_dir)?;
 
+        // Point `current` at this version so binary links survive version changes.
+        // Foreign-platform installs are staged only and never become current.
+        let current_link = package_dir.join(CURRENT_LINK_NAME);
+        let previous_current = std::fs::read_link(&current_link).ok();
+        let current_path = if link_binaries {
+            cleanup.restore_link_on_abort(&current_link, previous_current);
+            Self::point_current(&package_dir, version)?;
+            Some(current_link.clone())
+        } else {
+            None
+        };
+
         // Create symlinks for binaries
         let mut symlinks = Vec::new();
         let mut files = Vec::new();
 
-        // Get all binaries
-        if install_config.binaries.is_empty() {
-            anyhow::bail!("No binaries specified in install config");
-        }
-
         // Create symlinks for each binary
-        for binary_pattern in &install_config.binaries {
-            let binary_src = Self::resolve_path(&install_dir, binary_pattern)?;
+        let mut link_names = std::collections::HashSet::new();
+        for binary in &install_config.binaries {
+            let binary_src = Self::resolve_path(&install_dir, binary.src())?;
 
             // Validate binary is within install directory
             Self::validate_path_within_base(&binary_src, &canonical_install_dir, "Binary")?;
 
-            let binary_name = binary_src
-                .file_name()
-                .context("Invalid binary path")?
-                .to_string_lossy()
-                .to_string();
+            if !link_binaries {
+                log::debug!("Not linking binary: {}", binary_src.display());
+                continue;
+            }
+
+            let binary_name = match binary.link_name() {
+                Some(name) => {
+                    validate_link_name(name)?;
+                    name.to_string()
+                }
+                None => binary_src
+                    .file_name()
+                    .context("Invalid binary path")?
+                    .to_string_lossy()
+                    .to_string(),
+            };
+            if !link_names.insert(binary_name.clone()) {
+                anyhow::bail!(
+                    "Two install.binaries entries are linked as '{}'; give one a different name",
+                    binary_name
+                );
+            }
             let binary_link = bin_dir.join(&binary_name);
+            let binary_target = match binary_src.strip_prefix(&install_dir) {
+                Ok(relative) => current_link.join(relative),
+                Err(_) => binary_src.clone(),
+            };
 
-            // Create and validate symlink
+            // Create and validate symlink; a link it replaces is put back on abort
+            cleanup.restore_link_on_abort(&binary_link, std::fs::read_link(&binary_link).ok());
             Self::create_symlink(
-                &binary_src,
+                &binary_target,
                 &binary_link,
                 &canonical_install_dir,
                 &canonical_bin_dir,
@@ -110,6 +177,36 @@ impl Deployer {
             std::fs::copy(&src, &dst)?;
         }
 
+        // Defense in depth: extraction strips SUID/SGID bits, but that can be
+        // disabled and additional files are copied with their permissions
+        let mut warnings = Vec::new();
+        let setuid_files = Self::find_setuid_files(&install_dir)?;
+        if !setuid_files.is_empty() {
+            let listing = setuid_files
+                .iter()
+                .map(|p| format!("  {}", p.display()))
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            if extraction.reject_setuid_binaries {
+                return Err(OraError::SecurityPolicyViolation(format!(
+                    "package deploys files with SUID/SGID bits:\n{}\n\
+                     Set extraction.reject_setuid_binaries = false in security.toml to only warn",
+                    listing
+                ))
+                .into());
+            }
+
+            log::warn!("Deployed files carry SUID/SGID bits:\n{}", listing);
+            warnings.push(format!(
+                "The following installed files are SUID/SGID and run with elevated privileges:\n\
+                 {}\n\
+                 Remove the bits (chmod u-s,g-s) unless you trust this package, or set \
+                 extraction.reject_setuid_binaries = true in security.toml to refuse such installs",
+                listing
+            ));
+        }
+
         // Collect all installed files
         for entry in WalkDir::new(&install_dir) {
             let entry = entry?;
@@ -118,13 +215,102 @@ impl Deployer {
             }
         }
 
+        cleanup.commit();
         Ok(DeploymentResult {
             install_dir: install_dir.to_string_lossy().to_string(),
             symlinks,
             files,
+            current_path: current_path.map(|p| p.to_string_lossy().to_string()),
+            warnings,
         })
     }
 
+    /// Atomically repoints `<package_dir>/current` at `target` (relative to `package_dir`)
+    ///
+    /// The new link is created under a temporary name and renamed over the old
+    /// one, so readers never observe a missing `current`.
+    fn point_current(package_dir: &Path, target: impl AsRef<Path>) -> Result<()> {
+        let current_link = package_dir.join(CURRENT_LINK_NAME);
+        let tmp_link = package_dir.join(format!(".{}.tmp", CURRENT_LINK_NAME));
+
+        if tmp_link.is_symlink() {
+            std::fs::remove_file(&tmp_link)?;
+        }
+
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(target.as_ref(), &tmp_link)
+            .context(format!("Failed to create symlink: {}", tmp_link.display()))?;
+
+        #[cfg(windows)]
+        std::os::windows::fs::symlink_dir(target.as_ref(), &tmp_link)
+            .context(format!("Failed to create symlink: {}", tmp_link.display()))?;
+
+        std::fs::rename(&tmp_link, &current_link).context(format!(
+            "Failed to update symlink: {}",
+            current_link.display()
+        ))?;
+
+        log::debug!(
+            "Pointed {} at {}",
+            current_link.display(),
+            target.as_ref().display()
+        );
+        Ok(())
+    }
+
+    /// Lists regular files under `dir` that have the SUID or SGID bit set
+    #[cfg(unix)]
+    pub fn find_setuid_files(dir: &Path) -> Result<Vec<PathBuf>> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mut found = Vec::new();
+        for entry in WalkDir::new(dir) {
+            let entry = entry?;
+            if entry.file_type().is_file() && entry.metadata()?.permissions().mode() & 0o6000 != 0 {
+                found.push(entry.into_path());
+            }
+        }
+        Ok(found)
+    }
+
+    #[cfg(not(unix))]
+    pub fn find_setuid_files(_dir: &Path) -> Result<Vec<PathBuf>> {
+        Ok(Vec::new())
+    }
+
+    /// Places a verified extra download at `dst` in the extracted tree, so it is
+    /// deployed next to the binary with the rest of the package
+    pub fn stage_extra_download(extract_dir: &Path, file: &Path, dst: &str) -> Result<()> {
+        validate_extra_download_dst(dst)?;
+
+        let target = extract_dir.join(dst);
+        let parent = target.parent().context("Invalid destination path")?;
+        std::fs::create_dir_all(parent)?;
+
+        // The archive may hold symlinked directories; don't follow them out
+        let canonical_extract_dir = extract_dir
+            .canonicalize()
+            .context("Failed to canonicalize extract directory")?;
+        Self::validate_path_within_base(
+            parent,
+            &canonical_extract_dir,
+            "Extra download destination",
+        )?;
+
+        if let Ok(metadata) = target.symlink_metadata() {
+            if metadata.is_dir() {
+                anyhow::bail!(
+                    "Extra download destination '{}' is a directory in the archive",
+                    dst
+                );
+            }
+            std::fs::remove_file(&target)?;
+        }
+        std::fs::copy(file, &target)
+            .with_context(|| format!("Failed to place extra download at {}", dst))?;
+        Ok(())
+    }
+
     /// Validates that a path is within the base directory
     fn validate_path_within_base(path: &Path, base: &Path, description: &str) -> Result<()> {
         let canonical_path = path.canonicalize().context(format!(
@@ -145,6 +331,125 @@ impl Deployer {
         Ok(())
     }
 
+    /// Checks that every `binaries` pattern matches a file in the extracted tree
+    ///
+    /// On a miss the error lists the archive's top-level entries, which is
+    /// usually enough to spot a missing versioned directory in the pattern.
+    fn validate_binaries(extract_dir: &Path, install_config: &InstallConfig) -> Result<()> {
+        if install_config.binaries.is_empty() {
+            anyhow::bail!("No binaries specified in install config");
+        }
+
+        for binary in &install_config.binaries {
+            let pattern = binary.src();
+            let resolved = Self::resolve_path(extract_dir, pattern)
+                .ok()
+                .filter(|path| path.is_file());
+            let Some(binary) = resolved else {
+                anyhow::bail!(
+                    "Binary '{}' from install.binaries was not found in the archive.\n\
+                     Top-level entries of the extracted archive:\n{}\n\
+                     Patterns are relative to the archive root (e.g. '*/bin/tool').",
+                    pattern,
+                    Self::describe_top_level(extract_dir)
+                );
+            };
+
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let mode = std::fs::metadata(&binary)?.permissions().mode();
+                if mode & 0o111 == 0 {
+                    log::warn!(
+                        "Binary '{}' is not marked executable in the archive",
+                        binary.display()
+                    );
+                }
+            }
+            #[cfg(not(unix))]
+            let _ = binary;
+        }
+
+        Ok(())
+    }
+
+    /// Picks the binary of an archive shipped without `install.binaries`
+    ///
+    /// Accepts the only executable file of the tree, or its only file (which
+    /// is then made executable). Returns its path relative to `extract_dir`.
+    fn infer_binary(extract_dir: &Path) -> Result<String> {
+        let files: Vec<PathBuf> = WalkDir::new(extract_dir)
+            .follow_links(false)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .map(|entry| entry.into_path())
+            .collect();
+
+        let executables: Vec<&PathBuf> = files.iter().filter(|f| is_executable(f)).collect();
+        let binary = match (executables.as_slice(), files.as_slice()) {
+            ([only], _) => (*only).clone(),
+            (_, [only]) => {
+                make_executable(only)?;
+                only.clone()
+            }
+            _ => anyhow::bail!(
+                "No binaries specified in install config, and the archive doesn't contain \
+                 a single executable to use instead ({} files, {} executable).\n\
+                 Top-level entries of the extracted archive:\n{}",
+                files.len(),
+                executables.len(),
+                Self::describe_top_level(extract_dir)
+            ),
+        };
+
+        let relative = binary
+            .strip_prefix(extract_dir)
+            .context("Inferred binary is outside the extraction directory")?;
+        let relative = relative
+            .components()
+            .map(|c| c.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        log::info!(
+            "No install.binaries configured; linking the archive's only binary '{}'",
+            relative
+        );
+        Ok(relative)
+    }
+
+    /// Lists the entries directly under `dir`, directories with a trailing `/`
+    fn describe_top_level(dir: &Path) -> String {
+        const MAX_ENTRIES: usize = 20;
+
+        let mut names: Vec<String> = match std::fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(|e| e.ok())
+                .map(|e| {
+                    let name = e.file_name().to_string_lossy().into_owned();
+                    if e.path().is_dir() {
+                        format!("{}/", name)
+                    } else {
+                        name
+                    }
+                })
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        if names.is_empty() {
+            return "  (empty)".to_string();
+        }
+        names.sort();
+
+        let extra = names.len().saturating_sub(MAX_ENTRIES);
+        names.truncate(MAX_ENTRIES);
+        let mut listing: Vec<String> = names.iter().map(|n| format!("  {}", n)).collect();
+        if extra > 0 {
+            listing.push(format!("  ... and {} more", extra));
+        }
+        listing.join("\n")
+    }
+
     fn resolve_path(base: &Path, relative: &str) -> Result<PathBuf> {
         // Validate path length
         if relative.len() > MAX_PATH_LENGTH {
@@ -294,6 +599,7 @@ impl Deployer {
             .context("Failed to canonicalize destination directory")?;
 
         for entry in WalkDir::new(src) {
+            check_shutdown()?;
             let entry = entry?;
             let path = entry.path();
             let relative = path.strip_prefix(src)?;
@@ -374,4 +680,286 @@ pub struct DeploymentResult {
     pub install_dir: String,
     pub symlinks: Vec<String>,
     pub files: Vec<String>,
+    /// `packages/<name>/current` link, unless the install was staged for another platform
+    pub current_path: Option<String>,
+    /// Issues the caller should show once the install completes
+    pub warnings: Vec<String>,
+}
+
+/// Whether `path` has an execute bit (off Unix: an `.exe` extension)
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::metadata(path).is_ok_and(|m| m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("exe"))
+}
+
+/// Adds the execute bits matching the file's read bits
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut permissions = std::fs::metadata(path)?.permissions();
+    let mode = permissions.mode();
+    permissions.set_mode(mode | ((mode & 0o444) >> 2));
+    std::fs::set_permissions(path, permissions)
+        .context(format!("Failed to make {} executable", path.display()))
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
+
+/// Checks that a renamed binary's link name is a plain file name, so the link
+/// stays directly inside the bin directory
+pub fn validate_link_name(name: &str) -> Result<()> {
+    let is_plain =
+        !name.is_empty() && name != "." && name != ".." && !name.contains(['/', '\\', '\0']);
+    if !is_plain {
+        anyhow::bail!(
+            "Invalid binary name '{}': must be a file name without path separators",
+            name
+        );
+    }
+    Ok(())
+}
+
+/// Checks that an extra download's destination is a relative path that stays
+/// inside the package directory
+pub fn validate_extra_download_dst(dst: &str) -> Result<()> {
+    let path = Path::new(dst);
+    let contained = !dst.is_empty()
+        && !dst.contains('\0')
+        && path
+            .components()
+            .all(|c| matches!(c, std::path::Component::Normal(_)));
+    if !contained {
+        anyhow::bail!(
+            "Invalid extra download destination '{}': must be a relative path inside the package directory",
+            dst
+        );
+    }
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+
+    #[test]
+    fn test_find_setuid_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let plain = dir.path().join("plain");
+        let setuid = dir.path().join("nested").join("setuid");
+        std::fs::create_dir_all(setuid.parent().unwrap()).unwrap();
+        std::fs::write(&plain, b"").unwrap();
+        std::fs::write(&setuid, b"").unwrap();
+        std::fs::set_permissions(&setuid, std::fs::Permissions::from_mode(0o4755)).unwrap();
+
+        assert_eq!(
+            Deployer::find_setuid_files(dir.path()).unwrap(),
+            vec![setuid]
+        );
+    }
+
+    #[test]
+    fn test_point_current_replaces_existing_link() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("1.0.0")).unwrap();
+        std::fs::create_dir(dir.path().join("2.0.0")).unwrap();
+
+        Deployer::point_current(dir.path(), "1.0.0").unwrap();
+        Deployer::point_current(dir.path(), "2.0.0").unwrap();
+
+        let current = dir.path().join(CURRENT_LINK_NAME);
+        assert_eq!(std::fs::read_link(&current).unwrap(), Path::new("2.0.0"));
+        assert!(!dir.path().join(".current.tmp").exists());
+    }
+
+    fn install_config(binaries: &[&str]) -> InstallConfig {
+        InstallConfig {
+            mode: None,
+            binaries: binaries.iter().map(|b| BinarySpec::from(*b)).collect(),
+            extract_only: vec![],
+            files: vec![],
+            strip_single_dir: false,
+            extra_downloads: vec![],
+            post_install: None,
+            env: Default::default(),
+            path_additions: vec![],
+            env_exports: Default::default(),
+            overrides: vec![],
+        }
+    }
+
+    #[test]
+    fn test_validate_link_name() {
+        assert!(validate_link_name("myapp").is_ok());
+        assert!(validate_link_name("my-app.sh").is_ok());
+        for name in ["", ".", "..", "../myapp", "sub/myapp", "/usr/bin/myapp"] {
+            assert!(validate_link_name(name).is_err(), "{:?} accepted", name);
+        }
+    }
+
+    #[test]
+    fn test_validate_binaries_accepts_renamed_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("bin")).unwrap();
+        std::fs::write(dir.path().join("bin/myapp-cli"), b"").unwrap();
+
+        let mut config = install_config(&["bin/myapp-cli"]);
+        config.binaries.push(BinarySpec::Renamed {
+            src: "bin/myapp-cli".to_string(),
+            name: "myapp".to_string(),
+        });
+        assert!(Deployer::validate_binaries(dir.path(), &config).is_ok());
+
+        config.binaries = vec![BinarySpec::Renamed {
+            src: "bin/missing".to_string(),
+            name: "myapp".to_string(),
+        }];
+        assert!(Deployer::validate_binaries(dir.path(), &config).is_err());
+    }
+
+    #[test]
+    fn test_validate_binaries_lists_top_level_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let binary = dir.path().join("tool-1.0-linux").join("bin").join("tool");
+        std::fs::create_dir_all(binary.parent().unwrap()).unwrap();
+        std::fs::write(&binary, b"").unwrap();
+        std::fs::write(dir.path().join("README.md"), b"").unwrap();
+
+        assert!(Deployer::validate_binaries(dir.path(), &install_config(&["*/bin/tool"])).is_ok());
+
+        let err = Deployer::validate_binaries(dir.path(), &install_config(&["tool"]))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Binary 'tool'"));
+        assert!(err.contains("  README.md\n  tool-1.0-linux/"));
+
+        // A directory is not a binary
+        assert!(
+            Deployer::validate_binaries(dir.path(), &install_config(&["tool-1.0-linux"])).is_err()
+        );
+    }
+
+    #[test]
+    fn test_infer_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("tool-1.0")).unwrap();
+        let tool = dir.path().join("tool-1.0/tool");
+        std::fs::write(&tool, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&tool, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+        // A lone file is used and made executable
+        assert_eq!(Deployer::infer_binary(dir.path()).unwrap(), "tool-1.0/tool");
+        let mode = std::fs::metadata(&tool).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o755);
+
+        // Next to other files, the only executable wins
+        std::fs::write(dir.path().join("tool-1.0/README.md"), "docs").unwrap();
+        assert_eq!(Deployer::infer_binary(dir.path()).unwrap(), "tool-1.0/tool");
+
+        // Two executables are ambiguous
+        let other = dir.path().join("tool-1.0/helper");
+        std::fs::write(&other, "").unwrap();
+        std::fs::set_permissions(&other, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let err = Deployer::infer_binary(dir.path()).unwrap_err().to_string();
+        assert_eq!(
+            err,
+            "No binaries specified in install config, and the archive doesn't contain \
+             a single executable to use instead (3 files, 2 executable).\n\
+             Top-level entries of the extracted archive:\n  tool-1.0/"
+        );
+    }
+
+    #[test]
+    fn test_stage_extra_download() {
+        let dir = tempfile::tempdir().unwrap();
+        let extract_dir = dir.path().join("extract");
+        std::fs::create_dir(&extract_dir).unwrap();
+        let downloaded = dir.path().join("config.toml");
+        std::fs::write(&downloaded, "key = 1").unwrap();
+
+        Deployer::stage_extra_download(&extract_dir, &downloaded, "etc/tool/config.toml").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(extract_dir.join("etc/tool/config.toml")).unwrap(),
+            "key = 1"
+        );
+
+        for dst in ["", "/etc/passwd", "../outside", "etc/../../outside"] {
+            assert!(
+                Deployer::stage_extra_download(&extract_dir, &downloaded, dst).is_err(),
+                "{}",
+                dst
+            );
+        }
+
+        // A symlinked directory in the archive can't lead outside of it
+        std::os::unix::fs::symlink(dir.path(), extract_dir.join("escape")).unwrap();
+        assert!(
+            Deployer::stage_extra_download(&extract_dir, &downloaded, "escape/config.toml")
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_failed_deploy_keeps_existing_version_dir_and_links() {
+        // ORA_BIN_DIR would take precedence over install.bin_dir
+        if std::env::var_os("ORA_BIN_DIR").is_some() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let settings = InstallSettings {
+            packages_dir: Some(dir.path().join("packages").display().to_string()),
+            bin_dir: Some(dir.path().join("bin").display().to_string()),
+            ..Default::default()
+        };
+
+        // Left behind by an earlier install, and a link owned by another package
+        let install_dir = dir.path().join("packages/tool/1.0.0");
+        std::fs::create_dir_all(&install_dir).unwrap();
+        std::fs::write(install_dir.join("keep"), b"").unwrap();
+        std::fs::create_dir_all(dir.path().join("bin")).unwrap();
+        let link = dir.path().join("bin/tool");
+        let other = dir.path().join("other-tool");
+        std::fs::write(&other, b"").unwrap();
+        std::os::unix::fs::symlink(&other, &link).unwrap();
+
+        let extract_dir = dir.path().join("extract");
+        std::fs::create_dir_all(&extract_dir).unwrap();
+        std::fs::write(extract_dir.join("tool"), b"").unwrap();
+        std::fs::set_permissions(
+            extract_dir.join("tool"),
+            std::fs::Permissions::from_mode(0o755),
+        )
+        .unwrap();
+        // Fails after the binary is linked
+        let mut config = install_config(&["tool"]);
+        config.files.push(crate::config::repo::AdditionalFile {
+            src: "tool".to_string(),
+            dst: "../escaped".to_string(),
+        });
+
+        let result = Deployer::deploy(
+            &extract_dir,
+            &config,
+            InstallMode::Userland,
+            &settings,
+            &Default::default(),
+            "tool",
+            "1.0.0",
+            true,
+        );
+        assert!(result.is_err());
+        assert!(install_dir.join("keep").exists());
+        assert_eq!(std::fs::read_link(&link).unwrap(), other);
+        assert!(!dir.path().join("packages/tool/current").is_symlink());
+    }
 }
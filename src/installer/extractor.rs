@@ -1,15 +1,215 @@
 use anyhow::{Context, Result};
 use flate2::read::GzDecoder;
+use sha2::{Digest, Sha256};
 use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
 use tar::Archive;
 use xz2::read::XzDecoder;
 
+use crate::config::security_config::ExtractionSecurityConfig;
 use crate::config::security_limits::*;
+use crate::config::SecurityConfig;
+use crate::utils::shutdown::check_shutdown;
 
 pub struct Extractor;
 
+/// Archive members to extract, from `install.extract_only`; extracts everything when empty
+struct MemberFilter(Vec<glob::Pattern>);
+
+impl MemberFilter {
+    fn new(patterns: &[String]) -> Result<Self> {
+        patterns
+            .iter()
+            .map(|p| glob::Pattern::new(p).context(format!("Invalid extract_only pattern '{}'", p)))
+            .collect::<Result<Vec<_>>>()
+            .map(Self)
+    }
+
+    fn is_filtering(&self) -> bool {
+        !self.0.is_empty()
+    }
+
+    /// Whether an entry is extracted; `*` doesn't cross `/`, use `**` for that
+    fn includes(&self, entry_path: &Path) -> bool {
+        if self.0.is_empty() {
+            return true;
+        }
+
+        // Tar entries are often stored as "./dir/file"
+        let normalized: PathBuf = entry_path
+            .components()
+            .filter(|c| !matches!(c, std::path::Component::CurDir))
+            .collect();
+        let options = glob::MatchOptions {
+            require_literal_separator: true,
+            ..Default::default()
+        };
+        self.0
+            .iter()
+            .any(|pattern| pattern.matches_path_with(&normalized, options))
+    }
+}
+
+/// Archive formats the extractor understands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArchiveKind {
+    TarGz,
+    TarXz,
+    TarZst,
+    Zip,
+    Tar,
+    /// A single gzip-compressed file (e.g. `tool-linux-amd64.gz`)
+    Gz,
+    /// A single xz-compressed file
+    Xz,
+    /// A single zstd-compressed file
+    Zst,
+}
+
+impl ArchiveKind {
+    const GZIP_MAGIC: &'static [u8] = &[0x1f, 0x8b];
+    const XZ_MAGIC: &'static [u8] = &[0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00];
+    const ZSTD_MAGIC: &'static [u8] = &[0x28, 0xb5, 0x2f, 0xfd];
+    const ZIP_MAGIC: &'static [u8] = b"PK\x03\x04";
+    /// POSIX and GNU tar headers carry "ustar" at offset 257
+    const TAR_MAGIC_OFFSET: usize = 257;
+
+    fn from_extension(path: &Path) -> Option<Self> {
+        let path_str = path.to_string_lossy();
+        if path_str.ends_with(".tar.gz") || path_str.ends_with(".tgz") {
+            Some(Self::TarGz)
+        } else if path_str.ends_with(".tar.xz") || path_str.ends_with(".txz") {
+            Some(Self::TarXz)
+        } else if path_str.ends_with(".tar.zst") || path_str.ends_with(".tzst") {
+            Some(Self::TarZst)
+        } else if path_str.ends_with(".zip") {
+            Some(Self::Zip)
+        } else if path_str.ends_with(".tar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+
+    /// Identifies an archive from its leading bytes
+    ///
+    /// Compressed streams are peeked at after decompression to tell a
+    /// tarball from a single compressed file.
+    fn detect(path: &Path) -> Result<Self> {
+        let header = read_header(File::open(path).context("Failed to open archive")?)
+            .context("Failed to read archive")?;
+
+        let compressed = if header.starts_with(Self::GZIP_MAGIC) {
+            Some((Self::TarGz, Self::Gz))
+        } else if header.starts_with(Self::XZ_MAGIC) {
+            Some((Self::TarXz, Self::Xz))
+        } else if header.starts_with(Self::ZSTD_MAGIC) {
+            Some((Self::TarZst, Self::Zst))
+        } else {
+            None
+        };
+        if let Some((tarball, single)) = compressed {
+            let inner = read_header(single.decoder(path)?)
+                .context(format!("Failed to decompress {}", path.display()))?;
+            return Ok(if is_tar_header(&inner) {
+                tarball
+            } else {
+                single
+            });
+        }
+
+        if header.starts_with(Self::ZIP_MAGIC) {
+            Ok(Self::Zip)
+        } else if is_tar_header(&header) {
+            Ok(Self::Tar)
+        } else {
+            anyhow::bail!(
+                "Unsupported archive format: {} (unknown extension and no recognized signature)",
+                path.display()
+            )
+        }
+    }
+
+    /// Decompressing reader over a gzip, xz or zstd file
+    fn decoder(self, path: &Path) -> Result<Box<dyn Read>> {
+        let file = File::open(path).context("Failed to open archive")?;
+        Ok(match self {
+            Self::TarGz | Self::Gz => Box::new(GzDecoder::new(file)),
+            Self::TarXz | Self::Xz => Box::new(XzDecoder::new(file)),
+            Self::TarZst | Self::Zst => Box::new(
+                zstd::stream::read::Decoder::new(file).context("Failed to open zstd stream")?,
+            ),
+            Self::Zip | Self::Tar => Box::new(file),
+        })
+    }
+}
+
+/// Reads up to the first 512 bytes (one tar header block)
+fn read_header(reader: impl Read) -> std::io::Result<Vec<u8>> {
+    let mut header = Vec::with_capacity(512);
+    reader.take(512).read_to_end(&mut header)?;
+    Ok(header)
+}
+
+/// POSIX and GNU tar headers carry "ustar" at offset 257
+fn is_tar_header(header: &[u8]) -> bool {
+    header.get(ArchiveKind::TAR_MAGIC_OFFSET..ArchiveKind::TAR_MAGIC_OFFSET + 5)
+        == Some(b"ustar".as_slice())
+}
+
+/// A regular file written by an extraction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExtractedFile {
+    /// Path relative to the extraction directory
+    pub path: PathBuf,
+    /// Bytes written
+    pub size: u64,
+    /// Lowercase hex SHA-256, once [`ExtractionManifest::hash_files`] has run
+    pub sha256: Option<String>,
+}
+
+/// Regular files written by an extraction, in archive order
+///
+/// Directories, symlinks and hardlinks aren't listed.
+#[derive(Debug, Clone, Default)]
+pub struct ExtractionManifest {
+    pub files: Vec<ExtractedFile>,
+}
+
+impl ExtractionManifest {
+    pub fn total_bytes(&self) -> u64 {
+        self.files.iter().map(|file| file.size).sum()
+    }
+
+    /// Computes the SHA-256 of every listed file under `dest_dir`
+    pub fn hash_files(&mut self, dest_dir: &Path) -> Result<()> {
+        for file in &mut self.files {
+            let path = dest_dir.join(&file.path);
+            let mut reader = File::open(&path)
+                .context(format!("Failed to open {} for hashing", path.display()))?;
+            let mut hasher = Sha256::new();
+            std::io::copy(&mut reader, &mut hasher)
+                .context(format!("Failed to hash {}", path.display()))?;
+            file.sha256 = Some(format!("{:x}", hasher.finalize()));
+        }
+        Ok(())
+    }
+
+    fn record(&mut self, dest_dir: &Path, safe_path: &Path, size: u64) {
+        let path = safe_path
+            .strip_prefix(dest_dir)
+            .unwrap_or(safe_path)
+            .components()
+            .collect();
+        self.files.push(ExtractedFile {
+            path,
+            size,
+            sha256: None,
+        });
+    }
+}
+
 /// Tracks extraction statistics for security limits
 struct ExtractionStats {
     total_bytes_extracted: u64,
@@ -17,8 +217,21 @@ struct ExtractionStats {
 }
 
 impl Extractor {
-    pub fn extract(archive_path: &Path, dest_dir: &Path) -> Result<()> {
+    pub fn extract(archive_path: &Path, dest_dir: &Path) -> Result<ExtractionManifest> {
+        Self::extract_only(archive_path, dest_dir, &[])
+    }
+
+    /// Extracts only the members matching one of `patterns` (all when empty)
+    ///
+    /// Paths of skipped members are still validated, so a malicious archive is
+    /// rejected regardless of the filter.
+    pub fn extract_only(
+        archive_path: &Path,
+        dest_dir: &Path,
+        patterns: &[String],
+    ) -> Result<ExtractionManifest> {
         log::debug!("Extracting {:?} to {:?}", archive_path, dest_dir);
+        let filter = MemberFilter::new(patterns)?;
 
         // Clean extraction directory if it exists to prevent accumulation of old extracts
         if dest_dir.exists() {
@@ -30,23 +243,153 @@ impl Extractor {
         // Ensure destination directory exists
         std::fs::create_dir_all(dest_dir)?;
 
-        // Determine archive type by extension
+        let config = SecurityConfig::load().unwrap_or_default().extraction;
+
+        // Dispatch on the extension, falling back to the file's magic bytes
+        // for names like `download` or `tool.bin`
+        let kind = match ArchiveKind::from_extension(archive_path) {
+            Some(kind) => kind,
+            None => {
+                let kind = ArchiveKind::detect(archive_path)?;
+                log::debug!(
+                    "Detected {:?} archive from content of {:?}",
+                    kind,
+                    archive_path
+                );
+                kind
+            }
+        };
+
+        let mut manifest = ExtractionManifest::default();
+        match kind {
+            ArchiveKind::TarGz | ArchiveKind::TarXz | ArchiveKind::TarZst => {
+                let archive = Archive::new(kind.decoder(archive_path)?);
+                Self::extract_tar_safe(archive, dest_dir, &config, &filter, &mut manifest)?
+            }
+            ArchiveKind::Zip => {
+                Self::extract_zip(archive_path, dest_dir, &config, &filter, &mut manifest)?
+            }
+            ArchiveKind::Tar => {
+                Self::extract_tar(archive_path, dest_dir, &config, &filter, &mut manifest)?
+            }
+            ArchiveKind::Gz | ArchiveKind::Xz | ArchiveKind::Zst => Self::extract_single(
+                archive_path,
+                kind,
+                dest_dir,
+                &config,
+                &filter,
+                &mut manifest,
+            )?,
+        }
+
+        if filter.is_filtering() && std::fs::read_dir(dest_dir)?.next().is_none() {
+            anyhow::bail!(
+                "No archive members match extract_only patterns: {}",
+                patterns.join(", ")
+            );
+        }
+
+        log::debug!(
+            "Extraction completed: {} files, {} bytes",
+            manifest.files.len(),
+            manifest.total_bytes()
+        );
+        Ok(manifest)
+    }
+
+    /// Moves the contents of a single top-level directory up into `dest_dir`
+    ///
+    /// Used for `install.strip_single_dir`. Returns the name of the removed
+    /// directory, or `None` (leaving everything in place) unless `dest_dir`
+    /// holds exactly one directory. Manifest paths are rewritten to match.
+    pub fn unwrap_single_dir(
+        dest_dir: &Path,
+        manifest: &mut ExtractionManifest,
+    ) -> Result<Option<String>> {
+        let entries = std::fs::read_dir(dest_dir)
+            .context(format!("Failed to read {}", dest_dir.display()))?
+            .collect::<std::io::Result<Vec<_>>>()?;
+        let [entry] = entries.as_slice() else {
+            return Ok(None);
+        };
+        if !entry.file_type()?.is_dir() {
+            return Ok(None);
+        }
+        let name = entry.file_name();
+
+        // Renamed first, since it may contain a member with its own name
+        let wrapper = dest_dir.join(".ora-unwrap");
+        std::fs::rename(entry.path(), &wrapper)
+            .context(format!("Failed to unwrap {}", name.to_string_lossy()))?;
+        for child in std::fs::read_dir(&wrapper)? {
+            let child = child?;
+            std::fs::rename(child.path(), dest_dir.join(child.file_name())).context(format!(
+                "Failed to move {} out of {}",
+                child.file_name().to_string_lossy(),
+                name.to_string_lossy()
+            ))?;
+        }
+        std::fs::remove_dir(&wrapper)?;
+
+        for file in &mut manifest.files {
+            if let Ok(path) = file.path.strip_prefix(&name) {
+                file.path = path.to_path_buf();
+            }
+        }
+        Ok(Some(name.to_string_lossy().into_owned()))
+    }
+
+    /// Checks that a downloaded file's magic bytes match its archive extension
+    ///
+    /// Catches servers that answer with an HTML error page (or a redirect body)
+    /// instead of the archive, which would otherwise fail deep inside extraction.
+    pub fn check_archive_signature(archive_path: &Path) -> Result<()> {
         let path_str = archive_path.to_string_lossy();
 
-        if path_str.ends_with(".tar.gz") || path_str.ends_with(".tgz") {
-            Self::extract_tar_gz(archive_path, dest_dir)?;
-        } else if path_str.ends_with(".tar.xz") || path_str.ends_with(".txz") {
-            Self::extract_tar_xz(archive_path, dest_dir)?;
-        } else if path_str.ends_with(".zip") {
-            Self::extract_zip(archive_path, dest_dir)?;
-        } else if path_str.ends_with(".tar") {
-            Self::extract_tar(archive_path, dest_dir)?;
-        } else {
-            anyhow::bail!("Unsupported archive format: {}", path_str);
+        let (kind, signature): (&str, &[u8]) =
+            if path_str.ends_with(".gz") || path_str.ends_with(".tgz") {
+                ("gzip", ArchiveKind::GZIP_MAGIC)
+            } else if path_str.ends_with(".xz") || path_str.ends_with(".txz") {
+                ("xz", ArchiveKind::XZ_MAGIC)
+            } else if path_str.ends_with(".zst") || path_str.ends_with(".tzst") {
+                ("zstd", ArchiveKind::ZSTD_MAGIC)
+            } else if path_str.ends_with(".zip") {
+                ("zip", b"PK")
+            } else {
+                // Plain tar has no reliable leading signature; extraction will validate it
+                return Ok(());
+            };
+
+        let mut header = Vec::with_capacity(512);
+        File::open(archive_path)
+            .context("Failed to open downloaded archive")?
+            .take(512)
+            .read_to_end(&mut header)
+            .context("Failed to read downloaded archive")?;
+
+        if header.starts_with(signature) {
+            return Ok(());
         }
 
-        log::debug!("Extraction completed");
-        Ok(())
+        let mut message = format!(
+            "Downloaded file {} is not a valid {} archive.\n\
+             The server likely returned an error page instead of the archive; \
+             check that the download URL is correct.",
+            archive_path.display(),
+            kind
+        );
+
+        // Show the beginning of the body if it is text (HTML error page, JSON error, ...)
+        if !header.is_empty()
+            && header
+                .iter()
+                .all(|b| b.is_ascii_graphic() || b.is_ascii_whitespace())
+        {
+            let snippet: String = String::from_utf8_lossy(&header).chars().take(200).collect();
+            message.push_str(&format!("\nResponse begins with:\n  {}", snippet.trim()));
+        }
+
+        anyhow::bail!(message)
     }
 
     /// Checks extraction limits and updates statistics
@@ -182,29 +525,80 @@ impl Extractor {
         Ok(full_path)
     }
 
-    fn extract_tar_gz(archive_path: &Path, dest_dir: &Path) -> Result<()> {
+    fn extract_tar(
+        archive_path: &Path,
+        dest_dir: &Path,
+        config: &ExtractionSecurityConfig,
+        filter: &MemberFilter,
+        manifest: &mut ExtractionManifest,
+    ) -> Result<()> {
         let file = File::open(archive_path).context("Failed to open archive")?;
-        let decoder = GzDecoder::new(file);
-        let archive = Archive::new(decoder);
-        Self::extract_tar_safe(archive, dest_dir)
+        let archive = Archive::new(file);
+        Self::extract_tar_safe(archive, dest_dir, config, filter, manifest)
     }
 
-    fn extract_tar_xz(archive_path: &Path, dest_dir: &Path) -> Result<()> {
-        let file = File::open(archive_path).context("Failed to open archive")?;
-        let decoder = XzDecoder::new(file);
-        let archive = Archive::new(decoder);
-        Self::extract_tar_safe(archive, dest_dir)
-    }
+    /// Decompresses a single compressed file (not a tarball) into `dest_dir`
+    ///
+    /// The output is named after the artifact without its compression suffix
+    /// (`tool-linux-amd64.gz` -> `tool-linux-amd64`) and made executable.
+    fn extract_single(
+        archive_path: &Path,
+        kind: ArchiveKind,
+        dest_dir: &Path,
+        config: &ExtractionSecurityConfig,
+        filter: &MemberFilter,
+        manifest: &mut ExtractionManifest,
+    ) -> Result<()> {
+        let file_name = archive_path
+            .file_name()
+            .context("Invalid archive path")?
+            .to_string_lossy();
+        let name = [".gz", ".xz", ".zst"]
+            .iter()
+            .find_map(|suffix| file_name.strip_suffix(suffix))
+            .filter(|name| !name.is_empty())
+            .unwrap_or(&file_name);
+        log::debug!("Decompressing single {:?} file to {}", kind, name);
+
+        let entry_path = Path::new(name);
+        let safe_path = Self::validate_extraction_path(dest_dir, entry_path)?;
+        if !filter.includes(entry_path) {
+            log::debug!("Skipping {} (not in extract_only)", name);
+            return Ok(());
+        }
 
-    fn extract_tar(archive_path: &Path, dest_dir: &Path) -> Result<()> {
-        let file = File::open(archive_path).context("Failed to open archive")?;
-        let archive = Archive::new(file);
-        Self::extract_tar_safe(archive, dest_dir)
+        // The decompressed size is unknown up front: read one byte past the
+        // limit to tell a file of exactly the maximum size from a bomb
+        let mut output = File::create(&safe_path)
+            .context(format!("Failed to create file: {}", safe_path.display()))?;
+        let mut reader = kind
+            .decoder(archive_path)?
+            .take(MAX_EXTRACTED_FILE_SIZE + 1);
+        let written = std::io::copy(&mut reader, &mut output)
+            .context(format!("Failed to decompress {}", archive_path.display()))?;
+        if written > MAX_EXTRACTED_FILE_SIZE {
+            drop(output);
+            let _ = std::fs::remove_file(&safe_path);
+            anyhow::bail!(
+                "Decompressed file exceeds maximum allowed size ({} bytes). Possible zip bomb.",
+                MAX_EXTRACTED_FILE_SIZE
+            );
+        }
+
+        Self::apply_permissions(&safe_path, Some(0o755), config)?;
+        manifest.record(dest_dir, &safe_path, written);
+        Ok(())
     }
 
     /// Safe tar extraction with path validation
-    fn extract_tar_safe<R: std::io::Read>(mut archive: Archive<R>, dest_dir: &Path) -> Result<()> {
-        // Disable potentially dangerous features
+    fn extract_tar_safe<R: std::io::Read>(
+        mut archive: Archive<R>,
+        dest_dir: &Path,
+        config: &ExtractionSecurityConfig,
+        filter: &MemberFilter,
+        manifest: &mut ExtractionManifest,
+    ) -> Result<()> {
+        // Disable potentially dangerous features (permissions are applied by us)
         archive.set_preserve_permissions(false);
         archive.set_preserve_mtime(false);
         archive.set_unpack_xattrs(false);
@@ -218,6 +612,7 @@ impl Extractor {
             .entries()
             .context("Failed to read archive entries")?
         {
+            check_shutdown()?;
             let entry = entry_result.context("Failed to read archive entry")?;
 
             // Get entry path and validate it
@@ -230,27 +625,22 @@ impl Extractor {
             // Check entry type
             let entry_type = entry.header().entry_type();
 
+            // Parent directories of selected members are created as needed
+            if filter.is_filtering()
+                && (entry_type == tar::EntryType::Directory || !filter.includes(&entry_path_buf))
+            {
+                continue;
+            }
+
             match entry_type {
                 tar::EntryType::Regular | tar::EntryType::Continuous => {
                     // Get file size and check limits
                     let file_size = entry.header().size().context("Failed to get file size")?;
                     Self::check_extraction_limits(&mut stats, file_size)?;
 
-                    // Regular file - create parent directories and extract
-                    if let Some(parent) = safe_path.parent() {
-                        std::fs::create_dir_all(parent).context(format!(
-                            "Failed to create parent directory: {}",
-                            parent.display()
-                        ))?;
-                    }
-
-                    // Extract file with size limit (defense in depth)
-                    let mut limited_reader = entry.take(MAX_EXTRACTED_FILE_SIZE);
-                    let mut outfile = File::create(&safe_path)
-                        .context(format!("Failed to create file: {}", safe_path.display()))?;
-
-                    std::io::copy(&mut limited_reader, &mut outfile)
-                        .context(format!("Failed to extract file: {}", safe_path.display()))?;
+                    let mode = entry.header().mode().ok();
+                    let written = Self::write_file(entry, &safe_path, mode, config)?;
+                    manifest.record(dest_dir, &safe_path, written);
                 }
                 tar::EntryType::Directory => {
                     // Directory - count as file but no size
@@ -261,14 +651,41 @@ impl Extractor {
                         safe_path.display()
                     ))?;
                 }
-                tar::EntryType::Symlink | tar::EntryType::Link => {
-                    // SECURITY: Block symlinks and hardlinks during extraction
-                    // They will be validated separately during deployment
-                    log::warn!(
-                        "Skipping symlink/hardlink in archive: {}",
-                        entry_path_buf.display()
-                    );
-                    continue;
+                tar::EntryType::Symlink => {
+                    let target = entry
+                        .link_name()
+                        .context("Failed to read symlink target")?
+                        .context("Symlink entry has no target")?
+                        .to_path_buf();
+
+                    Self::check_extraction_limits(&mut stats, 0)?;
+                    Self::create_symlink_entry(
+                        dest_dir,
+                        &entry_path_buf,
+                        &safe_path,
+                        &target,
+                        config,
+                    )?;
+                }
+                tar::EntryType::Link => {
+                    if config.block_hardlinks {
+                        // SECURITY: Block hardlinks during extraction
+                        log::warn!("Skipping hardlink in archive: {}", entry_path_buf.display());
+                        continue;
+                    }
+
+                    let target = entry
+                        .link_name()
+                        .context("Failed to read hardlink target")?
+                        .context("Hardlink entry has no target")?
+                        .to_path_buf();
+                    let safe_target = Self::validate_extraction_path(dest_dir, &target)?;
+
+                    Self::check_extraction_limits(&mut stats, 0)?;
+                    std::fs::hard_link(&safe_target, &safe_path).context(format!(
+                        "Failed to create hardlink: {}",
+                        safe_path.display()
+                    ))?;
                 }
                 _ => {
                     // Skip other entry types (char devices, block devices, fifos, etc.)
@@ -291,7 +708,13 @@ impl Extractor {
         Ok(())
     }
 
-    fn extract_zip(archive_path: &Path, dest_dir: &Path) -> Result<()> {
+    fn extract_zip(
+        archive_path: &Path,
+        dest_dir: &Path,
+        config: &ExtractionSecurityConfig,
+        filter: &MemberFilter,
+        manifest: &mut ExtractionManifest,
+    ) -> Result<()> {
         let file = File::open(archive_path).context("Failed to open archive")?;
         let mut archive = zip::ZipArchive::new(file).context("Failed to read zip archive")?;
 
@@ -310,15 +733,21 @@ impl Extractor {
         }
 
         for i in 0..archive.len() {
-            let file = archive.by_index(i).context("Failed to read zip entry")?;
+            check_shutdown()?;
+            let mut file = archive.by_index(i).context("Failed to read zip entry")?;
 
             // Get the file name and validate it
-            let file_name = file.name();
-            let entry_path = Path::new(file_name);
+            let file_name = file.name().to_string();
+            let entry_path = Path::new(&file_name);
 
             // Validate the path doesn't escape
             let safe_path = Self::validate_extraction_path(dest_dir, entry_path)?;
 
+            // Parent directories of selected members are created as needed
+            if filter.is_filtering() && (file.is_dir() || !filter.includes(entry_path)) {
+                continue;
+            }
+
             if file.is_dir() {
                 // Directory - count as file but no size
                 Self::check_extraction_limits(&mut stats, 0)?;
@@ -327,47 +756,32 @@ impl Extractor {
                     "Failed to create directory: {}",
                     safe_path.display()
                 ))?;
+            } else if file.is_symlink() {
+                // Zip stores the symlink target as the entry content
+                let mut target = String::new();
+                (&mut file)
+                    .take(MAX_PATH_LENGTH as u64)
+                    .read_to_string(&mut target)
+                    .context(format!("Failed to read symlink target: {}", file_name))?;
+
+                Self::check_extraction_limits(&mut stats, 0)?;
+                Self::create_symlink_entry(
+                    dest_dir,
+                    entry_path,
+                    &safe_path,
+                    Path::new(&target),
+                    config,
+                )?;
             } else if file.is_file() {
                 // Get file size and check limits
                 let file_size = file.size();
                 Self::check_extraction_limits(&mut stats, file_size)?;
 
-                // Get unix mode before moving file
-                #[cfg(unix)]
-                let unix_mode = file.unix_mode();
-
-                // Regular file
-                if let Some(parent) = safe_path.parent() {
-                    std::fs::create_dir_all(parent).context(format!(
-                        "Failed to create parent directory: {}",
-                        parent.display()
-                    ))?;
-                }
-
-                let mut outfile = File::create(&safe_path)
-                    .context(format!("Failed to create file: {}", safe_path.display()))?;
-
-                // Extract with size limit (defense in depth)
-                let mut limited_reader = file.take(MAX_EXTRACTED_FILE_SIZE);
-                std::io::copy(&mut limited_reader, &mut outfile)
-                    .context(format!("Failed to extract file: {}", safe_path.display()))?;
-
-                // Set permissions on Unix (but not SUID/SGID bits)
-                #[cfg(unix)]
-                {
-                    use std::os::unix::fs::PermissionsExt;
-                    if let Some(mode) = unix_mode {
-                        // Mask out SUID (04000) and SGID (02000) bits for security
-                        let safe_mode = mode & 0o0777;
-                        std::fs::set_permissions(
-                            &safe_path,
-                            std::fs::Permissions::from_mode(safe_mode),
-                        )
-                        .context("Failed to set file permissions")?;
-                    }
-                }
+                let mode = file.unix_mode();
+                let written = Self::write_file(file, &safe_path, mode, config)?;
+                manifest.record(dest_dir, &safe_path, written);
             } else {
-                // Skip symlinks and other special files
+                // Skip other special files
                 log::warn!("Skipping special file in zip archive: {}", file_name);
                 continue;
             }
@@ -381,6 +795,206 @@ impl Extractor {
 
         Ok(())
     }
+
+    /// Writes a regular file entry and applies its permissions, returning the
+    /// number of bytes written
+    ///
+    /// Shared by the tar and zip code paths so both honor the same policy.
+    fn write_file<R: Read>(
+        reader: R,
+        safe_path: &Path,
+        mode: Option<u32>,
+        config: &ExtractionSecurityConfig,
+    ) -> Result<u64> {
+        if let Some(parent) = safe_path.parent() {
+            std::fs::create_dir_all(parent).context(format!(
+                "Failed to create parent directory: {}",
+                parent.display()
+            ))?;
+        }
+
+        let mut outfile = File::create(safe_path)
+            .context(format!("Failed to create file: {}", safe_path.display()))?;
+
+        // Extract with size limit (defense in depth)
+        let mut limited_reader = reader.take(MAX_EXTRACTED_FILE_SIZE);
+        let written = std::io::copy(&mut limited_reader, &mut outfile)
+            .context(format!("Failed to extract file: {}", safe_path.display()))?;
+
+        Self::apply_permissions(safe_path, mode, config)?;
+        Ok(written)
+    }
+
+    /// Applies the entry's Unix mode, masking SUID/SGID bits if configured
+    ///
+    /// Archives without a recorded mode (e.g. zips created on Windows) get
+    /// 0o755 for files that look like executables and 0o644 otherwise.
+    #[cfg(unix)]
+    fn apply_permissions(
+        path: &Path,
+        mode: Option<u32>,
+        config: &ExtractionSecurityConfig,
+    ) -> Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let mode = match mode {
+            Some(mode) => mode,
+            None if Self::looks_executable(path) => 0o755,
+            None => 0o644,
+        };
+
+        // Mask out SUID (04000) and SGID (02000) bits for security
+        let safe_mode = if config.strip_setuid_bits {
+            mode & 0o0777
+        } else {
+            mode & 0o7777
+        };
+
+        std::fs::set_permissions(path, std::fs::Permissions::from_mode(safe_mode))
+            .context(format!("Failed to set permissions: {}", path.display()))
+    }
+
+    #[cfg(not(unix))]
+    fn apply_permissions(
+        _path: &Path,
+        _mode: Option<u32>,
+        _config: &ExtractionSecurityConfig,
+    ) -> Result<()> {
+        Ok(())
+    }
+
+    /// Detects executables by magic bytes (ELF, Mach-O, shebang scripts)
+    #[cfg(unix)]
+    fn looks_executable(path: &Path) -> bool {
+        let mut header = [0u8; 4];
+        let read = File::open(path)
+            .and_then(|mut f| f.read(&mut header))
+            .unwrap_or(0);
+        let header = &header[..read];
+
+        header.starts_with(b"\x7fELF")
+            || header.starts_with(b"#!")
+            || header.starts_with(&[0xfe, 0xed, 0xfa, 0xce])
+            || header.starts_with(&[0xfe, 0xed, 0xfa, 0xcf])
+            || header.starts_with(&[0xce, 0xfa, 0xed, 0xfe])
+            || header.starts_with(&[0xcf, 0xfa, 0xed, 0xfe])
+            || header.starts_with(&[0xca, 0xfe, 0xba, 0xbe])
+    }
+
+    /// Creates a symlink entry, or skips it if symlinks are blocked
+    ///
+    /// The target must be relative and must not resolve outside the
+    /// extraction directory.
+    fn create_symlink_entry(
+        dest_dir: &Path,
+        entry_path: &Path,
+        safe_path: &Path,
+        target: &Path,
+        config: &ExtractionSecurityConfig,
+    ) -> Result<()> {
+        if config.block_symlinks {
+            // SECURITY: Block symlinks during extraction
+            log::warn!("Skipping symlink in archive: {}", entry_path.display());
+            return Ok(());
+        }
+
+        if let Some(parent) = safe_path.parent() {
+            std::fs::create_dir_all(parent).context(format!(
+                "Failed to create parent directory: {}",
+                parent.display()
+            ))?;
+        }
+
+        Self::validate_symlink_target(dest_dir, entry_path, safe_path, target)?;
+
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(target, safe_path)
+                .context(format!("Failed to create symlink: {}", safe_path.display()))?;
+        }
+
+        #[cfg(not(unix))]
+        {
+            log::warn!(
+                "Symlinks in archives are not supported on this platform, skipping: {}",
+                entry_path.display()
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Validates that a symlink target stays within the extraction directory
+    ///
+    /// The target is resolved from the link's real directory, following the
+    /// links extracted before it, so a chain of links that each look harmless
+    /// can't reach outside. `..` after a component that doesn't exist yet is
+    /// rejected, since a later entry could turn that component into a link.
+    fn validate_symlink_target(
+        dest_dir: &Path,
+        entry_path: &Path,
+        safe_path: &Path,
+        target: &Path,
+    ) -> Result<()> {
+        let outside = || {
+            anyhow::anyhow!(
+                "Symlink '{}' points outside destination directory: {}",
+                entry_path.display(),
+                target.display()
+            )
+        };
+
+        let root = dest_dir
+            .canonicalize()
+            .context("Failed to canonicalize destination directory")?;
+        let mut resolved = safe_path
+            .parent()
+            .unwrap_or(dest_dir)
+            .canonicalize()
+            .context("Failed to canonicalize symlink directory")?;
+        if !resolved.starts_with(&root) {
+            return Err(outside());
+        }
+
+        let mut missing = false;
+        for component in target.components() {
+            match component {
+                std::path::Component::Normal(name) => {
+                    resolved.push(name);
+                    if !missing {
+                        match resolved.canonicalize() {
+                            Ok(real) => resolved = real,
+                            Err(_) => missing = true,
+                        }
+                    }
+                }
+                std::path::Component::CurDir => {}
+                std::path::Component::ParentDir => {
+                    if missing {
+                        anyhow::bail!(
+                            "Symlink '{}' has '..' after a path that doesn't exist yet: {}",
+                            entry_path.display(),
+                            target.display()
+                        );
+                    }
+                    resolved.pop();
+                }
+                std::path::Component::RootDir | std::path::Component::Prefix(_) => {
+                    anyhow::bail!(
+                        "Symlink '{}' has absolute target: {}",
+                        entry_path.display(),
+                        target.display()
+                    );
+                }
+            }
+
+            if !resolved.starts_with(&root) {
+                return Err(outside());
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -470,4 +1084,483 @@ mod tests {
         let _ = fs::remove_file(&archive_path);
         let _ = fs::remove_dir_all(&dest);
     }
+
+    /// Builds a zip archive at `path` from (name, content, unix mode) entries
+    fn create_zip(path: &Path, entries: &[(&str, &[u8], u32)]) {
+        use std::io::Write;
+        use zip::write::SimpleFileOptions;
+
+        let file = fs::File::create(path).expect("Failed to create zip file");
+        let mut writer = zip::ZipWriter::new(file);
+        for (name, content, mode) in entries {
+            let options = SimpleFileOptions::default().unix_permissions(*mode);
+            writer
+                .start_file(*name, options)
+                .expect("Failed to start zip entry");
+            writer
+                .write_all(content)
+                .expect("Failed to write zip entry");
+        }
+        writer.finish().expect("Failed to finish zip");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_zip_preserves_executable_bit() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().expect("Failed to create temp dir");
+        let archive_path = temp.path().join("tool.zip");
+        let dest = temp.path().join("out");
+
+        create_zip(
+            &archive_path,
+            &[
+                ("tool/bin/tool", b"#!/bin/sh\necho hi\n", 0o755),
+                ("tool/README.md", b"readme", 0o644),
+            ],
+        );
+
+        Extractor::extract(&archive_path, &dest).expect("Failed to extract zip");
+
+        let mode = |p: &str| {
+            fs::metadata(dest.join(p))
+                .expect("Extracted file missing")
+                .permissions()
+                .mode()
+                & 0o7777
+        };
+        assert_eq!(mode("tool/bin/tool"), 0o755);
+        assert_eq!(mode("tool/README.md"), 0o644);
+    }
+
+    #[test]
+    fn test_extract_only_matching_members() {
+        let temp = tempfile::tempdir().expect("Failed to create temp dir");
+        let archive_path = temp.path().join("tool.zip");
+        let dest = temp.path().join("out");
+
+        create_zip(
+            &archive_path,
+            &[
+                ("tool-1.0/bin/tool", b"binary", 0o755),
+                ("tool-1.0/doc/manual.html", b"docs", 0o644),
+                ("tool-1.0/src/main.c", b"source", 0o644),
+            ],
+        );
+
+        let mut manifest = Extractor::extract_only(&archive_path, &dest, &["*/bin/*".to_string()])
+            .expect("Failed to extract zip");
+
+        assert!(dest.join("tool-1.0/bin/tool").exists());
+        assert_eq!(manifest.files.len(), 1);
+        assert_eq!(manifest.files[0].path, Path::new("tool-1.0/bin/tool"));
+        assert_eq!(manifest.total_bytes(), 6);
+        manifest.hash_files(&dest).unwrap();
+        assert_eq!(
+            manifest.files[0].sha256.as_deref(),
+            Some(crate::security::checksum::sha256_hex(b"binary").as_str())
+        );
+        assert!(!dest.join("tool-1.0/doc").exists());
+        assert!(!dest.join("tool-1.0/src").exists());
+
+        let err = Extractor::extract_only(&archive_path, &dest, &["*.exe".to_string()])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("No archive members match"));
+    }
+
+    #[test]
+    fn test_unwrap_single_dir() {
+        let temp = tempfile::tempdir().expect("Failed to create temp dir");
+        let archive_path = temp.path().join("tool.zip");
+        let dest = temp.path().join("out");
+
+        // The wrapper holds a member with its own name
+        create_zip(
+            &archive_path,
+            &[
+                ("tool/tool", b"binary", 0o755),
+                ("tool/doc/README", b"docs", 0o644),
+            ],
+        );
+        let mut manifest = Extractor::extract(&archive_path, &dest).unwrap();
+        assert_eq!(
+            Extractor::unwrap_single_dir(&dest, &mut manifest).unwrap(),
+            Some("tool".to_string())
+        );
+        assert!(dest.join("tool").is_file());
+        assert!(dest.join("doc/README").is_file());
+        assert!(!dest.join(".ora-unwrap").exists());
+        let paths: Vec<&Path> = manifest.files.iter().map(|f| f.path.as_path()).collect();
+        assert_eq!(paths, vec![Path::new("tool"), Path::new("doc/README")]);
+
+        // Nothing to unwrap once the top level has several entries
+        assert_eq!(
+            Extractor::unwrap_single_dir(&dest, &mut manifest).unwrap(),
+            None
+        );
+        assert!(dest.join("tool").is_file());
+    }
+
+    #[test]
+    fn test_zip_symlink_blocked_by_default() {
+        let temp = tempfile::tempdir().expect("Failed to create temp dir");
+        let archive_path = temp.path().join("links.zip");
+        let dest = temp.path().join("out");
+        fs::create_dir_all(&dest).expect("Failed to create dest dir");
+
+        {
+            use std::io::Write;
+            use zip::write::SimpleFileOptions;
+
+            let file = fs::File::create(&archive_path).expect("Failed to create zip file");
+            let mut writer = zip::ZipWriter::new(file);
+            let options = SimpleFileOptions::default();
+            writer
+                .start_file("bin/tool", options)
+                .expect("Failed to start zip entry");
+            writer.write_all(b"binary").expect("Failed to write entry");
+            writer
+                .add_symlink("bin/tool-link", "tool", options)
+                .expect("Failed to add symlink");
+            writer.finish().expect("Failed to finish zip");
+        }
+
+        let config = ExtractionSecurityConfig::default();
+        Extractor::extract_zip(
+            &archive_path,
+            &dest,
+            &config,
+            &MemberFilter::new(&[]).unwrap(),
+            &mut ExtractionManifest::default(),
+        )
+        .expect("Failed to extract zip");
+
+        assert!(dest.join("bin/tool").exists());
+        assert!(!dest.join("bin/tool-link").is_symlink());
+
+        #[cfg(unix)]
+        {
+            let dest = temp.path().join("out-links");
+            fs::create_dir_all(&dest).expect("Failed to create dest dir");
+            let config = ExtractionSecurityConfig {
+                block_symlinks: false,
+                ..Default::default()
+            };
+            Extractor::extract_zip(
+                &archive_path,
+                &dest,
+                &config,
+                &MemberFilter::new(&[]).unwrap(),
+                &mut ExtractionManifest::default(),
+            )
+            .expect("Failed to extract zip");
+            assert!(dest.join("bin/tool-link").is_symlink());
+        }
+    }
+
+    #[test]
+    fn test_archive_signature_detects_html() {
+        let temp = tempfile::tempdir().expect("Failed to create temp dir");
+
+        let html = temp.path().join("tool.tar.gz");
+        fs::write(
+            &html,
+            "<!DOCTYPE html><html><body>404 Not Found</body></html>",
+        )
+        .expect("Failed to write file");
+        let err = Extractor::check_archive_signature(&html).unwrap_err();
+        assert!(err.to_string().contains("error page"));
+        assert!(err.to_string().contains("404 Not Found"));
+
+        let gzip = temp.path().join("tool.tgz");
+        fs::write(&gzip, [0x1f, 0x8b, 0x08, 0x00]).expect("Failed to write file");
+        assert!(Extractor::check_archive_signature(&gzip).is_ok());
+
+        let zip = temp.path().join("tool.zip");
+        fs::write(&zip, b"PK\x03\x04").expect("Failed to write file");
+        assert!(Extractor::check_archive_signature(&zip).is_ok());
+    }
+
+    #[test]
+    fn test_extract_detects_format_from_content() {
+        let temp = tempfile::tempdir().expect("Failed to create temp dir");
+
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            Vec::new(),
+            flate2::Compression::default(),
+        ));
+        let mut header = tar::Header::new_gnu();
+        header.set_size(5);
+        header.set_mode(0o755);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, "tool", &b"hello"[..])
+            .expect("Failed to append entry");
+        let gzip = builder
+            .into_inner()
+            .and_then(|encoder| encoder.finish())
+            .expect("Failed to build archive");
+
+        let archive = temp.path().join("download.bin");
+        fs::write(&archive, gzip).expect("Failed to write archive");
+        assert_eq!(ArchiveKind::detect(&archive).unwrap(), ArchiveKind::TarGz);
+
+        let dest = temp.path().join("out");
+        Extractor::extract(&archive, &dest).expect("Failed to extract");
+        assert_eq!(fs::read(dest.join("tool")).unwrap(), b"hello");
+
+        let truncated = temp.path().join("download");
+        fs::write(&truncated, [0x28, 0xb5, 0x2f, 0xfd, 0x00]).expect("Failed to write file");
+        assert!(ArchiveKind::detect(&truncated).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_extract_single_compressed_file() {
+        use std::io::Write;
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().expect("Failed to create temp dir");
+        let binary = b"#!/bin/sh\necho hello\n";
+
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(binary).expect("Failed to compress");
+        let gz = temp.path().join("mytool-linux-amd64.gz");
+        fs::write(&gz, encoder.finish().expect("Failed to compress")).expect("Failed to write");
+
+        let zst = temp.path().join("mytool-linux-amd64.zst");
+        fs::write(
+            &zst,
+            zstd::encode_all(&binary[..], 0).expect("Failed to compress"),
+        )
+        .expect("Failed to write");
+
+        for (archive, kind) in [(&gz, ArchiveKind::Gz), (&zst, ArchiveKind::Zst)] {
+            assert_eq!(ArchiveKind::detect(archive).unwrap(), kind);
+            Extractor::check_archive_signature(archive).expect("Signature should match");
+
+            let dest = temp.path().join("out");
+            Extractor::extract(archive, &dest).expect("Failed to extract");
+            let output = dest.join("mytool-linux-amd64");
+            assert_eq!(fs::read(&output).unwrap(), binary);
+            let mode = fs::metadata(&output).unwrap().permissions().mode();
+            assert_eq!(mode & 0o111, 0o111);
+        }
+    }
+
+    #[test]
+    fn test_symlink_target_validation() {
+        let temp = tempfile::tempdir().expect("Failed to create temp dir");
+        let dest = temp.path();
+        fs::create_dir_all(dest.join("pkg/bin")).expect("Failed to create dest dir");
+        let entry = Path::new("pkg/bin/tool");
+        let link = dest.join(entry);
+        let check = |target: &str| {
+            Extractor::validate_symlink_target(dest, entry, &link, Path::new(target))
+        };
+
+        assert!(check("../lib/tool").is_ok());
+        assert!(check("../../../etc").is_err());
+        assert!(check("/etc/passwd").is_err());
+        assert!(check("missing/../../x").is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_symlink_chain_cannot_escape() {
+        let temp = tempfile::tempdir().expect("Failed to create temp dir");
+        let dest = temp.path().join("out");
+        fs::create_dir_all(&dest).expect("Failed to create dest dir");
+        let config = ExtractionSecurityConfig {
+            block_symlinks: false,
+            ..Default::default()
+        };
+        let link = |entry: &str, target: &str| {
+            Extractor::create_symlink_entry(
+                &dest,
+                Path::new(entry),
+                &dest.join(entry),
+                Path::new(target),
+                &config,
+            )
+        };
+
+        // `dir/up` points back at the extraction root, which is fine on its own...
+        link("dir/up", "..").expect("Link to the root should be allowed");
+        // ...but a link placed through it sits at the root, so `..` leaves it
+        assert!(link("dir/up/escape", "..").is_err());
+        assert!(!dest.join("escape").is_symlink());
+
+        // Same with `..` applied to a link that resolves to the root
+        link("self", ".").expect("Link to the root should be allowed");
+        assert!(link("dir/sneaky", "../self/..").is_err());
+
+        link("dir/ok", "up/dir").expect("Link within the root should be allowed");
+    }
+
+    /// Manifest entries as (path, size, sha256) once hashed
+    fn manifest_entries(
+        manifest: &mut ExtractionManifest,
+        dest: &Path,
+    ) -> Vec<(PathBuf, u64, String)> {
+        manifest
+            .hash_files(dest)
+            .expect("Failed to hash extracted files");
+        manifest
+            .files
+            .iter()
+            .map(|file| {
+                (
+                    file.path.clone(),
+                    file.size,
+                    file.sha256.clone().expect("File was not hashed"),
+                )
+            })
+            .collect()
+    }
+
+    fn entry(path: &str, content: &[u8]) -> (PathBuf, u64, String) {
+        (
+            PathBuf::from(path),
+            content.len() as u64,
+            crate::security::checksum::sha256_hex(content),
+        )
+    }
+
+    #[test]
+    fn test_manifest_of_tar_archive() {
+        let temp = tempfile::tempdir().expect("Failed to create temp dir");
+        let archive_path = temp.path().join("tool.tar.gz");
+        let dest = temp.path().join("out");
+
+        let mut builder = tar::Builder::new(flate2::write::GzEncoder::new(
+            fs::File::create(&archive_path).expect("Failed to create archive"),
+            flate2::Compression::default(),
+        ));
+        let mut dir = tar::Header::new_gnu();
+        dir.set_entry_type(tar::EntryType::Directory);
+        dir.set_size(0);
+        dir.set_mode(0o755);
+        dir.set_cksum();
+        builder
+            .append_data(&mut dir, "tool-1.0/bin/", &b""[..])
+            .expect("Failed to append directory");
+        for (name, content) in [
+            ("tool-1.0/bin/tool", &b"#!/bin/sh\necho tool\n"[..]),
+            ("tool-1.0/README", &b"readme"[..]),
+            ("tool-1.0/empty", &b""[..]),
+        ] {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder
+                .append_data(&mut header, name, content)
+                .expect("Failed to append entry");
+        }
+        builder
+            .into_inner()
+            .expect("Failed to finish tar")
+            .finish()
+            .expect("Failed to finish gzip");
+
+        let mut manifest = Extractor::extract(&archive_path, &dest).expect("Failed to extract");
+
+        // Directories aren't listed; files keep archive order
+        assert_eq!(
+            manifest_entries(&mut manifest, &dest),
+            vec![
+                entry("tool-1.0/bin/tool", b"#!/bin/sh\necho tool\n"),
+                entry("tool-1.0/README", b"readme"),
+                entry("tool-1.0/empty", b""),
+            ]
+        );
+        assert_eq!(manifest.total_bytes(), 26);
+    }
+
+    #[test]
+    fn test_manifest_of_zip_archive_and_stripped_dir() {
+        let temp = tempfile::tempdir().expect("Failed to create temp dir");
+        let archive_path = temp.path().join("tool.zip");
+        let dest = temp.path().join("out");
+
+        create_zip(
+            &archive_path,
+            &[
+                ("tool-1.0/bin/tool", b"binary", 0o755),
+                ("tool-1.0/doc/manual.html", b"<html></html>", 0o644),
+            ],
+        );
+
+        let mut manifest = Extractor::extract(&archive_path, &dest).expect("Failed to extract");
+        assert_eq!(
+            manifest_entries(&mut manifest, &dest),
+            vec![
+                entry("tool-1.0/bin/tool", b"binary"),
+                entry("tool-1.0/doc/manual.html", b"<html></html>"),
+            ]
+        );
+
+        // strip_single_dir rewrites the paths; hashing again reads the moved files
+        Extractor::unwrap_single_dir(&dest, &mut manifest).expect("Failed to unwrap");
+        assert_eq!(
+            manifest_entries(&mut manifest, &dest),
+            vec![
+                entry("bin/tool", b"binary"),
+                entry("doc/manual.html", b"<html></html>"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_manifest_of_single_compressed_file() {
+        use std::io::Write;
+
+        let temp = tempfile::tempdir().expect("Failed to create temp dir");
+        let archive_path = temp.path().join("tool-linux-amd64.gz");
+        let dest = temp.path().join("out");
+
+        let mut encoder = flate2::write::GzEncoder::new(
+            fs::File::create(&archive_path).expect("Failed to create archive"),
+            flate2::Compression::default(),
+        );
+        encoder.write_all(b"\x7fELF binary").unwrap();
+        encoder.finish().expect("Failed to finish gzip");
+
+        let mut manifest = Extractor::extract(&archive_path, &dest).expect("Failed to extract");
+        assert_eq!(
+            manifest_entries(&mut manifest, &dest),
+            vec![entry("tool-linux-amd64", b"\x7fELF binary")]
+        );
+    }
+
+    #[test]
+    fn test_manifest_lists_only_extract_only_members() {
+        let temp = tempfile::tempdir().expect("Failed to create temp dir");
+        let archive_path = temp.path().join("tool.zip");
+        let dest = temp.path().join("out");
+
+        create_zip(
+            &archive_path,
+            &[
+                ("tool/bin/tool", b"binary", 0o755),
+                ("tool/bin/helper", b"helper", 0o755),
+                ("tool/share/data.bin", b"data", 0o644),
+            ],
+        );
+
+        let mut manifest =
+            Extractor::extract_only(&archive_path, &dest, &["tool/bin/*".to_string()])
+                .expect("Failed to extract");
+        assert_eq!(
+            manifest_entries(&mut manifest, &dest),
+            vec![
+                entry("tool/bin/tool", b"binary"),
+                entry("tool/bin/helper", b"helper"),
+            ]
+        );
+        assert_eq!(manifest.total_bytes(), 12);
+    }
 }
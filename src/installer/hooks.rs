@@ -0,0 +1,136 @@
+use anyhow::{Context, Result};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio::process::Command as TokioCommand;
+use tokio::time::timeout;
+
+use crate::config::global::HooksConfig;
+use crate::config::SecurityConfig;
+use crate::storage::database::load_global_config;
+
+/// Global flag (set from `--no-hooks`); when enabled, user hooks are never run
+static HOOKS_DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable user hooks for the whole process
+pub fn set_hooks_disabled(disabled: bool) {
+    HOOKS_DISABLED.store(disabled, Ordering::SeqCst);
+}
+
+/// Check if user hooks are disabled
+pub fn hooks_disabled() -> bool {
+    HOOKS_DISABLED.load(Ordering::Relaxed)
+}
+
+/// Operation that triggered a hook
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HookEvent {
+    Install,
+    Uninstall,
+    Update,
+}
+
+impl HookEvent {
+    fn name(self) -> &'static str {
+        match self {
+            HookEvent::Install => "post_install",
+            HookEvent::Uninstall => "post_uninstall",
+            HookEvent::Update => "post_update",
+        }
+    }
+
+    fn command(self, hooks: &HooksConfig) -> Option<&str> {
+        match self {
+            HookEvent::Install => hooks.post_install.as_deref(),
+            HookEvent::Uninstall => hooks.post_uninstall.as_deref(),
+            HookEvent::Update => hooks.post_update.as_deref(),
+        }
+    }
+}
+
+/// Runs the user hook configured for `event`, if any
+///
+/// The command runs through `sh -c` with the package name and version as `$1`
+/// and `$2` (and `ORA_PACKAGE_NAME` / `ORA_PACKAGE_VERSION`), under the script
+/// timeout from `security.toml`. The operation has already completed, so hook
+/// failures are reported as warnings instead of errors.
+pub async fn run_hook(event: HookEvent, package: &str, version: &str) {
+    if hooks_disabled() {
+        log::debug!("Hooks disabled, not running {} hook", event.name());
+        return;
+    }
+
+    let hooks = match load_global_config().await {
+        Ok(config) => config.hooks,
+        Err(e) => {
+            log::warn!("Could not load configuration to run hooks: {}", e);
+            return;
+        }
+    };
+
+    let Some(command) = event.command(&hooks) else {
+        return;
+    };
+
+    if let Err(e) = execute_hook(event, command, package, version).await {
+        log::warn!("{} hook failed for {}: {:#}", event.name(), package, e);
+        eprintln!("⚠️  {} hook failed: {:#}", event.name(), e);
+    }
+}
+
+async fn execute_hook(event: HookEvent, command: &str, package: &str, version: &str) -> Result<()> {
+    let security_config = SecurityConfig::load().unwrap_or_default();
+
+    // Same interpreter policy as post-install scripts
+    if !security_config
+        .scripts
+        .allowed_interpreters
+        .iter()
+        .any(|i| i == "sh")
+    {
+        anyhow::bail!("'sh' is not in scripts.allowed_interpreters (security.toml)");
+    }
+
+    let timeout_seconds = security_config.scripts.timeout_seconds;
+    log::info!(
+        "Running {} hook for {} {} (timeout {}s)",
+        event.name(),
+        package,
+        version,
+        timeout_seconds
+    );
+
+    let mut cmd = TokioCommand::new("sh");
+    cmd.arg("-c")
+        .arg(command)
+        .arg("ora-hook")
+        .arg(package)
+        .arg(version)
+        .env("ORA_HOOK_EVENT", event.name())
+        .env("ORA_PACKAGE_NAME", package)
+        .env("ORA_PACKAGE_VERSION", version)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true);
+
+    let output = timeout(Duration::from_secs(timeout_seconds), cmd.output())
+        .await
+        .map_err(|_| anyhow::anyhow!("timed out after {} seconds", timeout_seconds))?
+        .context("Failed to execute hook")?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stdout.is_empty() {
+        log::debug!("{} hook stdout:\n{}", event.name(), stdout);
+    }
+    if !stderr.is_empty() {
+        log::warn!("{} hook stderr:\n{}", event.name(), stderr);
+    }
+
+    if !output.status.success() {
+        anyhow::bail!("exited with code {:?}", output.status.code());
+    }
+
+    Ok(())
+}
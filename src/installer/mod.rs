@@ -1,7 +1,9 @@
 pub mod deployer;
 pub mod downloader;
 pub mod extractor;
+pub mod hooks;
 pub mod post_install;
+pub mod shell_env;
 pub mod verifier;
 
 pub use deployer::Deployer;
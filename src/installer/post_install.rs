@@ -1,69 +1,165 @@
 use anyhow::{Context, Result};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::Stdio;
 use std::time::Duration;
 use tokio::process::Command as TokioCommand;
 use tokio::time::timeout;
 
+use crate::config::security_config::ScriptSecurityConfig;
+use crate::error::OraError;
+use crate::security::checksum::sha256_hex;
+
+/// Whether a post-install script was reviewed ahead of time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptApproval {
+    /// Its SHA-256 is listed for the package in `scripts.approved_scripts`
+    Approved,
+    /// Runs only after the usual confirmation
+    Unreviewed,
+}
+
+/// Checks a package's post-install script against `[scripts]` in security.toml
+///
+/// With `block_public_registry_scripts`, a script from a public registry is
+/// refused unless its SHA-256 is approved for the package or `allow_insecure`
+/// is given. An approval for a different hash doesn't count: the script
+/// changed since it was reviewed.
+pub fn check_script_policy(
+    package: &str,
+    script: &str,
+    from_public_registry: bool,
+    allow_insecure: bool,
+    config: &ScriptSecurityConfig,
+) -> Result<ScriptApproval> {
+    let digest = sha256_hex(script.as_bytes());
+    let approved = config.approved_scripts.get(package).map(|approved| {
+        let approved = approved.trim().to_ascii_lowercase();
+        approved
+            .strip_prefix("sha256:")
+            .map(str::to_string)
+            .unwrap_or(approved)
+    });
+    if approved.as_deref() == Some(digest.as_str()) {
+        return Ok(ScriptApproval::Approved);
+    }
+
+    let blocked = from_public_registry && config.block_public_registry_scripts && !allow_insecure;
+    match approved {
+        Some(approved) if blocked => Err(OraError::SecurityPolicyViolation(format!(
+            "the post-install script of '{}' changed since it was approved \
+             (approved {}, now {}); review it again and update \
+             scripts.approved_scripts in security.toml",
+            package, approved, digest
+        ))
+        .into()),
+        None if blocked => Err(OraError::SecurityPolicyViolation(format!(
+            "'{}' comes from a public registry and has a post-install script \
+             (scripts.block_public_registry_scripts). After reviewing the script, \
+             approve it in security.toml:\n\n[scripts.approved_scripts]\n\"{}\" = \"{}\"\n\n\
+             or install with --allow-insecure",
+            package, package, digest
+        ))
+        .into()),
+        Some(approved) => {
+            log::warn!(
+                "Post-install script of '{}' changed since it was approved (approved {}, now {})",
+                package,
+                approved,
+                digest
+            );
+            Ok(ScriptApproval::Unreviewed)
+        }
+        None => Ok(ScriptApproval::Unreviewed),
+    }
+}
+
+/// Runs a package's post-install script from its install directory
+///
+/// The script is hashed before it is shown, and the exact approved text is
+/// handed to `sh`. The install directory is resolved once and re-checked after
+/// confirmation, so neither can be swapped while the user reads the prompt.
+/// The script must pass `check_script_policy`; an approved one runs without
+/// confirmation.
 pub async fn run_post_install(
+    package: &str,
     script: &str,
     install_dir: &Path,
     version: &str,
     custom_env: &HashMap<String, String>,
+    from_public_registry: bool,
     allow_without_confirmation: bool,
 ) -> Result<()> {
     log::warn!("⚠️  SECURITY WARNING: Package contains post-install script");
     log::warn!("Post-install scripts can execute arbitrary code on your system");
 
+    let security_config = crate::config::SecurityConfig::load().unwrap_or_default();
+    let approval = check_script_policy(
+        package,
+        script,
+        from_public_registry,
+        allow_without_confirmation,
+        &security_config.scripts,
+    )?;
+    let work_dir = locked_install_dir(install_dir)?;
+    let digest = sha256_hex(script.as_bytes());
+
     // Show the script to the user
     println!("\n⚠️  SECURITY WARNING ⚠️");
     println!("This package contains a post-install script that will execute on your system.");
     println!("Post-install scripts can run arbitrary commands with your user permissions.");
-    println!("\n📜 Script content:");
-    println!("─────────────────────────────────────────────────────────");
-    println!("{}", script);
-    println!("─────────────────────────────────────────────────────────");
+    if security_config.scripts.show_script_content {
+        println!("\n📜 Script content:");
+        println!("─────────────────────────────────────────────────────────");
+        println!("{}", script);
+        println!("─────────────────────────────────────────────────────────");
+    }
+    println!("🔒 Script SHA-256: {}", digest);
+    println!("📁 Runs in: {}", work_dir.display());
 
     // Request explicit user confirmation unless --insecure flag was used
-    if !allow_without_confirmation {
-        println!("\n❓ Do you want to run this post-install script? [y/N]");
-
-        let mut input = String::new();
-        std::io::stdin()
-            .read_line(&mut input)
-            .context("Failed to read user input")?;
-
-        let confirmed =
-            input.trim().eq_ignore_ascii_case("y") || input.trim().eq_ignore_ascii_case("yes");
-
-        if !confirmed {
+    if approval == ScriptApproval::Approved {
+        println!("✅ Script approved in security.toml (scripts.approved_scripts)");
+    } else if !allow_without_confirmation && security_config.scripts.require_confirmation {
+        if !crate::utils::ui::confirm("\n❓ Do you want to run this post-install script? [y/N]")? {
             log::info!("Post-install script execution cancelled by user");
             println!("⚠️  Post-install script skipped. Package may not function correctly.");
             return Ok(());
         }
-    } else {
+    } else if allow_without_confirmation {
         log::warn!("Skipping user confirmation due to --insecure flag");
+    } else {
+        log::warn!("Skipping user confirmation (scripts.require_confirmation = false)");
     }
 
-    log::info!("Running post-install script with user approval");
+    // The directory must still be the one shown before confirmation
+    if locked_install_dir(install_dir)? != work_dir {
+        anyhow::bail!(
+            "Install directory {} changed while waiting for confirmation; \
+             refusing to run the post-install script",
+            install_dir.display()
+        );
+    }
+    if sha256_hex(script.as_bytes()) != digest {
+        anyhow::bail!("Post-install script changed after it was shown; refusing to run it");
+    }
+
+    log::info!("Running post-install script {} with user approval", digest);
 
     // Prepare environment
     let mut env_vars = HashMap::new();
     env_vars.insert(
         "INSTALL_DIR".to_string(),
-        install_dir.to_string_lossy().to_string(),
+        work_dir.to_string_lossy().to_string(),
     );
     env_vars.insert("VERSION".to_string(), version.to_string());
 
     // Add custom env vars
     for (key, value) in custom_env {
-        let resolved_value = resolve_env_value(value, install_dir, version);
+        let resolved_value = resolve_env_value(value, &work_dir, version);
         env_vars.insert(key.clone(), resolved_value);
     }
 
-    // Load security configuration to get timeout setting
-    let security_config = crate::config::SecurityConfig::load().unwrap_or_default();
     let timeout_duration = Duration::from_secs(security_config.scripts.timeout_seconds);
 
     log::info!(
@@ -75,7 +171,7 @@ pub async fn run_post_install(
     let mut cmd = TokioCommand::new("sh");
     cmd.arg("-c")
         .arg(script)
-        .current_dir(install_dir)
+        .current_dir(&work_dir)
         .envs(&env_vars)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped());
@@ -124,8 +220,80 @@ pub async fn run_post_install(
     Ok(())
 }
 
+/// Canonical install directory, refusing a symlink in its place
+fn locked_install_dir(install_dir: &Path) -> Result<PathBuf> {
+    let metadata = std::fs::symlink_metadata(install_dir).context(format!(
+        "Failed to inspect install directory {}",
+        install_dir.display()
+    ))?;
+    if !metadata.is_dir() {
+        anyhow::bail!(
+            "Install directory {} is not a directory (symlink or file), \
+             refusing to run the post-install script there",
+            install_dir.display()
+        );
+    }
+    install_dir
+        .canonicalize()
+        .context("Failed to canonicalize install directory")
+}
+
 fn resolve_env_value(value: &str, install_dir: &Path, version: &str) -> String {
     value
         .replace("{install_dir}", &install_dir.to_string_lossy())
         .replace("{version}", version)
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locked_install_dir_rejects_symlink() {
+        let temp = tempfile::tempdir().unwrap();
+        let real = temp.path().join("1.0.0");
+        std::fs::create_dir(&real).unwrap();
+        let link = temp.path().join("swapped");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        assert_eq!(
+            locked_install_dir(&real).unwrap(),
+            real.canonicalize().unwrap()
+        );
+        assert!(locked_install_dir(&link).is_err());
+        assert!(locked_install_dir(&temp.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_check_script_policy() {
+        let script = "echo configured";
+        let mut config = ScriptSecurityConfig::default();
+
+        // Public registries are blocked unless --allow-insecure is given
+        assert!(check_script_policy("tool", script, true, false, &config).is_err());
+        assert_eq!(
+            check_script_policy("tool", script, true, true, &config).unwrap(),
+            ScriptApproval::Unreviewed
+        );
+        assert_eq!(
+            check_script_policy("tool", script, false, false, &config).unwrap(),
+            ScriptApproval::Unreviewed
+        );
+
+        config.approved_scripts.insert(
+            "tool".to_string(),
+            format!("sha256:{}", sha256_hex(script.as_bytes()).to_uppercase()),
+        );
+        assert_eq!(
+            check_script_policy("tool", script, true, false, &config).unwrap(),
+            ScriptApproval::Approved
+        );
+
+        // The approval is per package and per script text
+        assert!(check_script_policy("other", script, true, false, &config).is_err());
+        let error = check_script_policy("tool", "echo changed", true, false, &config)
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("changed since it was approved"));
+    }
+}
@@ -0,0 +1,343 @@
+use anyhow::{Context, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Component, Path, PathBuf};
+
+use crate::config::repo::InstallConfig;
+use crate::config::security_config::ScriptSecurityConfig;
+use crate::error::OraError;
+use crate::storage::paths::Paths;
+
+/// Shell syntax used for environment snippets
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    /// sh, bash, zsh and other POSIX-compatible shells
+    Posix,
+    Fish,
+}
+
+impl Shell {
+    /// Detects the user's shell from `$SHELL`, defaulting to POSIX syntax
+    pub fn detect() -> Self {
+        let shell = std::env::var("SHELL").unwrap_or_default();
+        match Path::new(&shell).file_name().and_then(|n| n.to_str()) {
+            Some("fish") => Self::Fish,
+            _ => Self::Posix,
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Posix => "sh",
+            Self::Fish => "fish",
+        }
+    }
+}
+
+/// Directory holding one environment snippet per package (`<package>.sh` / `.fish`)
+pub fn env_dir() -> Result<PathBuf> {
+    Ok(Paths::data_dir()?.join("env.d"))
+}
+
+/// Writes the package's `path_additions` and `env_exports` as a snippet the
+/// user can source, replacing any snippet from a previous install
+///
+/// `base_dir` is the package's stable directory (its `current` link when there
+/// is one); `path_additions` are relative to it and `{install_dir}` in export
+/// values expands to it. Returns `None` when the package needs no environment.
+pub fn write_snippet(
+    package: &str,
+    base_dir: &Path,
+    install: &InstallConfig,
+    shell: Shell,
+) -> Result<Option<PathBuf>> {
+    remove_snippets(package)?;
+    if install.path_additions.is_empty() && install.env_exports.is_empty() {
+        return Ok(None);
+    }
+
+    let path_dirs = install
+        .path_additions
+        .iter()
+        .map(|dir| resolve_path_addition(base_dir, dir))
+        .collect::<Result<Vec<_>>>()?;
+    let exports = resolve_exports(base_dir, &install.env_exports)?;
+
+    let dir = env_dir()?;
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.{}", package, shell.extension()));
+    std::fs::write(&path, render(shell, package, &path_dirs, &exports)).context(format!(
+        "Failed to write environment snippet: {}",
+        path.display()
+    ))?;
+
+    Ok(Some(path))
+}
+
+/// Removes the package's snippets for every shell
+pub fn remove_snippets(package: &str) -> Result<()> {
+    let dir = env_dir()?;
+    for shell in [Shell::Posix, Shell::Fish] {
+        let path = dir.join(format!("{}.{}", package, shell.extension()));
+        if path.exists() {
+            std::fs::remove_file(&path).context(format!("Failed to remove {}", path.display()))?;
+        }
+    }
+    Ok(())
+}
+
+/// Line to add to the shell profile so every package snippet gets loaded
+pub fn profile_line(shell: Shell) -> Result<String> {
+    let dir = env_dir()?;
+    Ok(match shell {
+        Shell::Posix => format!(
+            "for f in {}/*.sh; do [ -r \"$f\" ] && . \"$f\"; done",
+            posix_quote(&dir.to_string_lossy())
+        ),
+        Shell::Fish => format!(
+            "for f in {}/*.fish; source $f; end",
+            fish_quote(&dir.to_string_lossy())
+        ),
+    })
+}
+
+fn resolve_path_addition(base_dir: &Path, dir: &str) -> Result<String> {
+    let relative = Path::new(dir);
+    if relative
+        .components()
+        .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+    {
+        anyhow::bail!(
+            "install.path_additions entry '{}' must be relative to the package directory",
+            dir
+        );
+    }
+    Ok(base_dir.join(relative).to_string_lossy().into_owned())
+}
+
+/// Variables that make shells or the dynamic loader run code; packages can't set them
+const DENIED_EXPORTS: &[&str] = &[
+    "PATH",
+    "LD_PRELOAD",
+    "LD_LIBRARY_PATH",
+    "LD_AUDIT",
+    "BASH_ENV",
+    "ENV",
+    "PROMPT_COMMAND",
+    "PS0",
+    "PS1",
+    "PS2",
+    "PS4",
+    "IFS",
+    "SHELLOPTS",
+    "ZDOTDIR",
+];
+
+/// Refuses the `env_exports` of a package from a public registry under
+/// `block_public_registry_scripts`, since they change every shell that sources
+/// the snippet; `allow_insecure` accepts them
+pub fn check_exports_policy(
+    package: &str,
+    exports: &HashMap<String, String>,
+    from_public_registry: bool,
+    allow_insecure: bool,
+    config: &ScriptSecurityConfig,
+) -> Result<()> {
+    if exports.is_empty()
+        || !from_public_registry
+        || !config.block_public_registry_scripts
+        || allow_insecure
+    {
+        return Ok(());
+    }
+
+    let mut names: Vec<&str> = exports.keys().map(String::as_str).collect();
+    names.sort_unstable();
+    Err(OraError::SecurityPolicyViolation(format!(
+        "'{}' comes from a public registry and exports environment variables ({}) \
+         (scripts.block_public_registry_scripts); after reviewing them, install with \
+         --allow-insecure",
+        package,
+        names.join(", ")
+    ))
+    .into())
+}
+
+/// Expands export values, which must name the package directory or a path in it
+fn resolve_exports(
+    base_dir: &Path,
+    exports: &HashMap<String, String>,
+) -> Result<BTreeMap<String, String>> {
+    exports
+        .iter()
+        .map(|(name, value)| {
+            let valid = name
+                .chars()
+                .next()
+                .is_some_and(|c| c == '_' || c.is_ascii_alphabetic())
+                && name.chars().all(|c| c == '_' || c.is_ascii_alphanumeric());
+            if !valid {
+                anyhow::bail!("Invalid install.env_exports variable '{}'", name);
+            }
+            if DENIED_EXPORTS.contains(&name.as_str()) || name.starts_with("DYLD_") {
+                anyhow::bail!(
+                    "install.env_exports can't set {} (use path_additions for PATH)",
+                    name
+                );
+            }
+            Ok((name.clone(), resolve_export_value(base_dir, name, value)?))
+        })
+        .collect()
+}
+
+fn resolve_export_value(base_dir: &Path, name: &str, value: &str) -> Result<String> {
+    let relative = match value.strip_prefix("{install_dir}") {
+        Some("") => return Ok(base_dir.to_string_lossy().into_owned()),
+        Some(rest) => rest.strip_prefix('/'),
+        None => None,
+    };
+    match relative {
+        Some(relative)
+            if Path::new(relative)
+                .components()
+                .all(|c| matches!(c, Component::Normal(_) | Component::CurDir)) =>
+        {
+            Ok(base_dir.join(relative).to_string_lossy().into_owned())
+        }
+        _ => anyhow::bail!(
+            "install.env_exports value of {} must be {{install_dir}} or a path under it, got '{}'",
+            name,
+            value
+        ),
+    }
+}
+
+/// Renders a snippet; sourcing it repeatedly never duplicates PATH entries
+fn render(
+    shell: Shell,
+    package: &str,
+    path_dirs: &[String],
+    exports: &BTreeMap<String, String>,
+) -> String {
+    let mut out = format!("# Environment for {} (generated by Ora)\n", package);
+    for dir in path_dirs {
+        out.push_str(&match shell {
+            Shell::Posix => format!(
+                "case \":$PATH:\" in *:{dir}:*) ;; *) PATH={dir}:\"$PATH\"; export PATH ;; esac\n",
+                dir = posix_quote(dir)
+            ),
+            Shell::Fish => format!(
+                "contains -- {dir} $PATH; or set -gx PATH {dir} $PATH\n",
+                dir = fish_quote(dir)
+            ),
+        });
+    }
+    for (name, value) in exports {
+        out.push_str(&match shell {
+            Shell::Posix => format!("export {}={}\n", name, posix_quote(value)),
+            Shell::Fish => format!("set -gx {} {}\n", name, fish_quote(value)),
+        });
+    }
+    out
+}
+
+fn posix_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+fn fish_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_posix_snippet() {
+        let base = Path::new("/home/me/.local/share/ora/packages/tool/current");
+        let dirs = vec![resolve_path_addition(base, "libexec").unwrap()];
+        let exports = resolve_exports(
+            base,
+            &HashMap::from([("TOOL_HOME".to_string(), "{install_dir}".to_string())]),
+        )
+        .unwrap();
+
+        let snippet = render(Shell::Posix, "tool", &dirs, &exports);
+
+        assert!(snippet.contains(
+            "case \":$PATH:\" in *:'/home/me/.local/share/ora/packages/tool/current/libexec':*)"
+        ));
+        assert!(snippet
+            .contains("export TOOL_HOME='/home/me/.local/share/ora/packages/tool/current'\n"));
+    }
+
+    #[test]
+    fn test_render_fish_snippet() {
+        let dirs = vec!["/opt/tool/plugins".to_string()];
+        let exports = BTreeMap::from([("TOOL_MODE".to_string(), "it's fast".to_string())]);
+
+        let snippet = render(Shell::Fish, "tool", &dirs, &exports);
+
+        assert!(snippet.contains(
+            "contains -- '/opt/tool/plugins' $PATH; or set -gx PATH '/opt/tool/plugins' $PATH\n"
+        ));
+        assert!(snippet.contains("set -gx TOOL_MODE 'it\\'s fast'\n"));
+    }
+
+    #[test]
+    fn test_rejects_unsafe_entries() {
+        let base = Path::new("/opt/tool");
+        assert!(resolve_path_addition(base, "../bin").is_err());
+        assert!(resolve_path_addition(base, "/usr/bin").is_err());
+
+        for name in [
+            "PATH",
+            "1ABC",
+            "BAD-NAME",
+            "",
+            "LD_PRELOAD",
+            "DYLD_INSERT_LIBRARIES",
+            "BASH_ENV",
+            "ENV",
+            "PROMPT_COMMAND",
+            "PS1",
+        ] {
+            let exports = HashMap::from([(name.to_string(), "{install_dir}".to_string())]);
+            assert!(resolve_exports(base, &exports).is_err(), "{}", name);
+        }
+
+        for value in [
+            "x",
+            "/etc/profile",
+            "{install_dir}/../other",
+            "{install_dir}/lib/../../other",
+            "{install_dir}//etc",
+            "{install_dir}lib",
+            "prefix{install_dir}",
+        ] {
+            let exports = HashMap::from([("TOOL_HOME".to_string(), value.to_string())]);
+            assert!(resolve_exports(base, &exports).is_err(), "{}", value);
+        }
+
+        let exports = HashMap::from([(
+            "TOOL_PLUGINS".to_string(),
+            "{install_dir}/share/plugins".to_string(),
+        )]);
+        assert_eq!(
+            resolve_exports(base, &exports).unwrap()["TOOL_PLUGINS"],
+            "/opt/tool/share/plugins"
+        );
+    }
+
+    #[test]
+    fn test_exports_from_public_registry_need_allow_insecure() {
+        let exports = HashMap::from([("TOOL_HOME".to_string(), "{install_dir}".to_string())]);
+        let config = ScriptSecurityConfig::default();
+
+        let err = check_exports_policy("tool", &exports, true, false, &config).unwrap_err();
+        assert!(err.to_string().contains("TOOL_HOME"));
+        assert!(check_exports_policy("tool", &exports, true, true, &config).is_ok());
+        assert!(check_exports_policy("tool", &exports, false, false, &config).is_ok());
+        assert!(check_exports_policy("tool", &HashMap::new(), true, false, &config).is_ok());
+    }
+}
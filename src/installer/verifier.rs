@@ -1,15 +1,38 @@
 use anyhow::{Context, Result};
 use std::path::Path;
 
-use crate::config::repo::{ChecksumConfig, GpgConfig, RepoConfig};
+use crate::config::repo::{ChecksumAlgorithm, ChecksumConfig, GpgConfig, RepoConfig};
 use crate::installer::downloader::Downloader;
+use crate::providers::VersionProvider;
+use crate::security::gpg::verify_key_fingerprint;
 use crate::security::{parse_checksum_file, verify_checksum, verify_signature};
 use crate::storage::cache::Cache;
+use crate::utils::http::is_offline_mode;
 
 pub struct Verifier {
     downloader: Downloader,
 }
 
+/// Checksum an archive must match, as published for the release
+#[derive(Debug, Clone)]
+pub struct ExpectedChecksum {
+    /// Lowercase hex digest
+    pub hash: String,
+    pub algorithm: ChecksumAlgorithm,
+    /// Whether the checksum file it was read from had a valid GPG signature
+    pub signed: bool,
+}
+
+/// Checks [`Verifier::verify`] performed on an archive
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Verification {
+    /// The archive matched its expected checksum
+    pub checksum: bool,
+    /// A valid GPG signature covers the archive, directly or through its
+    /// signed checksum file
+    pub signature: bool,
+}
+
 impl Verifier {
     pub fn new() -> Result<Self> {
         Ok(Self {
@@ -17,27 +40,282 @@ impl Verifier {
         })
     }
 
+    /// URL of the checksum file published for a download
+    ///
+    /// Expands `security.checksum.url`, or names a sibling of the download with
+    /// `filename_pattern`.
+    pub fn checksum_url(
+        checksum_config: &ChecksumConfig,
+        download_url: &str,
+        filename: &str,
+        version: &str,
+        os: &str,
+        arch: &str,
+    ) -> Result<String> {
+        use crate::utils::templating::resolve_template_safe;
+        use std::collections::HashMap;
+
+        let mut vars = HashMap::new();
+        vars.insert("version".to_string(), version.to_string());
+        vars.insert("os".to_string(), os.to_string());
+        vars.insert("arch".to_string(), arch.to_string());
+
+        match &checksum_config.filename_pattern {
+            Some(pattern) => {
+                vars.insert("filename".to_string(), filename.to_string());
+                let name = resolve_template_safe(pattern, &vars)
+                    .context("Failed to resolve checksum filename pattern")?;
+                sibling_url(download_url, &name)
+            }
+            None => resolve_template_safe(&checksum_config.url, &vars)
+                .context("Failed to resolve checksum URL template"),
+        }
+    }
+
+    /// Fetches the checksum the archive `filename` must match, if the package declares one
+    ///
+    /// The checksum file is cached in the downloads directory so offline
+    /// installs can verify (and locate) previously downloaded archives.
+    /// With `from_release_notes`, the hash is read from the release
+    /// description fetched through `provider` instead. With `filename_pattern`,
+    /// the archive has its own checksum file, found next to `download_url`.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn expected_checksum(
+        &self,
+        download_url: &str,
+        filename: &str,
+        config: &RepoConfig,
+        provider: &dyn VersionProvider,
+        version: &str,
+        os: &str,
+        arch: &str,
+    ) -> Result<Option<ExpectedChecksum>> {
+        use crate::utils::templating::resolve_template_safe;
+        use std::collections::HashMap;
+
+        let Some(checksum_config) = &config.security.checksum else {
+            return Ok(None);
+        };
+
+        if let Some(pattern) = &checksum_config.from_release_notes {
+            return self
+                .checksum_from_release_notes(checksum_config, pattern, provider, filename, version)
+                .await
+                .map(Some);
+        }
+        if checksum_config.url.is_empty() && checksum_config.filename_pattern.is_none() {
+            anyhow::bail!(
+                "security.checksum needs 'url', 'filename_pattern' or 'from_release_notes'"
+            );
+        }
+
+        let mut vars = HashMap::new();
+        vars.insert("version".to_string(), version.to_string());
+        vars.insert("os".to_string(), os.to_string());
+        vars.insert("arch".to_string(), arch.to_string());
+
+        let checksum_url =
+            Self::checksum_url(checksum_config, download_url, filename, version, os, arch)?;
+
+        let checksum_cache_path = Cache::download_path(&format!("{}.checksum", filename))?;
+
+        let checksum_content = if is_offline_mode() {
+            log::debug!(
+                "Offline mode: reading cached checksum from {}",
+                checksum_cache_path.display()
+            );
+            std::fs::read_to_string(&checksum_cache_path).context(format!(
+                "Offline mode: checksum file for {} is not cached",
+                filename
+            ))?
+        } else {
+            log::debug!("Downloading checksum from: {}", checksum_url);
+            let content = self
+                .downloader
+                .download_text(&checksum_url)
+                .await
+                .context("Failed to download checksum")?;
+
+            if checksum_config.signature_url.is_some() {
+                // The signature is checked against the file on disk
+                std::fs::write(&checksum_cache_path, &content)
+                    .context("Failed to store checksum file for signature verification")?;
+            } else if let Err(e) = std::fs::write(&checksum_cache_path, &content) {
+                log::debug!("Failed to cache checksum file: {}", e);
+            }
+            content
+        };
+
+        // No hash is trusted from a signed checksum file until its signature checks out
+        let signed = checksum_config.signature_url.is_some();
+        if let Some(signature_url) = &checksum_config.signature_url {
+            let signature_url = resolve_template_safe(signature_url, &vars)
+                .context("Failed to resolve checksum signature URL template")?;
+            self.verify_checksum_signature(
+                &checksum_cache_path,
+                &signature_url,
+                checksum_config,
+                filename,
+            )
+            .await?;
+        }
+
+        let hash = Self::parse_expected_hash(checksum_config, &checksum_content, filename)?;
+
+        Ok(Some(ExpectedChecksum {
+            hash: hash.trim().to_lowercase(),
+            algorithm: checksum_config.algorithm.clone(),
+            signed,
+        }))
+    }
+
+    /// Reads the archive's hash from the release notes, caching them next to
+    /// the downloads like a checksum file
+    async fn checksum_from_release_notes(
+        &self,
+        checksum_config: &ChecksumConfig,
+        pattern: &str,
+        provider: &dyn VersionProvider,
+        filename: &str,
+        version: &str,
+    ) -> Result<ExpectedChecksum> {
+        if checksum_config.signature_url.is_some() {
+            anyhow::bail!(
+                "security.checksum.signature_url can't be combined with from_release_notes"
+            );
+        }
+
+        let notes_cache_path = Cache::download_path(&format!("{}.checksum", filename))?;
+        let notes = if is_offline_mode() {
+            std::fs::read_to_string(&notes_cache_path).context(format!(
+                "Offline mode: release notes for {} are not cached",
+                filename
+            ))?
+        } else {
+            log::debug!("Reading checksum from the release notes of {}", version);
+            let notes = provider
+                .get_release_notes(version)
+                .await?
+                .filter(|notes| !notes.trim().is_empty())
+                .context(format!(
+                    "No release notes to read the checksum from for version {}",
+                    version
+                ))?;
+            if let Err(e) = std::fs::write(&notes_cache_path, &notes) {
+                log::debug!("Failed to cache release notes: {}", e);
+            }
+            notes
+        };
+
+        let hash = hash_from_release_notes(&notes, pattern, filename)?;
+        Ok(ExpectedChecksum {
+            hash: hash.trim().to_lowercase(),
+            algorithm: checksum_config.algorithm.clone(),
+            signed: false,
+        })
+    }
+
+    async fn verify_checksum_signature(
+        &self,
+        checksum_path: &Path,
+        signature_url: &str,
+        checksum_config: &ChecksumConfig,
+        filename: &str,
+    ) -> Result<()> {
+        // Pinned like security.gpg keys, before anything is verified with it
+        let public_key = checksum_config.public_key.as_deref().context(
+            "security.checksum.signature_url is set but security.checksum.public_key \
+             is missing; add the key that signs the checksum file",
+        )?;
+        if let Some(fingerprint) = &checksum_config.fingerprint {
+            verify_key_fingerprint(public_key.as_bytes(), fingerprint)?;
+        }
+
+        let sig_path = Cache::download_path(&format!("{}.checksum.sig", filename))?;
+
+        if is_offline_mode() {
+            if !sig_path.exists() {
+                anyhow::bail!(
+                    "Offline mode: checksum signature for {} is not cached",
+                    filename
+                );
+            }
+        } else {
+            log::debug!("Downloading checksum signature from: {}", signature_url);
+            self.downloader
+                .download(signature_url, &sig_path)
+                .await
+                .context("Failed to download checksum signature")?;
+        }
+
+        verify_signature(checksum_path, &sig_path, public_key)
+            .await
+            .context("Checksum file signature verification failed")?;
+
+        log::debug!("Checksum file signature verified");
+        Ok(())
+    }
+
+    fn parse_expected_hash(
+        checksum_config: &ChecksumConfig,
+        checksum_content: &str,
+        filename: &str,
+    ) -> Result<String> {
+        // A per-archive checksum file only holds the hash of that archive
+        if checksum_config.is_single_hash() || checksum_config.filename_pattern.is_some() {
+            // Extract only the hash part (first whitespace-delimited token)
+            // The file may contain "hash  filename" format
+            Ok(checksum_content
+                .split_whitespace()
+                .next()
+                .context("Empty checksum file")?
+                .to_string())
+        } else {
+            // Parse multi-hash file
+            parse_checksum_file(checksum_content, filename)
+                .ok_or_else(|| anyhow::anyhow!("Failed to find checksum for file in checksum file"))
+        }
+    }
+
+    /// Verifies a downloaded archive against `expected` (from [`Self::expected_checksum`])
+    /// and the package's GPG signature, returning the checks that were made
+    ///
+    /// `skip_checksum` only waives the checksum; the signature is still checked.
+    #[allow(clippy::too_many_arguments)]
     pub async fn verify(
         &self,
         file_path: &Path,
         config: &RepoConfig,
+        expected: Option<&ExpectedChecksum>,
         version: &str,
         os: &str,
         arch: &str,
         allow_insecure: bool,
-    ) -> Result<()> {
+        skip_checksum: bool,
+    ) -> Result<Verification> {
         log::debug!("Verifying downloaded file: {:?}", file_path);
+        let mut verification = Verification::default();
 
         // Check if we should skip verification
         if allow_insecure {
             log::warn!("Skipping security verification (--insecure flag)");
-            return Ok(());
+            return Ok(verification);
         }
 
         // Verify checksum if configured
-        if let Some(checksum_config) = &config.security.checksum {
-            self.verify_checksum_from_config(file_path, checksum_config, version, os, arch)
-                .await?;
+        if skip_checksum {
+            log::warn!(
+                "Checksum verification was skipped (--skip-checksum): the integrity of {} \
+                 has not been checked",
+                file_path
+                    .file_name()
+                    .map(|name| name.to_string_lossy())
+                    .unwrap_or_default()
+            );
+        } else if let Some(expected) = expected {
+            verify_checksum(file_path, &expected.hash, &expected.algorithm).await?;
+            verification.checksum = true;
+            verification.signature = expected.signed;
         } else if config.security.allow_insecure {
             log::warn!("No checksum configured and allow_insecure is true");
         } else {
@@ -48,16 +326,17 @@ impl Verifier {
         if let Some(gpg_config) = &config.security.gpg {
             self.verify_signature_from_config(file_path, gpg_config, version, os, arch)
                 .await?;
+            verification.signature = true;
         }
 
         log::debug!("Verification completed successfully");
-        Ok(())
+        Ok(verification)
     }
 
-    async fn verify_checksum_from_config(
+    async fn verify_signature_from_config(
         &self,
         file_path: &Path,
-        checksum_config: &ChecksumConfig,
+        gpg_config: &GpgConfig,
         version: &str,
         os: &str,
         arch: &str,
@@ -70,75 +349,224 @@ impl Verifier {
         vars.insert("os".to_string(), os.to_string());
         vars.insert("arch".to_string(), arch.to_string());
 
-        let checksum_url = resolve_template_safe(&checksum_config.url, &vars)
-            .context("Failed to resolve checksum URL template")?;
+        let signature_url = resolve_template_safe(&gpg_config.signature_url, &vars)
+            .context("Failed to resolve signature URL template")?;
 
-        log::debug!("Downloading checksum from: {}", checksum_url);
-        let checksum_content = self
-            .downloader
-            .download_text(&checksum_url)
-            .await
-            .context("Failed to download checksum")?;
+        // Signature and fetched key are cached next to the downloads, so
+        // offline installs can verify a cached archive
+        let file_name = file_path
+            .file_name()
+            .context("Invalid file path: no filename")?
+            .to_string_lossy();
+        let sig_path = Cache::download_path(&format!("{}.sig", file_name))?;
+        let key_path = Cache::download_path(&format!("{}.key", file_name))?;
 
-        let expected_hash = if checksum_config.is_single_hash() {
-            // Extract only the hash part (first whitespace-delimited token)
-            // The file may contain "hash  filename" format
-            checksum_content
-                .split_whitespace()
-                .next()
-                .context("Empty checksum file")?
-                .to_string()
+        if is_offline_mode() {
+            if !sig_path.exists() {
+                anyhow::bail!("Offline mode: signature for {} is not cached", file_name);
+            }
         } else {
-            // Parse multi-hash file
-            let filename = file_path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .context("Invalid filename")?;
-
-            parse_checksum_file(&checksum_content, filename).ok_or_else(|| {
-                anyhow::anyhow!("Failed to find checksum for file in checksum file")
-            })?
-        };
+            log::debug!("Downloading signature from: {}", signature_url);
+            self.downloader.download(&signature_url, &sig_path).await?;
+        }
 
-        verify_checksum(file_path, &expected_hash, &checksum_config.algorithm).await?;
+        let public_key = self.load_public_key(gpg_config, &key_path).await?.context(
+            "No GPG public key to verify the signature with; \
+             set security.gpg.public_key or public_key_url",
+        )?;
+        verify_signature(file_path, &sig_path, &public_key).await?;
 
         Ok(())
     }
 
-    async fn verify_signature_from_config(
+    /// Key material from `public_key` or `public_key_url`, checked against the
+    /// pinned `fingerprint` before anything is verified with it
+    ///
+    /// A fetched key is cached at `key_path`, which offline mode reads instead.
+    async fn load_public_key(
         &self,
-        file_path: &Path,
         gpg_config: &GpgConfig,
-        version: &str,
-        os: &str,
-        arch: &str,
-    ) -> Result<()> {
-        use crate::utils::templating::resolve_template_safe;
-        use std::collections::HashMap;
+        key_path: &Path,
+    ) -> Result<Option<String>> {
+        let public_key = match (&gpg_config.public_key, &gpg_config.public_key_url) {
+            (Some(key), _) => Some(key.clone()),
+            (None, Some(_)) if is_offline_mode() => Some(
+                std::fs::read_to_string(key_path)
+                    .context("Offline mode: GPG public key is not cached")?,
+            ),
+            (None, Some(url)) => {
+                log::debug!("Downloading GPG public key from: {}", url);
+                let key = self
+                    .downloader
+                    .download_text(url)
+                    .await
+                    .context("Failed to download GPG public key")?;
+                if let Err(e) = std::fs::write(key_path, &key) {
+                    log::debug!("Failed to cache GPG public key: {}", e);
+                }
+                Some(key)
+            }
+            (None, None) => None,
+        };
 
-        let mut vars = HashMap::new();
-        vars.insert("version".to_string(), version.to_string());
-        vars.insert("os".to_string(), os.to_string());
-        vars.insert("arch".to_string(), arch.to_string());
+        match (&public_key, &gpg_config.fingerprint) {
+            (Some(key), Some(fingerprint)) => verify_key_fingerprint(key.as_bytes(), fingerprint)?,
+            (Some(_), None) if gpg_config.public_key.is_none() => log::warn!(
+                "GPG public key fetched from {} is not pinned with security.gpg.fingerprint",
+                gpg_config.public_key_url.as_deref().unwrap_or_default()
+            ),
+            _ => {}
+        }
+        Ok(public_key)
+    }
+}
 
-        let signature_url = resolve_template_safe(&gpg_config.signature_url, &vars)
-            .context("Failed to resolve signature URL template")?;
+/// URL of the file `name` in the same directory as the archive at `download_url`
+fn sibling_url(download_url: &str, name: &str) -> Result<String> {
+    if name.is_empty() || name.contains('/') || name.contains("..") {
+        anyhow::bail!("Invalid checksum file name '{}'", name);
+    }
+    let mut url =
+        url::Url::parse(download_url).context(format!("Invalid download URL: {}", download_url))?;
+    url.path_segments_mut()
+        .map_err(|_| anyhow::anyhow!("Download URL {} has no path", download_url))?
+        .pop()
+        .push(name);
+    url.set_query(None);
+    url.set_fragment(None);
+    Ok(url.to_string())
+}
 
-        log::debug!("Downloading signature from: {}", signature_url);
+/// Finds the hash of `filename` in release notes
+///
+/// `pattern`'s first capture group is the hash and `{filename}` in it matches
+/// the archive name literally. When the pattern matches nothing, the notes are
+/// read as pasted `hash  filename` lines.
+fn hash_from_release_notes(notes: &str, pattern: &str, filename: &str) -> Result<String> {
+    let pattern = pattern.replace("{filename}", &regex::escape(filename));
+    let re = crate::utils::regex::build_safe_regex(&pattern)
+        .context("Invalid security.checksum.from_release_notes pattern")?;
 
-        // Download signature file
-        let file_name = file_path
-            .file_name()
-            .context("Invalid file path: no filename")?
-            .to_string_lossy();
-        let sig_filename = format!("{}.sig", file_name);
-        let sig_path = Cache::download_path(&sig_filename)?;
+    let mut hashes: Vec<String> = re
+        .captures_iter(notes)
+        .filter_map(|cap| cap.get(1))
+        .map(|m| m.as_str().to_lowercase())
+        .collect();
+    hashes.sort();
+    hashes.dedup();
 
-        self.downloader.download(&signature_url, &sig_path).await?;
+    match hashes.len() {
+        1 => Ok(hashes.remove(0)),
+        0 => parse_checksum_file(notes, filename).context(format!(
+            "No checksum for {} found in the release notes",
+            filename
+        )),
+        n => anyhow::bail!(
+            "from_release_notes pattern matched {} different hashes; \
+             use {{filename}} in the pattern to select the one for {}",
+            n,
+            filename
+        ),
+    }
+}
 
-        // Verify signature
-        verify_signature(file_path, &sig_path, gpg_config.public_key.as_deref()).await?;
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-        Ok(())
+    const NOTES: &str = "## Changes\n\
+        - Faster startup\n\
+        \n\
+        SHA256 (tool-linux-x86_64.tar.gz) = AAAA1111\n\
+        SHA256 (tool-macos-aarch64.tar.gz) = bbbb2222\n";
+
+    #[test]
+    fn test_hash_from_release_notes() {
+        let pattern = r"SHA256 \({filename}\) = ([0-9a-fA-F]+)";
+        assert_eq!(
+            hash_from_release_notes(NOTES, pattern, "tool-linux-x86_64.tar.gz").unwrap(),
+            "aaaa1111"
+        );
+        assert_eq!(
+            hash_from_release_notes(NOTES, pattern, "tool-macos-aarch64.tar.gz").unwrap(),
+            "bbbb2222"
+        );
+        assert!(hash_from_release_notes(NOTES, pattern, "tool-freebsd.tar.gz").is_err());
+
+        // Without {filename} the pattern must not be ambiguous
+        let err = hash_from_release_notes(NOTES, r"= ([0-9a-fA-F]+)", "tool-linux-x86_64.tar.gz")
+            .unwrap_err();
+        assert!(err.to_string().contains("{filename}"));
+    }
+
+    fn checksum_config(extra: &str) -> ChecksumConfig {
+        toml::from_str(&format!("algorithm = \"sha256\"\n{}", extra)).unwrap()
+    }
+
+    #[test]
+    fn test_aggregate_checksum_file() {
+        let config = checksum_config(
+            "url = \"https://example.com/{version}/SHA256SUMS\"\nformat = \"multi-hash\"",
+        );
+        let sums = "aaaa1111  tool-linux-amd64.tar.gz\nbbbb2222 *tool-darwin-arm64.tar.gz\n";
+        assert_eq!(
+            Verifier::parse_expected_hash(&config, sums, "tool-darwin-arm64.tar.gz").unwrap(),
+            "bbbb2222"
+        );
+        assert!(Verifier::parse_expected_hash(&config, sums, "tool-windows.zip").is_err());
+    }
+
+    #[test]
+    fn test_per_file_checksum() {
+        let config = checksum_config("filename_pattern = \"{filename}.sha256\"");
+        assert_eq!(
+            sibling_url(
+                "https://github.com/o/tool/releases/download/v1.0/tool-linux-amd64.tar.gz",
+                "tool-linux-amd64.tar.gz.sha256"
+            )
+            .unwrap(),
+            "https://github.com/o/tool/releases/download/v1.0/tool-linux-amd64.tar.gz.sha256"
+        );
+        assert!(sibling_url("https://example.com/tool.tar.gz", "../SHA256SUMS").is_err());
+
+        // Read as a single hash, whether or not the file also names the archive
+        for content in ["AAAA1111\n", "aaaa1111  tool-linux-amd64.tar.gz\n"] {
+            assert_eq!(
+                Verifier::parse_expected_hash(&config, content, "tool-linux-amd64.tar.gz")
+                    .unwrap()
+                    .to_lowercase(),
+                "aaaa1111"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_checksum_signature_needs_the_checksum_key() {
+        let config = checksum_config(
+            "url = \"https://example.com/SHA256SUMS\"\n\
+             signature_url = \"https://example.com/SHA256SUMS.asc\"",
+        );
+        let err = Verifier::new()
+            .unwrap()
+            .verify_checksum_signature(
+                Path::new("SHA256SUMS"),
+                "https://example.com/SHA256SUMS.asc",
+                &config,
+                "tool.tar.gz",
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("security.checksum.public_key"));
+        assert!(!err.to_string().contains("security.gpg"));
+    }
+
+    #[test]
+    fn test_hash_from_pasted_checksum_lines() {
+        let notes = "Checksums:\n\ncccc3333  tool-linux-x86_64.tar.gz\n";
+        assert_eq!(
+            hash_from_release_notes(notes, r"sha256: ([0-9a-f]+)", "tool-linux-x86_64.tar.gz")
+                .unwrap(),
+            "cccc3333"
+        );
     }
 }
@@ -1,3 +1,4 @@
+pub mod api;
 pub mod cli;
 pub mod config;
 pub mod error;
@@ -7,3 +8,5 @@ pub mod registry;
 pub mod security;
 pub mod storage;
 pub mod utils;
+
+pub use api::{InstallOutcome, InstallSpec, Ora, VerificationReport};
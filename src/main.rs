@@ -1,9 +1,9 @@
 use anyhow::Result;
 use clap::Parser;
 use env_logger::Env;
-use std::sync::atomic::{AtomicBool, Ordering};
 use tokio::signal;
 
+mod api;
 mod cli;
 mod config;
 mod error;
@@ -16,14 +16,6 @@ mod utils;
 
 use cli::{Cli, Commands};
 
-/// Global shutdown flag accessible to all operations
-pub static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
-
-/// Check if shutdown has been requested
-pub fn is_shutdown_requested() -> bool {
-    SHUTDOWN_REQUESTED.load(Ordering::Relaxed)
-}
-
 #[tokio::main]
 async fn main() -> Result<()> {
     // Parse CLI arguments early to get verbosity flags
@@ -43,14 +35,66 @@ async fn main() -> Result<()> {
 
     let mut builder = env_logger::Builder::from_env(Env::default().default_filter_or(log_level));
 
-    // Configure timestamp based on verbosity
-    if !cli.debug && !cli.verbose {
+    // Configure record format and timestamp based on verbosity
+    if cli.log_format == utils::logging::LogFormat::Json {
+        utils::logging::use_json_format(&mut builder);
+    } else if !cli.debug && !cli.verbose {
         // Default mode: no timestamps for cleaner user-facing output
         builder.format_timestamp(None);
     }
 
     builder.init();
 
+    // NO_COLOR (https://no-color.org) disables colors; --plain also drops emoji
+    let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    if cli.plain || no_color {
+        console::set_colors_enabled(false);
+        console::set_colors_enabled_stderr(false);
+    }
+    if cli.plain {
+        utils::ui::set_plain_output(true);
+    }
+    if cli.yes {
+        utils::ui::set_assume_yes(true);
+    }
+
+    // Config file overrides must be in place before anything loads a config
+    if let Some(path) = cli.config.clone() {
+        storage::paths::Paths::set_config_file_override(path);
+    }
+    if let Some(path) = cli.security_config.clone() {
+        storage::paths::Paths::set_security_config_file_override(path);
+    }
+
+    if cli.offline {
+        utils::http::set_offline_mode(true);
+        log::info!("Offline mode enabled: network access is disabled");
+    }
+
+    if let Some(requested) = cli.concurrency {
+        let applied = utils::http::set_concurrency_override(requested);
+        if applied != requested {
+            utils::ui::warning(&format!(
+                "--concurrency {} is above the maximum; using {}",
+                requested, applied
+            ));
+        }
+    }
+
+    if cli.no_hooks {
+        installer::hooks::set_hooks_disabled(true);
+        log::info!("User hooks disabled");
+    }
+
+    let keep_downloads = cli.keep_downloads
+        || config::SecurityConfig::load()
+            .map(|c| c.resources.keep_downloads)
+            .unwrap_or(false);
+    if keep_downloads {
+        storage::cache::Cache::set_keep_downloads(true);
+        log::info!("Downloaded archives will be kept");
+    }
+
     // Set up graceful shutdown handler for SIGINT (Ctrl+C) and SIGTERM
     tokio::spawn(async {
         #[cfg(unix)]
@@ -74,17 +118,20 @@ async fn main() -> Result<()> {
             log::warn!("🛑 Received Ctrl+C, initiating graceful shutdown...");
         }
 
-        SHUTDOWN_REQUESTED.store(true, Ordering::SeqCst);
+        utils::shutdown::request_shutdown();
 
         log::debug!("Cleaning up temporary files...");
 
+        // Remove partially deployed versions and the links pointing into them
+        utils::shutdown::rollback_pending();
+
         // Attempt to clean up cache downloads
-        if let Err(e) = storage::cache::Cache::clear_downloads() {
+        if let Err(e) = storage::cache::Cache::cleanup_downloads() {
             log::warn!("Failed to clean up downloads during shutdown: {}", e);
         }
 
         log::debug!("✅ Graceful shutdown complete");
-        std::process::exit(130); // Exit code 130 = terminated by Ctrl+C
+        std::process::exit(error::exit_code::INTERRUPTED); // Exit code 130 = terminated by Ctrl+C
     });
 
     // Execute command
@@ -99,6 +146,13 @@ async fn main() -> Result<()> {
         Commands::Validate(args) => cli::commands::validate::execute(args).await,
         Commands::Security(args) => cli::commands::security::execute(args).await,
         Commands::Config(args) => cli::commands::config::execute(args).await,
+        Commands::Cache(args) => cli::commands::cache::execute(args).await,
+        Commands::SelfUpdate(args) => cli::commands::self_update::execute(args).await,
+        Commands::Export(args) => cli::commands::export::execute(args).await,
+        Commands::Import(args) => cli::commands::import::execute(args).await,
+        Commands::Lock(args) => cli::commands::lock::execute(args).await,
+        Commands::Alias(args) => cli::commands::alias::execute(args).await,
+        Commands::Platform(args) => cli::commands::platform::execute(args).await,
     };
 
     if let Err(e) = result {
@@ -106,11 +160,12 @@ async fn main() -> Result<()> {
 
         // Clean up on error
         log::debug!("Cleaning up after error...");
-        if let Err(cleanup_err) = storage::cache::Cache::clear_downloads() {
+        if let Err(cleanup_err) = storage::cache::Cache::cleanup_downloads() {
             log::warn!("Failed to clean up downloads: {}", cleanup_err);
         }
 
-        std::process::exit(1);
+        // Map the error category to a distinct exit code for scripts
+        std::process::exit(error::exit_code_for(&e));
     }
 
     Ok(())
@@ -1,9 +1,11 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
 
-use crate::config::repo::RepoConfig;
+use crate::config::repo::{RepoConfig, SourceConfig};
 use crate::providers::discovery::VersionDiscovery;
-use crate::providers::traits::{Version, VersionProvider};
+use crate::providers::traits::{
+    is_prerelease_tag, normalize_published_at, platform_download_url, Version, VersionProvider,
+};
 use crate::utils::http::HttpClient;
 use crate::utils::templating::resolve_template_safe;
 use std::collections::HashMap;
@@ -12,18 +14,200 @@ pub struct CustomApiProvider {
     #[allow(dead_code)]
     api_url: String,
     config: RepoConfig,
-    #[allow(dead_code)]
     client: HttpClient,
+    request: Option<ApiRequest>,
 }
 
 impl CustomApiProvider {
     pub fn new(api_url: String, config: RepoConfig) -> Result<Self> {
+        let request = ApiRequest::from_source(&config.name, &config.source)?;
         Ok(Self {
             api_url,
             config,
             client: HttpClient::new()?,
+            request,
         })
     }
+
+    /// Queries the download API and reads the archive URL at `json_path`
+    async fn resolve_download_url(
+        &self,
+        api_url: &str,
+        json_path: &str,
+        vars: &HashMap<String, String>,
+    ) -> Result<String> {
+        let content = match &self.request {
+            Some(request) => request.send(&self.client, api_url, Some(vars)).await?,
+            None => self.client.get_text(api_url).await?,
+        };
+        let json: serde_json::Value =
+            serde_json::from_str(&content).context("Failed to parse download API response")?;
+
+        VersionDiscovery::json_values(&json, json_path)?
+            .into_iter()
+            .next()
+            .ok_or_else(|| anyhow::anyhow!("No download URL found at path: {}", json_path))
+    }
+}
+
+/// Method, headers and body configured for custom-api requests
+#[derive(Clone)]
+pub struct ApiRequest {
+    method: reqwest::Method,
+    /// Header values with environment references expanded; never logged
+    headers: Vec<(String, String)>,
+    body: Option<String>,
+    allow_insecure_headers: bool,
+}
+
+impl ApiRequest {
+    /// Builds the request from the source config of `package`, or `None` for
+    /// a plain GET
+    pub fn from_source(package: &str, source: &SourceConfig) -> Result<Option<Self>> {
+        if source.method.is_none() && source.headers.is_empty() && source.body.is_none() {
+            return Ok(None);
+        }
+
+        let method = match source.method.as_deref().map(str::to_ascii_uppercase) {
+            None => reqwest::Method::GET,
+            Some(method) if method == "GET" => reqwest::Method::GET,
+            Some(method) if method == "POST" => reqwest::Method::POST,
+            Some(other) => anyhow::bail!(
+                "Unsupported custom-api method '{}' (expected GET or POST)",
+                other
+            ),
+        };
+        if source.body.is_some() && method != reqwest::Method::POST {
+            anyhow::bail!("custom-api 'body' requires method = \"POST\"");
+        }
+
+        let mut headers = source
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                reqwest::header::HeaderName::from_bytes(name.as_bytes())
+                    .map_err(|_| anyhow::anyhow!("Invalid header name '{}'", name))?;
+                let value = resolve_env_refs(package, value)
+                    .context(format!("Failed to resolve value of header '{}'", name))?;
+                Ok((name.clone(), value))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        headers.sort();
+
+        Ok(Some(Self {
+            method,
+            headers,
+            body: source.body.clone(),
+            allow_insecure_headers: source.allow_insecure_headers,
+        }))
+    }
+
+    /// Sends the request to `url`, filling `{version}`-style placeholders in
+    /// the body from `vars`, and returns the response text
+    pub async fn send(
+        &self,
+        client: &HttpClient,
+        url: &str,
+        vars: Option<&HashMap<String, String>>,
+    ) -> Result<String> {
+        let body = match (&self.body, vars) {
+            (Some(body), Some(vars)) => Some(resolve_template_safe(body, vars)?),
+            (body, _) => body.clone(),
+        };
+
+        log::debug!(
+            "{} {} (headers: {})",
+            self.method,
+            url,
+            self.redacted_headers()
+        );
+
+        let headers: Vec<(&str, &str)> = self
+            .headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        client
+            .request_text_with_secret_headers(
+                self.method.clone(),
+                url,
+                &headers,
+                body.as_deref(),
+                self.allow_insecure_headers,
+            )
+            .await
+    }
+
+    /// Header names for logging, with every value redacted
+    fn redacted_headers(&self) -> String {
+        if self.headers.is_empty() {
+            return "none".to_string();
+        }
+        self.headers
+            .iter()
+            .map(|(name, _)| format!("{}: <redacted>", name))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Expands `${NAME}` references in a header value from the environment
+///
+/// Only variables named for the package (`ORA_<PACKAGE>__*`, see
+/// [`env_prefix`]) can be referenced, so a .repo file can't send unrelated
+/// secrets from the environment, including other packages' tokens, to its
+/// server. The suffix must start with a letter or digit and can't contain
+/// `__`, so no variable belongs to two packages (`a` can't read
+/// `ORA_A_B__TOKEN`, `ORA_A__B__TOKEN` or `ORA_A___TOKEN`).
+fn resolve_env_refs(package: &str, value: &str) -> Result<String> {
+    let prefix = env_prefix(package);
+    let mut resolved = String::new();
+    let mut rest = value;
+
+    while let Some(start) = rest.find("${") {
+        resolved.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after.find('}').context("Unterminated '${' reference")?;
+        let name = &after[..end];
+
+        let valid = name
+            .strip_prefix(&prefix)
+            .filter(|suffix| suffix.starts_with(|c: char| c.is_ascii_alphanumeric()))
+            .is_some_and(|suffix| !suffix.contains("__"))
+            && name.chars().all(|c| c == '_' || c.is_ascii_alphanumeric());
+        if !valid {
+            anyhow::bail!(
+                "Environment reference '${{{}}}' is not allowed: '{}' can only use {}* variables",
+                name,
+                package,
+                prefix
+            );
+        }
+        let value = std::env::var(name)
+            .map_err(|_| anyhow::anyhow!("Environment variable {} is not set", name))?;
+        resolved.push_str(&value);
+        rest = &after[end + 1..];
+    }
+
+    resolved.push_str(rest);
+    Ok(resolved)
+}
+
+/// Prefix of the environment variables a package's headers may reference:
+/// `ORA_`, the package name in uppercase with other characters replaced by
+/// `_`, then `__` (`my-tool` reads `ORA_MY_TOOL__*`)
+fn env_prefix(package: &str) -> String {
+    let name: String = package
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    format!("ORA_{}__", name)
 }
 
 #[async_trait]
@@ -32,17 +216,19 @@ impl VersionProvider for CustomApiProvider {
         // Use version discovery if configured
         if let Some(version_config) = &self.config.source.version {
             log::debug!("Using version discovery for custom API");
-            let discovery = VersionDiscovery::new(version_config.clone())?;
-            let version_strings = discovery.discover_versions().await?;
+            let discovery =
+                VersionDiscovery::new(version_config.clone())?.with_request(self.request.clone());
+            let releases = discovery.discover_releases().await?;
 
             // Convert to Version structs
-            let versions: Vec<Version> = version_strings
+            let versions: Vec<Version> = releases
                 .into_iter()
-                .map(|tag| Version {
+                .map(|(tag, published_at)| Version {
                     tag: tag.clone(),
                     name: tag.clone(),
-                    published_at: String::new(),
-                    prerelease: tag.contains("alpha") || tag.contains("beta") || tag.contains("rc"),
+                    published_at: normalize_published_at(published_at.as_deref()),
+                    prerelease: is_prerelease_tag(&tag),
+                    assets: Vec::new(),
                 })
                 .collect();
 
@@ -67,12 +253,13 @@ impl VersionProvider for CustomApiProvider {
             .context("No download configuration found")?;
 
         if let Some(url_template) = &download_config.url {
-            Ok(resolve_template_safe(url_template, &vars)?)
+            let url = resolve_template_safe(url_template, &vars)?;
+            match &download_config.json_path {
+                Some(json_path) => self.resolve_download_url(&url, json_path, &vars).await,
+                None => Ok(url),
+            }
         } else if let Some(urls) = &download_config.urls {
-            let platform_key = format!("{}_{}", os, arch);
-            urls.get(&platform_key)
-                .cloned()
-                .ok_or_else(|| anyhow::anyhow!("No download URL for platform: {}", platform_key))
+            platform_download_url(urls, os, arch)
         } else {
             anyhow::bail!("No download configuration found")
         }
@@ -117,3 +304,83 @@ impl VersionProvider for CustomApiProvider {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(toml: &str) -> SourceConfig {
+        toml::from_str(&format!("type = \"custom-api\"\n{}", toml)).unwrap()
+    }
+
+    #[test]
+    fn test_request_from_source() {
+        assert!(ApiRequest::from_source("tool", &source(""))
+            .unwrap()
+            .is_none());
+
+        let request = ApiRequest::from_source(
+            "tool",
+            &source(
+            "method = \"post\"\nbody = '{\"v\": \"{version}\"}'\nheaders = { X-Api-Key = \"abc\", Accept = \"application/json\" }",
+        ))
+        .unwrap()
+        .unwrap();
+        assert_eq!(request.method, reqwest::Method::POST);
+        assert_eq!(
+            request.redacted_headers(),
+            "Accept: <redacted>, X-Api-Key: <redacted>"
+        );
+
+        assert!(ApiRequest::from_source("tool", &source("method = \"DELETE\"")).is_err());
+        assert!(ApiRequest::from_source("tool", &source("body = \"{}\"")).is_err());
+    }
+
+    #[test]
+    fn test_env_refs() {
+        std::env::set_var("ORA_TEST_CUSTOM_API__TOKEN", "s3cret");
+        let package = "test-custom.api";
+        assert_eq!(env_prefix(package), "ORA_TEST_CUSTOM_API__");
+        assert_eq!(
+            resolve_env_refs(package, "Bearer ${ORA_TEST_CUSTOM_API__TOKEN}").unwrap(),
+            "Bearer s3cret"
+        );
+        assert_eq!(resolve_env_refs(package, "plain").unwrap(), "plain");
+
+        assert!(resolve_env_refs(package, "${HOME}").is_err());
+        assert!(resolve_env_refs(package, "${ORA_TEST_CUSTOM_API__UNSET}").is_err());
+        assert!(resolve_env_refs(package, "${ORA_TEST_CUSTOM_API__TOKEN").is_err());
+        assert!(resolve_env_refs(package, "${ORA_TEST_CUSTOM_API__}").is_err());
+
+        // Another package's (or Ora's own) variables are off limits
+        let err = resolve_env_refs("other", "${ORA_TEST_CUSTOM_API__TOKEN}").unwrap_err();
+        assert!(err.to_string().contains("ORA_OTHER__*"));
+        assert!(resolve_env_refs(package, "${ORA_GITHUB_TOKEN}").is_err());
+    }
+
+    #[test]
+    fn test_env_refs_dont_reach_longer_package_names() {
+        std::env::set_var("ORA_TEST_ENV_A_B__TOKEN", "a-b");
+        std::env::set_var("ORA_TEST_ENV_A__B__TOKEN", "a--b");
+        std::env::set_var("ORA_TEST_ENV_A___TOKEN", "a_");
+
+        // `test-env-a` shares a prefix with each of these packages
+        let package = "test-env-a";
+        assert!(resolve_env_refs(package, "${ORA_TEST_ENV_A_B__TOKEN}").is_err());
+        assert!(resolve_env_refs(package, "${ORA_TEST_ENV_A__B__TOKEN}").is_err());
+        assert!(resolve_env_refs(package, "${ORA_TEST_ENV_A___TOKEN}").is_err());
+
+        assert_eq!(
+            resolve_env_refs("test-env-a-b", "${ORA_TEST_ENV_A_B__TOKEN}").unwrap(),
+            "a-b"
+        );
+        assert_eq!(
+            resolve_env_refs("test-env-a--b", "${ORA_TEST_ENV_A__B__TOKEN}").unwrap(),
+            "a--b"
+        );
+        assert_eq!(
+            resolve_env_refs("test-env-a_", "${ORA_TEST_ENV_A___TOKEN}").unwrap(),
+            "a_"
+        );
+    }
+}
@@ -2,7 +2,7 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 
 use crate::config::repo::RepoConfig;
-use crate::providers::traits::{Version, VersionProvider};
+use crate::providers::traits::{platform_download_url, Version, VersionProvider};
 use crate::utils::templating::resolve_template_safe;
 use std::collections::HashMap;
 
@@ -24,8 +24,9 @@ impl VersionProvider for DirectUrlProvider {
         Ok(vec![Version {
             tag: "latest".to_string(),
             name: "Latest".to_string(),
-            published_at: chrono::Utc::now().to_rfc3339(),
+            published_at: String::new(), // Release date unknown
             prerelease: false,
+            assets: Vec::new(),
         }])
     }
 
@@ -46,10 +47,7 @@ impl VersionProvider for DirectUrlProvider {
             resolve_template_safe(url_template, &vars)
                 .context("Failed to resolve download URL template")
         } else if let Some(urls) = &download_config.urls {
-            let platform_key = format!("{}_{}", os, arch);
-            urls.get(&platform_key)
-                .cloned()
-                .ok_or_else(|| anyhow::anyhow!("No download URL for platform: {}", platform_key))
+            platform_download_url(urls, os, arch)
         } else {
             anyhow::bail!("No download configuration found")
         }
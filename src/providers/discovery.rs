@@ -1,4 +1,5 @@
 use crate::config::repo::{DiscoveryType, VersionDiscoveryConfig};
+use crate::providers::custom_api::ApiRequest;
 use crate::utils::http::HttpClient;
 use anyhow::{Context, Result};
 use serde_json::Value;
@@ -7,6 +8,8 @@ use serde_json::Value;
 pub struct VersionDiscovery {
     config: VersionDiscoveryConfig,
     client: HttpClient,
+    /// Method, headers and body for the discovery request (plain GET when unset)
+    request: Option<ApiRequest>,
 }
 
 impl VersionDiscovery {
@@ -14,73 +17,129 @@ impl VersionDiscovery {
         Ok(Self {
             config,
             client: HttpClient::new()?,
+            request: None,
         })
     }
 
+    /// Sends the discovery request with a custom method, headers and body
+    pub fn with_request(mut self, request: Option<ApiRequest>) -> Self {
+        self.request = request;
+        self
+    }
+
+    /// Fetches the discovery URL
+    async fn fetch(&self) -> Result<String> {
+        match &self.request {
+            Some(request) => {
+                request
+                    .send(&self.client, &self.config.discovery_url, None)
+                    .await
+            }
+            None => self.client.get_text(&self.config.discovery_url).await,
+        }
+    }
+
     /// Discover available versions from the configured source
     pub async fn discover_versions(&self) -> Result<Vec<String>> {
+        Ok(self
+            .discover_releases()
+            .await?
+            .into_iter()
+            .map(|(version, _)| version)
+            .collect())
+    }
+
+    /// Discover available versions with their raw release dates (`None` when unknown)
+    pub async fn discover_releases(&self) -> Result<Vec<(String, Option<String>)>> {
         log::debug!("Discovering versions from: {}", self.config.discovery_url);
 
+        let undated = |versions: Vec<String>| versions.into_iter().map(|v| (v, None)).collect();
+
         match &self.config.discovery_type {
             DiscoveryType::GithubApi => self.discover_github_api().await,
             DiscoveryType::GitlabApi => self.discover_gitlab_api().await,
             DiscoveryType::Json => self.discover_json().await,
-            DiscoveryType::Text => self.discover_text().await,
-            DiscoveryType::HtmlScraping => self.discover_html().await,
+            DiscoveryType::Text => self.discover_text().await.map(undated),
+            DiscoveryType::HtmlScraping => self.discover_html().await.map(undated),
         }
     }
 
     /// Discover versions from GitHub API
-    async fn discover_github_api(&self) -> Result<Vec<String>> {
-        let content = self.client.get_text(&self.config.discovery_url).await?;
+    async fn discover_github_api(&self) -> Result<Vec<(String, Option<String>)>> {
+        let content = self.fetch().await?;
         let releases: Vec<Value> =
             serde_json::from_str(&content).context("Failed to parse GitHub API response")?;
 
-        let versions: Vec<String> = releases
-            .iter()
-            .filter_map(|release| release["tag_name"].as_str().map(|s| s.to_string()))
-            .collect();
-
-        Ok(versions)
+        Ok(Self::tags_with_dates(&releases, "published_at"))
     }
 
     /// Discover versions from GitLab API
-    async fn discover_gitlab_api(&self) -> Result<Vec<String>> {
-        let content = self.client.get_text(&self.config.discovery_url).await?;
+    async fn discover_gitlab_api(&self) -> Result<Vec<(String, Option<String>)>> {
+        let content = self.fetch().await?;
         let releases: Vec<Value> =
             serde_json::from_str(&content).context("Failed to parse GitLab API response")?;
 
-        let versions: Vec<String> = releases
-            .iter()
-            .filter_map(|release| release["tag_name"].as_str().map(|s| s.to_string()))
-            .collect();
+        Ok(Self::tags_with_dates(&releases, "released_at"))
+    }
 
-        Ok(versions)
+    /// Pairs each release's `tag_name` with its `date_field`
+    fn tags_with_dates(releases: &[Value], date_field: &str) -> Vec<(String, Option<String>)> {
+        releases
+            .iter()
+            .filter_map(|release| {
+                let tag = release["tag_name"].as_str()?.to_string();
+                let date = release[date_field].as_str().map(|s| s.to_string());
+                Some((tag, date))
+            })
+            .collect()
     }
 
     /// Discover versions from JSON with JSONPath
-    async fn discover_json(&self) -> Result<Vec<String>> {
-        let content = self.client.get_text(&self.config.discovery_url).await?;
+    async fn discover_json(&self) -> Result<Vec<(String, Option<String>)>> {
+        let content = self.fetch().await?;
         let json: Value =
             serde_json::from_str(&content).context("Failed to parse JSON response")?;
 
-        if let Some(json_path) = &self.config.json_path {
-            self.extract_from_json(&json, json_path)
-        } else {
-            anyhow::bail!("json_path is required for JSON discovery type")
-        }
+        self.extract_releases_from_json(&json)
     }
 
-    /// Extract versions from JSON using simple path syntax
-    /// Supports: $.field, $.array[*], $.nested.field
-    fn extract_from_json(&self, json: &Value, path: &str) -> Result<Vec<String>> {
-        let path = path.trim_start_matches("$.");
-        let parts: Vec<&str> = path.split('.').collect();
+    /// Extracts versions, and dates when `date_json_path` is configured
+    ///
+    /// Dates are matched to versions by position; if the two paths don't yield
+    /// the same number of values, dates are ignored.
+    fn extract_releases_from_json(&self, json: &Value) -> Result<Vec<(String, Option<String>)>> {
+        let json_path = self
+            .config
+            .json_path
+            .as_ref()
+            .context("json_path is required for JSON discovery type")?;
+        let versions = self.extract_from_json(json, json_path)?;
+
+        let dates = match &self.config.date_json_path {
+            Some(date_path) => {
+                let dates = Self::json_values(json, date_path)?;
+                if dates.len() == versions.len() {
+                    dates.into_iter().map(Some).collect()
+                } else {
+                    log::warn!(
+                        "date_json_path returned {} values for {} versions, ignoring release dates",
+                        dates.len(),
+                        versions.len()
+                    );
+                    vec![None; versions.len()]
+                }
+            }
+            None => vec![None; versions.len()],
+        };
 
-        let current = json;
-        let mut results = Vec::new();
+        Ok(versions.into_iter().zip(dates).collect())
+    }
 
-        Self::traverse_json_path(current, &parts, 0, &mut results)?;
+    /// Extract versions from JSON using simple path syntax
+    /// Supports: $.field, $.array[*], $.array[0], $.array[-1], $.nested.field
+    /// and filters such as $.releases[?(@.draft==false)].tag_name
+    fn extract_from_json(&self, json: &Value, path: &str) -> Result<Vec<String>> {
+        let results = Self::json_values(json, path)?;
 
         if results.is_empty() {
             anyhow::bail!("No versions found at path: {}", path);
@@ -89,49 +148,58 @@ impl VersionDiscovery {
         Ok(results)
     }
 
+    /// All string values at `path` (same syntax as `json_path`)
+    pub(crate) fn json_values(json: &Value, path: &str) -> Result<Vec<String>> {
+        let path = path.trim_start_matches('$').trim_start_matches('.');
+        let parts = split_json_path(path)?
+            .into_iter()
+            .map(PathSegment::parse)
+            .collect::<Result<Vec<_>>>()
+            .context(format!("Invalid JSON path: {}", path))?;
+
+        let mut results = Vec::new();
+        Self::traverse_json_path(json, &parts, 0, &mut results);
+        Ok(results)
+    }
+
     fn traverse_json_path(
         current: &Value,
-        parts: &[&str],
+        parts: &[PathSegment],
         depth: usize,
         results: &mut Vec<String>,
-    ) -> Result<()> {
+    ) {
         if depth >= parts.len() {
             // Reached the end of the path
             if let Some(s) = current.as_str() {
                 results.push(s.to_string());
             }
-            return Ok(());
+            return;
         }
 
-        let part = parts[depth];
-
-        if part.ends_with("[*]") {
-            // Array traversal
-            let field = part.trim_end_matches("[*]");
-            let array = if field.is_empty() {
-                current
-            } else {
-                &current[field]
-            };
-
-            if let Some(arr) = array.as_array() {
-                for item in arr {
-                    Self::traverse_json_path(item, parts, depth + 1, results)?;
-                }
-            }
+        let segment = &parts[depth];
+        let start = if segment.field.is_empty() {
+            Some(current)
         } else {
             // Object field access
-            if let Some(next) = current.get(part) {
-                Self::traverse_json_path(next, parts, depth + 1, results)?;
-            }
+            current.get(&segment.field)
+        };
+
+        let mut values: Vec<&Value> = start.into_iter().collect();
+        for selector in &segment.selectors {
+            values = values
+                .into_iter()
+                .flat_map(|value| selector.apply(value))
+                .collect();
         }
 
-        Ok(())
+        for value in values {
+            Self::traverse_json_path(value, parts, depth + 1, results);
+        }
     }
 
     /// Discover versions from plain text using regex
     async fn discover_text(&self) -> Result<Vec<String>> {
-        let content = self.client.get_text(&self.config.discovery_url).await?;
+        let content = self.fetch().await?;
 
         if let Some(pattern) = &self.config.regex {
             // SECURITY: Use safe regex builder with ReDoS protection
@@ -157,7 +225,7 @@ impl VersionDiscovery {
     async fn discover_html(&self) -> Result<Vec<String>> {
         // For HTML scraping, we'll use regex on the HTML content
         // This is a simple implementation - could be enhanced with proper HTML parsing
-        let content = self.client.get_text(&self.config.discovery_url).await?;
+        let content = self.fetch().await?;
 
         if let Some(pattern) = &self.config.regex {
             // SECURITY: Use safe regex builder with ReDoS protection
@@ -190,6 +258,168 @@ impl VersionDiscovery {
     }
 }
 
+/// One `.`-separated step of a JSON path: a field name followed by any
+/// number of `[...]` selectors
+struct PathSegment {
+    field: String,
+    selectors: Vec<Selector>,
+}
+
+enum Selector {
+    /// `[*]`: every array element
+    All,
+    /// `[N]`: one element, counting from the end when negative
+    Index(i64),
+    /// `[?(@.key==value)]` / `[?(@.key!=value)]`: elements whose field matches
+    Filter {
+        key: Vec<String>,
+        value: Value,
+        negate: bool,
+    },
+}
+
+impl PathSegment {
+    fn parse(part: &str) -> Result<Self> {
+        let (field, mut rest) = match part.find('[') {
+            Some(i) => (&part[..i], &part[i..]),
+            None => (part, ""),
+        };
+
+        let mut selectors = Vec::new();
+        while !rest.is_empty() {
+            let inner = rest
+                .strip_prefix('[')
+                .context(format!("Expected '[' in '{}'", part))?;
+            let end = closing_bracket(inner).context(format!("Unclosed '[' in '{}'", part))?;
+            selectors.push(Selector::parse(&inner[..end])?);
+            rest = &inner[end + 1..];
+        }
+
+        Ok(Self {
+            field: field.to_string(),
+            selectors,
+        })
+    }
+}
+
+impl Selector {
+    fn parse(selector: &str) -> Result<Self> {
+        let selector = selector.trim();
+        if selector == "*" {
+            return Ok(Self::All);
+        }
+        if let Ok(index) = selector.parse::<i64>() {
+            return Ok(Self::Index(index));
+        }
+
+        let predicate = selector
+            .strip_prefix("?(")
+            .and_then(|s| s.strip_suffix(')'))
+            .context(format!(
+                "Unsupported selector '[{}]' (expected [*], [N] or [?(@.key==value)])",
+                selector
+            ))?;
+        let (key, value, negate) = if let Some((key, value)) = predicate.split_once("!=") {
+            (key, value, true)
+        } else if let Some((key, value)) = predicate.split_once("==") {
+            (key, value, false)
+        } else {
+            anyhow::bail!("Filter '{}' must use == or !=", predicate);
+        };
+
+        let key = key
+            .trim()
+            .strip_prefix("@.")
+            .context(format!("Filter key must start with '@.': '{}'", key.trim()))?;
+        let value = value.trim();
+        let value = match value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')) {
+            Some(quoted) => Value::String(quoted.to_string()),
+            None => {
+                serde_json::from_str(value).context(format!("Invalid filter value '{}'", value))?
+            }
+        };
+
+        Ok(Self::Filter {
+            key: key.split('.').map(str::to_string).collect(),
+            value,
+            negate,
+        })
+    }
+
+    fn apply<'a>(&self, value: &'a Value) -> Vec<&'a Value> {
+        let Some(items) = value.as_array() else {
+            return Vec::new();
+        };
+
+        match self {
+            Self::All => items.iter().collect(),
+            Self::Index(index) => {
+                let index = if *index < 0 {
+                    items.len() as i64 + index
+                } else {
+                    *index
+                };
+                usize::try_from(index)
+                    .ok()
+                    .and_then(|i| items.get(i))
+                    .into_iter()
+                    .collect()
+            }
+            Self::Filter { key, value, negate } => items
+                .iter()
+                .filter(|item| {
+                    let field = key.iter().try_fold(*item, |current, k| current.get(k));
+                    (field == Some(value)) != *negate
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Splits a path on `.` outside of brackets, so filters like
+/// `[?(@.draft==false)]` stay in one segment
+fn split_json_path(path: &str) -> Result<Vec<&str>> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+
+    for (i, c) in path.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth = depth
+                    .checked_sub(1)
+                    .context(format!("Unbalanced ']' in JSON path: {}", path))?
+            }
+            '.' if depth == 0 => {
+                parts.push(&path[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 {
+        anyhow::bail!("Unclosed '[' in JSON path: {}", path);
+    }
+    parts.push(&path[start..]);
+
+    Ok(parts)
+}
+
+/// Position of the `]` matching an already-consumed `[`
+fn closing_bracket(input: &str) -> Option<usize> {
+    let mut depth = 0usize;
+    for (i, c) in input.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' if depth == 0 => return Some(i),
+            ']' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -208,8 +438,10 @@ mod tests {
                 regex: None,
                 url_pattern: None,
                 version_pattern: None,
+                date_json_path: None,
             },
             client: HttpClient::new().expect("Failed to create HTTP client in test"),
+            request: None,
         };
 
         let result = discovery
@@ -235,8 +467,10 @@ mod tests {
                 regex: None,
                 url_pattern: None,
                 version_pattern: None,
+                date_json_path: None,
             },
             client: HttpClient::new().expect("Failed to create HTTP client in test"),
+            request: None,
         };
 
         let result = discovery
@@ -244,4 +478,91 @@ mod tests {
             .expect("Failed to extract from JSON in test");
         assert_eq!(result, vec!["1.0.0", "2.0.0"]);
     }
+
+    #[test]
+    fn test_json_release_dates() {
+        let json = serde_json::json!({
+            "releases": [
+                {"version": "1.0.0", "date": "2024-01-05T10:00:00Z"},
+                {"version": "2.0.0", "date": "2024-03-01T08:30:00Z"}
+            ]
+        });
+
+        let mut discovery = VersionDiscovery {
+            config: VersionDiscoveryConfig {
+                discovery_url: "".to_string(),
+                discovery_type: DiscoveryType::Json,
+                json_path: Some("$.releases[*].version".to_string()),
+                regex: None,
+                url_pattern: None,
+                version_pattern: None,
+                date_json_path: Some("$.releases[*].date".to_string()),
+            },
+            client: HttpClient::new().expect("Failed to create HTTP client in test"),
+            request: None,
+        };
+
+        let result = discovery
+            .extract_releases_from_json(&json)
+            .expect("Failed to extract releases in test");
+        assert_eq!(
+            result,
+            vec![
+                (
+                    "1.0.0".to_string(),
+                    Some("2024-01-05T10:00:00Z".to_string())
+                ),
+                (
+                    "2.0.0".to_string(),
+                    Some("2024-03-01T08:30:00Z".to_string())
+                ),
+            ]
+        );
+
+        // Mismatched date count: dates are dropped rather than misattributed
+        discovery.config.date_json_path = Some("$.releases[0].date".to_string());
+        let result = discovery
+            .extract_releases_from_json(&json)
+            .expect("Failed to extract releases in test");
+        assert!(result.iter().all(|(_, date)| date.is_none()));
+    }
+
+    #[test]
+    fn test_json_path_index_and_filter() {
+        let json = serde_json::json!({
+            "data": [
+                {"tag_name": "v3.0.0-rc1", "draft": true, "meta": {"channel": "beta"}},
+                {"tag_name": "v2.1.0", "draft": false, "meta": {"channel": "stable"}},
+                {"tag_name": "v2.0.0", "draft": false, "meta": {"channel": "stable"}}
+            ]
+        });
+
+        let values = |path: &str| VersionDiscovery::json_values(&json, path).unwrap();
+
+        assert_eq!(values("$.data[0].tag_name"), vec!["v3.0.0-rc1"]);
+        assert_eq!(values("$.data[-1].tag_name"), vec!["v2.0.0"]);
+        assert!(values("$.data[7].tag_name").is_empty());
+        assert_eq!(
+            values("$.data[?(@.draft==false)].tag_name"),
+            vec!["v2.1.0", "v2.0.0"]
+        );
+        assert_eq!(
+            values("$.data[?(@.meta.channel != 'stable')].tag_name"),
+            vec!["v3.0.0-rc1"]
+        );
+        assert_eq!(values("$.data[*].tag_name").len(), 3);
+
+        for invalid in [
+            "$.data[?(@.draft)]",
+            "$.data[abc]",
+            "$.data[0",
+            "$.data[?(draft==true)]",
+        ] {
+            assert!(
+                VersionDiscovery::json_values(&json, invalid).is_err(),
+                "{}",
+                invalid
+            );
+        }
+    }
 }
@@ -3,11 +3,20 @@ use async_trait::async_trait;
 use serde::Deserialize;
 
 use crate::config::repo::RepoConfig;
-use crate::providers::traits::{Version, VersionProvider};
+use crate::providers::traits::{
+    filtered_asset_url, normalize_published_at, platform_download_url, ReleaseAsset, Version,
+    VersionProvider,
+};
 use crate::utils::http::HttpClient;
 use crate::utils::templating::resolve_template_safe;
 use std::collections::HashMap;
 
+/// Largest page size the GitHub API accepts
+const PAGE_SIZE: usize = 100;
+
+/// Pages of releases (or assets) fetched at most, i.e. 1000 entries
+const MAX_PAGES: usize = 10;
+
 pub struct GithubProvider {
     repo: String,
     config: RepoConfig,
@@ -18,8 +27,35 @@ pub struct GithubProvider {
 struct GithubRelease {
     tag_name: String,
     name: String,
-    published_at: String,
+    /// Null for draft releases
+    published_at: Option<String>,
     prerelease: bool,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubReleaseDetails {
+    id: u64,
+    body: Option<String>,
+    #[serde(default)]
+    assets: Vec<GithubAsset>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubAsset {
+    name: String,
+    #[serde(default)]
+    browser_download_url: String,
+}
+
+impl From<GithubAsset> for ReleaseAsset {
+    fn from(asset: GithubAsset) -> Self {
+        ReleaseAsset {
+            name: asset.name,
+            url: asset.browser_download_url,
+        }
+    }
 }
 
 impl GithubProvider {
@@ -34,6 +70,40 @@ impl GithubProvider {
     fn api_url(&self) -> String {
         format!("https://api.github.com/repos/{}/releases", self.repo)
     }
+
+    async fn fetch_release(&self, version: &str) -> Result<GithubReleaseDetails> {
+        let url = format!("{}/tags/{}", self.api_url(), urlencoding::encode(version));
+        self.client
+            .get_json(&url)
+            .await
+            .context(format!("Failed to fetch GitHub release {}", version))
+    }
+
+    /// Every asset of a release, including those past the first page
+    ///
+    /// Release objects embed at most one page of assets; only a full page is
+    /// worth following up with the paginated assets endpoint.
+    async fn fetch_release_assets(&self, version: &str) -> Result<Vec<ReleaseAsset>> {
+        let release = self.fetch_release(version).await?;
+        let assets = if release.assets.len() < PAGE_SIZE {
+            release.assets
+        } else {
+            let url = format!(
+                "{}/{}/assets?per_page={}",
+                self.api_url(),
+                release.id,
+                PAGE_SIZE
+            );
+            self.client
+                .get_json_pages(&url, MAX_PAGES)
+                .await
+                .context(format!(
+                    "Failed to fetch assets of GitHub release {}",
+                    version
+                ))?
+        };
+        Ok(assets.into_iter().map(ReleaseAsset::from).collect())
+    }
 }
 
 #[async_trait]
@@ -41,10 +111,10 @@ impl VersionProvider for GithubProvider {
     async fn list_versions(&self) -> Result<Vec<Version>> {
         log::debug!("Fetching versions from GitHub: {}", self.repo);
 
-        let url = self.api_url();
+        let url = format!("{}?per_page={}", self.api_url(), PAGE_SIZE);
         let releases: Vec<GithubRelease> = self
             .client
-            .get_json(&url)
+            .get_json_pages(&url, MAX_PAGES)
             .await
             .context("Failed to fetch GitHub releases")?;
 
@@ -53,13 +123,19 @@ impl VersionProvider for GithubProvider {
             .map(|r| Version {
                 tag: r.tag_name,
                 name: r.name,
-                published_at: r.published_at,
+                published_at: normalize_published_at(r.published_at.as_deref()),
                 prerelease: r.prerelease,
+                assets: r.assets.into_iter().map(ReleaseAsset::from).collect(),
             })
             .collect())
     }
 
     async fn get_download_url(&self, version: &str, os: &str, arch: &str) -> Result<String> {
+        if let Some(filter) = self.config.get_url_filter(os, arch) {
+            let assets = self.fetch_release_assets(version).await?;
+            return filtered_asset_url(&assets, filter, version);
+        }
+
         let mut vars = HashMap::new();
         vars.insert("version".to_string(), version.to_string());
         vars.insert("os".to_string(), os.to_string());
@@ -76,15 +152,22 @@ impl VersionProvider for GithubProvider {
             resolve_template_safe(url_template, &vars)
                 .context("Failed to resolve download URL template")
         } else if let Some(urls) = &download_config.urls {
-            let platform_key = format!("{}_{}", os, arch);
-            urls.get(&platform_key)
-                .cloned()
-                .context(format!("No download URL for platform: {}", platform_key))
+            platform_download_url(urls, os, arch)
         } else {
             anyhow::bail!("No download configuration found")
         }
     }
 
+    async fn download_url_for(&self, version: &Version, os: &str, arch: &str) -> Result<String> {
+        // A full page of embedded assets may be truncated; fetch them all instead
+        match self.config.get_url_filter(os, arch) {
+            Some(filter) if !version.assets.is_empty() && version.assets.len() < PAGE_SIZE => {
+                filtered_asset_url(&version.assets, filter, &version.tag)
+            }
+            _ => self.get_download_url(&version.tag, os, arch).await,
+        }
+    }
+
     async fn get_checksum_url(
         &self,
         version: &str,
@@ -124,4 +207,26 @@ impl VersionProvider for GithubProvider {
             Ok(None)
         }
     }
+
+    async fn get_release_notes(&self, version: &str) -> Result<Option<String>> {
+        Ok(self.fetch_release(version).await?.body)
+    }
+
+    async fn available_assets(&self, version: &str) -> Result<Vec<String>> {
+        Ok(self
+            .fetch_release_assets(version)
+            .await?
+            .into_iter()
+            .map(|asset| asset.name)
+            .collect())
+    }
+
+    /// Assets picked by `platform.url_filters` need a published release, which
+    /// the listing has; a `download.url` template may also point at bare tags
+    fn lists_all_versions(&self) -> bool {
+        self.config
+            .platform
+            .as_ref()
+            .is_some_and(|platform| !platform.url_filters.is_empty())
+    }
 }
@@ -3,14 +3,20 @@ use async_trait::async_trait;
 use serde::Deserialize;
 
 use crate::config::repo::RepoConfig;
-use crate::providers::traits::{Version, VersionProvider};
+use crate::providers::traits::{
+    filtered_asset_url, normalize_published_at, platform_download_url, ReleaseAsset, Version,
+    VersionProvider,
+};
 use crate::utils::http::HttpClient;
 use crate::utils::templating::resolve_template_safe;
 use std::collections::HashMap;
 
 pub struct GitlabProvider {
-    repo: String,
+    /// Project identifier for the API: numeric ID or URL-encoded path
+    project: String,
     instance: String,
+    /// Access token sent as PRIVATE-TOKEN (never logged)
+    token: Option<String>,
     config: RepoConfig,
     client: HttpClient,
 }
@@ -19,52 +25,175 @@ pub struct GitlabProvider {
 struct GitlabRelease {
     tag_name: String,
     name: String,
-    released_at: String,
+    released_at: Option<String>,
+    #[serde(default)]
+    assets: GitlabAssets,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitlabReleaseDetails {
+    description: Option<String>,
+    #[serde(default)]
+    assets: GitlabAssets,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct GitlabAssets {
+    #[serde(default)]
+    links: Vec<GitlabAssetLink>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitlabAssetLink {
+    name: String,
+    #[serde(default)]
+    url: String,
+}
+
+impl From<GitlabAssetLink> for ReleaseAsset {
+    fn from(link: GitlabAssetLink) -> Self {
+        ReleaseAsset {
+            name: link.name,
+            url: link.url,
+        }
+    }
 }
 
 impl GitlabProvider {
-    pub fn new(repo: String, instance: Option<String>, config: RepoConfig) -> Result<Self> {
+    pub fn new(
+        repo: Option<String>,
+        project_id: Option<u64>,
+        instance: Option<String>,
+        config: RepoConfig,
+    ) -> Result<Self> {
+        let project = match (project_id, repo) {
+            (Some(id), _) => id.to_string(),
+            (None, Some(repo)) => Self::encode_project_path(&repo)?,
+            (None, None) => {
+                anyhow::bail!("GitLab provider requires a 'repo' or 'project_id' field")
+            }
+        };
+
+        let token = match &config.source.token_env {
+            Some(var) => Self::read_token(var)?,
+            None => None,
+        };
+
         Ok(Self {
-            repo,
-            instance: instance.unwrap_or_else(|| "https://gitlab.com".to_string()),
+            project,
+            instance: instance
+                .map(|i| i.trim_end_matches('/').to_string())
+                .unwrap_or_else(|| "https://gitlab.com".to_string()),
+            token,
             config,
             client: HttpClient::new()?,
         })
     }
 
+    /// URL-encodes a `group/subgroup/project` path for the projects API
+    fn encode_project_path(repo: &str) -> Result<String> {
+        let path = repo.trim_matches('/');
+        if path.is_empty()
+            || path
+                .split('/')
+                .any(|segment| segment.is_empty() || segment == "..")
+        {
+            anyhow::bail!("Invalid GitLab project path: '{}'", repo);
+        }
+        Ok(urlencoding::encode(path).into_owned())
+    }
+
+    /// Reads the access token from the environment variable named in `token_env`
+    ///
+    /// Only variables named like `*GITLAB*TOKEN` are accepted so a .repo file
+    /// can't exfiltrate unrelated secrets from the environment.
+    fn read_token(var: &str) -> Result<Option<String>> {
+        if !var.contains("GITLAB") || !var.ends_with("TOKEN") {
+            anyhow::bail!(
+                "token_env '{}' is not allowed: the variable name must contain 'GITLAB' and end with 'TOKEN'",
+                var
+            );
+        }
+
+        match std::env::var(var) {
+            Ok(token) if !token.is_empty() => Ok(Some(token)),
+            _ => {
+                log::warn!(
+                    "GitLab token variable {} is not set, using unauthenticated requests",
+                    var
+                );
+                Ok(None)
+            }
+        }
+    }
+
     fn api_url(&self) -> String {
-        let encoded_repo = urlencoding::encode(&self.repo);
         format!(
             "{}/api/v4/projects/{}/releases",
-            self.instance, encoded_repo
+            self.instance, self.project
         )
     }
+
+    async fn fetch_release(&self, version: &str) -> Result<GitlabReleaseDetails> {
+        let url = format!("{}/{}", self.api_url(), urlencoding::encode(version));
+        match &self.token {
+            Some(token) => {
+                self.client
+                    .get_json_with_secret_headers(&url, &[("PRIVATE-TOKEN", token.as_str())])
+                    .await
+            }
+            None => self.client.get_json(&url).await,
+        }
+        .context(format!("Failed to fetch GitLab release {}", version))
+    }
 }
 
 #[async_trait]
 impl VersionProvider for GitlabProvider {
     async fn list_versions(&self) -> Result<Vec<Version>> {
-        log::debug!("Fetching versions from GitLab: {}", self.repo);
+        log::debug!(
+            "Fetching versions from GitLab: {} (project {})",
+            self.instance,
+            self.project
+        );
 
         let url = self.api_url();
-        let releases: Vec<GitlabRelease> = self
-            .client
-            .get_json(&url)
-            .await
-            .context("Failed to fetch GitLab releases")?;
+        let releases: Vec<GitlabRelease> = match &self.token {
+            Some(token) => {
+                log::debug!("Using GitLab access token (redacted)");
+                self.client
+                    .get_json_with_secret_headers(&url, &[("PRIVATE-TOKEN", token.as_str())])
+                    .await
+            }
+            None => self.client.get_json(&url).await,
+        }
+        .context("Failed to fetch GitLab releases")?;
 
         Ok(releases
             .into_iter()
             .map(|r| Version {
                 tag: r.tag_name,
                 name: r.name,
-                published_at: r.released_at,
+                published_at: normalize_published_at(r.released_at.as_deref()),
                 prerelease: false,
+                assets: r.assets.links.into_iter().map(ReleaseAsset::from).collect(),
             })
             .collect())
     }
 
     async fn get_download_url(&self, version: &str, os: &str, arch: &str) -> Result<String> {
+        if let Some(filter) = self.config.get_url_filter(os, arch) {
+            let assets: Vec<ReleaseAsset> = self
+                .fetch_release(version)
+                .await?
+                .assets
+                .links
+                .into_iter()
+                .map(ReleaseAsset::from)
+                .collect();
+            return filtered_asset_url(&assets, filter, version);
+        }
+
         let mut vars = HashMap::new();
         vars.insert("version".to_string(), version.to_string());
         vars.insert("os".to_string(), os.to_string());
@@ -80,15 +209,21 @@ impl VersionProvider for GitlabProvider {
         if let Some(url_template) = &download_config.url {
             Ok(resolve_template_safe(url_template, &vars)?)
         } else if let Some(urls) = &download_config.urls {
-            let platform_key = format!("{}_{}", os, arch);
-            urls.get(&platform_key)
-                .cloned()
-                .context(format!("No download URL for platform: {}", platform_key))
+            platform_download_url(urls, os, arch)
         } else {
             anyhow::bail!("No download configuration found")
         }
     }
 
+    async fn download_url_for(&self, version: &Version, os: &str, arch: &str) -> Result<String> {
+        match self.config.get_url_filter(os, arch) {
+            Some(filter) if !version.assets.is_empty() => {
+                filtered_asset_url(&version.assets, filter, &version.tag)
+            }
+            _ => self.get_download_url(&version.tag, os, arch).await,
+        }
+    }
+
     async fn get_checksum_url(
         &self,
         version: &str,
@@ -127,4 +262,47 @@ impl VersionProvider for GitlabProvider {
             Ok(None)
         }
     }
+
+    async fn get_release_notes(&self, version: &str) -> Result<Option<String>> {
+        Ok(self.fetch_release(version).await?.description)
+    }
+
+    async fn available_assets(&self, version: &str) -> Result<Vec<String>> {
+        Ok(self
+            .fetch_release(version)
+            .await?
+            .assets
+            .links
+            .into_iter()
+            .map(|link| link.name)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_project_path() {
+        assert_eq!(
+            GitlabProvider::encode_project_path("group/subgroup/project").unwrap(),
+            "group%2Fsubgroup%2Fproject"
+        );
+        assert_eq!(
+            GitlabProvider::encode_project_path("/group/project/").unwrap(),
+            "group%2Fproject"
+        );
+        assert!(GitlabProvider::encode_project_path("group//project").is_err());
+        assert!(GitlabProvider::encode_project_path("").is_err());
+    }
+
+    #[test]
+    fn test_read_token_rejects_unrelated_variables() {
+        assert!(GitlabProvider::read_token("AWS_SECRET_ACCESS_KEY").is_err());
+        assert!(GitlabProvider::read_token("HOME").is_err());
+        assert!(GitlabProvider::read_token("ORA_TEST_UNSET_GITLAB_TOKEN")
+            .unwrap()
+            .is_none());
+    }
 }
@@ -6,12 +6,15 @@ pub mod gitlab;
 pub mod traits;
 pub mod webpage_scraping;
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use std::sync::Arc;
 
-pub use traits::VersionProvider;
+pub use traits::{is_newer_version, is_prerelease_tag, latest_version, Version, VersionProvider};
 
 use crate::config::repo::{ProviderType, RepoConfig};
+use crate::error::OraError;
+use crate::storage::cache::Cache;
+use crate::utils::http::is_offline_mode;
 use custom_api::CustomApiProvider;
 use direct_url::DirectUrlProvider;
 use github::GithubProvider;
@@ -28,18 +31,12 @@ pub fn create_provider(config: &RepoConfig) -> Result<Arc<dyn VersionProvider>>
                 .ok_or_else(|| anyhow::anyhow!("GitHub provider requires 'repo' field"))?;
             Ok(Arc::new(GithubProvider::new(repo, config.clone())?))
         }
-        ProviderType::GitlabReleases => {
-            let repo = config
-                .source
-                .repo
-                .clone()
-                .ok_or_else(|| anyhow::anyhow!("GitLab provider requires 'repo' field"))?;
-            Ok(Arc::new(GitlabProvider::new(
-                repo,
-                config.source.instance.clone(),
-                config.clone(),
-            )?))
-        }
+        ProviderType::GitlabReleases => Ok(Arc::new(GitlabProvider::new(
+            config.source.repo.clone(),
+            config.source.project_id,
+            config.source.instance.clone(),
+            config.clone(),
+        )?)),
         ProviderType::CustomApi => {
             let api_url =
                 config.source.api_url.clone().ok_or_else(|| {
@@ -53,3 +50,141 @@ pub fn create_provider(config: &RepoConfig) -> Result<Arc<dyn VersionProvider>>
         }
     }
 }
+
+/// List versions from a provider and cache the result on disk
+///
+/// In offline mode the provider is not queried; the last cached list is returned instead.
+pub async fn list_versions_cached(
+    provider: &dyn VersionProvider,
+    package_name: &str,
+) -> Result<Vec<Version>> {
+    let cache_path = Cache::versions_path(package_name)?;
+
+    if is_offline_mode() {
+        let content = std::fs::read_to_string(&cache_path).context(format!(
+            "Offline mode: no cached version list for '{}'. \
+             Run once while online or pass --version explicitly.",
+            package_name
+        ))?;
+        log::info!(
+            "Offline mode: using cached version list for '{}'",
+            package_name
+        );
+        return serde_json::from_str(&content).context("Failed to parse cached version list");
+    }
+
+    let versions = provider.list_versions().await?;
+
+    match serde_json::to_string(&versions) {
+        Ok(content) => {
+            if let Err(e) = std::fs::write(&cache_path, content) {
+                log::debug!("Failed to cache version list for '{}': {}", package_name, e);
+            }
+        }
+        Err(e) => log::debug!("Failed to serialize version list: {}", e),
+    }
+
+    Ok(versions)
+}
+
+/// Decides which version to install, enforcing the prerelease policy
+///
+/// Without `requested`, picks the latest acceptable version from a single listing
+/// and returns it whole (tag and assets), so URL resolution uses that exact
+/// release even if a newer one is published meanwhile. An explicitly requested
+/// alpha/beta/rc version is refused unless `allow_prerelease` is set.
+pub async fn resolve_version(
+    provider: &dyn VersionProvider,
+    package_name: &str,
+    requested: Option<&str>,
+    allow_prerelease: bool,
+) -> Result<Version> {
+    if let Some(requested) = requested {
+        if allow_prerelease || !is_prerelease_tag(requested) {
+            if provider.lists_all_versions() {
+                let listed = list_versions_cached(provider, package_name).await.ok();
+                if let Some(versions) = listed {
+                    ensure_listed(&versions, package_name, requested)?;
+                }
+            }
+            return Ok(Version::from_tag(requested));
+        }
+
+        let stable = list_versions_cached(provider, package_name)
+            .await
+            .ok()
+            .and_then(|versions| latest_version(&versions, false).map(|v| v.tag.clone()));
+        let hint = match stable {
+            Some(tag) => format!("Latest stable version: {}", tag),
+            None => "No stable version is available".to_string(),
+        };
+        anyhow::bail!(
+            "Version '{}' of '{}' is a prerelease.\n\
+             {}\n\
+             Pass --allow-prerelease (or set install.allow_prerelease = true) to install it anyway.",
+            requested,
+            package_name,
+            hint
+        );
+    }
+
+    let versions = list_versions_cached(provider, package_name).await?;
+    if let Some(latest) = latest_version(&versions, allow_prerelease) {
+        return Ok(latest.clone());
+    }
+
+    if versions.is_empty() {
+        return Err(version_not_found(package_name, "latest").context("No versions available"));
+    }
+    anyhow::bail!(
+        "Only prerelease versions of '{}' are available (latest: {}).\n\
+         Pass --allow-prerelease to install one.",
+        package_name,
+        latest_version(&versions, true).map_or("unknown", |v| v.tag.as_str())
+    )
+}
+
+/// Fails with `VersionNotFound` unless `requested` is in a complete listing
+///
+/// Tags match with or without a leading `v`. An empty listing proves nothing.
+fn ensure_listed(versions: &[Version], package_name: &str, requested: &str) -> Result<()> {
+    let same_tag = |tag: &str| tag.trim_start_matches('v') == requested.trim_start_matches('v');
+    if versions.is_empty() || versions.iter().any(|v| same_tag(&v.tag)) {
+        return Ok(());
+    }
+    Err(version_not_found(package_name, requested).context(format!(
+        "Version '{}' of '{}' does not exist (latest: {})",
+        requested,
+        package_name,
+        latest_version(versions, true).map_or("unknown", |v| v.tag.as_str())
+    )))
+}
+
+fn version_not_found(package_name: &str, version: &str) -> anyhow::Error {
+    OraError::VersionNotFound {
+        package: package_name.to_string(),
+        version: version.to_string(),
+    }
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{exit_code, exit_code_for};
+
+    #[test]
+    fn test_ensure_listed() {
+        let versions = vec![Version::from_tag("v1.0.0"), Version::from_tag("v1.1.0")];
+        assert!(ensure_listed(&versions, "tool", "1.0.0").is_ok());
+        assert!(ensure_listed(&versions, "tool", "v1.1.0").is_ok());
+        assert!(ensure_listed(&[], "tool", "v9.9.9").is_ok());
+
+        let err = ensure_listed(&versions, "tool", "v9.9.9").unwrap_err();
+        assert_eq!(exit_code_for(&err), exit_code::NOT_FOUND);
+        assert_eq!(
+            err.to_string(),
+            "Version 'v9.9.9' of 'tool' does not exist (latest: v1.1.0)"
+        );
+    }
+}
@@ -1,20 +1,250 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-#[derive(Debug, Clone)]
+use crate::error::OraError;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Version {
     pub tag: String,
     #[allow(dead_code)]
     pub name: String,
-    #[allow(dead_code)]
+    /// Release date as RFC 3339 (ISO-8601) in UTC, empty when unknown
     pub published_at: String,
     pub prerelease: bool,
+    /// Downloads published with the release, when the listing includes them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+/// A downloadable file attached to a release
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub url: String,
+}
+
+impl ReleaseAsset {
+    /// Asset named after the last segment of `url`
+    pub fn from_url(url: &str) -> Self {
+        Self {
+            name: url.rsplit('/').next().unwrap_or_default().to_string(),
+            url: url.to_string(),
+        }
+    }
+}
+
+/// Picks the release asset matching a `platform.url_filters` entry
+///
+/// Assets whose name or URL contains `filter` are candidates; archives are
+/// preferred over other files (e.g. a `.tar.gz` over a `.deb` with the same
+/// platform string), and checksums, signatures and system packages come last.
+pub fn select_release_asset<'a>(
+    assets: &'a [ReleaseAsset],
+    filter: &str,
+) -> Option<&'a ReleaseAsset> {
+    const ARCHIVES: &[&str] = &[
+        ".tar.gz", ".tgz", ".tar.xz", ".txz", ".tar.zst", ".tzst", ".tar.bz2", ".zip", ".tar",
+    ];
+    const LAST: &[&str] = &[
+        ".sha256",
+        ".sha512",
+        ".sha256sum",
+        ".md5",
+        ".asc",
+        ".sig",
+        ".sbom",
+        ".deb",
+        ".rpm",
+        ".apk",
+        ".msi",
+        ".pkg",
+        ".dmg",
+    ];
+
+    let rank = |asset: &ReleaseAsset| {
+        let name = asset.name.to_lowercase();
+        if ARCHIVES.iter().any(|ext| name.ends_with(ext)) {
+            0
+        } else if LAST.iter().any(|ext| name.ends_with(ext)) || name.contains("checksums") {
+            2
+        } else {
+            1
+        }
+    };
+
+    assets
+        .iter()
+        .filter(|asset| asset.name.contains(filter) || asset.url.contains(filter))
+        .min_by_key(|asset| rank(asset))
+}
+
+/// URL of the asset of `version` selected by a `platform.url_filters` entry
+pub fn filtered_asset_url(assets: &[ReleaseAsset], filter: &str, version: &str) -> Result<String> {
+    select_release_asset(assets, filter)
+        .map(|asset| asset.url.clone())
+        .with_context(|| {
+            format!(
+                "No release asset of {} matches url filter '{}' (assets: {})",
+                version,
+                filter,
+                assets
+                    .iter()
+                    .map(|asset| asset.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })
+}
+
+/// URL declared for `<os>_<arch>` in `download.urls`, or an error listing the
+/// platforms the table does declare
+pub fn platform_download_url(
+    urls: &HashMap<String, String>,
+    os: &str,
+    arch: &str,
+) -> Result<String> {
+    let platform_key = format!("{}_{}", os, arch);
+    urls.get(&platform_key).cloned().ok_or_else(|| {
+        platform_not_supported(os, arch).context(format!(
+            "No download URL for platform: {} (download.urls has: {})",
+            platform_key,
+            platform_keys(urls)
+        ))
+    })
+}
+
+/// Error for a platform the `.repo` file declares nothing for
+pub fn platform_not_supported(os: &str, arch: &str) -> anyhow::Error {
+    OraError::PlatformNotSupported {
+        os: os.to_string(),
+        arch: arch.to_string(),
+    }
+    .into()
+}
+
+/// Sorted keys of a per-platform table of the `.repo` file, for error messages
+pub fn platform_keys(entries: &HashMap<String, String>) -> String {
+    let mut keys: Vec<&str> = entries.keys().map(String::as_str).collect();
+    if keys.is_empty() {
+        return "no entries".to_string();
+    }
+    keys.sort_unstable();
+    keys.join(", ")
+}
+
+impl Version {
+    /// A version known only by its tag (e.g. requested with `--version`)
+    pub fn from_tag(tag: &str) -> Self {
+        Self {
+            tag: tag.to_string(),
+            name: tag.to_string(),
+            published_at: String::new(),
+            prerelease: is_prerelease_tag(tag),
+            assets: Vec::new(),
+        }
+    }
+
+    /// Whether this is a prerelease, per the provider or the tag itself
+    pub fn is_prerelease(&self) -> bool {
+        self.prerelease || is_prerelease_tag(&self.tag)
+    }
+
+    /// Release date, if the provider reported one
+    pub fn published_date(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.published_at)
+            .ok()
+            .map(|date| date.with_timezone(&Utc))
+    }
+}
+
+/// Detects alpha/beta/rc tags (e.g. `v2.0.0-beta.1`, `1.4rc2`)
+///
+/// The marker has to be a whole identifier between separators (`-`, `.`, `_`,
+/// `+`), optionally right after the version digits and followed by a number,
+/// so words like `source` or `arch` don't count.
+pub fn is_prerelease_tag(tag: &str) -> bool {
+    tag.to_lowercase()
+        .split(['-', '.', '_', '+'])
+        .map(|part| part.trim_start_matches(|c: char| c.is_ascii_digit()))
+        .any(|part| {
+            ["alpha", "beta", "rc"].iter().any(|marker| {
+                part.strip_prefix(marker)
+                    .is_some_and(|number| number.chars().all(|c| c.is_ascii_digit()))
+            })
+        })
+}
+
+/// Latest version by semver (string comparison as fallback), skipping
+/// prereleases unless `allow_prerelease` is set
+pub fn latest_version(versions: &[Version], allow_prerelease: bool) -> Option<&Version> {
+    versions
+        .iter()
+        .filter(|v| allow_prerelease || !v.is_prerelease())
+        .max_by(|a, b| {
+            match (
+                semver::Version::parse(a.tag.trim_start_matches('v')),
+                semver::Version::parse(b.tag.trim_start_matches('v')),
+            ) {
+                (Ok(v_a), Ok(v_b)) => v_a.cmp(&v_b),
+                _ => a.tag.cmp(&b.tag),
+            }
+        })
+}
+
+/// Whether `candidate` is a newer release than `installed`
+///
+/// Compared by semver ignoring a leading `v`; tags that aren't semver only
+/// count as newer when they differ.
+pub fn is_newer_version(candidate: &str, installed: &str) -> bool {
+    let parse = |v: &str| semver::Version::parse(v.trim().trim_start_matches('v')).ok();
+    match (parse(candidate), parse(installed)) {
+        (Some(candidate), Some(installed)) => candidate > installed,
+        _ => candidate.trim_start_matches('v') != installed.trim_start_matches('v'),
+    }
+}
+
+/// Normalizes a release timestamp from provider metadata to RFC 3339 in UTC
+///
+/// Accepts RFC 3339 timestamps (any offset) and plain `YYYY-MM-DD` dates.
+/// Returns an empty string when the value is missing or unrecognized.
+pub fn normalize_published_at(raw: Option<&str>) -> String {
+    let Some(raw) = raw.map(str::trim).filter(|s| !s.is_empty()) else {
+        return String::new();
+    };
+
+    let parsed = DateTime::parse_from_rfc3339(raw)
+        .map(|date| date.with_timezone(&Utc))
+        .ok()
+        .or_else(|| {
+            NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|date| date.and_utc())
+        });
+
+    match parsed {
+        Some(date) => date.to_rfc3339_opts(SecondsFormat::Secs, true),
+        None => {
+            log::debug!("Ignoring unrecognized release date: {}", raw);
+            String::new()
+        }
+    }
 }
 
 #[async_trait]
 pub trait VersionProvider: Send + Sync {
     async fn list_versions(&self) -> Result<Vec<Version>>;
     async fn get_download_url(&self, version: &str, os: &str, arch: &str) -> Result<String>;
+    /// Download URL for a version picked from `list_versions`
+    ///
+    /// Providers whose listing carries asset URLs pick from those, so the URL
+    /// matches the release that was selected even if a newer one appeared since.
+    async fn download_url_for(&self, version: &Version, os: &str, arch: &str) -> Result<String> {
+        self.get_download_url(&version.tag, os, arch).await
+    }
     /// Reserved for future use when checksum URL fetching is implemented.
     #[allow(dead_code)]
     async fn get_checksum_url(&self, version: &str, os: &str, arch: &str)
@@ -27,4 +257,198 @@ pub trait VersionProvider: Send + Sync {
         os: &str,
         arch: &str,
     ) -> Result<Option<String>>;
+    /// Release description for `version`, for providers that publish one
+    async fn get_release_notes(&self, _version: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+    /// File names published for `version`, to explain failed platform matches
+    async fn available_assets(&self, _version: &str) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+    /// Whether `list_versions` covers every installable version, so a
+    /// requested version missing from it doesn't exist
+    fn lists_all_versions(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_platform_download_url_lists_declared_platforms() {
+        let urls = HashMap::from([
+            (
+                "linux_x86_64".to_string(),
+                "https://example.com/a".to_string(),
+            ),
+            (
+                "darwin_arm64".to_string(),
+                "https://example.com/b".to_string(),
+            ),
+        ]);
+        assert_eq!(
+            platform_download_url(&urls, "linux", "x86_64").unwrap(),
+            "https://example.com/a"
+        );
+        let error = platform_download_url(&urls, "linux", "aarch64").unwrap_err();
+        assert_eq!(
+            crate::error::exit_code_for(&error),
+            crate::error::exit_code::PLATFORM_UNSUPPORTED
+        );
+        assert_eq!(
+            error.to_string(),
+            "No download URL for platform: linux_aarch64 \
+             (download.urls has: darwin_arm64, linux_x86_64)"
+        );
+        assert_eq!(platform_keys(&HashMap::new()), "no entries");
+    }
+
+    #[test]
+    fn test_normalize_published_at() {
+        assert_eq!(
+            normalize_published_at(Some("2024-01-05T10:00:00Z")),
+            "2024-01-05T10:00:00Z"
+        );
+        // GitLab style: fractional seconds
+        assert_eq!(
+            normalize_published_at(Some("2019-01-03T01:56:19.539Z")),
+            "2019-01-03T01:56:19Z"
+        );
+        // Offsets are converted to UTC
+        assert_eq!(
+            normalize_published_at(Some("2024-01-05T12:00:00+02:00")),
+            "2024-01-05T10:00:00Z"
+        );
+        assert_eq!(
+            normalize_published_at(Some("2024-01-05")),
+            "2024-01-05T00:00:00Z"
+        );
+        assert_eq!(normalize_published_at(Some("last tuesday")), "");
+        assert_eq!(normalize_published_at(Some("")), "");
+        assert_eq!(normalize_published_at(None), "");
+    }
+
+    fn version(tag: &str, prerelease: bool) -> Version {
+        Version {
+            tag: tag.to_string(),
+            name: tag.to_string(),
+            published_at: String::new(),
+            prerelease,
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_select_release_asset() {
+        let assets: Vec<ReleaseAsset> = [
+            "tool_1.0_linux_amd64.deb",
+            "tool_1.0_linux_amd64.tar.gz.sha256",
+            "tool_1.0_linux_amd64.tar.gz",
+            "tool_1.0_linux_arm64.tar.gz",
+            "tool_1.0_linux_amd64_musl",
+        ]
+        .iter()
+        .map(|name| ReleaseAsset {
+            name: name.to_string(),
+            url: format!("https://example.com/{}", name),
+        })
+        .collect();
+
+        assert_eq!(
+            select_release_asset(&assets, "linux_amd64").unwrap().name,
+            "tool_1.0_linux_amd64.tar.gz"
+        );
+        assert_eq!(
+            select_release_asset(&assets, "linux_amd64_musl")
+                .unwrap()
+                .name,
+            "tool_1.0_linux_amd64_musl"
+        );
+        assert_eq!(
+            select_release_asset(&assets[..2], "linux_amd64")
+                .unwrap()
+                .name,
+            "tool_1.0_linux_amd64.deb"
+        );
+        assert!(select_release_asset(&assets, "darwin").is_none());
+    }
+
+    #[test]
+    fn test_is_prerelease() {
+        assert!(is_prerelease_tag("v2.0.0-beta.1"));
+        assert!(is_prerelease_tag("1.4RC2"));
+        assert!(is_prerelease_tag("3.0.0-alpha"));
+        assert!(is_prerelease_tag("v1.0.0_rc1"));
+        assert!(!is_prerelease_tag("v1.2.3"));
+
+        // Markers inside other words
+        assert!(!is_prerelease_tag("v1.2.3-arch"));
+        assert!(!is_prerelease_tag("source-1.2.3"));
+        assert!(!is_prerelease_tag("march-2024.1"));
+        assert!(!is_prerelease_tag("v1.0-rcfix"));
+
+        // Provider flag wins even when the tag looks stable
+        assert!(version("v1.2.3", true).is_prerelease());
+        assert!(version("v1.3.0-rc.1", false).is_prerelease());
+    }
+
+    #[test]
+    fn test_latest_version() {
+        let versions = vec![
+            version("v1.9.0", false),
+            version("v1.10.0", false),
+            version("v2.0.0-rc.1", false),
+            version("v1.11.0", true),
+        ];
+
+        assert_eq!(latest_version(&versions, false).unwrap().tag, "v1.10.0");
+        assert_eq!(latest_version(&versions, true).unwrap().tag, "v2.0.0-rc.1");
+        assert!(latest_version(&versions[2..], false).is_none());
+    }
+
+    #[test]
+    fn test_is_newer_version() {
+        assert!(is_newer_version("v1.10.0", "1.9.0"));
+        assert!(!is_newer_version("1.9.0", "v1.9.0"));
+        assert!(!is_newer_version("v1.8.0", "1.9.0"));
+        assert!(is_newer_version("2024-02", "2024-01"));
+        assert!(!is_newer_version("nightly", "nightly"));
+    }
+
+    #[test]
+    fn test_published_date() {
+        let mut version = Version {
+            tag: "v1.0.0".to_string(),
+            name: "v1.0.0".to_string(),
+            published_at: "2024-01-05T10:00:00Z".to_string(),
+            prerelease: false,
+            assets: Vec::new(),
+        };
+        assert_eq!(
+            version.published_date().map(|d| d.to_rfc3339()),
+            Some("2024-01-05T10:00:00+00:00".to_string())
+        );
+
+        version.published_at.clear();
+        assert!(version.published_date().is_none());
+    }
+
+    #[test]
+    fn test_version_assets() {
+        // Version lists cached before assets were recorded still load
+        let cached: Vec<Version> = serde_json::from_str(
+            r#"[{"tag":"v1.0.0","name":"v1.0.0","published_at":"","prerelease":false}]"#,
+        )
+        .unwrap();
+        assert!(cached[0].assets.is_empty());
+
+        let asset = ReleaseAsset::from_url("https://example.com/v1.0.0/tool-linux-x86_64.tar.gz");
+        assert_eq!(asset.name, "tool-linux-x86_64.tar.gz");
+
+        let pinned = Version::from_tag("v2.0.0-rc.1");
+        assert!(pinned.is_prerelease());
+        assert!(pinned.assets.is_empty());
+    }
 }
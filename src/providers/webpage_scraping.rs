@@ -5,9 +5,12 @@ use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::config::repo::RepoConfig;
-use crate::providers::traits::{Version, VersionProvider};
+use crate::providers::traits::{
+    is_prerelease_tag, platform_keys, platform_not_supported, ReleaseAsset, Version,
+    VersionProvider,
+};
 use crate::storage::paths::Paths;
-use crate::utils::http::HttpClient;
+use crate::utils::http::{is_offline_mode, HttpClient};
 
 /// Cached URL data for webpage scraping
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -82,7 +85,11 @@ impl WebpageScrapingProvider {
         let content = std::fs::read_to_string(&cache_path)?;
         let cache: UrlCache = serde_json::from_str(&content)?;
 
-        if self.is_cache_valid(&cache).await? {
+        if is_offline_mode() {
+            // Offline mode: any cached data is better than no data
+            log::info!("Offline mode: using cache from: {:?}", cache_path);
+            self.cache = Some(cache);
+        } else if self.is_cache_valid(&cache).await? {
             log::info!("Using valid cache from: {:?}", cache_path);
             self.cache = Some(cache);
         } else {
@@ -229,6 +236,43 @@ impl WebpageScrapingProvider {
         "unknown".to_string()
     }
 
+    /// URL filter configured for a platform in `platform.url_filters`
+    fn url_filter(&self, os: &str, arch: &str) -> Result<&str> {
+        let Some(platform) = &self.config.platform else {
+            anyhow::bail!("platform config with url_filters required for webpage-scraping");
+        };
+        self.config.get_url_filter(os, arch).ok_or_else(|| {
+            platform_not_supported(os, arch).context(format!(
+                "No URL filter found for platform: {}_{} (platform.url_filters has: {})",
+                os,
+                arch,
+                platform_keys(&platform.url_filters)
+            ))
+        })
+    }
+
+    /// Error for a version with no scraped URL matching `url_filter`, listing
+    /// the platforms that were scraped for it (or the scraped versions)
+    fn no_matching_url(urls: &[&str], version: &str, url_filter: &str) -> anyhow::Error {
+        let mut platforms: Vec<String> = urls
+            .iter()
+            .map(|url| Self::extract_platform_from_url(url))
+            .collect();
+        platforms.sort();
+        platforms.dedup();
+        let scraped = if platforms.is_empty() {
+            "no URLs for this version".to_string()
+        } else {
+            platforms.join(", ")
+        };
+        anyhow::anyhow!(
+            "No URL found for version={}, platform={} (scraped platforms: {})",
+            version,
+            url_filter,
+            scraped
+        )
+    }
+
     /// Get or scrape URLs
     async fn get_urls(&mut self) -> Result<Vec<CachedUrl>> {
         // Try to load cache first
@@ -295,10 +339,15 @@ impl VersionProvider for WebpageScrapingProvider {
         let version_list: Vec<Version> = versions
             .into_iter()
             .map(|tag| Version {
-                tag: tag.clone(),
+                assets: urls
+                    .iter()
+                    .filter(|u| u.version == tag)
+                    .map(|u| ReleaseAsset::from_url(&u.url))
+                    .collect(),
                 name: tag.clone(),
                 published_at: String::new(),
-                prerelease: tag.contains("alpha") || tag.contains("beta") || tag.contains("rc"),
+                prerelease: is_prerelease_tag(&tag),
+                tag,
             })
             .collect();
 
@@ -309,26 +358,9 @@ impl VersionProvider for WebpageScrapingProvider {
         // Need mutable self to load cache/scrape
         let mut provider = Self::new(self.config.clone())?;
         let urls = provider.get_urls().await?;
-
-        // Get platform filters from config
-        let platform_config = self
-            .config
-            .platform
-            .as_ref()
-            .context("platform config with url_filters required for webpage-scraping")?;
-
-        // Build platform key: e.g., "linux_x86_64"
+        let url_filter = self.url_filter(os, arch)?;
         let platform_key = format!("{}_{}", os, arch);
 
-        // Get the URL filter for this platform
-        let url_filter = platform_config
-            .url_filters
-            .get(&platform_key)
-            .context(format!(
-                "No URL filter found for platform: {}",
-                platform_key
-            ))?;
-
         log::debug!(
             "Looking for version={}, platform={}, filter={}",
             version,
@@ -337,15 +369,50 @@ impl VersionProvider for WebpageScrapingProvider {
         );
 
         // Find matching URL
-        let matching_url = urls
+        let version_urls: Vec<&CachedUrl> = urls.iter().filter(|u| u.version == version).collect();
+        match version_urls
             .iter()
-            .find(|u| u.version == version && u.platform.contains(url_filter))
-            .context(format!(
-                "No URL found for version={}, platform={}",
-                version, url_filter
-            ))?;
+            .find(|u| u.platform.contains(url_filter))
+        {
+            Some(matching_url) => Ok(matching_url.url.clone()),
+            None => {
+                let scraped: Vec<&str> = version_urls.iter().map(|u| u.url.as_str()).collect();
+                Err(Self::no_matching_url(&scraped, version, url_filter))
+            }
+        }
+    }
+
+    async fn download_url_for(&self, version: &Version, os: &str, arch: &str) -> Result<String> {
+        if version.assets.is_empty() {
+            return self.get_download_url(&version.tag, os, arch).await;
+        }
 
-        Ok(matching_url.url.clone())
+        // Pick from the URLs scraped with the version, without scraping again
+        let url_filter = self.url_filter(os, arch)?;
+        version
+            .assets
+            .iter()
+            .find(|asset| Self::extract_platform_from_url(&asset.url).contains(url_filter))
+            .map(|asset| asset.url.clone())
+            .ok_or_else(|| {
+                let scraped: Vec<&str> = version
+                    .assets
+                    .iter()
+                    .map(|asset| asset.url.as_str())
+                    .collect();
+                Self::no_matching_url(&scraped, &version.tag, url_filter)
+            })
+    }
+
+    async fn available_assets(&self, version: &str) -> Result<Vec<String>> {
+        let mut provider = Self::new(self.config.clone())?;
+        Ok(provider
+            .get_urls()
+            .await?
+            .into_iter()
+            .filter(|u| u.version == version)
+            .map(|u| u.url.rsplit('/').next().unwrap_or_default().to_string())
+            .collect())
     }
 
     async fn get_checksum_url(
@@ -368,3 +435,60 @@ impl VersionProvider for WebpageScrapingProvider {
         Ok(None)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn provider(url_filters: &str) -> WebpageScrapingProvider {
+        let config: RepoConfig = toml::from_str(&format!(
+            r#"
+name = "tool"
+description = "Tool"
+
+[source]
+type = "webpage-scraping"
+
+[install]
+binaries = ["tool"]
+
+[security]
+
+[platform.url_filters]
+{}
+"#,
+            url_filters
+        ))
+        .unwrap();
+        WebpageScrapingProvider::new(config).unwrap()
+    }
+
+    #[test]
+    fn test_missing_url_filter_lists_configured_platforms() {
+        let provider = provider("linux_amd64 = \"linux-x64\"\ndarwin_arm64 = \"darwin-arm64\"");
+        assert_eq!(provider.url_filter("linux", "amd64").unwrap(), "linux-x64");
+        let error = provider
+            .url_filter("linux", "x86_64")
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            error,
+            "No URL filter found for platform: linux_x86_64 \
+             (platform.url_filters has: darwin_arm64, linux_amd64)"
+        );
+    }
+
+    #[test]
+    fn test_no_matching_url_lists_scraped_platforms() {
+        let urls = [
+            "https://example.com/tool-1.0.0-darwin-arm64.zip",
+            "https://example.com/tool-1.0.0-linux-x64.tar.gz",
+        ];
+        let error = WebpageScrapingProvider::no_matching_url(&urls, "1.0.0", "linux-arm64");
+        assert_eq!(
+            error.to_string(),
+            "No URL found for version=1.0.0, platform=linux-arm64 \
+             (scraped platforms: darwin-arm64, linux-x64)"
+        );
+    }
+}
@@ -1,12 +1,122 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use crate::storage::cache::Cache;
 
 pub struct RegistryIndex;
 
+/// Prebuilt package index published by a registry at its `index_url`
+///
+/// Lets `ora registry sync --index-only` skip cloning the registry: the
+/// `.repo` file of a package is fetched from its entry's `url` when needed and
+/// checked against the entry's `sha256`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct IndexArtifact {
+    #[serde(default)]
+    pub packages: Vec<IndexEntry>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct IndexEntry {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// URL of the package's .repo file, absolute or relative to the index
+    pub url: String,
+    /// SHA-256 of the .repo file (hex, optionally prefixed with "sha256:")
+    pub sha256: String,
+}
+
+impl IndexArtifact {
+    pub fn parse(content: &str) -> Result<Self> {
+        let index: Self =
+            serde_json::from_str(content).context("Failed to parse registry index")?;
+        for entry in &index.packages {
+            if entry.name.is_empty() || entry.name.contains('/') || entry.name.contains("..") {
+                anyhow::bail!("Invalid package name '{}' in registry index", entry.name);
+            }
+        }
+        Ok(index)
+    }
+
+    /// Reads the index saved by the last index-only sync of a registry, if any
+    pub fn load(registry_name: &str) -> Result<Option<Self>> {
+        let path = Cache::registry_index_path(registry_name)?;
+        match std::fs::read_to_string(&path) {
+            Ok(content) => Self::parse(&content)
+                .with_context(|| format!("Invalid registry index {}", path.display()))
+                .map(Some),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self, registry_name: &str) -> Result<()> {
+        let path = Cache::registry_index_path(registry_name)?;
+        let content = serde_json::to_string(self).context("Failed to serialize registry index")?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Drops the saved index of a registry, so lookups no longer use it
+    pub fn remove(registry_name: &str) -> Result<()> {
+        let path = Cache::registry_index_path(registry_name)?;
+        match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => {
+                Err(e).with_context(|| format!("Failed to remove {}", path.display()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn get(&self, package_name: &str) -> Option<&IndexEntry> {
+        self.packages
+            .iter()
+            .find(|entry| entry.name == package_name)
+    }
+
+    /// Maps each package to its .repo checksum, to count changes between syncs
+    pub fn checksums(&self) -> HashMap<String, String> {
+        self.packages
+            .iter()
+            .map(|entry| (entry.name.clone(), entry.sha256.to_lowercase()))
+            .collect()
+    }
+}
+
+impl IndexEntry {
+    /// Absolute URL of the entry's .repo file
+    pub fn repo_url(&self, index_url: &str) -> Result<String> {
+        let base = url::Url::parse(index_url)
+            .context(format!("Invalid registry index URL: {}", index_url))?;
+        let url = base.join(&self.url).context(format!(
+            "Invalid .repo URL for '{}' in registry index",
+            self.name
+        ))?;
+        Ok(url.to_string())
+    }
+}
+
 impl RegistryIndex {
-    pub async fn search_packages(registry_name: &str, query: &str) -> Result<Vec<String>> {
-        let registry_path = Cache::registry_path(registry_name)?;
+    pub async fn search_packages(
+        registry_name: &str,
+        query: &str,
+        shared_dirs: &[String],
+    ) -> Result<Vec<String>> {
+        let query_lower = query.to_lowercase();
+
+        // Registries synced with --index-only have an index instead of a clone
+        if let Some(index) = IndexArtifact::load(registry_name)? {
+            return Ok(index
+                .packages
+                .into_iter()
+                .map(|entry| entry.name)
+                .filter(|name| name.to_lowercase().contains(&query_lower))
+                .collect());
+        }
+
+        let registry_path = Cache::find_registry_clone(registry_name, shared_dirs)?;
         let packages_dir = registry_path.join("packages");
 
         if !packages_dir.exists() {
@@ -14,7 +124,6 @@ impl RegistryIndex {
         }
 
         let mut results = Vec::new();
-        let query_lower = query.to_lowercase();
 
         // Read all .repo files in packages directory
         if let Ok(entries) = std::fs::read_dir(&packages_dir) {
@@ -35,8 +144,11 @@ impl RegistryIndex {
 
     /// Reserved for future use when listing all packages is needed.
     #[allow(dead_code)]
-    pub async fn list_all_packages(registry_name: &str) -> Result<Vec<String>> {
-        let registry_path = Cache::registry_path(registry_name)?;
+    pub async fn list_all_packages(
+        registry_name: &str,
+        shared_dirs: &[String],
+    ) -> Result<Vec<String>> {
+        let registry_path = Cache::find_registry_clone(registry_name, shared_dirs)?;
         let packages_dir = registry_path.join("packages");
 
         if !packages_dir.exists() {
@@ -61,3 +173,43 @@ impl RegistryIndex {
         Ok(results)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_index_artifact() {
+        let index = IndexArtifact::parse(
+            r#"{"packages": [
+                {"name": "ripgrep", "description": "Fast grep", "url": "packages/ripgrep.repo", "sha256": "ABCD"},
+                {"name": "fd", "url": "https://cdn.example.com/fd.repo", "sha256": "ef01"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let ripgrep = index.get("ripgrep").unwrap();
+        assert_eq!(
+            ripgrep
+                .repo_url("https://registry.example.com/ora/index.json")
+                .unwrap(),
+            "https://registry.example.com/ora/packages/ripgrep.repo"
+        );
+        assert_eq!(
+            index
+                .get("fd")
+                .unwrap()
+                .repo_url("https://registry.example.com/ora/index.json")
+                .unwrap(),
+            "https://cdn.example.com/fd.repo"
+        );
+        assert!(index.get("bat").is_none());
+        assert_eq!(index.checksums()["ripgrep"], "abcd");
+
+        assert!(IndexArtifact::parse(
+            r#"{"packages": [{"name": "../x", "url": "x.repo", "sha256": "ab"}]}"#
+        )
+        .is_err());
+        assert!(IndexArtifact::parse("not json").is_err());
+    }
+}
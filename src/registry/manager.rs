@@ -1,14 +1,100 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 
-use crate::config::global::Registry;
+use crate::config::global::{Registry, TrustLevel};
 use crate::config::repo::RepoConfig;
-use crate::registry::sync::RegistrySync;
+use crate::config::repo_lint::lint_repo;
+use crate::config::SecurityConfig;
+use crate::error::OraError;
+use crate::providers::{create_provider, latest_version};
+use crate::registry::index::IndexArtifact;
+use crate::registry::sync::{RegistrySync, RegistryType, SyncSummary};
+use crate::registry::trust::{key_fingerprint, KeyTrust, TrustStore};
+use crate::security::gpg::normalize_fingerprint;
+use crate::security::validate_git_url;
 use crate::storage::cache::Cache;
 use crate::storage::database::{load_global_config, save_global_config};
+use crate::ui_println;
+use crate::utils::http::{is_offline_mode, HttpClient};
+use crate::utils::ui;
+use std::io::{IsTerminal, Write};
+use std::path::Path;
 
 pub struct RegistryManager;
 
+/// One registry as printed by `ora registry list --json`
+#[derive(Debug, Serialize)]
+struct RegistryListEntry {
+    name: String,
+    url: String,
+    trust_level: TrustLevel,
+    enabled: bool,
+    priority: Option<u8>,
+    /// `null` when never synced (and always for Direct URL registries)
+    last_synced_at: Option<DateTime<Utc>>,
+    /// `.repo` files in the local clone or index; `null` when not synced
+    package_count: Option<usize>,
+}
+
+impl RegistryListEntry {
+    fn new(registry: &Registry, shared_dirs: &[String]) -> Self {
+        let (last_synced_at, package_count) = if RegistryType::from_url(&registry.url)
+            == RegistryType::DirectUrl
+        {
+            // A Direct URL registry is a single .repo file fetched on demand
+            (None, Some(1))
+        } else if let Some(index) = IndexArtifact::load(&registry.name).ok().flatten() {
+            (
+                Cache::registry_path(&registry.name)
+                    .ok()
+                    .and_then(|path| RegistrySync::last_synced_at(&path)),
+                Some(index.packages.len()),
+            )
+        } else {
+            match Cache::find_registry_clone(&registry.name, shared_dirs) {
+                Ok(path) if path.join(".git").exists() => (
+                    RegistrySync::last_synced_at(&path),
+                    Some(
+                        RegistrySync::repo_file_snapshot(&path, registry.get_registry_dir()).len(),
+                    ),
+                ),
+                _ => (None, None),
+            }
+        };
+
+        Self {
+            name: registry.name.clone(),
+            url: registry.url.clone(),
+            trust_level: registry.trust_level.clone(),
+            enabled: registry.enabled,
+            priority: registry.priority,
+            last_synced_at,
+            package_count,
+        }
+    }
+}
+
+fn parse_trust_level(value: &str) -> Result<TrustLevel> {
+    match value.to_lowercase().as_str() {
+        "public" => Ok(TrustLevel::Public),
+        "private" => Ok(TrustLevel::Private),
+        _ => anyhow::bail!("Invalid trust level, must be 'public' or 'private'"),
+    }
+}
+
+/// Whether a registry lookup failed only because the package isn't there
+fn is_package_not_found(err: &anyhow::Error) -> bool {
+    err.chain().any(|cause| {
+        matches!(
+            cause.downcast_ref::<OraError>(),
+            Some(OraError::PackageNotFound(_))
+        )
+    })
+}
+
 impl RegistryManager {
+    #[allow(clippy::too_many_arguments)]
     pub async fn add_registry(
         name: String,
         url: String,
@@ -17,6 +103,8 @@ impl RegistryManager {
         pin_cert: bool,
         branch: Option<String>,
         registry_dir: Option<String>,
+        index_checksum: Option<String>,
+        index_url: Option<String>,
     ) -> Result<()> {
         let mut config = load_global_config().await?;
 
@@ -25,11 +113,7 @@ impl RegistryManager {
             anyhow::bail!("Registry '{}' already exists", name);
         }
 
-        let trust_level = match trust_level.to_lowercase().as_str() {
-            "public" => crate::config::global::TrustLevel::Public,
-            "private" => crate::config::global::TrustLevel::Private,
-            _ => anyhow::bail!("Invalid trust level, must be 'public' or 'private'"),
-        };
+        let trust_level = parse_trust_level(&trust_level)?;
 
         let tls = if ca_cert.is_some() || pin_cert {
             Some(crate::config::global::TlsConfig {
@@ -44,46 +128,67 @@ impl RegistryManager {
 
         let registry = Registry {
             name: name.clone(),
-            url: url.clone(),
+            url,
             trust_level,
             enabled: true,
             tls,
             gpg_key: None,
             branch,
             registry_dir,
+            index_checksum,
+            index_url,
             priority: None,
+            allow_prerelease: None,
         };
 
-        config.registries.push(registry);
+        config.registries.push(registry.clone());
         save_global_config(&config).await?;
 
         log::info!("Registry '{}' added successfully", name);
         println!("✅ Registry '{}' added successfully", name);
 
-        // Sync the registry
-        RegistrySync::sync_registry(&name, &url).await?;
+        // Sync the registry, from its index alone when it publishes one
+        Self::ensure_key_trusted(&registry)?;
+        let summary = RegistrySync::sync_registry(&registry, registry.index_url.is_some()).await?;
+        Self::print_sync_summary(summary, "");
 
         Ok(())
     }
 
-    pub async fn list_registries(verbose: bool) -> Result<()> {
+    pub async fn list_registries(verbose: bool, json: bool) -> Result<()> {
         let config = load_global_config().await?;
 
+        if json {
+            let entries: Vec<RegistryListEntry> = config
+                .registries
+                .iter()
+                .map(|registry| RegistryListEntry::new(registry, &config.registry_cache_dirs))
+                .collect();
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&entries).context("Failed to serialize registries")?
+            );
+            return Ok(());
+        }
+
         if config.registries.is_empty() {
-            println!("No registries configured");
+            ui_println!("No registries configured");
             return Ok(());
         }
 
         for registry in &config.registries {
             if verbose {
-                println!("Name: {}", registry.name);
-                println!("  URL: {}", registry.url);
-                println!("  Trust Level: {:?}", registry.trust_level);
-                println!("  Enabled: {}", registry.enabled);
-                println!();
+                ui_println!("Name: {}", registry.name);
+                ui_println!("  URL: {}", registry.url);
+                if let Some(branch) = &registry.branch {
+                    ui_println!("  Branch: {}", branch);
+                }
+                ui_println!("  Trust Level: {:?}", registry.trust_level);
+                ui_println!("  Enabled: {}", registry.enabled);
+                ui_println!();
             } else {
                 let enabled_mark = if registry.enabled { "✅" } else { "❌" };
-                println!("{} {} - {}", enabled_mark, registry.name, registry.url);
+                ui_println!("{} {} - {}", enabled_mark, registry.name, registry.url);
             }
         }
 
@@ -101,13 +206,125 @@ impl RegistryManager {
         }
 
         save_global_config(&config).await?;
+
+        // A registry added again later under this name must be trusted anew
+        Self::forget_trusted_key(&name);
+        // Nor should a new registry under this name see the old index
+        if let Err(e) = IndexArtifact::remove(&name) {
+            log::warn!("Failed to remove the index of '{}': {:#}", name, e);
+        }
         log::info!("Registry '{}' removed", name);
         println!("✅ Registry '{}' removed", name);
 
         Ok(())
     }
 
-    pub async fn sync_registries(name: Option<String>) -> Result<()> {
+    /// Points a registry at a new URL, keeping its settings and local clone
+    ///
+    /// A Git clone's `origin` is re-pointed so the next sync pulls from the new
+    /// URL; the clone is dropped when the registry becomes a Direct URL one.
+    /// The pinned `index_checksum`, trusted key and downloaded index belonged
+    /// to the old URL and are dropped.
+    pub async fn set_registry_url(name: String, url: String) -> Result<()> {
+        let registry_type = RegistryType::from_url(&url);
+        let valid = match registry_type {
+            RegistryType::Git => validate_git_url(&url),
+            RegistryType::DirectUrl => HttpClient::validate_url(&url).map(|_| ()),
+        };
+        valid.context(format!("Invalid registry URL: {}", url))?;
+
+        let mut config = load_global_config().await?;
+        let registry = config
+            .registries
+            .iter_mut()
+            .find(|r| r.name == name)
+            .context(format!("Registry '{}' not found", name))?;
+        if registry.url == url {
+            println!("Registry '{}' already points to {}", name, url);
+            return Ok(());
+        }
+        let previous = std::mem::replace(&mut registry.url, url.clone());
+        // Pins made for the old URL don't vouch for the new one
+        let cleared_checksum = registry.index_checksum.take().is_some();
+
+        // Re-point the clone before saving, so a failure leaves both on the old URL
+        let clone_path = Cache::registry_path(&name)?;
+        let has_clone = clone_path.join(".git").exists();
+        let repointed = has_clone && matches!(registry_type, RegistryType::Git);
+        if repointed {
+            RegistrySync::set_origin_url(&clone_path, &url)?;
+        }
+        if let Err(e) = save_global_config(&config).await {
+            if repointed {
+                if let Err(restore) = RegistrySync::set_origin_url(&clone_path, &previous) {
+                    log::warn!(
+                        "Failed to point {} back at {}: {}",
+                        clone_path.display(),
+                        previous,
+                        restore
+                    );
+                }
+            }
+            return Err(e);
+        }
+
+        println!(
+            "✅ Registry '{}' now points to {} (was {})",
+            name, url, previous
+        );
+        if cleared_checksum {
+            println!(
+                "   Cleared index_checksum; pin the new URL's content again once you've verified it"
+            );
+        }
+        if Self::forget_trusted_key(&name) {
+            println!("   Forgot the trusted signing key; the next sync asks to trust it again");
+        }
+
+        // An index downloaded from the old URL must not answer lookups
+        IndexArtifact::remove(&name)?;
+
+        if !has_clone {
+            return Ok(());
+        }
+        match registry_type {
+            RegistryType::Git => {
+                println!(
+                    "   The local clone now pulls from the new URL; update it with: ora registry sync {}",
+                    name
+                );
+            }
+            RegistryType::DirectUrl => {
+                std::fs::remove_dir_all(&clone_path)
+                    .context(format!("Failed to remove {}", clone_path.display()))?;
+                println!("   Removed the local clone; Direct URL registries are fetched on demand");
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn set_registry_trust(name: String, trust_level: String) -> Result<()> {
+        let trust_level = parse_trust_level(&trust_level)?;
+
+        let mut config = load_global_config().await?;
+        let registry = config
+            .registries
+            .iter_mut()
+            .find(|r| r.name == name)
+            .context(format!("Registry '{}' not found", name))?;
+        let previous = std::mem::replace(&mut registry.trust_level, trust_level.clone());
+        save_global_config(&config).await?;
+
+        println!(
+            "✅ Registry '{}' is now {} (was {})",
+            name,
+            format!("{:?}", trust_level).to_lowercase(),
+            format!("{:?}", previous).to_lowercase()
+        );
+        Ok(())
+    }
+
+    pub async fn sync_registries(name: Option<String>, index_only: bool) -> Result<()> {
         let config = load_global_config().await?;
 
         if let Some(name) = name {
@@ -118,8 +335,10 @@ impl RegistryManager {
                 .context(format!("Registry '{}' not found", name))?;
 
             println!("Syncing registry: {}", registry.name);
-            RegistrySync::sync_registry(&registry.name, &registry.url).await?;
+            Self::ensure_key_trusted(registry)?;
+            let summary = RegistrySync::sync_registry(registry, index_only).await?;
             println!("✅ Registry '{}' synced successfully", registry.name);
+            Self::print_sync_summary(summary, "   ");
         } else {
             if config.registries.is_empty() {
                 println!("No registries configured. Add one with: ora registry add <name> <url>");
@@ -146,8 +365,15 @@ impl RegistryManager {
 
             for registry in enabled_registries {
                 println!("  → Syncing '{}'...", registry.name);
-                match RegistrySync::sync_registry(&registry.name, &registry.url).await {
-                    Ok(_) => println!("    ✅ Synced successfully"),
+                let synced = match Self::ensure_key_trusted(registry) {
+                    Ok(()) => RegistrySync::sync_registry(registry, index_only).await,
+                    Err(e) => Err(e),
+                };
+                match synced {
+                    Ok(summary) => {
+                        println!("    ✅ Synced successfully");
+                        Self::print_sync_summary(summary, "       ");
+                    }
                     Err(e) => {
                         log::error!("Failed to sync registry '{}': {}", registry.name, e);
                         println!("    ❌ Failed: {}", e);
@@ -161,6 +387,161 @@ impl RegistryManager {
         Ok(())
     }
 
+    /// Drops the signing key pinned for a registry; returns whether one was pinned
+    fn forget_trusted_key(name: &str) -> bool {
+        let forgotten = TrustStore::load().and_then(|mut store| {
+            let forgotten = store.forget(name);
+            if forgotten {
+                store.save()?;
+            }
+            Ok(forgotten)
+        });
+        forgotten.unwrap_or_else(|e| {
+            log::warn!("Failed to forget trusted key of '{}': {:#}", name, e);
+            false
+        })
+    }
+
+    /// Checks a signing registry's key against the one pinned on first use
+    ///
+    /// An unknown key is shown and must be confirmed before it is pinned; a key
+    /// that differs from the pinned one is refused until re-trusted explicitly
+    /// with `ora registry trust`. The sync then verifies the registry's signed
+    /// commit or index with that key.
+    fn ensure_key_trusted(registry: &Registry) -> Result<()> {
+        let Some(gpg_key) = &registry.gpg_key else {
+            return Ok(());
+        };
+        if RegistryType::from_url(&registry.url) == RegistryType::DirectUrl {
+            ui::warning(&format!(
+                "Registry '{}' is a Direct URL registry, which can't be signed; \
+                 its gpg_key is ignored (pin index_checksum instead)",
+                registry.name
+            ));
+            return Ok(());
+        }
+        let fingerprint = key_fingerprint(gpg_key)?;
+        let mut store = TrustStore::load()?;
+
+        match store.check(&registry.name, &fingerprint) {
+            KeyTrust::Trusted => {
+                log::debug!(
+                    "Registry '{}' key {} is trusted",
+                    registry.name,
+                    fingerprint
+                );
+                Ok(())
+            }
+            KeyTrust::Changed { trusted } => anyhow::bail!(
+                "❌ Signing key of registry '{}' has CHANGED\n\
+                 \n\
+                 Trusted key: {}\n\
+                 Current key: {}\n\
+                 \n\
+                 This can mean the registry (or your configuration) was tampered with.\n\
+                 If the registry announced a key rotation, trust the new key with:\n\
+                 └─ ora registry trust {} --fingerprint {}",
+                registry.name,
+                trusted,
+                fingerprint,
+                registry.name,
+                fingerprint
+            ),
+            KeyTrust::Unknown => {
+                println!(
+                    "\n🔑 Registry '{}' is signed with a key you haven't trusted yet:",
+                    registry.name
+                );
+                println!("   {}", fingerprint);
+                println!("   Check this fingerprint with the registry's maintainers.");
+                if !ui::confirm("\n❓ Trust this key for future syncs? [y/N]")? {
+                    anyhow::bail!(
+                        "Key of registry '{}' not trusted; sync cancelled.\n\
+                         To trust it non-interactively: ora registry trust {} --fingerprint {}",
+                        registry.name,
+                        registry.name,
+                        fingerprint
+                    );
+                }
+                store.trust(&registry.name, &fingerprint);
+                store.save()?;
+                println!("✅ Trusted key {} for '{}'", fingerprint, registry.name);
+                Ok(())
+            }
+        }
+    }
+
+    /// Pins a registry's current key, replacing any previously trusted one
+    ///
+    /// With `fingerprint`, the key is trusted without prompting if it matches;
+    /// otherwise the user confirms after seeing the old and new fingerprints.
+    pub async fn trust_registry_key(name: String, fingerprint: Option<String>) -> Result<()> {
+        let config = load_global_config().await?;
+        let registry = config
+            .registries
+            .iter()
+            .find(|r| r.name == name)
+            .ok_or_else(|| OraError::RegistryNotFound(name.clone()))?;
+        let gpg_key = registry
+            .gpg_key
+            .as_deref()
+            .context(format!("Registry '{}' has no gpg_key configured", name))?;
+
+        let current = key_fingerprint(gpg_key)?;
+        let mut store = TrustStore::load()?;
+        let previous = match store.check(&name, &current) {
+            KeyTrust::Trusted => {
+                println!("✅ Key {} of '{}' is already trusted", current, name);
+                return Ok(());
+            }
+            KeyTrust::Changed { trusted } => Some(trusted),
+            KeyTrust::Unknown => None,
+        };
+
+        match fingerprint {
+            Some(expected) => {
+                if normalize_fingerprint(&expected) != current {
+                    anyhow::bail!(
+                        "Fingerprint {} does not match the key configured for '{}' ({})",
+                        expected,
+                        name,
+                        current
+                    );
+                }
+            }
+            None => {
+                if let Some(previous) = &previous {
+                    println!("🔑 Previously trusted key: {}", previous);
+                }
+                println!("🔑 Key to trust for '{}': {}", name, current);
+                if !ui::confirm("\n❓ Trust this key? [y/N]")? {
+                    anyhow::bail!("Key not trusted");
+                }
+            }
+        }
+
+        store.trust(&name, &current);
+        store.save()?;
+        match previous {
+            Some(previous) => println!("✅ Rotated key of '{}': {} → {}", name, previous, current),
+            None => println!("✅ Trusted key {} for '{}'", current, name),
+        }
+        Ok(())
+    }
+
+    /// Prints how many .repo files a synced Git registry holds and how many changed
+    fn print_sync_summary(summary: Option<SyncSummary>, indent: &str) {
+        if let Some(summary) = summary {
+            println!(
+                "{}📦 {} .repo file{} ({} changed since last sync)",
+                indent,
+                summary.repo_files,
+                if summary.repo_files == 1 { "" } else { "s" },
+                summary.changed
+            );
+        }
+    }
+
     pub async fn find_package(package_name: &str) -> Result<(RepoConfig, String)> {
         let config = load_global_config().await?;
 
@@ -182,57 +563,99 @@ impl RegistryManager {
             );
         }
 
-        let mut found_registries = Vec::new();
-        let mut first_match: Option<(RepoConfig, String)> = None;
-        let enabled_count = config.registries.iter().filter(|r| r.enabled).count();
-
-        for registry in &config.registries {
-            if !registry.enabled {
-                continue;
-            }
+        // Lower priority value wins; registries without one keep their config order after
+        let mut registries: Vec<&Registry> =
+            config.registries.iter().filter(|r| r.enabled).collect();
+        registries.sort_by_key(|r| r.priority.unwrap_or(u8::MAX));
+        let enabled_count = registries.len();
 
+        // Registries that have the package but fail to load it (oversized or
+        // unparsable .repo, failed checksum) are reported if nothing else matches
+        let mut matches: Vec<(&Registry, RepoConfig)> = Vec::new();
+        let mut failures: Vec<(&Registry, anyhow::Error)> = Vec::new();
+        for registry in registries {
             match RegistrySync::find_package_in_registry(&registry.name, package_name).await {
-                Ok(repo_config) => {
-                    found_registries.push(registry.name.clone());
-
-                    if first_match.is_none() {
-                        first_match = Some((repo_config, registry.name.clone()));
-                    }
-                }
-                Err(_) => {
-                    continue;
+                Ok(repo_config) => matches.push((registry, repo_config)),
+                Err(e) if is_package_not_found(&e) => {}
+                Err(e) => {
+                    log::debug!(
+                        "Failed to load '{}' from registry '{}': {:#}",
+                        package_name,
+                        registry.name,
+                        e
+                    );
+                    failures.push((registry, e));
                 }
             }
         }
 
-        match first_match {
-            Some((repo_config, registry_name)) => {
-                // Warn if package exists in multiple registries
-                if found_registries.len() > 1 {
-                    log::warn!(
-                        "⚠️  Package '{}' exists in multiple registries: {}",
-                        package_name,
-                        found_registries.join(", ")
-                    );
-                    log::warn!(
-                        "Using '{}' from registry '{}'. To select a specific registry, use: ora install {}@<registry>",
-                        package_name,
-                        registry_name,
-                        package_name
-                    );
-                }
+        if matches.is_empty() {
+            if let Some((registry, e)) = failures.into_iter().next() {
+                let message = format!(
+                    "Failed to load package '{}' from registry '{}': {:#}",
+                    package_name, registry.name, e
+                );
+                return Err(e.context(message));
+            }
+        }
+
+        if matches.len() > 1 {
+            let names: Vec<&str> = matches.iter().map(|(r, _)| r.name.as_str()).collect();
+
+            if SecurityConfig::load()
+                .unwrap_or_default()
+                .registries
+                .fail_on_ambiguous_package
+            {
+                anyhow::bail!(
+                    "❌ Package '{}' exists in multiple registries: {}\n\
+                     \n\
+                     Select one explicitly:\n\
+                     └─ ora install {}@<registry>\n\
+                     \n\
+                     (registries.fail_on_ambiguous_package is enabled in security.toml)",
+                    package_name,
+                    names.join(", "),
+                    package_name
+                );
+            }
 
+            let interactive = std::io::stdin().is_terminal()
+                && std::io::stdout().is_terminal()
+                && !ui::assume_yes();
+            let index = if interactive {
+                Self::select_registry(package_name, &matches).await?
+            } else {
+                log::warn!(
+                    "⚠️  Package '{}' exists in multiple registries: {}",
+                    package_name,
+                    names.join(", ")
+                );
+                log::warn!(
+                    "Using '{}' from registry '{}'. To select a specific registry, use: ora install {}@<registry>",
+                    package_name,
+                    names[0],
+                    package_name
+                );
+                0
+            };
+            let (registry, repo_config) = matches.swap_remove(index);
+            return Ok((repo_config, registry.name.clone()));
+        }
+
+        match matches.pop() {
+            Some((registry, repo_config)) => {
                 log::debug!(
                     "Found package '{}' in registry '{}'",
                     package_name,
-                    registry_name
+                    registry.name
                 );
-                Ok((repo_config, registry_name))
+                Ok((repo_config, registry.name.clone()))
             }
             None => {
                 // UX IMPROVEMENT: Better error message when package not found
-                if enabled_count == 0 {
-                    anyhow::bail!(
+                let message = if enabled_count == 0 {
+                    format!(
                         "Package '{}' not found - No enabled registries.\n\
                          \n\
                          You have {} configured registr{} but {} disabled.\n\
@@ -249,9 +672,9 @@ impl RegistryManager {
                         } else {
                             "they're all"
                         }
-                    );
+                    )
                 } else {
-                    anyhow::bail!(
+                    format!(
                         "Package '{}' not found in any of {} configured registr{}.\n\
                          \n\
                          Searched in: {}\n\
@@ -271,8 +694,72 @@ impl RegistryManager {
                             .collect::<Vec<_>>()
                             .join(", "),
                         package_name
-                    );
-                }
+                    )
+                };
+
+                Err(
+                    anyhow::Error::new(OraError::PackageNotFound(package_name.to_string()))
+                        .context(message),
+                )
+            }
+        }
+    }
+
+    /// Asks which registry to take an ambiguous package from; returns an index into `matches`
+    async fn select_registry(
+        package_name: &str,
+        matches: &[(&Registry, RepoConfig)],
+    ) -> Result<usize> {
+        println!(
+            "\n📦 Package '{}' exists in {} registries:",
+            package_name,
+            matches.len()
+        );
+        for (i, (registry, repo_config)) in matches.iter().enumerate() {
+            println!(
+                "  {}) {:<20} trust: {:<8} latest: {}",
+                i + 1,
+                registry.name,
+                format!("{:?}", registry.trust_level).to_lowercase(),
+                Self::latest_tag(repo_config).await
+            );
+        }
+
+        loop {
+            print!("❓ Install from which registry? [1-{}] ", matches.len());
+            std::io::stdout().flush()?;
+            let mut input = String::new();
+            if std::io::stdin()
+                .read_line(&mut input)
+                .context("Failed to read user input")?
+                == 0
+            {
+                anyhow::bail!("No registry selected for '{}'", package_name);
+            }
+            match input.trim().parse::<usize>() {
+                Ok(n) if (1..=matches.len()).contains(&n) => return Ok(n - 1),
+                _ => println!("Please enter a number between 1 and {}", matches.len()),
+            }
+        }
+    }
+
+    /// Latest stable tag of a package definition, for display only
+    async fn latest_tag(repo_config: &RepoConfig) -> String {
+        if is_offline_mode() {
+            return "unknown (offline)".to_string();
+        }
+        // Not cached: the version cache is keyed by package name, not by registry
+        let versions = match create_provider(repo_config) {
+            Ok(provider) => provider.list_versions().await,
+            Err(e) => Err(e),
+        };
+        match versions {
+            Ok(versions) => latest_version(&versions, false)
+                .map(|v| v.tag.clone())
+                .unwrap_or_else(|| "none".to_string()),
+            Err(e) => {
+                log::debug!("Failed to list versions of '{}': {}", repo_config.name, e);
+                "unknown".to_string()
             }
         }
     }
@@ -292,12 +779,10 @@ impl RegistryManager {
                 registry_name
             ))?;
 
-        let repo_config = RegistrySync::find_package_in_registry(&registry.name, package_name)
-            .await
-            .context(format!(
-                "Package '{}' not found in registry '{}'",
-                package_name, registry_name
-            ))?;
+        // The lookup's own error already names the package and registry, and
+        // keeps the reason (oversized or unparsable .repo, failed checksum)
+        let repo_config =
+            RegistrySync::find_package_in_registry(&registry.name, package_name).await?;
 
         log::debug!(
             "Found package '{}' in registry '{}'",
@@ -307,66 +792,146 @@ impl RegistryManager {
         Ok((repo_config, registry.name.clone()))
     }
 
-    pub async fn verify_registry(name: String) -> Result<()> {
+    /// Shows a registry's settings and what its local clone contains
+    ///
+    /// Reads the local clone only; unlike `verify_registry` no git or remote
+    /// checks are done.
+    pub async fn registry_info(name: String) -> Result<()> {
         let config = load_global_config().await?;
 
-        println!("Verifying registry: {}", name);
-        println!();
-
-        // 1. Check if registry exists in config
         let registry = config
             .registries
             .iter()
             .find(|r| r.name == name)
-            .context(format!("Registry '{}' not found in configuration", name))?;
+            .ok_or_else(|| OraError::RegistryNotFound(name.clone()))?;
 
-        println!("✅ Registry found in configuration");
-        println!("  Name: {}", registry.name);
+        println!("Registry: {}", registry.name);
         println!("  URL: {}", registry.url);
+        if let Some(branch) = &registry.branch {
+            println!("  Branch: {}", branch);
+        }
         println!("  Trust Level: {:?}", registry.trust_level);
         println!("  Enabled: {}", registry.enabled);
 
+        if RegistryType::from_url(&registry.url) == RegistryType::DirectUrl {
+            println!("  Type: Direct URL (fetched on demand, never synced)");
+            return Ok(());
+        }
+
+        if let Some(index_url) = &registry.index_url {
+            println!("  Index URL: {}", index_url);
+        }
+
+        let index = IndexArtifact::load(&name)?;
+        let registry_path = Cache::find_registry_clone(&name, &config.registry_cache_dirs)?;
+        if index.is_none() && !registry_path.join(".git").exists() {
+            println!("  Last sync: never");
+            println!("\n  Run 'ora registry sync {}' to download it", name);
+            return Ok(());
+        }
+
+        if index.is_some() {
+            println!("  Type: index only (.repo files fetched on demand)");
+        } else {
+            println!("  Path: {}", registry_path.display());
+        }
+        match RegistrySync::last_synced_at(&registry_path) {
+            Some(date) => {
+                let days = (chrono::Utc::now() - date).num_days().max(0);
+                println!(
+                    "  Last sync: {} ({} days ago)",
+                    date.format("%Y-%m-%d %H:%M UTC"),
+                    days
+                );
+            }
+            None => println!("  Last sync: unknown"),
+        }
+
+        let mut packages: Vec<String> = match index {
+            Some(index) => index.packages.into_iter().map(|entry| entry.name).collect(),
+            None => RegistrySync::repo_file_snapshot(&registry_path, registry.get_registry_dir())
+                .into_keys()
+                .map(|file| file.trim_end_matches(".repo").to_string())
+                .collect(),
+        };
+        packages.sort();
+
+        println!("  Packages: {}", packages.len());
+        if !packages.is_empty() {
+            let examples = packages.iter().take(5).cloned().collect::<Vec<_>>();
+            let more = if packages.len() > examples.len() {
+                ", ..."
+            } else {
+                ""
+            };
+            println!("  Examples: {}{}", examples.join(", "), more);
+        }
+
+        Ok(())
+    }
+
+    /// Checks a registry's config, clone and layout; `deep` also parses and
+    /// lints every `.repo` file, failing if any has errors
+    pub async fn verify_registry(name: String, deep: bool) -> Result<()> {
+        let config = load_global_config().await?;
+
+        ui_println!("Verifying registry: {}", name);
+        ui_println!();
+
+        // 1. Check if registry exists in config
+        let registry = config
+            .registries
+            .iter()
+            .find(|r| r.name == name)
+            .context(format!("Registry '{}' not found in configuration", name))?;
+
+        ui_println!("✅ Registry found in configuration");
+        ui_println!("  Name: {}", registry.name);
+        ui_println!("  URL: {}", registry.url);
+        ui_println!("  Trust Level: {:?}", registry.trust_level);
+        ui_println!("  Enabled: {}", registry.enabled);
+
         // 2. Check if registry directory exists (has been synced)
-        let registry_path = Cache::registry_path(&name)?;
+        let registry_path = Cache::find_registry_clone(&name, &config.registry_cache_dirs)?;
 
         if !registry_path.exists() {
-            println!("❌ Registry not synced locally");
-            println!("  Expected path: {:?}", registry_path);
-            println!("\n  Run 'ora registry sync {}' to download it", name);
+            ui_println!("❌ Registry not synced locally");
+            ui_println!("  Expected path: {:?}", registry_path);
+            ui_println!("\n  Run 'ora registry sync {}' to download it", name);
             anyhow::bail!("Registry '{}' not synced", name);
         }
 
-        println!("✅ Registry synced locally");
-        println!("  Path: {:?}", registry_path);
+        ui_println!("✅ Registry synced locally");
+        ui_println!("  Path: {:?}", registry_path);
 
         // 3. Check if it's a valid git repository
         match git2::Repository::open(&registry_path) {
             Ok(repo) => {
-                println!("✅ Valid git repository");
+                ui_println!("✅ Valid git repository");
 
                 // Get current HEAD commit
                 if let Ok(head) = repo.head() {
                     if let Some(commit) = head.target() {
-                        println!("  Commit: {}", commit);
+                        ui_println!("  Commit: {}", commit);
                     }
                 }
 
                 // Check remote URL
                 if let Ok(remote) = repo.find_remote("origin") {
                     if let Some(url) = remote.url() {
-                        println!("  Remote: {}", url);
+                        ui_println!("  Remote: {}", url);
 
                         // Verify it matches the configured URL
                         if url != registry.url {
-                            println!("  ⚠️  Warning: Remote URL doesn't match configured URL");
-                            println!("     Configured: {}", registry.url);
-                            println!("     Actual: {}", url);
+                            ui_println!("  ⚠️  Warning: Remote URL doesn't match configured URL");
+                            ui_println!("     Configured: {}", registry.url);
+                            ui_println!("     Actual: {}", url);
                         }
                     }
                 }
             }
             Err(e) => {
-                println!("❌ Not a valid git repository: {}", e);
+                ui_println!("❌ Not a valid git repository: {}", e);
                 anyhow::bail!("Registry directory exists but is not a valid git repository");
             }
         }
@@ -376,8 +941,8 @@ impl RegistryManager {
         let registry_dir_path = registry_path.join(registry_dir_name);
 
         if !registry_dir_path.exists() {
-            println!("❌ Missing '{}/' directory", registry_dir_name);
-            println!(
+            ui_println!("❌ Missing '{}/' directory", registry_dir_name);
+            ui_println!(
                 "  A valid registry must contain a '{}/' directory with .repo files",
                 registry_dir_name
             );
@@ -388,7 +953,7 @@ impl RegistryManager {
             );
         }
 
-        println!("✅ '{}/' directory exists", registry_dir_name);
+        ui_println!("✅ '{}/' directory exists", registry_dir_name);
         let registry_dir = registry_dir_path;
 
         // 5. Count .repo files
@@ -405,10 +970,10 @@ impl RegistryManager {
         }
 
         if repo_files.is_empty() {
-            println!("⚠️  Warning: No .repo files found in registry directory");
-            println!("  This registry appears to be empty");
+            ui_println!("⚠️  Warning: No .repo files found in registry directory");
+            ui_println!("  This registry appears to be empty");
         } else {
-            println!(
+            ui_println!(
                 "✅ Found {} package definition{}",
                 repo_files.len(),
                 if repo_files.len() == 1 { "" } else { "s" }
@@ -417,17 +982,83 @@ impl RegistryManager {
             // Show first few packages
             let display_count = std::cmp::min(5, repo_files.len());
             for (i, file) in repo_files.iter().take(display_count).enumerate() {
-                println!("  {}. {}", i + 1, file.trim_end_matches(".repo"));
+                ui_println!("  {}. {}", i + 1, file.trim_end_matches(".repo"));
             }
 
             if repo_files.len() > display_count {
-                println!("  ... and {} more", repo_files.len() - display_count);
+                ui_println!("  ... and {} more", repo_files.len() - display_count);
+            }
+        }
+
+        if deep {
+            Self::lint_repo_files(&registry_dir, &repo_files)?;
+        }
+
+        ui_println!();
+        ui_println!("✅ Registry '{}' verification complete!", name);
+
+        Ok(())
+    }
+
+    /// Parses and lints each `.repo` file, reporting every failing package
+    fn lint_repo_files(registry_dir: &Path, repo_files: &[String]) -> Result<()> {
+        ui_println!();
+        ui_println!("Checking {} package definitions...", repo_files.len());
+
+        let mut sorted: Vec<&String> = repo_files.iter().collect();
+        sorted.sort();
+
+        let mut failed = 0;
+        let mut warned = 0;
+        for file in sorted {
+            let path = registry_dir.join(file);
+            let parsed = std::fs::read_to_string(&path)
+                .context("Failed to read file")
+                .and_then(|content| {
+                    toml::from_str::<RepoConfig>(&content).context("Failed to parse")
+                });
+
+            let report = match parsed {
+                Ok(repo_config) => lint_repo(&repo_config),
+                Err(e) => {
+                    failed += 1;
+                    ui_println!("❌ {}: {:#}", file, e);
+                    continue;
+                }
+            };
+
+            if !report.is_ok() {
+                failed += 1;
+                ui_println!("❌ {}", file);
+                for error in &report.errors {
+                    ui_println!("     {}", error);
+                }
+            }
+            if !report.warnings.is_empty() {
+                warned += 1;
+                for warning in &report.warnings {
+                    ui_println!("  ⚠️  {}: {}", file, warning);
+                }
             }
         }
 
-        println!();
-        println!("✅ Registry '{}' verification complete!", name);
+        if failed > 0 {
+            anyhow::bail!(
+                "{} of {} package definitions have errors",
+                failed,
+                repo_files.len()
+            );
+        }
 
+        ui_println!(
+            "✅ All {} package definitions are valid{}",
+            repo_files.len(),
+            if warned > 0 {
+                format!(" ({} with warnings)", warned)
+            } else {
+                String::new()
+            }
+        );
         Ok(())
     }
 }
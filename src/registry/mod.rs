@@ -1,6 +1,7 @@
 pub mod index;
 pub mod manager;
 pub mod sync;
+pub mod trust;
 
 pub use index::RegistryIndex;
 pub use manager::RegistryManager;
@@ -1,12 +1,116 @@
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 
+use crate::config::global::{Registry, TrustLevel};
 use crate::config::repo::RepoConfig;
+use crate::config::SecurityConfig;
+use crate::error::OraError;
+use crate::registry::index::IndexArtifact;
+use crate::security::gpg;
 use crate::storage::cache::Cache;
 use crate::utils::http;
+use crate::utils::ui;
 
 pub struct RegistrySync;
 
+/// Pause before retrying a git operation that failed on a transient network error
+const GIT_RETRY_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+
+/// Common causes of a failed git clone or fetch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GitFailure {
+    AuthRequired,
+    NotFound,
+    /// Timeouts, refused or reset connections, DNS failures
+    Network,
+    ShallowUnsupported,
+    Other,
+}
+
+impl GitFailure {
+    fn classify(error: &git2::Error) -> Self {
+        let message = error.message().to_lowercase();
+        let mentions = |needles: &[&str]| needles.iter().any(|needle| message.contains(needle));
+
+        if error.code() == git2::ErrorCode::Auth
+            || mentions(&["401", "403", "authentication", "credentials"])
+        {
+            GitFailure::AuthRequired
+        } else if mentions(&["shallow"]) {
+            GitFailure::ShallowUnsupported
+        } else if mentions(&[
+            "404",
+            "not found",
+            "does not appear to be a git repository",
+            "failed to resolve path",
+        ]) {
+            GitFailure::NotFound
+        } else if mentions(&[
+            "timed out",
+            "timeout",
+            "connection reset",
+            "connection refused",
+            "failed to connect",
+            "failed to resolve",
+            "could not resolve",
+            "unexpected eof",
+            "broken pipe",
+        ]) {
+            GitFailure::Network
+        } else {
+            GitFailure::Other
+        }
+    }
+
+    /// Actionable explanation of the failure for a registry at `url`
+    fn hint(self, url: &str) -> Option<String> {
+        let credentials = "Ora authenticates HTTPS registries through your git credential \
+                           helper (see 'git help credential'); configure one for this host, \
+                           e.g. git config --global credential.helper store";
+        match self {
+            GitFailure::AuthRequired => Some(format!(
+                "Registry {} requires authentication.\n{}",
+                url, credentials
+            )),
+            // Hosts such as GitHub answer 404 for private repositories without credentials
+            GitFailure::NotFound if url.ends_with(".git") && url.starts_with("https://") => {
+                Some(format!(
+                    "Repository {} was not found. Check the URL; if it is a private \
+                     repository, it is hidden until you authenticate.\n{}",
+                    url, credentials
+                ))
+            }
+            GitFailure::NotFound => Some(format!(
+                "Repository {} was not found. Check the registry URL with 'ora registry info'",
+                url
+            )),
+            GitFailure::Network => Some(format!(
+                "Could not reach {} (network timeout or connection failure). Check your \
+                 connection and proxy settings (network.proxy in security.toml), or use \
+                 --offline with an existing clone",
+                url
+            )),
+            GitFailure::ShallowUnsupported => Some(format!(
+                "The server of {} does not support shallow clones, which Ora uses to limit \
+                 registry downloads. Host the registry on a server with smart HTTP support",
+                url
+            )),
+            GitFailure::Other => None,
+        }
+    }
+}
+
+/// Contents of a Git registry after a sync
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SyncSummary {
+    /// Number of .repo files in the registry directory
+    pub repo_files: usize,
+    /// .repo files added, modified or removed by this sync
+    pub changed: usize,
+}
+
 #[derive(Debug, PartialEq)]
 pub enum RegistryType {
     Git,       // Git repository (contains .git in URL)
@@ -33,19 +137,20 @@ impl RegistryType {
 }
 
 impl RegistrySync {
-    pub async fn sync_registry(name: &str, url: &str) -> Result<()> {
-        // Load config to get the branch if specified
-        let config = crate::storage::database::load_global_config().await?;
-        let branch = config
-            .registries
-            .iter()
-            .find(|r| r.name == name)
-            .and_then(|r| r.branch.clone());
-
-        Self::sync_registry_with_branch(name, url, branch.as_deref()).await
-    }
-
-    async fn sync_registry_with_branch(name: &str, url: &str, branch: Option<&str>) -> Result<()> {
+    /// Syncs a registry, cloning or pulling its configured branch (or the default branch)
+    ///
+    /// With `index_only`, a Git registry that publishes an `index_url` is
+    /// synced by downloading that index instead; one that doesn't is cloned.
+    ///
+    /// Returns a summary of the registry's .repo files for Git registries, and
+    /// `None` for Direct URL registries, which are fetched on demand.
+    pub async fn sync_registry(
+        registry: &Registry,
+        index_only: bool,
+    ) -> Result<Option<SyncSummary>> {
+        let name = registry.name.as_str();
+        let url = registry.url.as_str();
+        let branch = registry.branch.as_deref();
         let registry_type = RegistryType::from_url(url);
 
         match registry_type {
@@ -60,28 +165,324 @@ impl RegistrySync {
                 } else {
                     log::debug!("Syncing Git registry '{}' from {}", name, url);
                 }
+
+                if index_only {
+                    match &registry.index_url {
+                        Some(index_url) => {
+                            return Self::sync_index(registry, index_url).await.map(Some)
+                        }
+                        None => ui::warning(&format!(
+                            "Registry '{}' publishes no index_url; falling back to a full clone",
+                            name
+                        )),
+                    }
+                }
                 let registry_path = Cache::registry_path(name)?;
+                let registry_dir = registry.get_registry_dir();
+
+                // Offline mode: never fetch or clone, rely on an existing local
+                // clone (possibly in a shared registry cache)
+                if http::is_offline_mode() {
+                    let shared_dirs = crate::storage::database::load_global_config()
+                        .await
+                        .map(|c| c.registry_cache_dirs)
+                        .unwrap_or_default();
+                    let clone_path = Cache::find_registry_clone(name, &shared_dirs)?;
+                    if clone_path.join(".git").exists() {
+                        log::info!(
+                            "Offline mode: using existing local clone of registry '{}' at {}",
+                            name,
+                            clone_path.display()
+                        );
+                        let snapshot = Self::repo_file_snapshot(&clone_path, registry_dir);
+                        return Ok(Some(SyncSummary {
+                            repo_files: snapshot.len(),
+                            changed: 0,
+                        }));
+                    }
+                    anyhow::bail!(
+                        "Offline mode: registry '{}' has no local clone at {}.\n\
+                         Sync it once while online: ora registry sync {}",
+                        name,
+                        registry_path.display(),
+                        name
+                    );
+                }
 
                 // Check if it's a git repository
-                if registry_path.join(".git").exists() {
+                let before = if registry_path.join(".git").exists() {
+                    let before = Self::repo_file_snapshot(&registry_path, registry_dir);
                     // Pull latest changes
-                    Self::git_pull(&registry_path, branch)?;
+                    Self::git_pull(&registry_path, branch, registry.gpg_key.as_deref())?;
+                    before
                 } else {
                     // Clone for the first time
-                    Self::git_clone(url, &registry_path, branch)?;
+                    Self::git_clone(url, &registry_path, branch, registry.gpg_key.as_deref())?;
+                    HashMap::new()
+                };
+
+                if let Err(e) = Self::record_sync_time(&registry_path) {
+                    log::warn!("Could not record sync time of registry '{}': {}", name, e);
                 }
+                // Lookups now go to the clone rather than an older index-only sync
+                IndexArtifact::remove(name)?;
+
+                let after = Self::repo_file_snapshot(&registry_path, registry_dir);
+                let summary = SyncSummary {
+                    repo_files: after.len(),
+                    changed: Self::count_changes(&before, &after),
+                };
 
-                log::info!("Registry '{}' synced successfully", name);
+                log::info!(
+                    "Registry '{}' synced successfully ({} .repo files, {} changed)",
+                    name,
+                    summary.repo_files,
+                    summary.changed
+                );
+                Ok(Some(summary))
             }
             RegistryType::DirectUrl => {
                 log::debug!("Syncing Direct URL registry '{}' from {}", name, url);
                 // For Direct URL registries, we fetch the .repo file on-demand
                 // No need to sync/download it now
                 log::debug!("Direct URL registries are fetched on-demand, no sync needed");
+                Ok(None)
             }
         }
+    }
 
-        Ok(())
+    /// Downloads a Git registry's prebuilt package index instead of cloning it
+    ///
+    /// The index is size-capped by `registries.max_registry_size`; the .repo
+    /// files it lists are fetched and checksum-verified on demand.
+    async fn sync_index(registry: &Registry, index_url: &str) -> Result<SyncSummary> {
+        let name = registry.name.as_str();
+        let previous = IndexArtifact::load(name).ok().flatten();
+
+        if http::is_offline_mode() {
+            let Some(index) = previous else {
+                anyhow::bail!(
+                    "Offline mode: registry '{}' has no downloaded index.\n\
+                     Sync it once while online: ora registry sync {} --index-only",
+                    name,
+                    name
+                );
+            };
+            log::info!(
+                "Offline mode: using the downloaded index of registry '{}'",
+                name
+            );
+            return Ok(SyncSummary {
+                repo_files: index.packages.len(),
+                changed: 0,
+            });
+        }
+
+        log::debug!(
+            "Downloading index of registry '{}' from {}",
+            name,
+            index_url
+        );
+        let max_size = SecurityConfig::load()
+            .unwrap_or_default()
+            .registries
+            .max_registry_size;
+        let content = http::HttpClient::new()?
+            .get_text_limited(index_url, max_size)
+            .await
+            .context(format!(
+                "Failed to download registry index from {}",
+                index_url
+            ))?;
+        if let Some(gpg_key) = &registry.gpg_key {
+            let signature_url = format!("{}.asc", index_url);
+            let signature = http::HttpClient::new()?
+                .get_text_limited(&signature_url, max_size)
+                .await
+                .context(format!(
+                    "Failed to download the index signature from {}",
+                    signature_url
+                ))?;
+            gpg::verify_detached(content.as_bytes(), signature.as_bytes(), gpg_key).context(
+                format!("Index of registry '{}' is not signed by its gpg_key", name),
+            )?;
+            log::debug!("Index signature of registry '{}' verified", name);
+        }
+        let index = IndexArtifact::parse(&content)
+            .context(format!("Invalid index served by registry '{}'", name))?;
+        index.save(name)?;
+
+        if let Err(e) = Self::record_sync_time(&Cache::registry_path(name)?) {
+            log::warn!("Could not record sync time of registry '{}': {}", name, e);
+        }
+
+        let summary = SyncSummary {
+            repo_files: index.packages.len(),
+            changed: Self::count_changes(
+                &previous.map(|p| p.checksums()).unwrap_or_default(),
+                &index.checksums(),
+            ),
+        };
+        log::info!(
+            "Registry '{}' index synced successfully ({} packages, {} changed)",
+            name,
+            summary.repo_files,
+            summary.changed
+        );
+        Ok(summary)
+    }
+
+    /// Fetches a package's .repo file listed in a registry's downloaded index
+    async fn fetch_indexed_repo(
+        registry: &Registry,
+        index: &IndexArtifact,
+        package_name: &str,
+    ) -> Result<RepoConfig> {
+        let entry = index.get(package_name).ok_or_else(|| {
+            anyhow::Error::new(OraError::PackageNotFound(package_name.to_string())).context(
+                format!(
+                    "Package '{}' not found in the index of registry '{}'",
+                    package_name, registry.name
+                ),
+            )
+        })?;
+        let index_url = registry.index_url.as_deref().context(format!(
+            "Registry '{}' has an index but no index_url; sync it again",
+            registry.name
+        ))?;
+        let repo_url = entry.repo_url(index_url)?;
+
+        log::debug!(
+            "Fetching .repo file of '{}' from {}",
+            package_name,
+            repo_url
+        );
+        let max_toml_size = SecurityConfig::load()
+            .unwrap_or_default()
+            .validation
+            .max_toml_size;
+        let content = http::HttpClient::new()?
+            .get_text_limited(&repo_url, max_toml_size)
+            .await
+            .context(format!("Failed to fetch .repo file from {}", repo_url))?;
+        crate::security::checksum::verify_content_sha256(content.as_bytes(), &entry.sha256)
+            .context(format!(
+                ".repo file of '{}' does not match the checksum in the index of registry '{}'",
+                package_name, registry.name
+            ))?;
+
+        toml::from_str(&content).context("Failed to parse .repo file")
+    }
+
+    /// File next to a registry clone holding the time of its last successful sync
+    fn sync_stamp_path(clone_path: &Path) -> Option<PathBuf> {
+        let name = clone_path.file_name()?.to_string_lossy();
+        Some(clone_path.with_file_name(format!("{}.last_sync", name)))
+    }
+
+    fn record_sync_time(clone_path: &Path) -> Result<()> {
+        let stamp = Self::sync_stamp_path(clone_path).context("Invalid registry path")?;
+        std::fs::write(&stamp, Utc::now().to_rfc3339())
+            .context(format!("Failed to write {}", stamp.display()))
+    }
+
+    /// When the registry clone at `clone_path` was last synced, if known
+    pub fn last_synced_at(clone_path: &Path) -> Option<DateTime<Utc>> {
+        let content = std::fs::read_to_string(Self::sync_stamp_path(clone_path)?).ok()?;
+        DateTime::parse_from_rfc3339(content.trim())
+            .ok()
+            .map(|date| date.with_timezone(&Utc))
+    }
+
+    /// Maps each .repo file in the registry directory at HEAD to its blob id
+    ///
+    /// Returns an empty map if the clone or the directory can't be read.
+    pub fn repo_file_snapshot(repo_path: &Path, registry_dir: &str) -> HashMap<String, git2::Oid> {
+        let mut snapshot = HashMap::new();
+
+        let tree = git2::Repository::open(repo_path).ok().and_then(|repo| {
+            let head_tree = repo.head().ok()?.peel_to_tree().ok()?;
+            let entry = head_tree.get_path(Path::new(registry_dir)).ok()?;
+            let object = entry.to_object(&repo).ok()?;
+            object.into_tree().ok().map(|tree| {
+                tree.iter()
+                    .filter(|e| e.kind() == Some(git2::ObjectType::Blob))
+                    .filter_map(|e| Some((e.name()?.to_string(), e.id())))
+                    .collect::<Vec<_>>()
+            })
+        });
+
+        for (name, id) in tree.unwrap_or_default() {
+            if name.ends_with(".repo") {
+                snapshot.insert(name, id);
+            }
+        }
+
+        snapshot
+    }
+
+    /// Counts .repo files added, modified or removed between two snapshots
+    fn count_changes<V: PartialEq>(
+        before: &HashMap<String, V>,
+        after: &HashMap<String, V>,
+    ) -> usize {
+        let added_or_modified = after
+            .iter()
+            .filter(|(name, id)| before.get(*name) != Some(*id))
+            .count();
+        let removed = before
+            .keys()
+            .filter(|name| !after.contains_key(*name))
+            .count();
+        added_or_modified + removed
+    }
+
+    /// Builds fetch options that report git transfer progress on a progress bar
+    ///
+    /// Fetches go through `network.proxy` when configured, otherwise through the
+    /// proxy git itself would use (`http.proxy`, proxy environment variables).
+    fn fetch_options_with_progress(
+        progress: &indicatif::ProgressBar,
+    ) -> Result<git2::FetchOptions<'static>> {
+        let progress = progress.clone();
+        let mut callbacks = git2::RemoteCallbacks::new();
+        callbacks.transfer_progress(move |stats| {
+            progress.set_length(stats.total_objects() as u64);
+            progress.set_position(stats.received_objects() as u64);
+            progress.set_message(indicatif::HumanBytes(stats.received_bytes() as u64).to_string());
+            true
+        });
+        // Private HTTPS registries authenticate through the user's git credential
+        // helper; asked once, so rejected credentials fail instead of looping
+        let mut credentials_asked = false;
+        callbacks.credentials(move |url, username, allowed| {
+            if credentials_asked || !allowed.contains(git2::CredentialType::USER_PASS_PLAINTEXT) {
+                return Err(git2::Error::new(
+                    git2::ErrorCode::Auth,
+                    git2::ErrorClass::Http,
+                    "authentication required but no credentials available",
+                ));
+            }
+            credentials_asked = true;
+            let config = git2::Config::open_default()?;
+            git2::Cred::credential_helper(&config, url, username)
+        });
+
+        let mut proxy_options = git2::ProxyOptions::new();
+        match SecurityConfig::load()
+            .unwrap_or_default()
+            .network
+            .proxy
+            .authenticated_url()?
+        {
+            Some(url) => proxy_options.url(url.as_str()),
+            None => proxy_options.auto(),
+        };
+
+        let mut fetch_options = git2::FetchOptions::new();
+        fetch_options.remote_callbacks(callbacks);
+        fetch_options.proxy_options(proxy_options);
+        Ok(fetch_options)
     }
 
     pub async fn find_package_in_registry(
@@ -101,11 +502,20 @@ impl RegistrySync {
 
         match registry_type {
             RegistryType::Git => {
+                // Registry synced with --index-only: fetch the .repo file it lists
+                if let Some(index) = IndexArtifact::load(registry_name)? {
+                    return Self::fetch_indexed_repo(registry, &index, package_name).await;
+                }
+
                 // Git registry: look for .repo file in local clone
-                let registry_path = Cache::registry_path(registry_name)?;
+                let registry_path =
+                    Cache::find_registry_clone(registry_name, &config.registry_cache_dirs)?;
 
                 if !registry_path.exists() {
-                    anyhow::bail!("Registry '{}' not synced", registry_name);
+                    return Err(anyhow::Error::new(OraError::PackageNotFound(
+                        package_name.to_string(),
+                    ))
+                    .context(format!("Registry '{}' not synced", registry_name)));
                 }
 
                 // Look for package.repo file in configured registry directory
@@ -115,16 +525,34 @@ impl RegistrySync {
                     .join(format!("{}.repo", package_name));
 
                 if !repo_file.exists() {
-                    anyhow::bail!(
+                    return Err(anyhow::Error::new(OraError::PackageNotFound(
+                        package_name.to_string(),
+                    ))
+                    .context(format!(
                         "Package '{}' not found in registry '{}'. \
-                         Registry must contain a '{}/' directory with .repo files.",
-                        package_name,
-                        registry_name,
-                        registry_dir
-                    );
+                                 Registry must contain a '{}/' directory with .repo files.",
+                        package_name, registry_name, registry_dir
+                    )));
                 }
 
                 // Load and parse .repo file
+                let max_toml_size = SecurityConfig::load()
+                    .unwrap_or_default()
+                    .validation
+                    .max_toml_size;
+                let file_size = tokio::fs::metadata(&repo_file)
+                    .await
+                    .context("Failed to read .repo file")?
+                    .len();
+                if file_size > max_toml_size {
+                    anyhow::bail!(
+                        ".repo file {} is too large ({} bytes, maximum {} bytes)",
+                        repo_file.display(),
+                        file_size,
+                        max_toml_size
+                    );
+                }
+
                 let content = tokio::fs::read_to_string(&repo_file)
                     .await
                     .context("Failed to read .repo file")?;
@@ -138,16 +566,20 @@ impl RegistrySync {
                 // Direct URL registry: fetch .repo file via HTTP
                 log::debug!("Fetching .repo file from {}", registry.url);
 
+                // Cap the download so a malicious registry can't stream an
+                // unbounded document into the TOML parser
+                let max_toml_size = SecurityConfig::load()
+                    .unwrap_or_default()
+                    .validation
+                    .max_toml_size;
                 let client = http::HttpClient::new()?;
-                let response = client
-                    .get(&registry.url)
+                let content = client
+                    .get_text_limited(&registry.url, max_toml_size)
                     .await
                     .context(format!("Failed to fetch .repo file from {}", registry.url))?;
 
-                let content = response
-                    .text()
-                    .await
-                    .context("Failed to read .repo file content")?;
+                // Verify the fetched definition before parsing it
+                Self::verify_index_checksum(registry, &content)?;
 
                 let repo_config: RepoConfig =
                     toml::from_str(&content).context("Failed to parse .repo file")?;
@@ -166,7 +598,52 @@ impl RegistrySync {
         }
     }
 
-    fn git_clone(url: &str, dest: &PathBuf, branch: Option<&str>) -> Result<()> {
+    /// Verifies a Direct URL registry's .repo content against its pinned checksum
+    ///
+    /// Registries without a pinned checksum are rejected when the security
+    /// policy requires checksums for their trust level.
+    fn verify_index_checksum(registry: &Registry, content: &str) -> Result<()> {
+        if let Some(expected) = &registry.index_checksum {
+            crate::security::checksum::verify_content_sha256(content.as_bytes(), expected)
+                .context(format!(
+                    ".repo file served by registry '{}' does not match its pinned checksum",
+                    registry.name
+                ))?;
+            log::debug!("Registry '{}' index checksum verified", registry.name);
+            return Ok(());
+        }
+
+        let policy = SecurityConfig::load().unwrap_or_default().registries;
+        let required = match registry.trust_level {
+            TrustLevel::Public => policy.require_checksums_public,
+            TrustLevel::Private => policy.require_checksums_private,
+        };
+
+        if required {
+            return Err(OraError::SecurityPolicyViolation(format!(
+                "registry '{}' has no index_checksum pinned for its .repo file.\n\
+                 Current content hash: sha256:{}\n\
+                 Verify it, then set index_checksum for this registry in config.toml \
+                 (or re-add it with --index-checksum <sha256>)",
+                registry.name,
+                crate::security::checksum::sha256_hex(content.as_bytes())
+            ))
+            .into());
+        }
+
+        log::warn!(
+            "Registry '{}' .repo file is not integrity-checked (no index_checksum)",
+            registry.name
+        );
+        Ok(())
+    }
+
+    fn git_clone(
+        url: &str,
+        dest: &PathBuf,
+        branch: Option<&str>,
+        gpg_key: Option<&str>,
+    ) -> Result<()> {
         if let Some(br) = branch {
             log::debug!("Cloning {} to {:?} (branch: {})", url, dest, br);
         } else {
@@ -181,32 +658,91 @@ impl RegistrySync {
             std::fs::create_dir_all(parent)?;
         }
 
-        let mut builder = git2::build::RepoBuilder::new();
+        let progress = ui::create_transfer_progress_bar("Cloning");
+        let result = Self::with_retry("Failed to clone repository", url, || {
+            let mut builder = git2::build::RepoBuilder::new();
+            let mut fetch_options = Self::fetch_options_with_progress(&progress)
+                .map_err(|e| git2::Error::from_str(&format!("{:#}", e)))?;
 
-        // Use shallow clone (depth=1) to protect against git bombs.
-        // file:// URLs don't support shallow clones in git2; size check below
-        // still protects against local git bombs.
-        if !url.starts_with("file://") {
-            let mut fetch_options = git2::FetchOptions::new();
-            fetch_options.depth(1);
+            // Use shallow clone (depth=1) to protect against git bombs.
+            // file:// URLs don't support shallow clones in git2; size check below
+            // still protects against local git bombs.
+            if !url.starts_with("file://") {
+                fetch_options.depth(1);
+                log::debug!("Using shallow clone (depth=1) for security");
+            } else {
+                log::debug!("Skipping depth=1 for file:// URL (not supported by git2)");
+            }
             builder.fetch_options(fetch_options);
-            log::debug!("Using shallow clone (depth=1) for security");
-        } else {
-            log::debug!("Skipping depth=1 for file:// URL (not supported by git2)");
-        }
 
-        // Set branch if specified
-        if let Some(br) = branch {
-            builder.branch(br);
-        }
+            // Set branch if specified
+            if let Some(br) = branch {
+                builder.branch(br);
+            }
 
-        builder
-            .clone(url, dest)
-            .context("Failed to clone repository")?;
+            let result = builder.clone(url, dest);
+            // Don't leave a partial clone behind for the retry (or the next sync)
+            if result.is_err() && dest.exists() {
+                let _ = std::fs::remove_dir_all(dest);
+            }
+            result.map(|_| ())
+        });
+        progress.finish_and_clear();
+        result?;
 
         // Check repository size after cloning
         Self::check_repo_size(dest)?;
 
+        if let Some(gpg_key) = gpg_key {
+            let verified = git2::Repository::open(dest)
+                .context("Failed to open the cloned registry")
+                .and_then(|repo| {
+                    let head = repo
+                        .head()
+                        .and_then(|head| head.peel_to_commit())
+                        .context("Failed to read the cloned HEAD commit")?
+                        .id();
+                    Self::verify_commit_signature(&repo, head, gpg_key)
+                });
+            if let Err(e) = verified {
+                // Never leave an unverified registry behind for lookups
+                let _ = std::fs::remove_dir_all(dest);
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fails unless `commit` is signed by `gpg_key`
+    ///
+    /// Registries with a `gpg_key` must sign the commits they publish; the tip
+    /// of the synced branch is checked before Ora uses it.
+    fn verify_commit_signature(
+        repo: &git2::Repository,
+        commit: git2::Oid,
+        gpg_key: &str,
+    ) -> Result<()> {
+        let (signature, signed_data) = repo
+            .extract_signature(&commit, None)
+            .context(format!("Registry commit {} is not signed", commit))?;
+        gpg::verify_detached(&signed_data, &signature, gpg_key).context(format!(
+            "Registry commit {} is not signed by the registry's gpg_key",
+            commit
+        ))?;
+        log::debug!("Signature of registry commit {} verified", commit);
+        Ok(())
+    }
+
+    /// Re-points the `origin` remote of a registry clone, e.g. after the
+    /// registry moved to another host
+    pub fn set_origin_url(repo_path: &Path, url: &str) -> Result<()> {
+        let repo = git2::Repository::open(repo_path).context(format!(
+            "Failed to open registry clone at {}",
+            repo_path.display()
+        ))?;
+        repo.remote_set_url("origin", url)
+            .context("Failed to update the clone's origin URL")?;
         Ok(())
     }
 
@@ -215,7 +751,7 @@ impl RegistrySync {
 
         // Calculate .git directory size
         let git_dir = repo_path.join(".git");
-        let size_bytes = Self::dir_size(&git_dir)?;
+        let size_bytes = Cache::dir_size(&git_dir)?;
         let size_mb = size_bytes / (1024 * 1024);
 
         // Load config to get max size limit
@@ -251,72 +787,269 @@ impl RegistrySync {
         Ok(())
     }
 
-    fn dir_size(path: &std::path::Path) -> Result<u64> {
-        let mut total = 0;
-
-        if path.is_dir() {
-            for entry in std::fs::read_dir(path)? {
-                let entry = entry?;
-                let path = entry.path();
-
-                if path.is_dir() {
-                    total += Self::dir_size(&path)?;
-                } else {
-                    total += entry.metadata()?.len();
-                }
-            }
-        }
-
-        Ok(total)
-    }
-
-    fn git_pull(repo_path: &PathBuf, branch: Option<&str>) -> Result<()> {
+    fn git_pull(repo_path: &PathBuf, branch: Option<&str>, gpg_key: Option<&str>) -> Result<()> {
         log::debug!("Pulling latest changes in {:?}", repo_path);
 
         let repo = git2::Repository::open(repo_path).context("Failed to open repository")?;
 
-        // Determine which branch to fetch
-        let branches_to_fetch: Vec<&str> = if let Some(br) = branch {
-            vec![br]
-        } else {
-            vec!["main", "master"]
+        // Use the configured branch, or the remote's default branch
+        let branch_name = match branch {
+            Some(br) => br.to_string(),
+            None => Self::default_branch(&repo)?,
         };
+        log::debug!("Fetching branch '{}'", branch_name);
 
-        // Fetch
+        // Fetch into the remote-tracking ref so it can be compared with the local branch
+        let remote_refname = format!("refs/remotes/origin/{}", branch_name);
+        let refspec = format!("+refs/heads/{}:{}", branch_name, remote_refname);
         let mut remote = repo.find_remote("origin")?;
-        remote.fetch(&branches_to_fetch, None, None)?;
+        let url = remote.url().unwrap_or("origin").to_string();
+        let progress = ui::create_transfer_progress_bar("Fetching");
+        let result = Self::with_retry(
+            &format!("Failed to fetch branch '{}'", branch_name),
+            &url,
+            || {
+                let mut fetch_options = Self::fetch_options_with_progress(&progress)
+                    .map_err(|e| git2::Error::from_str(&format!("{:#}", e)))?;
+                remote.fetch(&[&refspec], Some(&mut fetch_options), None)
+            },
+        );
+        progress.finish_and_clear();
+        result?;
 
-        // Get reference to FETCH_HEAD
-        let fetch_head = repo.find_reference("FETCH_HEAD")?;
-        let fetch_commit = repo.reference_to_annotated_commit(&fetch_head)?;
+        let remote_ref = repo.find_reference(&remote_refname).context(format!(
+            "Branch '{}' not found on remote 'origin'",
+            branch_name
+        ))?;
+        let fetch_commit = repo.reference_to_annotated_commit(&remote_ref)?;
+        if let Some(gpg_key) = gpg_key {
+            Self::verify_commit_signature(&repo, fetch_commit.id(), gpg_key)?;
+        }
 
-        // Merge
-        let analysis = repo.merge_analysis(&[&fetch_commit])?;
+        let local_refname = format!("refs/heads/{}", branch_name);
+        let mut reference = match repo.find_reference(&local_refname) {
+            Ok(reference) => reference,
+            Err(_) => {
+                // Branch not checked out locally yet (e.g. configured branch changed)
+                log::debug!("Creating local branch '{}'", branch_name);
+                repo.reference(
+                    &local_refname,
+                    fetch_commit.id(),
+                    false,
+                    "Create branch from origin",
+                )?
+            }
+        };
+
+        let analysis = repo.merge_analysis_for_ref(&reference, &[&fetch_commit])?;
 
         if analysis.0.is_up_to_date() {
             log::debug!("Already up to date");
         } else if analysis.0.is_fast_forward() {
-            // Fast-forward merge
-            // Use specified branch or try main/master
-            let branch_name = branch.unwrap_or("main");
-            let refname = format!("refs/heads/{}", branch_name);
-            let mut reference = repo.find_reference(&refname).or_else(|_| {
-                // Fallback to master if main doesn't exist
-                if branch.is_none() {
-                    repo.find_reference("refs/heads/master")
-                } else {
-                    Err(git2::Error::from_str("Branch not found"))
-                }
-            })?;
             reference.set_target(fetch_commit.id(), "Fast-forward")?;
-            repo.set_head(
-                reference
-                    .name()
-                    .ok_or_else(|| git2::Error::from_str("Invalid reference name"))?,
-            )?;
-            repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))?;
+        } else {
+            // Local clone diverged from the remote (e.g. force-push upstream).
+            // The registry clone is a cache, so reset it to the remote state.
+            log::warn!(
+                "Local registry clone at {:?} diverged from origin/{}, resetting to remote",
+                repo_path,
+                branch_name
+            );
+            reference.set_target(fetch_commit.id(), "Reset to origin")?;
         }
 
+        repo.set_head(&local_refname)?;
+        repo.checkout_head(Some(git2::build::CheckoutBuilder::default().force()))?;
+
         Ok(())
     }
+
+    /// Runs a git network operation, retrying it once after a transient
+    /// network failure, and explains common failures
+    fn with_retry<T>(
+        action: &str,
+        url: &str,
+        mut operation: impl FnMut() -> std::result::Result<T, git2::Error>,
+    ) -> Result<T> {
+        let result = match operation() {
+            Err(e) if GitFailure::classify(&e) == GitFailure::Network => {
+                log::debug!("Transient git failure for {}: {}", url, e);
+                ui::warning(&format!("Network error reaching {}, retrying...", url));
+                std::thread::sleep(GIT_RETRY_DELAY);
+                operation()
+            }
+            result => result,
+        };
+
+        result.map_err(|e| {
+            let message = match GitFailure::classify(&e).hint(url) {
+                Some(hint) => format!("{}: {}", action, hint),
+                None => action.to_string(),
+            };
+            anyhow::Error::new(e).context(message)
+        })
+    }
+
+    /// Determines the remote's default branch from `origin/HEAD`
+    ///
+    /// Falls back to the currently checked-out branch for clones that
+    /// don't record `origin/HEAD`.
+    fn default_branch(repo: &git2::Repository) -> Result<String> {
+        if let Ok(origin_head) = repo.find_reference("refs/remotes/origin/HEAD") {
+            if let Some(name) = origin_head
+                .symbolic_target()
+                .and_then(|target| target.strip_prefix("refs/remotes/origin/"))
+            {
+                return Ok(name.to_string());
+            }
+        }
+
+        let head = repo.head().context("Failed to read repository HEAD")?;
+        if head.is_branch() {
+            if let Some(name) = head.shorthand() {
+                return Ok(name.to_string());
+            }
+        }
+
+        anyhow::bail!(
+            "Could not determine default branch of registry at {:?}. \
+             Set one explicitly with 'ora registry add --branch <branch>'",
+            repo.path()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(code: git2::ErrorCode, message: &str) -> git2::Error {
+        git2::Error::new(code, git2::ErrorClass::Http, message)
+    }
+
+    #[test]
+    fn test_classify_git_errors() {
+        use git2::ErrorCode;
+
+        let cases = [
+            (
+                ErrorCode::Auth,
+                "remote authentication required",
+                GitFailure::AuthRequired,
+            ),
+            (
+                ErrorCode::GenericError,
+                "unexpected http status code: 401",
+                GitFailure::AuthRequired,
+            ),
+            (
+                ErrorCode::GenericError,
+                "unexpected http status code: 404",
+                GitFailure::NotFound,
+            ),
+            (
+                ErrorCode::GenericError,
+                "operation timed out",
+                GitFailure::Network,
+            ),
+            (
+                ErrorCode::GenericError,
+                "failed to resolve address for example.com",
+                GitFailure::Network,
+            ),
+            (
+                ErrorCode::GenericError,
+                "server does not support shallow fetches",
+                GitFailure::ShallowUnsupported,
+            ),
+            (
+                ErrorCode::GenericError,
+                "invalid pack file",
+                GitFailure::Other,
+            ),
+        ];
+        for (code, message, expected) in cases {
+            assert_eq!(
+                GitFailure::classify(&error(code, message)),
+                expected,
+                "{}",
+                message
+            );
+        }
+    }
+
+    #[test]
+    fn test_not_found_hint_mentions_credentials_for_https_git_urls() {
+        let hint = GitFailure::NotFound
+            .hint("https://github.com/org/private.git")
+            .unwrap();
+        assert!(hint.contains("private repository"));
+        assert!(hint.contains("credential.helper"));
+
+        let hint = GitFailure::NotFound.hint("file:///tmp/registry").unwrap();
+        assert!(!hint.contains("credential"));
+        assert!(GitFailure::Other
+            .hint("https://example.com/r.git")
+            .is_none());
+    }
+
+    #[test]
+    fn test_with_retry_retries_network_failures_once() {
+        let mut calls = 0;
+        let result = RegistrySync::with_retry("Failed to clone repository", "file:///r", || {
+            calls += 1;
+            if calls == 1 {
+                Err(error(
+                    git2::ErrorCode::GenericError,
+                    "connection reset by peer",
+                ))
+            } else {
+                Ok(())
+            }
+        });
+        assert!(result.is_ok());
+        assert_eq!(calls, 2);
+
+        let mut calls = 0;
+        let result: Result<()> =
+            RegistrySync::with_retry("Failed to clone repository", "file:///r", || {
+                calls += 1;
+                Err(error(
+                    git2::ErrorCode::GenericError,
+                    "unexpected http status code: 404",
+                ))
+            });
+        assert_eq!(calls, 1);
+        assert!(format!("{:#}", result.unwrap_err()).contains("was not found"));
+    }
+
+    #[test]
+    fn test_unsigned_registry_commit_is_refused() {
+        let temp = tempfile::tempdir().unwrap();
+        let repo = git2::Repository::init(temp.path()).unwrap();
+        let tree = repo
+            .find_tree(repo.treebuilder(None).unwrap().write().unwrap())
+            .unwrap();
+        let author = git2::Signature::now("Ora Test", "test@example.com").unwrap();
+        let key = include_str!("../../tests/fixtures/gpg/release-key.asc");
+
+        let unsigned = repo
+            .commit(Some("HEAD"), &author, &author, "Add packages", &tree, &[])
+            .unwrap();
+        let err = RegistrySync::verify_commit_signature(&repo, unsigned, key).unwrap_err();
+        assert!(err.to_string().contains("is not signed"));
+
+        // A signature that isn't by the registry key
+        let content = repo
+            .commit_create_buffer(&author, &author, "Add packages", &tree, &[])
+            .unwrap();
+        let forged = repo
+            .commit_signed(
+                content.as_str().unwrap(),
+                include_str!("../../tests/fixtures/gpg/SHA256SUMS.asc"),
+                None,
+            )
+            .unwrap();
+        assert!(RegistrySync::verify_commit_signature(&repo, forged, key).is_err());
+    }
 }
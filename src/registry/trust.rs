@@ -0,0 +1,138 @@
+//! Trust-on-first-use storage for registry signing keys
+//!
+//! The first sync of a registry with a `gpg_key` asks the user to confirm the
+//! key's fingerprint, which is then pinned in `trusted_keys.toml`. Later syncs
+//! refuse a different key until it is trusted again with `ora registry trust`,
+//! and verify the registry's signed commits or index against the pinned key.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::security::gpg::{normalize_fingerprint, public_key_fingerprint};
+use crate::storage::paths::Paths;
+
+/// A registry key the user accepted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrustedKey {
+    pub fingerprint: String,
+    pub trusted_at: DateTime<Utc>,
+}
+
+/// How a registry's configured key compares with the pinned one
+#[derive(Debug, PartialEq, Eq)]
+pub enum KeyTrust {
+    Trusted,
+    /// No key pinned for this registry yet
+    Unknown,
+    /// A different key was pinned before
+    Changed {
+        trusted: String,
+    },
+}
+
+/// Pinned registry keys, by registry name
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TrustStore {
+    #[serde(default)]
+    pub keys: BTreeMap<String, TrustedKey>,
+}
+
+impl TrustStore {
+    pub fn load() -> Result<Self> {
+        let path = Paths::trusted_keys_file()?;
+        match std::fs::read_to_string(&path) {
+            Ok(content) => toml::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+        }
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let path = Paths::trusted_keys_file()?;
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let content = toml::to_string_pretty(self).context("Failed to serialize trusted keys")?;
+        std::fs::write(&path, content)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn check(&self, registry: &str, fingerprint: &str) -> KeyTrust {
+        match self.keys.get(registry) {
+            Some(key) if key.fingerprint == fingerprint => KeyTrust::Trusted,
+            Some(key) => KeyTrust::Changed {
+                trusted: key.fingerprint.clone(),
+            },
+            None => KeyTrust::Unknown,
+        }
+    }
+
+    pub fn trust(&mut self, registry: &str, fingerprint: &str) {
+        self.keys.insert(
+            registry.to_string(),
+            TrustedKey {
+                fingerprint: fingerprint.to_string(),
+                trusted_at: Utc::now(),
+            },
+        );
+    }
+
+    /// Drops the pinned key of a registry; returns whether one was pinned
+    pub fn forget(&mut self, registry: &str) -> bool {
+        self.keys.remove(registry).is_some()
+    }
+}
+
+/// OpenPGP fingerprint of a registry's `gpg_key`, an armored public key
+///
+/// Fails without the `gpg` feature, since the key could not verify anything.
+pub fn key_fingerprint(gpg_key: &str) -> Result<String> {
+    let fingerprint = public_key_fingerprint(gpg_key.trim().as_bytes())
+        .context("Invalid gpg_key; it must be the registry's armored public key")?;
+    Ok(normalize_fingerprint(&fingerprint))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "gpg")]
+    #[test]
+    fn test_key_fingerprint() {
+        let key = include_str!("../../tests/fixtures/gpg/release-key.asc");
+        assert_eq!(
+            key_fingerprint(key).unwrap(),
+            "43FA23D18744084E658ADF547725D6577EEB7A95"
+        );
+        // A bare fingerprint can't verify signatures
+        assert!(key_fingerprint("43FA23D18744084E658ADF547725D6577EEB7A95").is_err());
+    }
+
+    #[cfg(not(feature = "gpg"))]
+    #[test]
+    fn test_key_fingerprint_needs_gpg_feature() {
+        let key = include_str!("../../tests/fixtures/gpg/release-key.asc");
+        assert!(key_fingerprint(key).is_err());
+    }
+
+    #[test]
+    fn test_trust_store_check() {
+        let mut store = TrustStore::default();
+        assert_eq!(store.check("main", "AAAA"), KeyTrust::Unknown);
+
+        store.trust("main", "AAAA");
+        assert_eq!(store.check("main", "AAAA"), KeyTrust::Trusted);
+        assert_eq!(
+            store.check("main", "BBBB"),
+            KeyTrust::Changed {
+                trusted: "AAAA".to_string()
+            }
+        );
+
+        assert!(store.forget("main"));
+        assert_eq!(store.check("main", "BBBB"), KeyTrust::Unknown);
+    }
+}
@@ -1,10 +1,118 @@
 use anyhow::{Context, Result};
-use chrono::Utc;
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
 use tokio::fs::OpenOptions;
 use tokio::io::AsyncWriteExt;
 
 use crate::storage::paths::Paths;
 
+/// Kind of operation recorded in the audit log
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditAction {
+    Install,
+    Uninstall,
+    Update,
+    Security,
+}
+
+/// Whether the recorded operation went through
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditOutcome {
+    Success,
+    Failure,
+}
+
+/// One line of the audit log (stored as a JSON object per line)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub timestamp: DateTime<Utc>,
+    pub action: AuditAction,
+    pub package: String,
+    pub version: String,
+    /// Version replaced by an update
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_version: Option<String>,
+    /// Where the package came from (`registry:<name>`, `file:<path>`, `local`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Login of the user who ran Ora
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user: Option<String>,
+    pub outcome: AuditOutcome,
+    #[serde(default)]
+    pub allow_insecure: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub details: Option<String>,
+}
+
+impl AuditEvent {
+    fn new(action: AuditAction, package: &str, version: &str, success: bool) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            action,
+            package: package.to_string(),
+            version: version.to_string(),
+            previous_version: None,
+            source: None,
+            user: current_user(),
+            outcome: if success {
+                AuditOutcome::Success
+            } else {
+                AuditOutcome::Failure
+            },
+            allow_insecure: false,
+            details: None,
+        }
+    }
+}
+
+/// Output format of `ora security audit`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum AuditFormat {
+    /// One line per event followed by a summary (default)
+    Text,
+    /// A JSON object with `events` and `summary`
+    Json,
+}
+
+/// Event counts reported by `ora security audit`
+#[derive(Debug, Default, PartialEq, Eq, Serialize)]
+pub struct AuditSummary {
+    pub total: usize,
+    pub installs: usize,
+    pub uninstalls: usize,
+    pub updates: usize,
+    pub failures: usize,
+    pub insecure: usize,
+}
+
+impl AuditSummary {
+    pub fn from_events(events: &[AuditEvent]) -> Self {
+        let mut summary = Self {
+            total: events.len(),
+            ..Default::default()
+        };
+        for event in events {
+            match event.action {
+                AuditAction::Install => summary.installs += 1,
+                AuditAction::Uninstall => summary.uninstalls += 1,
+                AuditAction::Update => summary.updates += 1,
+                AuditAction::Security => {}
+            }
+            if event.outcome == AuditOutcome::Failure {
+                summary.failures += 1;
+            }
+            if event.allow_insecure {
+                summary.insecure += 1;
+            }
+        }
+        summary
+    }
+}
+
 pub struct AuditLogger;
 
 impl AuditLogger {
@@ -16,44 +124,44 @@ impl AuditLogger {
         &self,
         package: &str,
         version: &str,
-        registry: &str,
+        source: &str,
+        allow_insecure: bool,
         success: bool,
     ) -> Result<()> {
-        let message = format!(
-            "[{}] INSTALL package={} version={} registry={} success={}",
-            Utc::now().to_rfc3339(),
-            package,
-            version,
-            registry,
-            success
-        );
-        self.write_log(&message).await
+        let mut event = AuditEvent::new(AuditAction::Install, package, version, success);
+        event.source = Some(source.to_string());
+        event.allow_insecure = allow_insecure;
+        self.write_event(&event).await
     }
 
     pub async fn log_uninstall(&self, package: &str, version: &str, success: bool) -> Result<()> {
-        let message = format!(
-            "[{}] UNINSTALL package={} version={} success={}",
-            Utc::now().to_rfc3339(),
-            package,
-            version,
-            success
-        );
-        self.write_log(&message).await
+        let event = AuditEvent::new(AuditAction::Uninstall, package, version, success);
+        self.write_event(&event).await
+    }
+
+    pub async fn log_update(
+        &self,
+        package: &str,
+        previous_version: &str,
+        version: &str,
+        allow_insecure: bool,
+        success: bool,
+    ) -> Result<()> {
+        let mut event = AuditEvent::new(AuditAction::Update, package, version, success);
+        event.previous_version = Some(previous_version.to_string());
+        event.allow_insecure = allow_insecure;
+        self.write_event(&event).await
     }
 
     /// Reserved for future use when security event logging is implemented.
     #[allow(dead_code)]
     pub async fn log_security_event(&self, event_type: &str, details: &str) -> Result<()> {
-        let message = format!(
-            "[{}] SECURITY event={} details={}",
-            Utc::now().to_rfc3339(),
-            event_type,
-            details
-        );
-        self.write_log(&message).await
+        let mut event = AuditEvent::new(AuditAction::Security, "", "", true);
+        event.details = Some(format!("{}: {}", event_type, details));
+        self.write_event(&event).await
     }
 
-    async fn write_log(&self, message: &str) -> Result<()> {
+    async fn write_event(&self, event: &AuditEvent) -> Result<()> {
         let log_file = Paths::audit_log_file()?;
 
         // Ensure parent directory exists
@@ -68,10 +176,169 @@ impl AuditLogger {
             .await
             .context("Failed to open audit log")?;
 
-        file.write_all(message.as_bytes()).await?;
+        let line = serde_json::to_string(event).context("Failed to serialize audit event")?;
+        file.write_all(line.as_bytes()).await?;
         file.write_all(b"\n").await?;
         file.sync_all().await?;
 
         Ok(())
     }
 }
+
+/// Reads every event of an audit log, oldest first
+///
+/// Lines written by older versions (`[<time>] INSTALL package=... success=...`)
+/// are still understood; lines that can't be parsed are skipped.
+pub fn read_events(path: &Path) -> Result<Vec<AuditEvent>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read audit log {}", path.display()))
+        }
+    };
+
+    Ok(content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| {
+            let event = serde_json::from_str(line)
+                .ok()
+                .or_else(|| parse_legacy_line(line));
+            if event.is_none() {
+                log::debug!("Skipping unreadable audit log line: {}", line);
+            }
+            event
+        })
+        .collect())
+}
+
+/// Parses the `key=value` lines written before the audit log switched to JSON
+fn parse_legacy_line(line: &str) -> Option<AuditEvent> {
+    let rest = line.strip_prefix('[')?;
+    let (timestamp, rest) = rest.split_once("] ")?;
+    let timestamp = DateTime::parse_from_rfc3339(timestamp)
+        .ok()?
+        .with_timezone(&Utc);
+
+    let mut words = rest.split_whitespace();
+    let action = match words.next()? {
+        "INSTALL" => AuditAction::Install,
+        "UNINSTALL" => AuditAction::Uninstall,
+        "SECURITY" => AuditAction::Security,
+        _ => return None,
+    };
+    let fields: std::collections::HashMap<&str, &str> =
+        words.filter_map(|word| word.split_once('=')).collect();
+
+    Some(AuditEvent {
+        timestamp,
+        action,
+        package: fields.get("package").unwrap_or(&"").to_string(),
+        version: fields.get("version").unwrap_or(&"").to_string(),
+        previous_version: None,
+        source: fields.get("registry").map(|s| s.to_string()),
+        user: None,
+        outcome: match fields.get("success") {
+            Some(&"false") => AuditOutcome::Failure,
+            _ => AuditOutcome::Success,
+        },
+        allow_insecure: false,
+        details: fields.get("details").map(|s| s.to_string()),
+    })
+}
+
+/// Parses a `--since` value: a relative age (`30d`, `12h`, `2w`), a date
+/// (`2026-01-31`) or an RFC 3339 timestamp
+pub fn parse_since(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let value = value.trim();
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc());
+    }
+
+    let invalid = || {
+        anyhow::anyhow!(
+            "Invalid --since value '{}' (use e.g. 30d, 12h, 2w or 2026-01-31)",
+            value
+        )
+    };
+    let unit_at = value.len().checked_sub(1).ok_or_else(invalid)?;
+    let (amount, unit) = value.split_at(unit_at);
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    let age = match unit {
+        "h" => chrono::Duration::try_hours(amount),
+        "d" => chrono::Duration::try_days(amount),
+        "w" => chrono::Duration::try_weeks(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)?;
+    Ok(now - age)
+}
+
+/// Login name of the user running Ora, if known
+fn current_user() -> Option<String> {
+    ["USER", "USERNAME", "LOGNAME"]
+        .iter()
+        .find_map(|var| std::env::var(var).ok().filter(|v| !v.is_empty()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_since() {
+        let now = DateTime::parse_from_rfc3339("2026-03-31T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(
+            parse_since("30d", now).unwrap(),
+            DateTime::parse_from_rfc3339("2026-03-01T12:00:00Z").unwrap()
+        );
+        assert_eq!(
+            parse_since("12h", now).unwrap(),
+            DateTime::parse_from_rfc3339("2026-03-31T00:00:00Z").unwrap()
+        );
+        assert_eq!(
+            parse_since("2026-03-01", now).unwrap(),
+            DateTime::parse_from_rfc3339("2026-03-01T00:00:00Z").unwrap()
+        );
+        assert!(parse_since("30x", now).is_err());
+        assert!(parse_since("", now).is_err());
+    }
+
+    #[test]
+    fn test_read_events_mixed_formats() {
+        let temp = tempfile::tempdir().unwrap();
+        let path = temp.path().join("audit.log");
+        let mut event = AuditEvent::new(AuditAction::Update, "fd", "10.2.0", false);
+        event.previous_version = Some("10.1.0".to_string());
+        event.allow_insecure = true;
+        std::fs::write(
+            &path,
+            format!(
+                "[2026-01-05T10:00:00+00:00] INSTALL package=rg version=14.1.0 registry=registry:main success=true\n\
+                 not an event\n\
+                 {}\n",
+                serde_json::to_string(&event).unwrap()
+            ),
+        )
+        .unwrap();
+
+        let events = read_events(&path).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].package, "rg");
+        assert_eq!(events[0].source.as_deref(), Some("registry:main"));
+        assert_eq!(events[1], event);
+
+        let summary = AuditSummary::from_events(&events);
+        assert_eq!(summary.installs, 1);
+        assert_eq!(summary.updates, 1);
+        assert_eq!(summary.failures, 1);
+        assert_eq!(summary.insecure, 1);
+    }
+}
@@ -56,6 +56,36 @@ pub async fn compute_checksum(file_path: &Path, algorithm: &ChecksumAlgorithm) -
     Ok(hash)
 }
 
+/// Verify in-memory content against an expected SHA-256 hash
+///
+/// The expected hash may be prefixed with "sha256:".
+pub fn verify_content_sha256(content: &[u8], expected_hash: &str) -> Result<()> {
+    let expected_hash = expected_hash.trim();
+    let expected_hash = expected_hash
+        .strip_prefix("sha256:")
+        .unwrap_or(expected_hash)
+        .to_lowercase();
+    let computed_hash = sha256_hex(content);
+
+    if computed_hash != expected_hash {
+        log::error!(
+            "Checksum mismatch! Expected: {}, Got: {}",
+            expected_hash,
+            computed_hash
+        );
+        return Err(OraError::ChecksumMismatch.into());
+    }
+
+    Ok(())
+}
+
+/// Hex-encoded SHA-256 of in-memory content
+pub fn sha256_hex(content: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(content);
+    format!("{:x}", hasher.finalize())
+}
+
 pub fn parse_checksum_file(content: &str, filename: &str) -> Option<String> {
     // Parse checksums in format: "hash  filename" or "hash *filename"
     for line in content.lines() {
@@ -118,6 +148,14 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_verify_content_sha256() {
+        let hash = sha256_hex(b"name = \"jq\"");
+        assert!(verify_content_sha256(b"name = \"jq\"", &hash).is_ok());
+        assert!(verify_content_sha256(b"name = \"jq\"", &format!("sha256:{}", hash)).is_ok());
+        assert!(verify_content_sha256(b"name = \"evil\"", &hash).is_err());
+    }
+
     #[test]
     fn test_parse_checksum_file_not_found() {
         let content = "abc123def456  example.tar.gz";
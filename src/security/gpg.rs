@@ -1,36 +1,191 @@
 use anyhow::Result;
 use std::path::Path;
 
-/// Verify GPG signature of a file
+/// Verifies a detached GPG signature of a file against `public_key`
 ///
-/// **SECURITY WARNING**: GPG verification is not yet implemented.
-/// This function will FAIL by default to prevent false sense of security.
+/// The signature and key may be armored or binary. Any key of the
+/// certificate (or keyring) that is valid under the standard policy may have
+/// made the signature.
+#[cfg(feature = "gpg")]
+pub async fn verify_signature(
+    file_path: &Path,
+    signature_path: &Path,
+    public_key: &str,
+) -> Result<()> {
+    use anyhow::Context;
+
+    let data = tokio::fs::read(file_path)
+        .await
+        .context(format!("Failed to read {}", file_path.display()))?;
+    let signature = tokio::fs::read(signature_path)
+        .await
+        .context(format!("Failed to read {}", signature_path.display()))?;
+
+    verify_detached(&data, &signature, public_key).context(format!(
+        "GPG signature of {} is not valid",
+        file_path.display()
+    ))?;
+
+    log::debug!("GPG signature of {} verified", file_path.display());
+    Ok(())
+}
+
+/// Verifies a detached GPG signature of `data` against `public_key`
+#[cfg(feature = "gpg")]
+pub fn verify_detached(data: &[u8], signature: &[u8], public_key: &str) -> Result<()> {
+    use anyhow::Context;
+    use sequoia_openpgp::cert::CertParser;
+    use sequoia_openpgp::parse::stream::DetachedVerifierBuilder;
+    use sequoia_openpgp::parse::Parse;
+    use sequoia_openpgp::policy::StandardPolicy;
+
+    let certs = CertParser::from_bytes(public_key.as_bytes())
+        .context("Failed to parse GPG public key")?
+        .collect::<sequoia_openpgp::Result<Vec<_>>>()
+        .context("Failed to parse GPG public key")?;
+    if certs.is_empty() {
+        anyhow::bail!("GPG public key contains no certificate");
+    }
+
+    let policy = StandardPolicy::new();
+    let mut verifier = DetachedVerifierBuilder::from_bytes(signature)
+        .context("Failed to parse GPG signature")?
+        .with_policy(&policy, None, SignatureHelper { certs })
+        .context("Failed to set up GPG signature verification")?;
+    verifier.verify_bytes(data)?;
+    Ok(())
+}
+
+/// Hands the configured certificates to sequoia and accepts a detached
+/// signature when at least one of its signatures checks out
+#[cfg(feature = "gpg")]
+struct SignatureHelper {
+    certs: Vec<sequoia_openpgp::Cert>,
+}
+
+#[cfg(feature = "gpg")]
+impl sequoia_openpgp::parse::stream::VerificationHelper for SignatureHelper {
+    fn get_certs(
+        &mut self,
+        _ids: &[sequoia_openpgp::KeyHandle],
+    ) -> sequoia_openpgp::Result<Vec<sequoia_openpgp::Cert>> {
+        Ok(self.certs.clone())
+    }
+
+    fn check(
+        &mut self,
+        structure: sequoia_openpgp::parse::stream::MessageStructure,
+    ) -> sequoia_openpgp::Result<()> {
+        use sequoia_openpgp::parse::stream::MessageLayer;
+
+        for layer in structure.into_iter() {
+            if let MessageLayer::SignatureGroup { results } = layer {
+                if results.iter().any(|result| result.is_ok()) {
+                    return Ok(());
+                }
+                let reasons = results
+                    .iter()
+                    .filter_map(|result| result.as_ref().err())
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join("; ");
+                return Err(anyhow::anyhow!(
+                    "no good signature from the configured key ({})",
+                    reasons
+                ));
+            }
+        }
+        Err(anyhow::anyhow!("no signature found"))
+    }
+}
+
+/// Verifies a detached GPG signature of a file
 ///
-/// To bypass (NOT RECOMMENDED), use the `--allow-insecure` flag or set
-/// `security.registries.require_gpg_signatures = false` in config.
+/// Verification needs the `gpg` feature; without it this always fails rather
+/// than letting an unverified file through.
+#[cfg(not(feature = "gpg"))]
 pub async fn verify_signature(
     file_path: &Path,
     _signature_path: &Path,
-    _public_key: Option<&str>,
+    _public_key: &str,
 ) -> Result<()> {
-    // SECURITY: Fail loudly instead of silently succeeding
-    log::error!("❌ GPG signature verification is NOT IMPLEMENTED");
-    log::error!("File: {:?}", file_path);
-    log::error!("GPG verification cannot protect against tampered packages.");
-
+    log::error!(
+        "Cannot verify the GPG signature of {}: Ora was built without the 'gpg' feature",
+        file_path.display()
+    );
     anyhow::bail!(
-        "GPG signature verification is not yet implemented. \
-         This is a critical security feature that prevents package tampering. \
+        "GPG signature verification requires Ora to be built with the 'gpg' feature. \
          \n\nOptions:\
-         \n  1. Wait for GPG implementation (recommended)\
+         \n  1. Install a build of Ora with the 'gpg' feature enabled (recommended)\
          \n  2. Use checksum-only verification (less secure)\
-         \n  3. Use --allow-insecure flag (NOT RECOMMENDED for production)\
-         \n\nTo disable this check globally, set in ~/.config/ora/security.toml:\
-         \n  [registries]\
-         \n  require_gpg_signatures = false"
+         \n  3. Use --allow-insecure flag (NOT RECOMMENDED for production)"
     );
 }
 
+/// Verifies a detached GPG signature of `data`
+///
+/// Without the `gpg` feature this always fails, like [`verify_signature`].
+#[cfg(not(feature = "gpg"))]
+pub fn verify_detached(_data: &[u8], _signature: &[u8], _public_key: &str) -> Result<()> {
+    anyhow::bail!("GPG signature verification requires Ora to be built with the 'gpg' feature");
+}
+
+/// Fingerprint of the primary key in armored or binary OpenPGP key material
+#[cfg(feature = "gpg")]
+pub fn public_key_fingerprint(key_data: &[u8]) -> Result<String> {
+    use anyhow::Context;
+    use sequoia_openpgp::parse::Parse;
+
+    let cert =
+        sequoia_openpgp::Cert::from_bytes(key_data).context("Failed to parse GPG public key")?;
+    Ok(cert.fingerprint().to_hex())
+}
+
+/// Fingerprint of the primary key in armored or binary OpenPGP key material
+///
+/// Parsing keys needs the `gpg` feature; without it this always fails, so a
+/// pinned fingerprint is never silently skipped.
+#[cfg(not(feature = "gpg"))]
+pub fn public_key_fingerprint(_key_data: &[u8]) -> Result<String> {
+    anyhow::bail!("Checking a GPG key fingerprint requires Ora to be built with the 'gpg' feature");
+}
+
+/// Uppercase hex of a fingerprint, without spaces or a `0x` prefix
+pub fn normalize_fingerprint(fingerprint: &str) -> String {
+    fingerprint
+        .trim()
+        .trim_start_matches("0x")
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>()
+        .to_ascii_uppercase()
+}
+
+/// Whether `fingerprint` looks like a full v4 (40 hex) or v6 (64 hex) fingerprint
+pub fn is_valid_fingerprint(fingerprint: &str) -> bool {
+    let fingerprint = normalize_fingerprint(fingerprint);
+    matches!(fingerprint.len(), 40 | 64) && fingerprint.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Fails unless `key_data` is the key with the `expected` fingerprint
+///
+/// Run before the key is trusted for anything, so a hijacked `public_key_url`
+/// serving another key aborts the install.
+pub fn verify_key_fingerprint(key_data: &[u8], expected: &str) -> Result<()> {
+    let actual = normalize_fingerprint(&public_key_fingerprint(key_data)?);
+    let expected = normalize_fingerprint(expected);
+    if actual != expected {
+        anyhow::bail!(
+            "GPG public key fingerprint mismatch: expected {}, got {}. \
+             The key may have been replaced; refusing to trust it.",
+            expected,
+            actual
+        );
+    }
+    log::debug!("GPG public key fingerprint {} matches", actual);
+    Ok(())
+}
+
 /// Import a GPG public key
 ///
 /// **NOT IMPLEMENTED**: This function will fail.
@@ -52,3 +207,83 @@ pub fn check_key_revoked(_fingerprint: &str, _revoked_keys: &[String]) -> bool {
     log::warn!("GPG key revocation check not implemented, assuming key is valid");
     false
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_fingerprint() {
+        assert_eq!(
+            normalize_fingerprint(" 0x1234 5678 9abc def0 1234  5678 9ABC DEF0 1234 5678 "),
+            "123456789ABCDEF0123456789ABCDEF012345678"
+        );
+        assert!(is_valid_fingerprint(
+            "1234 5678 9abc def0 1234  5678 9ABC DEF0 1234 5678"
+        ));
+        assert!(!is_valid_fingerprint("ABCD1234"));
+        assert!(!is_valid_fingerprint(
+            "ZZZZ5678 9abc def0 1234  5678 9ABC DEF0 1234 5678"
+        ));
+    }
+
+    #[cfg(feature = "gpg")]
+    const RELEASE_KEY: &str = include_str!("../../tests/fixtures/gpg/release-key.asc");
+    #[cfg(feature = "gpg")]
+    const RELEASE_KEY_FINGERPRINT: &str = "43FA 23D1 8744 084E 658A  DF54 7725 D657 7EEB 7A95";
+
+    #[cfg(feature = "gpg")]
+    #[test]
+    fn test_public_key_fingerprint_of_armored_key() {
+        assert_eq!(
+            public_key_fingerprint(RELEASE_KEY.as_bytes()).unwrap(),
+            normalize_fingerprint(RELEASE_KEY_FINGERPRINT)
+        );
+        assert!(verify_key_fingerprint(RELEASE_KEY.as_bytes(), RELEASE_KEY_FINGERPRINT).is_ok());
+
+        let other = include_str!("../../tests/fixtures/gpg/other-key.asc");
+        let err = verify_key_fingerprint(other.as_bytes(), RELEASE_KEY_FINGERPRINT).unwrap_err();
+        assert!(err.to_string().contains("fingerprint mismatch"));
+    }
+
+    #[cfg(feature = "gpg")]
+    #[tokio::test]
+    async fn test_verify_signature_of_checksum_file() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/gpg");
+        let checksums = fixtures.join("SHA256SUMS");
+        let signature = fixtures.join("SHA256SUMS.asc");
+
+        verify_signature(&checksums, &signature, RELEASE_KEY)
+            .await
+            .expect("Signature made by the release key should verify");
+
+        // Signed by the release key, so another key must not accept it
+        let other = include_str!("../../tests/fixtures/gpg/other-key.asc");
+        assert!(verify_signature(&checksums, &signature, other)
+            .await
+            .is_err());
+
+        // A checksum file changed after signing
+        let temp = tempfile::tempdir().unwrap();
+        let tampered = temp.path().join("SHA256SUMS");
+        let content = std::fs::read_to_string(&checksums).unwrap();
+        std::fs::write(&tampered, content.replace("3b7e", "0000")).unwrap();
+        assert!(verify_signature(&tampered, &signature, RELEASE_KEY)
+            .await
+            .is_err());
+    }
+
+    #[cfg(not(feature = "gpg"))]
+    #[tokio::test]
+    async fn test_verify_signature_fails_without_gpg_feature() {
+        let fixtures = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/gpg");
+        let err = verify_signature(
+            &fixtures.join("SHA256SUMS"),
+            &fixtures.join("SHA256SUMS.asc"),
+            include_str!("../../tests/fixtures/gpg/release-key.asc"),
+        )
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("'gpg' feature"));
+    }
+}
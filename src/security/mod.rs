@@ -2,10 +2,11 @@ pub mod audit;
 pub mod checksum;
 pub mod git;
 pub mod gpg;
+pub mod scan;
 pub mod tls;
 pub mod warnings;
 
-pub use audit::AuditLogger;
+pub use audit::{AuditFormat, AuditLogger, AuditSummary};
 pub use checksum::{parse_checksum_file, verify_checksum};
 pub use git::validate_git_url;
 pub use gpg::verify_signature;
@@ -0,0 +1,216 @@
+//! Security posture of installed packages, reported by `ora security scan`
+
+use serde::Serialize;
+use std::path::Path;
+
+use crate::config::global::{Registry, TrustLevel};
+use crate::config::installed::InstalledPackage;
+use crate::installer::deployer::Deployer;
+
+/// How serious a finding is; any `High` finding fails the scan
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Not enough was recorded at install time to tell
+    Unknown,
+    Low,
+    Medium,
+    High,
+}
+
+impl Severity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Unknown => "UNKNOWN",
+            Severity::Low => "LOW",
+            Severity::Medium => "MEDIUM",
+            Severity::High => "HIGH",
+        }
+    }
+}
+
+/// One policy violation of an installed package
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Finding {
+    pub package: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Checks an installed package against the security policy
+///
+/// `registries` are the currently configured registries, used to tell
+/// whether the package's registry still exists and how far it is trusted.
+pub fn scan_package(package: &InstalledPackage, registries: &[Registry]) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let mut report = |severity, message: String| {
+        findings.push(Finding {
+            package: package.name.clone(),
+            severity,
+            message,
+        })
+    };
+
+    if package.allow_insecure {
+        report(
+            Severity::High,
+            "installed with --allow-insecure: checksum and signature checks were skipped"
+                .to_string(),
+        );
+    }
+
+    if let Some(name) = package.registry_source.strip_prefix("registry:") {
+        match registries.iter().find(|registry| registry.name == name) {
+            None => report(
+                Severity::Medium,
+                format!(
+                    "installed from registry '{}', which is no longer configured; \
+                     it can't be updated or re-verified",
+                    name
+                ),
+            ),
+            Some(registry) => {
+                let public = matches!(registry.trust_level, TrustLevel::Public);
+                if public && package.checksums.is_empty() && !package.allow_insecure {
+                    report(
+                        Severity::Unknown,
+                        "checksum status unknown (installed before checksums were recorded); \
+                         reinstall to record it"
+                            .to_string(),
+                    );
+                } else if public && !package.has_archive_checksum() && !package.allow_insecure {
+                    report(
+                        Severity::High,
+                        format!(
+                            "installed from public registry '{}' without a recorded checksum",
+                            name
+                        ),
+                    );
+                }
+                if !registry.enabled {
+                    report(
+                        Severity::Low,
+                        format!("installed from registry '{}', which is disabled", name),
+                    );
+                }
+            }
+        }
+    }
+
+    let install_dir = Path::new(&package.install_dir);
+    if install_dir.exists() {
+        match Deployer::find_setuid_files(install_dir) {
+            Ok(files) => {
+                for file in files {
+                    report(
+                        Severity::High,
+                        format!("{} has the SUID or SGID bit set", file.display()),
+                    );
+                }
+            }
+            Err(e) => report(
+                Severity::Low,
+                format!(
+                    "could not check file modes in {}: {}",
+                    install_dir.display(),
+                    e
+                ),
+            ),
+        }
+    } else {
+        report(
+            Severity::Medium,
+            format!("install directory {} is missing", install_dir.display()),
+        );
+    }
+
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn package(registry_source: &str, install_dir: &Path) -> InstalledPackage {
+        InstalledPackage {
+            schema_version: "0.1".to_string(),
+            name: "tool".to_string(),
+            version: "1.0.0".to_string(),
+            installed_at: chrono::Utc::now(),
+            install_mode: "userland".to_string(),
+            install_dir: install_dir.display().to_string(),
+            files: vec![],
+            symlinks: vec![],
+            current_path: None,
+            registry_source: registry_source.to_string(),
+            checksums: HashMap::new(),
+            allow_insecure: false,
+            download_bytes: None,
+            duration_ms: None,
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn registry(name: &str) -> Registry {
+        toml::from_str(&format!(
+            "name = \"{}\"\nurl = \"https://example.com/{}.git\"",
+            name, name
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_scan_package() {
+        let dir = tempfile::tempdir().unwrap();
+        let registries = vec![registry("main")];
+
+        let mut clean = package("registry:main", dir.path());
+        clean
+            .checksums
+            .insert("archive".to_string(), "sha256:abcd".to_string());
+        assert!(scan_package(&clean, &registries).is_empty());
+
+        let mut unverified = package("registry:main", dir.path());
+        unverified
+            .checksums
+            .insert("file:bin/tool".to_string(), "sha256:abcd".to_string());
+        let findings = scan_package(&unverified, &registries);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::High);
+        assert!(findings[0].message.contains("without a recorded checksum"));
+
+        // Older Ora versions recorded no checksums at all
+        let legacy = package("registry:main", dir.path());
+        let findings = scan_package(&legacy, &registries);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::Unknown);
+        assert!(findings[0]
+            .message
+            .contains("installed before checksums were recorded"));
+
+        let mut insecure = package("registry:gone", dir.path());
+        insecure.allow_insecure = true;
+        let severities: Vec<Severity> = scan_package(&insecure, &registries)
+            .into_iter()
+            .map(|finding| finding.severity)
+            .collect();
+        assert_eq!(severities, vec![Severity::High, Severity::Medium]);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_scan_flags_setuid_files() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let binary = dir.path().join("tool");
+        std::fs::write(&binary, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o4755)).unwrap();
+
+        let findings = scan_package(&package("local", dir.path()), &[]);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].severity, Severity::High);
+        assert!(findings[0].message.contains("SUID"));
+    }
+}
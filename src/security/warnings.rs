@@ -1,4 +1,5 @@
 use crate::config::global::GlobalConfig;
+use crate::config::installed::InstalledPackage;
 use crate::config::repo::RepoConfig;
 use crate::error::OraError;
 use anyhow::Result;
@@ -56,6 +57,56 @@ impl SecurityWarningManager {
         Ok(())
     }
 
+    /// Reminders for updating `installed` from the current `.repo` file: how
+    /// it was installed, and how the package's security posture changed since
+    ///
+    /// A `.repo` that itself allows insecure installs gets its warning from
+    /// [`Self::check_and_warn`] during the update's install instead.
+    pub fn update_notices(installed: &InstalledPackage, repo: &RepoConfig) -> Vec<String> {
+        let mut notices = Vec::new();
+        let name = &installed.name;
+
+        if installed.allow_insecure && !repo.security.allow_insecure {
+            notices.push(format!(
+                "'{}' was installed with --allow-insecure, so this update skips checksum and \
+                 signature checks too; reinstall it without the flag to verify it",
+                name
+            ));
+        }
+        match (
+            installed.has_archive_checksum(),
+            repo.security.checksum.is_some(),
+        ) {
+            (false, true) if !installed.allow_insecure => notices.push(format!(
+                "'{}' now declares a checksum; the installed version was never verified against one",
+                name
+            )),
+            (true, false) => notices.push(format!(
+                "'{}' no longer declares a checksum; the update can't be verified like the installed version was",
+                name
+            )),
+            _ => {}
+        }
+
+        let download_urls = repo.source.download.iter().flat_map(|download| {
+            download
+                .url
+                .iter()
+                .chain(download.urls.iter().flat_map(|urls| urls.values()))
+        });
+        let checksum_url = repo.security.checksum.iter().map(|checksum| &checksum.url);
+        if download_urls
+            .chain(checksum_url)
+            .any(|url| url.starts_with("http://"))
+        {
+            notices.push(format!(
+                "'{}' is downloaded over plain HTTP, which anyone on the network path can tamper with",
+                name
+            ));
+        }
+        notices
+    }
+
     fn default_warning_message(package_name: &str) -> String {
         format!(
             "Package '{}' cannot verify checksums or signatures automatically. \
@@ -100,3 +151,89 @@ impl GlobalConfig {
             .unwrap_or(false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn installed(allow_insecure: bool, archive_checksum: bool) -> InstalledPackage {
+        let mut package: InstalledPackage = toml::from_str(
+            r#"
+            name = "tool"
+            version = "1.0.0"
+            installed_at = "2024-01-01T00:00:00Z"
+            install_mode = "userland"
+            install_dir = "/test/dir"
+            files = []
+            symlinks = []
+            registry_source = "registry:main"
+            "#,
+        )
+        .unwrap();
+        package.allow_insecure = allow_insecure;
+        if archive_checksum {
+            package
+                .checksums
+                .insert("archive".to_string(), "sha256:abcd".to_string());
+        }
+        package
+    }
+
+    fn repo(extra: &str) -> RepoConfig {
+        toml::from_str(&format!(
+            r#"
+name = "tool"
+description = "A tool"
+
+[source]
+type = "direct-url"
+
+[install]
+binaries = ["tool"]
+
+{}
+"#,
+            extra
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_update_notices() {
+        let https = r#"
+[source.download]
+url = "https://example.com/tool-{version}.tar.gz"
+"#;
+        let checksum = r#"
+[security.checksum]
+url = "https://example.com/SHA256SUMS"
+algorithm = "sha256"
+"#;
+        let secure = repo(&format!("{}{}", https, checksum));
+        assert!(
+            SecurityWarningManager::update_notices(&installed(false, true), &secure).is_empty()
+        );
+
+        let notices = SecurityWarningManager::update_notices(&installed(true, false), &secure);
+        assert_eq!(notices.len(), 1);
+        assert!(notices[0].contains("--allow-insecure"));
+
+        let notices = SecurityWarningManager::update_notices(&installed(false, false), &secure);
+        assert!(notices[0].contains("now declares a checksum"));
+
+        let insecure_http = repo(
+            r#"
+[source.download]
+url = "http://example.com/tool-{version}.tar.gz"
+
+[security]
+allow_insecure = true
+"#,
+        );
+        let notices =
+            SecurityWarningManager::update_notices(&installed(false, true), &insecure_http);
+        assert_eq!(notices.len(), 2);
+        assert!(notices[0].contains("no longer declares a checksum"));
+        assert!(notices[1].contains("plain HTTP"));
+    }
+}
@@ -1,12 +1,32 @@
 // Cache module for storing temporary downloads and registry data
 use anyhow::Result;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::storage::paths::Paths;
 
+/// Global flag (set from `--keep-downloads` or `resources.keep_downloads`);
+/// when enabled, downloaded archives are never cleaned up automatically
+static KEEP_DOWNLOADS: AtomicBool = AtomicBool::new(false);
+
 pub struct Cache;
 
 impl Cache {
+    /// Enable or disable retention of downloaded archives
+    pub fn set_keep_downloads(enabled: bool) {
+        KEEP_DOWNLOADS.store(enabled, Ordering::SeqCst);
+    }
+
+    /// Check if downloaded archives should be kept
+    pub fn keep_downloads() -> bool {
+        KEEP_DOWNLOADS.load(Ordering::Relaxed)
+    }
+
+    /// Path of the downloads directory
+    pub fn downloads_dir() -> Result<PathBuf> {
+        Ok(Paths::cache_dir()?.join("downloads"))
+    }
+
     pub fn download_path(filename: &str) -> Result<PathBuf> {
         // Validate filename
         if filename.is_empty() {
@@ -28,21 +48,330 @@ impl Cache {
         Ok(downloads_dir.join(filename))
     }
 
+    /// Content-addressed location of an archive whose checksum is known up front
+    ///
+    /// Archives live in `downloads/by-checksum/<hash>/<filename>`, so packages or
+    /// reinstalls fetching identical content share one download.
+    pub fn checksum_download_path(hash: &str, filename: &str) -> Result<PathBuf> {
+        if hash.is_empty() || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            anyhow::bail!("Invalid checksum for download cache: '{}'", hash);
+        }
+        if filename.is_empty() || filename.contains('/') || filename.contains("..") {
+            anyhow::bail!("Invalid filename for download cache: '{}'", filename);
+        }
+
+        let dir = Self::downloads_dir()?
+            .join("by-checksum")
+            .join(hash.to_lowercase());
+        std::fs::create_dir_all(&dir)?;
+        Ok(dir.join(filename))
+    }
+
+    /// Writable location of a registry clone (where syncs clone and pull)
     pub fn registry_path(registry_name: &str) -> Result<PathBuf> {
         let registries_dir = Paths::registries_cache_dir()?;
         std::fs::create_dir_all(&registries_dir)?;
         Ok(registries_dir.join(registry_name))
     }
 
-    pub fn clear_downloads() -> Result<()> {
-        let cache_dir = Paths::cache_dir()?;
-        let downloads_dir = cache_dir.join("downloads");
-        if downloads_dir.exists() {
-            std::fs::remove_dir_all(&downloads_dir)?;
+    /// Index saved by an index-only sync of a registry, next to where its clone would be
+    pub fn registry_index_path(registry_name: &str) -> Result<PathBuf> {
+        let registries_dir = Paths::registries_cache_dir()?;
+        std::fs::create_dir_all(&registries_dir)?;
+        Ok(registries_dir.join(format!("{}.index.json", registry_name)))
+    }
+
+    /// Location to read a registry clone from
+    ///
+    /// The writable cache wins when it holds a clone; otherwise the shared
+    /// registry caches are searched in order. Falls back to the writable path
+    /// when no clone exists anywhere.
+    pub fn find_registry_clone(registry_name: &str, shared_dirs: &[String]) -> Result<PathBuf> {
+        let primary = Self::registry_path(registry_name)?;
+        if primary.exists() {
+            return Ok(primary);
+        }
+
+        for dir in Paths::shared_registry_cache_dirs(shared_dirs) {
+            let candidate = dir.join(registry_name);
+            if candidate.exists() {
+                log::debug!(
+                    "Using shared clone of registry '{}' at {}",
+                    registry_name,
+                    candidate.display()
+                );
+                return Ok(candidate);
+            }
+        }
+
+        Ok(primary)
+    }
+
+    /// Path of the cached version list for a package (used by offline mode)
+    pub fn versions_path(package_name: &str) -> Result<PathBuf> {
+        if package_name.is_empty() || package_name.contains('/') || package_name.contains("..") {
+            anyhow::bail!("Invalid package name for version cache: '{}'", package_name);
+        }
+
+        let versions_dir = Paths::cache_dir()?.join("versions");
+        std::fs::create_dir_all(&versions_dir)?;
+        Ok(versions_dir.join(format!("{}.json", package_name)))
+    }
+
+    /// Total size in bytes of a directory (recursive)
+    pub fn dir_size(path: &Path) -> Result<u64> {
+        Ok(Self::dir_usage(path)?.0)
+    }
+
+    /// Total size in bytes and number of files under a directory (recursive)
+    ///
+    /// Symlinks are counted as files and not followed.
+    pub fn dir_usage(path: &Path) -> Result<(u64, usize)> {
+        let mut total = 0;
+        let mut files = 0;
+
+        if path.is_dir() {
+            for entry in std::fs::read_dir(path)? {
+                let entry = entry?;
+
+                if entry.file_type()?.is_dir() {
+                    let (size, count) = Self::dir_usage(&entry.path())?;
+                    total += size;
+                    files += count;
+                } else {
+                    total += entry.metadata()?.len();
+                    files += 1;
+                }
+            }
+        }
+
+        Ok((total, files))
+    }
+
+    /// Evicts the least recently used downloads until the downloads directory
+    /// fits in `max_bytes`
+    ///
+    /// Extraction directories are neither counted nor evicted. Cached checksum
+    /// and signature files (which offline installs verify against) and `keep`
+    /// (the archive being installed) count towards the budget but are never
+    /// evicted. Returns the evicted files with their sizes.
+    pub fn enforce_download_budget(max_bytes: u64, keep: &Path) -> Result<Vec<(PathBuf, u64)>> {
+        Self::evict_least_recently_used(&Self::downloads_dir()?, max_bytes, keep)
+    }
+
+    fn evict_least_recently_used(
+        downloads_dir: &Path,
+        max_bytes: u64,
+        keep: &Path,
+    ) -> Result<Vec<(PathBuf, u64)>> {
+        let mut candidates = Vec::new();
+        Self::collect_archives(downloads_dir, &mut candidates)?;
+        let mut total: u64 = candidates.iter().map(|(_, _, size)| size).sum();
+        if total <= max_bytes {
+            return Ok(Vec::new());
+        }
+
+        candidates.retain(|(path, _, _)| path != keep && !is_verification_file(path));
+        candidates.sort_by_key(|(_, modified, _)| *modified);
+
+        let mut evicted = Vec::new();
+        for (path, _, size) in candidates {
+            if total <= max_bytes {
+                break;
+            }
+            std::fs::remove_file(&path)?;
+            total = total.saturating_sub(size);
+
+            // Drop the emptied by-checksum/<hash> directory
+            if let Some(parent) = path.parent() {
+                if parent != downloads_dir {
+                    let _ = std::fs::remove_dir(parent);
+                }
+            }
+            evicted.push((path, size));
+        }
+
+        Ok(evicted)
+    }
+
+    /// Files under the downloads directory with their mtime and size, skipping
+    /// extraction directories
+    fn collect_archives(
+        dir: &Path,
+        out: &mut Vec<(PathBuf, std::time::SystemTime, u64)>,
+    ) -> Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let file_type = entry.file_type()?;
+            if file_type.is_dir() {
+                if !entry.file_name().to_string_lossy().ends_with("_extract") {
+                    Self::collect_archives(&entry.path(), out)?;
+                }
+            } else if file_type.is_file() {
+                let metadata = entry.metadata()?;
+                out.push((entry.path(), metadata.modified()?, metadata.len()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Marks a cached download as just used, for least-recently-used eviction
+    pub fn touch(path: &Path) -> Result<()> {
+        std::fs::File::options()
+            .append(true)
+            .open(path)?
+            .set_modified(std::time::SystemTime::now())?;
+        Ok(())
+    }
+
+    /// Automatic cleanup of downloads (on error or interrupt)
+    ///
+    /// Removes what the failed operation leaves behind (extraction directories
+    /// and unfinished `.part` downloads) but keeps cached archives, which are
+    /// verified again before reuse. Does nothing but report the retained path
+    /// when downloads are kept.
+    pub fn cleanup_downloads() -> Result<()> {
+        if Self::keep_downloads() {
+            println!("📦 Downloads kept in {}", Self::downloads_dir()?.display());
+            return Ok(());
+        }
+        let downloads_dir = Self::downloads_dir()?;
+        if downloads_dir.is_dir() {
+            Self::remove_scratch(&downloads_dir)?;
+        }
+        Ok(())
+    }
+
+    fn remove_scratch(dir: &Path) -> Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if entry.file_type()?.is_dir() {
+                if name.ends_with("_extract") {
+                    std::fs::remove_dir_all(entry.path())?;
+                } else {
+                    Self::remove_scratch(&entry.path())?;
+                }
+            } else if name.ends_with(".part") {
+                std::fs::remove_file(entry.path())?;
+            }
         }
         Ok(())
     }
 }
 
+/// Cached checksum files, release notes, signatures and signing keys
+/// (`.checksum`, `.sig`, `.key`)
+fn is_verification_file(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext == "checksum" || ext == "sig" || ext == "key")
+}
+
 // Note: Temporary file/directory guard structs removed
 // Using tempfile crate directly (tempfile::NamedTempFile, tempfile::TempDir) instead
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    fn write_with_age(path: &Path, size: usize, age_secs: u64) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![0u8; size]).unwrap();
+        std::fs::File::options()
+            .append(true)
+            .open(path)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(age_secs))
+            .unwrap();
+    }
+
+    #[test]
+    fn test_enforce_download_budget_evicts_oldest() {
+        let temp = tempfile::tempdir().unwrap();
+        let downloads = temp.path().join("downloads");
+
+        let oldest = downloads.join("by-checksum").join("abc").join("old.tar.gz");
+        let older = downloads.join("older.tar.gz");
+        let current = downloads.join("current.tar.gz");
+        write_with_age(&oldest, 100, 300);
+        write_with_age(&older, 100, 200);
+        write_with_age(&current, 100, 400);
+        write_with_age(&downloads.join("tool_extract").join("tool"), 100, 500);
+
+        let evicted = Cache::evict_least_recently_used(&downloads, 150, &current).unwrap();
+
+        assert_eq!(evicted, vec![(oldest.clone(), 100), (older.clone(), 100)]);
+        assert!(!oldest.parent().unwrap().exists());
+        assert!(current.exists());
+        assert!(downloads.join("tool_extract").join("tool").exists());
+        assert!(Cache::evict_least_recently_used(&downloads, 150, &current)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_enforce_download_budget_keeps_verification_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let downloads = temp.path().join("downloads");
+
+        let checksum = downloads.join("tool.tar.gz.checksum");
+        let signature = downloads.join("tool.tar.gz.checksum.sig");
+        let key = downloads.join("tool.tar.gz.key");
+        let archive = downloads.join("tool.tar.gz");
+        write_with_age(&checksum, 100, 500);
+        write_with_age(&signature, 100, 500);
+        write_with_age(&key, 100, 500);
+        write_with_age(&archive, 100, 100);
+        // Not counted: 400 bytes of downloads fit a 400-byte budget
+        write_with_age(&downloads.join("tool_extract").join("tool"), 1000, 500);
+
+        assert!(
+            Cache::evict_least_recently_used(&downloads, 400, Path::new("/none"))
+                .unwrap()
+                .is_empty()
+        );
+
+        let evicted =
+            Cache::evict_least_recently_used(&downloads, 100, Path::new("/none")).unwrap();
+        assert_eq!(evicted, vec![(archive.clone(), 100)]);
+        assert!(checksum.exists());
+        assert!(signature.exists());
+        assert!(key.exists());
+    }
+
+    #[test]
+    fn test_remove_scratch_keeps_cached_archives() {
+        let temp = tempfile::tempdir().unwrap();
+        let downloads = temp.path().join("downloads");
+
+        let cached = downloads
+            .join("by-checksum")
+            .join("abc")
+            .join("tool.tar.gz");
+        let loose = downloads.join("other.tar.gz");
+        let checksum = downloads.join("other.tar.gz.checksum");
+        let partial = downloads
+            .join("by-checksum")
+            .join("def")
+            .join("new.tar.gz.part");
+        let extract = downloads.join("tool_extract");
+        for path in [
+            &cached,
+            &loose,
+            &checksum,
+            &partial,
+            &extract.join("bin/tool"),
+        ] {
+            write_with_age(path, 10, 0);
+        }
+
+        Cache::remove_scratch(&downloads).unwrap();
+
+        assert!(cached.exists());
+        assert!(loose.exists());
+        assert!(checksum.exists());
+        assert!(!partial.exists());
+        assert!(!extract.exists());
+    }
+}
@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 
 use crate::config::{GlobalConfig, InstalledDatabase};
+use crate::error::OraError;
 use crate::storage::paths::Paths;
 use crate::utils::io_errors::read_file_user_friendly_async;
 
@@ -27,14 +28,19 @@ pub async fn load_global_config() -> Result<GlobalConfig> {
                         return Err(e);
                     }
 
-                    // Save migrated config back to disk
+                    // Save migrated config back to disk (a frozen config is only
+                    // migrated in memory, so reads keep working)
                     if config.config_version != old_version {
                         log::info!(
                             "Config migrated from {} to {}",
                             old_version,
                             config.config_version
                         );
-                        save_global_config(&config).await?;
+                        if config_frozen()? {
+                            log::info!("Configuration is frozen; not saving the migrated config");
+                        } else {
+                            save_global_config(&config).await?;
+                        }
                     }
 
                     Ok(config)
@@ -67,12 +73,64 @@ pub async fn load_global_config() -> Result<GlobalConfig> {
     }
 }
 
+/// Whether the configuration is managed and must not be modified by Ora
+///
+/// Set with `ORA_FROZEN_CONFIG=1` or `frozen = true` in config.toml. The flag
+/// is read from the file on disk rather than from a loaded `GlobalConfig`, so
+/// no command can bypass it by saving a config that doesn't carry it.
+pub fn config_frozen() -> Result<bool> {
+    // The variable can only freeze: unsetting the file's flag needs write access to it
+    if let Ok(value) = std::env::var("ORA_FROZEN_CONFIG") {
+        if matches!(
+            value.trim().to_ascii_lowercase().as_str(),
+            "1" | "true" | "yes"
+        ) {
+            return Ok(true);
+        }
+    }
+
+    let path = Paths::config_file()?;
+    let content = match std::fs::read_to_string(&path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(false),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", path.display()));
+        }
+    };
+    // An unparseable file is reported by load_global_config; here it only
+    // matters whether the flag is set
+    Ok(toml::from_str::<toml::Value>(&content)
+        .ok()
+        .and_then(|value| value.get("frozen").and_then(toml::Value::as_bool))
+        .unwrap_or(false))
+}
+
+/// Fails with a clear error when the configuration is frozen
+///
+/// Called before every write to config.toml or security.toml.
+pub fn ensure_config_writable() -> Result<()> {
+    if config_frozen()? {
+        return Err(OraError::ConfigError(
+            "configuration is managed/frozen and can't be modified by ora \
+             (frozen = true in config.toml, or ORA_FROZEN_CONFIG is set); \
+             ask your administrator to change it"
+                .to_string(),
+        )
+        .into());
+    }
+    Ok(())
+}
+
 /// Save global configuration with user-friendly error messages
 pub async fn save_global_config(config: &GlobalConfig) -> Result<()> {
     use crate::utils::io_errors::write_file_user_friendly_async;
 
+    ensure_config_writable()?;
     Paths::ensure_directories()?;
     let path = Paths::config_file()?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
 
     let content = toml::to_string_pretty(config)
         .context("Failed to serialize global configuration to TOML")?;
@@ -1,75 +1,166 @@
-use crate::config::global::InstallMode;
+use crate::config::global::{InstallMode, InstallSettings};
 use anyhow::{Context, Result};
-use std::path::PathBuf;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+/// Global config file from `--config`, taking precedence over `ORA_CONFIG_DIR`
+static CONFIG_FILE_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
+
+/// Security config file from `--security-config`, taking precedence over `ORA_CONFIG_DIR`
+static SECURITY_CONFIG_FILE_OVERRIDE: OnceLock<PathBuf> = OnceLock::new();
 
 pub struct Paths;
 
 impl Paths {
-    pub fn config_dir() -> Result<PathBuf> {
-        // Check for ORA_CONFIG_DIR env var first (for testing)
-        if let Ok(dir) = std::env::var("ORA_CONFIG_DIR") {
-            return Ok(PathBuf::from(dir));
-        }
+    /// Use `path` as the global config file for the rest of the process
+    pub fn set_config_file_override(path: PathBuf) {
+        let _ = CONFIG_FILE_OVERRIDE.set(path);
+    }
 
-        dirs::config_dir()
-            .context("Failed to get config directory")
-            .map(|p| p.join("ora"))
+    /// Use `path` as the security config file for the rest of the process
+    pub fn set_security_config_file_override(path: PathBuf) {
+        let _ = SECURITY_CONFIG_FILE_OVERRIDE.set(path);
+    }
+
+    /// `ORA_CONFIG_DIR`, else `$XDG_CONFIG_HOME/ora` (`~/.config/ora`) on Linux
+    /// and `~/Library/Application Support/ora` on macOS
+    pub fn config_dir() -> Result<PathBuf> {
+        resolve_dir(
+            std::env::var_os("ORA_CONFIG_DIR"),
+            dirs::config_dir(),
+            "config",
+        )
     }
 
     pub fn config_file() -> Result<PathBuf> {
+        if let Some(path) = CONFIG_FILE_OVERRIDE.get() {
+            return Ok(path.clone());
+        }
         Ok(Self::config_dir()?.join("config.toml"))
     }
 
+    pub fn security_config_file() -> Result<PathBuf> {
+        if let Some(path) = SECURITY_CONFIG_FILE_OVERRIDE.get() {
+            return Ok(path.clone());
+        }
+        Ok(Self::config_dir()?.join("security.toml"))
+    }
+
+    /// Registry signing keys pinned on first sync
+    pub fn trusted_keys_file() -> Result<PathBuf> {
+        Ok(Self::config_dir()?.join("trusted_keys.toml"))
+    }
+
     pub fn installed_db_file() -> Result<PathBuf> {
         Ok(Self::config_dir()?.join("installed.toml"))
     }
 
+    /// `ORA_DATA_DIR`, else `$XDG_DATA_HOME/ora` (`~/.local/share/ora`) on Linux
+    /// and `~/Library/Application Support/ora` on macOS
     pub fn data_dir() -> Result<PathBuf> {
-        // Check for ORA_DATA_DIR env var first (for testing)
-        if let Ok(dir) = std::env::var("ORA_DATA_DIR") {
-            return Ok(PathBuf::from(dir));
-        }
-
-        dirs::data_local_dir()
-            .context("Failed to get data directory")
-            .map(|p| p.join("ora"))
+        resolve_dir(
+            std::env::var_os("ORA_DATA_DIR"),
+            dirs::data_local_dir(),
+            "data",
+        )
     }
 
-    pub fn packages_dir(mode: InstallMode) -> Result<PathBuf> {
+    /// Package install root
+    ///
+    /// Userland installs honor `install.packages_dir` from the global config;
+    /// system installs always use `/opt/ora/packages`.
+    pub fn packages_dir(mode: InstallMode, settings: &InstallSettings) -> Result<PathBuf> {
         match mode {
-            InstallMode::Userland => Ok(Self::data_dir()?.join("packages")),
+            InstallMode::Userland => match &settings.packages_dir {
+                Some(dir) => Self::expand_home(dir),
+                None => Ok(Self::data_dir()?.join("packages")),
+            },
             InstallMode::System => Ok(PathBuf::from("/opt/ora/packages")),
         }
     }
 
-    pub fn bin_dir(mode: InstallMode) -> Result<PathBuf> {
+    /// Directory binaries are linked into
+    ///
+    /// Userland installs check `ORA_BIN_DIR`, then `install.bin_dir` from the
+    /// global config; system installs always use `/usr/local/bin`.
+    pub fn bin_dir(mode: InstallMode, settings: &InstallSettings) -> Result<PathBuf> {
         match mode {
-            InstallMode::Userland => dirs::home_dir()
-                .context("Failed to get home directory")
-                .map(|p| p.join(".local/bin")),
+            InstallMode::Userland => {
+                if let Ok(dir) = std::env::var("ORA_BIN_DIR") {
+                    return Self::expand_home(&dir);
+                }
+                match &settings.bin_dir {
+                    Some(dir) => Self::expand_home(dir),
+                    None => dirs::home_dir()
+                        .context("Failed to get home directory")
+                        .map(|p| p.join(".local/bin")),
+                }
+            }
             InstallMode::System => Ok(PathBuf::from("/usr/local/bin")),
         }
     }
 
-    pub fn cache_dir() -> Result<PathBuf> {
-        // Check for ORA_CACHE_DIR env var first (for testing)
-        if let Ok(dir) = std::env::var("ORA_CACHE_DIR") {
-            return Ok(PathBuf::from(dir));
+    /// Creates `dir` if needed and checks that files can be written to it
+    pub fn ensure_writable_dir(dir: &Path, what: &str) -> Result<()> {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {} {}", what, dir.display()))?;
+        tempfile::NamedTempFile::new_in(dir)
+            .map(drop)
+            .with_context(|| format!("{} {} is not writable", what, dir.display()))
+    }
+
+    /// Expands a leading `~/` and rejects relative paths, which would depend
+    /// on the working directory
+    fn expand_home(dir: &str) -> Result<PathBuf> {
+        let path = match dir.strip_prefix("~/") {
+            Some(rest) => dirs::home_dir()
+                .context("Failed to get home directory")?
+                .join(rest),
+            None => PathBuf::from(dir),
+        };
+        if !path.is_absolute() {
+            anyhow::bail!("Directory must be an absolute path: {}", dir);
         }
+        Ok(path)
+    }
 
-        dirs::cache_dir()
-            .context("Failed to get cache directory")
-            .map(|p| p.join("ora"))
+    /// `ORA_CACHE_DIR`, else `$XDG_CACHE_HOME/ora` (`~/.cache/ora`) on Linux
+    /// and `~/Library/Caches/ora` on macOS
+    pub fn cache_dir() -> Result<PathBuf> {
+        resolve_dir(
+            std::env::var_os("ORA_CACHE_DIR"),
+            dirs::cache_dir(),
+            "cache",
+        )
     }
 
     pub fn registries_cache_dir() -> Result<PathBuf> {
         Ok(Self::cache_dir()?.join("registries"))
     }
 
+    /// Read-only registry cache directories, in search order
+    ///
+    /// Entries from `ORA_REGISTRY_CACHE_DIRS` (a `PATH`-style list) come first,
+    /// followed by `registry_cache_dirs` from the global config.
+    pub fn shared_registry_cache_dirs(config_dirs: &[String]) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = std::env::var_os("ORA_REGISTRY_CACHE_DIRS")
+            .map(|value| std::env::split_paths(&value).collect())
+            .unwrap_or_default();
+        dirs.extend(config_dirs.iter().map(PathBuf::from));
+        dirs.retain(|dir| !dir.as_os_str().is_empty());
+        dirs
+    }
+
     pub fn audit_log_file() -> Result<PathBuf> {
         Ok(Self::data_dir()?.join("audit.log"))
     }
 
+    /// Packages already updated by an interrupted `update --all`
+    pub fn update_progress_file() -> Result<PathBuf> {
+        Ok(Self::data_dir()?.join("update-progress.toml"))
+    }
+
     pub fn ensure_directories() -> Result<()> {
         std::fs::create_dir_all(Self::config_dir()?)?;
         std::fs::create_dir_all(Self::data_dir()?)?;
@@ -78,3 +169,80 @@ impl Paths {
         Ok(())
     }
 }
+
+/// An `ORA_*_DIR` override when set and not empty, else `ora` under the
+/// platform directory (from the `dirs` crate, which follows XDG on Linux,
+/// ignoring relative `XDG_*` values, and Apple's conventions on macOS)
+fn resolve_dir(
+    override_dir: Option<OsString>,
+    platform_dir: Option<PathBuf>,
+    what: &str,
+) -> Result<PathBuf> {
+    match override_dir.filter(|dir| !dir.is_empty()) {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => platform_dir
+            .with_context(|| format!("Failed to get {} directory", what))
+            .map(|dir| dir.join("ora")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_dir() {
+        let platform = Some(PathBuf::from("/home/user/.config"));
+
+        assert_eq!(
+            resolve_dir(Some("/tmp/ora-config".into()), platform.clone(), "config").unwrap(),
+            PathBuf::from("/tmp/ora-config")
+        );
+        // An empty override is treated as unset
+        assert_eq!(
+            resolve_dir(Some("".into()), platform.clone(), "config").unwrap(),
+            PathBuf::from("/home/user/.config/ora")
+        );
+        assert_eq!(
+            resolve_dir(None, platform, "config").unwrap(),
+            PathBuf::from("/home/user/.config/ora")
+        );
+        assert!(resolve_dir(None, None, "config").is_err());
+    }
+
+    #[test]
+    fn test_packages_dir_override() {
+        let settings = InstallSettings {
+            packages_dir: Some("/srv/tools/packages".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            Paths::packages_dir(InstallMode::Userland, &settings).unwrap(),
+            PathBuf::from("/srv/tools/packages")
+        );
+        // System installs keep their fixed layout
+        assert_eq!(
+            Paths::packages_dir(InstallMode::System, &settings).unwrap(),
+            PathBuf::from("/opt/ora/packages")
+        );
+    }
+
+    #[test]
+    fn test_relative_override_rejected() {
+        let settings = InstallSettings {
+            packages_dir: Some("packages".to_string()),
+            ..Default::default()
+        };
+        assert!(Paths::packages_dir(InstallMode::Userland, &settings).is_err());
+    }
+
+    #[test]
+    fn test_ensure_writable_dir_creates_missing() {
+        let temp = tempfile::tempdir().unwrap();
+        let dir = temp.path().join("nested/bin");
+
+        Paths::ensure_writable_dir(&dir, "Bin directory").unwrap();
+        assert!(dir.is_dir());
+    }
+}
@@ -1,27 +1,169 @@
 use anyhow::{Context, Result};
-use reqwest::{Client, Response};
+use reqwest::{Client, ClientBuilder, NoProxy, Proxy, RequestBuilder, Response, StatusCode};
 use std::net::{IpAddr, ToSocketAddrs};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::OnceLock;
 use std::time::Duration;
+use tokio::sync::Semaphore;
+
+use crate::config::security_config::{NetworkSecurityConfig, ProxyConfig};
+use crate::error::OraError;
+
+/// How many times a rate-limited request is retried before giving up
+const MAX_RATE_LIMIT_RETRIES: u32 = 3;
+
+/// Longest `Retry-After` we are willing to sleep through; longer waits fail immediately
+const MAX_RATE_LIMIT_WAIT: Duration = Duration::from_secs(60);
+
+/// Global offline flag (set from `--offline`); when enabled, every request fails fast
+static OFFLINE_MODE: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable offline mode for the whole process
+pub fn set_offline_mode(enabled: bool) {
+    OFFLINE_MODE.store(enabled, Ordering::SeqCst);
+}
+
+/// Check if offline mode is enabled
+pub fn is_offline_mode() -> bool {
+    OFFLINE_MODE.load(Ordering::Relaxed)
+}
+
+/// Most downloads ever run at once, whatever the config or `--concurrency` ask for
+pub const MAX_CONCURRENT_DOWNLOADS: usize = 16;
+
+/// Download concurrency from `--concurrency`; 0 means unset
+static CONCURRENCY_OVERRIDE: AtomicUsize = AtomicUsize::new(0);
+
+/// Slots shared by every download of the process, sized on first use
+static DOWNLOAD_SLOTS: OnceLock<Semaphore> = OnceLock::new();
+
+/// Overrides `resources.max_concurrent_downloads` for the whole process
+///
+/// Must be called before the first download. Returns the value applied,
+/// clamped to [`MAX_CONCURRENT_DOWNLOADS`].
+pub fn set_concurrency_override(downloads: usize) -> usize {
+    let downloads = downloads.clamp(1, MAX_CONCURRENT_DOWNLOADS);
+    CONCURRENCY_OVERRIDE.store(downloads, Ordering::SeqCst);
+    downloads
+}
+
+/// How many downloads may run at once: `--concurrency`, else
+/// `resources.max_concurrent_downloads` (unlimited up to the cap when resource
+/// limits are disabled)
+pub fn max_concurrent_downloads() -> usize {
+    match CONCURRENCY_OVERRIDE.load(Ordering::SeqCst) {
+        0 => {
+            let resources = crate::config::SecurityConfig::load()
+                .unwrap_or_default()
+                .resources;
+            if resources.enabled {
+                resources
+                    .max_concurrent_downloads
+                    .clamp(1, MAX_CONCURRENT_DOWNLOADS)
+            } else {
+                MAX_CONCURRENT_DOWNLOADS
+            }
+        }
+        downloads => downloads,
+    }
+}
+
+fn download_slots() -> &'static Semaphore {
+    DOWNLOAD_SLOTS.get_or_init(new_download_slots)
+}
+
+fn new_download_slots() -> Semaphore {
+    Semaphore::new(max_concurrent_downloads())
+}
 
 pub struct HttpClient {
     client: Client,
+    /// Client used for authenticated requests; never follows redirects so
+    /// credentials can't be forwarded to another host
+    no_redirect_client: Client,
+    /// Per-request timeout from `network.timeout_seconds`, overriding the
+    /// client-wide default
+    request_timeout: Duration,
+    /// Offline mode as of the client's creation; requests fail fast when set
+    offline: bool,
+    /// Address checks from the security config, loaded once per client
+    network: NetworkSecurityConfig,
 }
 
 impl HttpClient {
     pub fn new() -> Result<Self> {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(300))
-            // Use curl-like user agent for better compatibility with websites
-            // that serve different content based on user agent
-            .user_agent("curl/8.0.0")
-            .build()
-            .context("Failed to create HTTP client")?;
+        let config = crate::config::SecurityConfig::load().unwrap_or_default();
 
-        Ok(Self { client })
+        let client = Self::with_proxy(
+            Client::builder()
+                .timeout(Duration::from_secs(300))
+                // User agent is configurable; some websites serve different content
+                // based on it, hence the curl-compatible option
+                .user_agent(config.network.effective_user_agent()),
+            &config.network.proxy,
+        )?
+        .build()
+        .context("Failed to create HTTP client")?;
+
+        let no_redirect_client = Self::with_proxy(
+            Client::builder()
+                .timeout(Duration::from_secs(300))
+                .user_agent(config.network.effective_user_agent())
+                .redirect(reqwest::redirect::Policy::none()),
+            &config.network.proxy,
+        )?
+        .build()
+        .context("Failed to create HTTP client")?;
+
+        Ok(Self {
+            client,
+            no_redirect_client,
+            request_timeout: Duration::from_secs(config.network.timeout_seconds),
+            offline: is_offline_mode(),
+            network: config.network,
+        })
+    }
+
+    /// Routes requests through `network.proxy` when configured
+    ///
+    /// Without it, reqwest already honors HTTP_PROXY, HTTPS_PROXY, ALL_PROXY and
+    /// NO_PROXY. Either way URL validation applies to the target host, not the proxy.
+    fn with_proxy(builder: ClientBuilder, proxy_config: &ProxyConfig) -> Result<ClientBuilder> {
+        let Some(url) = proxy_config.authenticated_url()? else {
+            return Ok(builder);
+        };
+
+        let mut proxy = Proxy::all(url.as_str()).context("Invalid proxy URL")?;
+        if let Some(username) = &proxy_config.username {
+            proxy = proxy.basic_auth(username, proxy_config.password.as_deref().unwrap_or(""));
+        }
+        let no_proxy = if proxy_config.no_proxy.is_empty() {
+            NoProxy::from_env()
+        } else {
+            NoProxy::from_string(&proxy_config.no_proxy.join(","))
+        };
+        proxy = proxy.no_proxy(no_proxy);
+
+        log::debug!(
+            "Using proxy {}",
+            proxy_config.display_url().unwrap_or_default()
+        );
+        Ok(builder.proxy(proxy))
     }
 
     /// Validates URL for security before making requests
-    fn validate_url(url: &str) -> Result<url::Url> {
+    pub fn validate_url(url: &str) -> Result<url::Url> {
+        let network = crate::config::SecurityConfig::load()
+            .unwrap_or_default()
+            .network;
+        Self::validate_url_with(url, &network)
+    }
+
+    /// [`Self::validate_url`] with the given network settings
+    ///
+    /// `allowed_cidrs` only lifts the private-address check; link-local and
+    /// cloud metadata addresses stay blocked.
+    fn validate_url_with(url: &str, network: &NetworkSecurityConfig) -> Result<url::Url> {
         // Parse the URL
         let parsed = url::Url::parse(url).context(format!("Invalid URL: {}", url))?;
 
@@ -43,12 +185,27 @@ impl HttpClient {
                 anyhow::bail!("Access to localhost is not allowed for security reasons");
             }
 
-            // Try to parse as IP address and check if private
-            if let Ok(ip) = host.parse::<IpAddr>() {
-                if Self::is_private_ip(&ip) {
-                    anyhow::bail!(
+            // Try to parse as IP address and check if private, unless the
+            // security config lists it in network.allowed_cidrs/denied_cidrs
+            let literal_ip = host
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .parse::<IpAddr>();
+            if let Ok(ip) = literal_ip {
+                match network.cidr_verdict(&ip)? {
+                    Some(false) => {
+                        anyhow::bail!("Access to {} is blocked by network.denied_cidrs", ip)
+                    }
+                    Some(true) => log::debug!("{} is allowed by network.allowed_cidrs", ip),
+                    None if Self::is_private_ip(&ip) => anyhow::bail!(
                         "Access to private IP addresses is not allowed for security reasons: {}",
                         ip
+                    ),
+                    None => {}
+                }
+                if Self::is_link_local_ip(&ip.to_canonical()) {
+                    anyhow::bail!(
+                        "Access to link-local addresses is not allowed for security reasons"
                     );
                 }
             }
@@ -72,8 +229,10 @@ impl HttpClient {
     }
 
     /// Checks if an IP address is in a private range
+    ///
+    /// IPv4-mapped IPv6 addresses (`::ffff:10.0.0.1`) are checked as IPv4.
     fn is_private_ip(ip: &IpAddr) -> bool {
-        match ip {
+        match ip.to_canonical() {
             IpAddr::V4(ipv4) => {
                 // 10.0.0.0/8
                 ipv4.octets()[0] == 10
@@ -106,13 +265,11 @@ impl HttpClient {
     /// 4. Application makes request to private IP thinking it's safe
     ///
     /// This function re-resolves DNS just before making the request and validates
-    /// that all resolved IPs are safe.
-    fn validate_dns_resolution(url: &url::Url) -> Result<()> {
-        // Load security config
-        let config = crate::config::SecurityConfig::load().unwrap_or_default();
-
+    /// that all resolved IPs are safe. `allowed_cidrs` lifts the localhost and
+    /// private-address checks, but not the link-local and metadata ones.
+    fn validate_dns_resolution(url: &url::Url, network: &NetworkSecurityConfig) -> Result<()> {
         // Check if DNS validation is enabled
-        if !config.network.validate_dns_resolution {
+        if !network.validate_dns_resolution {
             log::warn!("DNS validation is disabled - vulnerable to DNS rebinding attacks");
             return Ok(());
         }
@@ -121,13 +278,25 @@ impl HttpClient {
         let host = url.host_str().context("URL must have a valid host")?;
 
         // If host is already an IP address, validate it directly
-        if let Ok(ip) = host.parse::<IpAddr>() {
-            if config.network.block_localhost && ip.is_loopback() {
+        let literal_ip = host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<IpAddr>();
+        if let Ok(ip) = literal_ip {
+            let allowed = match network.cidr_verdict(&ip)? {
+                Some(false) => anyhow::bail!("Access to {} is blocked by network.denied_cidrs", ip),
+                Some(allowed) => allowed,
+                None => false,
+            };
+            if !allowed && network.block_localhost && ip.is_loopback() {
                 anyhow::bail!("Access to localhost IP is blocked: {}", ip);
             }
-            if config.network.block_private_ips && Self::is_private_ip(&ip) {
+            if !allowed && network.block_private_ips && Self::is_private_ip(&ip) {
                 anyhow::bail!("Access to private IP is blocked: {}", ip);
             }
+            if network.block_link_local && Self::is_link_local_ip(&ip) {
+                anyhow::bail!("Access to link-local IP is blocked: {}", ip);
+            }
             return Ok(());
         }
 
@@ -151,8 +320,23 @@ impl HttpClient {
 
         // Validate each resolved IP
         for ip in resolved_ips {
+            // Explicit allow/deny ranges take precedence over the localhost and
+            // private-address checks
+            let allowed = match network.cidr_verdict(&ip)? {
+                Some(false) => anyhow::bail!(
+                    "Hostname '{}' resolved to {}, which is blocked by network.denied_cidrs",
+                    host,
+                    ip
+                ),
+                Some(allowed) => {
+                    log::debug!("{} ({}) is allowed by network.allowed_cidrs", host, ip);
+                    allowed
+                }
+                None => false,
+            };
+
             // Check for localhost
-            if config.network.block_localhost && ip.is_loopback() {
+            if !allowed && network.block_localhost && ip.is_loopback() {
                 log::error!("❌ DNS rebinding attack detected!");
                 log::error!("Hostname '{}' resolved to localhost IP: {}", host, ip);
                 anyhow::bail!(
@@ -163,7 +347,7 @@ impl HttpClient {
             }
 
             // Check for private IPs
-            if config.network.block_private_ips && Self::is_private_ip(&ip) {
+            if !allowed && network.block_private_ips && Self::is_private_ip(&ip) {
                 log::error!("❌ DNS rebinding attack detected!");
                 log::error!("Hostname '{}' resolved to private IP: {}", host, ip);
                 anyhow::bail!(
@@ -174,7 +358,7 @@ impl HttpClient {
             }
 
             // Check for link-local addresses
-            if config.network.block_link_local && Self::is_link_local_ip(&ip) {
+            if network.block_link_local && Self::is_link_local_ip(&ip) {
                 log::error!("❌ DNS rebinding attack detected!");
                 log::error!("Hostname '{}' resolved to link-local IP: {}", host, ip);
                 anyhow::bail!(
@@ -185,7 +369,7 @@ impl HttpClient {
             }
 
             // Check for cloud metadata endpoints
-            if config.network.block_metadata_endpoints
+            if network.block_metadata_endpoints
                 && matches!(ip, IpAddr::V4(ipv4) if ipv4.octets() == [169, 254, 169, 254])
             {
                 log::error!("❌ SSRF attack attempt detected!");
@@ -221,34 +405,140 @@ impl HttpClient {
     }
 
     pub async fn get(&self, url: &str) -> Result<Response> {
+        // Fail fast without touching the network in offline mode
+        if self.offline {
+            return Err(OraError::NetworkError(format!(
+                "Offline mode is enabled, refusing to fetch {}",
+                url
+            ))
+            .into());
+        }
+
         // Validate URL before request
-        let parsed_url = Self::validate_url(url)?;
+        let parsed_url = Self::validate_url_with(url, &self.network)?;
 
         // SECURITY: DNS rebinding protection
         // Re-validate DNS resolution just before making the request
-        Self::validate_dns_resolution(&parsed_url)?;
+        Self::validate_dns_resolution(&parsed_url, &self.network)?;
 
-        self.client
-            .get(url)
-            .send()
+        let request = self.client.get(url).timeout(self.request_timeout);
+        self.send_with_retry(request, url)
             .await
             .context(format!("Failed to GET {}", url))
     }
 
+    /// Sends a request, waiting out rate limits before retrying
+    ///
+    /// A 403 or 429 carrying `Retry-After` (GitHub's secondary rate limits) is
+    /// retried after the indicated delay; a bare 429 backs off exponentially.
+    /// After [`MAX_RATE_LIMIT_RETRIES`] attempts, or when the server asks for
+    /// more than [`MAX_RATE_LIMIT_WAIT`], the rate-limited response is returned
+    /// as is so callers report the HTTP error.
+    async fn send_with_retry(&self, request: RequestBuilder, url: &str) -> Result<Response> {
+        let mut attempt = 0;
+        loop {
+            let response = request
+                .try_clone()
+                .context("Request cannot be retried")?
+                .send()
+                .await?;
+
+            let Some(delay) = rate_limit_delay(&response, attempt) else {
+                return Ok(response);
+            };
+            if attempt >= MAX_RATE_LIMIT_RETRIES {
+                log::warn!(
+                    "Still rate limited by {} after {} retries, giving up",
+                    url,
+                    attempt
+                );
+                return Ok(response);
+            }
+            if delay > MAX_RATE_LIMIT_WAIT {
+                log::warn!(
+                    "Rate limited by {} for {}s (more than {}s), giving up",
+                    url,
+                    delay.as_secs(),
+                    MAX_RATE_LIMIT_WAIT.as_secs()
+                );
+                return Ok(response);
+            }
+
+            attempt += 1;
+            log::warn!(
+                "Rate limited by {} (HTTP {}), retrying in {}s (attempt {}/{})",
+                url,
+                response.status().as_u16(),
+                delay.as_secs(),
+                attempt,
+                MAX_RATE_LIMIT_RETRIES
+            );
+            drop(response);
+            tokio::time::sleep(delay).await;
+            crate::utils::shutdown::check_shutdown()?;
+        }
+    }
+
+    /// Checks whether a URL points to an existing resource (HEAD request)
+    ///
+    /// Any failure (invalid URL, network error, non-success status) yields `false`.
+    pub async fn url_exists(&self, url: &str) -> bool {
+        if self.offline {
+            return false;
+        }
+
+        match self.head_status(url).await {
+            Ok(status) => status.is_success(),
+            Err(e) => {
+                log::debug!("HEAD {} failed: {:#}", url, e);
+                false
+            }
+        }
+    }
+
+    /// Status of a HEAD request to `url`, after following redirects
+    ///
+    /// The URL goes through the same scheme, host and DNS checks as downloads.
+    pub async fn head_status(&self, url: &str) -> Result<StatusCode> {
+        if self.offline {
+            anyhow::bail!("Offline mode: not checking {}", url);
+        }
+
+        let parsed_url = Self::validate_url_with(url, &self.network)?;
+        Self::validate_dns_resolution(&parsed_url, &self.network)?;
+
+        let response = self
+            .client
+            .head(url)
+            .timeout(self.request_timeout)
+            .send()
+            .await
+            .with_context(|| format!("HEAD {} failed", url))?;
+        Ok(response.status())
+    }
+
     pub async fn download_file(&self, url: &str, dest: &std::path::Path) -> Result<()> {
         log::debug!("Downloading {} to {:?}", url, dest);
 
         // Validate URL before download
-        Self::validate_url(url)?;
+        Self::validate_url_with(url, &self.network)?;
+
+        let _slot = download_slots()
+            .acquire()
+            .await
+            .context("Download scheduler is closed")?;
 
         let response = self.get(url).await?;
 
         if !response.status().is_success() {
-            anyhow::bail!("HTTP error: {}", response.status());
+            return Err(
+                OraError::NetworkError(format!("HTTP error: {}", response.status())).into(),
+            );
         }
 
         // Check content length if available
-        if let Some(content_length) = response.content_length() {
+        let content_length = response.content_length();
+        if let Some(content_length) = content_length {
             use crate::config::security_limits::MAX_DOWNLOAD_SIZE;
             if content_length > MAX_DOWNLOAD_SIZE {
                 anyhow::bail!(
@@ -264,46 +554,344 @@ impl HttpClient {
             .await
             .context("Failed to read response bytes")?;
 
-        tokio::fs::write(dest, &bytes)
+        // Written next to the destination first, so an interrupted write never
+        // leaves a truncated file where the download cache would find it
+        let mut partial = dest.as_os_str().to_owned();
+        partial.push(".part");
+        let partial = std::path::PathBuf::from(partial);
+        tokio::fs::write(&partial, &bytes)
             .await
             .context("Failed to write file")?;
 
+        // A dropped connection can leave a short file that would only fail later,
+        // in checksum verification or extraction
+        let written = tokio::fs::metadata(&partial)
+            .await
+            .context("Failed to read downloaded file")?
+            .len();
+        if let Err(e) = check_download_length(written, content_length) {
+            let _ = tokio::fs::remove_file(&partial).await;
+            return Err(e);
+        }
+
+        tokio::fs::rename(&partial, dest)
+            .await
+            .context("Failed to write file")?;
         Ok(())
     }
 
+    /// GET a text document (HTML pages, checksum files, version lists)
+    ///
+    /// The body is capped at `validation.max_json_size`, the general limit for
+    /// remote documents, and the whole request (including reading the body) is
+    /// bounded by `network.timeout_seconds`.
     pub async fn get_text(&self, url: &str) -> Result<String> {
+        self.get_text_limited(url, Self::max_json_size()).await
+    }
+
+    /// GET a text document, failing if the body exceeds `max_size` bytes
+    pub async fn get_text_limited(&self, url: &str, max_size: u64) -> Result<String> {
         // Validate URL
-        Self::validate_url(url)?;
+        Self::validate_url_with(url, &self.network)?;
 
         let response = self.get(url).await?;
 
         if !response.status().is_success() {
-            anyhow::bail!("HTTP error: {}", response.status());
+            return Err(
+                OraError::NetworkError(format!("HTTP error: {}", response.status())).into(),
+            );
+        }
+
+        let body = Self::read_body_limited(url, response, max_size).await?;
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+
+    /// Reads a response body, refusing anything larger than `max_size` bytes
+    ///
+    /// Checks `Content-Length` up front and also caps the streamed read, since
+    /// the header can be missing or wrong.
+    async fn read_body_limited(
+        url: &str,
+        mut response: Response,
+        max_size: u64,
+    ) -> Result<Vec<u8>> {
+        if let Some(content_length) = response.content_length() {
+            if content_length > max_size {
+                anyhow::bail!(
+                    "Response from {} is too large ({} bytes, maximum {} bytes)",
+                    url,
+                    content_length,
+                    max_size
+                );
+            }
+        }
+
+        let mut body = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .context("Failed to read response body")?
+        {
+            if body.len() as u64 + chunk.len() as u64 > max_size {
+                anyhow::bail!(
+                    "Response from {} exceeds maximum size ({} bytes)",
+                    url,
+                    max_size
+                );
+            }
+            body.extend_from_slice(&chunk);
+        }
+
+        Ok(body)
+    }
+
+    /// Maximum JSON response size from the security configuration
+    fn max_json_size() -> u64 {
+        crate::config::SecurityConfig::load()
+            .unwrap_or_default()
+            .validation
+            .max_json_size
+    }
+
+    /// GET a JSON document, sending extra secret headers (e.g. API tokens)
+    ///
+    /// Header values are marked sensitive so they never appear in debug output,
+    /// HTTPS is required, and redirects are not followed.
+    pub async fn get_json_with_secret_headers<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<T> {
+        if self.offline {
+            return Err(OraError::NetworkError(format!(
+                "Offline mode is enabled, refusing to fetch {}",
+                url
+            ))
+            .into());
+        }
+
+        let parsed_url = Self::validate_url_with(url, &self.network)?;
+        if parsed_url.scheme() != "https" {
+            anyhow::bail!("Refusing to send credentials over non-HTTPS URL: {}", url);
+        }
+        Self::validate_dns_resolution(&parsed_url, &self.network)?;
+
+        let mut request = self
+            .no_redirect_client
+            .get(url)
+            .timeout(self.request_timeout);
+        for (name, value) in headers {
+            let mut header_value = reqwest::header::HeaderValue::from_str(value)
+                .map_err(|_| anyhow::anyhow!("Invalid value for header '{}'", name))?;
+            header_value.set_sensitive(true);
+            request = request.header(*name, header_value);
         }
 
-        response
-            .text()
+        let response = self
+            .send_with_retry(request, url)
             .await
-            .context("Failed to read response text")
+            .context(format!("Failed to GET {}", url))?;
+
+        if !response.status().is_success() {
+            return Err(
+                OraError::NetworkError(format!("HTTP error: {}", response.status())).into(),
+            );
+        }
+
+        let body = Self::read_body_limited(url, response, Self::max_json_size()).await?;
+        serde_json::from_slice(&body).context("Failed to parse JSON response")
     }
 
     pub async fn get_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T> {
         // Validate URL
-        Self::validate_url(url)?;
+        Self::validate_url_with(url, &self.network)?;
 
         let response = self.get(url).await?;
 
         if !response.status().is_success() {
-            anyhow::bail!("HTTP error: {}", response.status());
+            return Err(
+                OraError::NetworkError(format!("HTTP error: {}", response.status())).into(),
+            );
         }
 
-        response
-            .json::<T>()
+        let body = Self::read_body_limited(url, response, Self::max_json_size()).await?;
+        serde_json::from_slice(&body).context("Failed to parse JSON response")
+    }
+
+    /// GET a paginated JSON list, following `Link: rel="next"` headers
+    ///
+    /// Items of every page are concatenated. At most `max_pages` pages are
+    /// fetched, so a server that keeps linking to a next page can't loop forever.
+    pub async fn get_json_pages<T: serde::de::DeserializeOwned>(
+        &self,
+        url: &str,
+        max_pages: usize,
+    ) -> Result<Vec<T>> {
+        let mut items = Vec::new();
+        let mut next = Some(url.to_string());
+        let mut pages = 0;
+
+        while let Some(page_url) = next.take() {
+            if pages == max_pages {
+                log::warn!(
+                    "Stopped after {} pages of {}; later results were ignored",
+                    max_pages,
+                    url
+                );
+                break;
+            }
+
+            let response = self.get(&page_url).await?;
+            if !response.status().is_success() {
+                return Err(
+                    OraError::NetworkError(format!("HTTP error: {}", response.status())).into(),
+                );
+            }
+            next = response
+                .headers()
+                .get(reqwest::header::LINK)
+                .and_then(|v| v.to_str().ok())
+                .and_then(next_page_link);
+
+            let body = Self::read_body_limited(&page_url, response, Self::max_json_size()).await?;
+            let page: Vec<T> =
+                serde_json::from_slice(&body).context("Failed to parse JSON response")?;
+            items.extend(page);
+            pages += 1;
+        }
+
+        Ok(items)
+    }
+
+    /// Sends a request with secret headers and an optional body, returning the
+    /// response text (capped like JSON responses)
+    ///
+    /// Header values are marked sensitive and the request never follows
+    /// redirects. Headers are refused over plain HTTP unless `allow_http` is set.
+    pub async fn request_text_with_secret_headers(
+        &self,
+        method: reqwest::Method,
+        url: &str,
+        headers: &[(&str, &str)],
+        body: Option<&str>,
+        allow_http: bool,
+    ) -> Result<String> {
+        if self.offline {
+            return Err(OraError::NetworkError(format!(
+                "Offline mode is enabled, refusing to fetch {}",
+                url
+            ))
+            .into());
+        }
+
+        let parsed_url = Self::validate_url_with(url, &self.network)?;
+        if !headers.is_empty() && parsed_url.scheme() != "https" && !allow_http {
+            anyhow::bail!("Refusing to send headers over non-HTTPS URL: {}", url);
+        }
+        Self::validate_dns_resolution(&parsed_url, &self.network)?;
+
+        let mut request = self
+            .no_redirect_client
+            .request(method.clone(), url)
+            .timeout(self.request_timeout);
+        for (name, value) in headers {
+            let mut header_value = reqwest::header::HeaderValue::from_str(value)
+                .map_err(|_| anyhow::anyhow!("Invalid value for header '{}'", name))?;
+            header_value.set_sensitive(true);
+            request = request.header(*name, header_value);
+        }
+        if let Some(body) = body {
+            request = request.body(body.to_string());
+        }
+
+        let response = self
+            .send_with_retry(request, url)
             .await
-            .context("Failed to parse JSON response")
+            .context(format!("Failed to {} {}", method, url))?;
+
+        if !response.status().is_success() {
+            return Err(
+                OraError::NetworkError(format!("HTTP error: {}", response.status())).into(),
+            );
+        }
+
+        let body = Self::read_body_limited(url, response, Self::max_json_size()).await?;
+        Ok(String::from_utf8_lossy(&body).into_owned())
+    }
+}
+
+/// Fails when fewer (or more) bytes were written than the server's `Content-Length`
+fn check_download_length(written: u64, content_length: Option<u64>) -> Result<()> {
+    match content_length {
+        Some(expected) if written != expected => {
+            let problem = if written < expected {
+                "truncated"
+            } else {
+                "longer than declared"
+            };
+            Err(OraError::NetworkError(format!(
+                "download {} (expected {} bytes, got {})",
+                problem, expected, written
+            ))
+            .into())
+        }
+        _ => Ok(()),
     }
 }
 
+/// Target of the `rel="next"` entry of a `Link` header, if any
+fn next_page_link(header: &str) -> Option<String> {
+    header.split(',').find_map(|link| {
+        let mut parts = link.split(';');
+        let target = parts.next()?.trim().strip_prefix('<')?.strip_suffix('>')?;
+        let is_next = parts.any(|param| {
+            param.trim().strip_prefix("rel=").is_some_and(|rel| {
+                rel.trim_matches('"')
+                    .split_whitespace()
+                    .any(|r| r == "next")
+            })
+        });
+        is_next.then(|| target.to_string())
+    })
+}
+
+/// How long to wait before retrying a rate-limited response, if it is one
+fn rate_limit_delay(response: &Response, attempt: u32) -> Option<Duration> {
+    let status = response.status();
+    if status != StatusCode::TOO_MANY_REQUESTS && status != StatusCode::FORBIDDEN {
+        return None;
+    }
+
+    let retry_after = response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| parse_retry_after(v, chrono::Utc::now()));
+
+    match retry_after {
+        Some(delay) => Some(delay),
+        // A plain 403 is a permission error, not a rate limit
+        None if status == StatusCode::TOO_MANY_REQUESTS => {
+            Some(Duration::from_secs(1 << attempt.min(5)))
+        }
+        None => None,
+    }
+}
+
+/// Parses a `Retry-After` value: either delay seconds or an HTTP date
+fn parse_retry_after(value: &str, now: chrono::DateTime<chrono::Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&chrono::Utc) - now)
+            .to_std()
+            .unwrap_or_default(),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -347,6 +935,156 @@ mod tests {
         assert!(HttpClient::validate_url("http://169.254.1.1/test").is_err());
     }
 
+    #[test]
+    fn test_allowed_cidrs_keep_link_local_and_metadata_blocked() {
+        let network = NetworkSecurityConfig {
+            allowed_cidrs: vec!["10.0.0.0/8".to_string(), "169.254.0.0/16".to_string()],
+            ..Default::default()
+        };
+
+        assert!(HttpClient::validate_url_with("http://10.0.0.1/test", &network).is_ok());
+        assert!(HttpClient::validate_url_with(
+            "http://169.254.169.254/latest/meta-data/",
+            &network
+        )
+        .is_err());
+        assert!(HttpClient::validate_url_with("http://169.254.1.1/test", &network).is_err());
+        assert!(HttpClient::validate_url_with("http://[fe80::1]/test", &network).is_err());
+    }
+
+    #[test]
+    fn test_concurrency_override_is_clamped_and_bounds_downloads() {
+        assert_eq!(set_concurrency_override(0), 1);
+        assert_eq!(
+            set_concurrency_override(MAX_CONCURRENT_DOWNLOADS + 1),
+            MAX_CONCURRENT_DOWNLOADS
+        );
+        assert_eq!(set_concurrency_override(2), 2);
+        assert_eq!(max_concurrent_downloads(), 2);
+
+        // A third download waits until one of the first two is done
+        let slots = new_download_slots();
+        let first = slots.try_acquire().expect("first slot");
+        let _second = slots.try_acquire().expect("second slot");
+        assert!(slots.try_acquire().is_err());
+        drop(first);
+        assert!(slots.try_acquire().is_ok());
+
+        CONCURRENCY_OVERRIDE.store(0, Ordering::SeqCst);
+    }
+
+    #[test]
+    fn test_offline_mode_blocks_requests() {
+        let mut client = HttpClient::new().expect("Failed to create HTTP client");
+        let runtime = tokio::runtime::Runtime::new().expect("Failed to create runtime");
+
+        // Set on the client rather than globally, so parallel tests keep going online
+        client.offline = true;
+        let result = runtime.block_on(client.get("https://example.com/file.tar.gz"));
+
+        let err = result.expect_err("Request should fail in offline mode");
+        assert!(err.to_string().contains("Offline mode"));
+    }
+
+    #[test]
+    fn test_read_body_limited_enforces_max_size() {
+        let runtime = tokio::runtime::Runtime::new().expect("Failed to create runtime");
+        runtime.block_on(async {
+            let mut server = mockito::Server::new_async().await;
+            let sized = server
+                .mock("GET", "/sized.json")
+                .with_body("x".repeat(64))
+                .create_async()
+                .await;
+            let chunked = server
+                .mock("GET", "/chunked.json")
+                .with_chunked_body(|w| std::io::Write::write_all(w, &[b'x'; 64]))
+                .expect(2)
+                .create_async()
+                .await;
+
+            let fetch = |path: &str| reqwest::get(format!("{}{}", server.url(), path));
+
+            // Content-Length is checked before anything is read
+            let response = fetch("/sized.json").await.unwrap();
+            let err = HttpClient::read_body_limited("sized.json", response, 32)
+                .await
+                .unwrap_err();
+            assert!(err
+                .to_string()
+                .contains("too large (64 bytes, maximum 32 bytes)"));
+
+            // Without it, the streamed read stops at the cap
+            let response = fetch("/chunked.json").await.unwrap();
+            assert!(response.content_length().is_none());
+            let err = HttpClient::read_body_limited("chunked.json", response, 32)
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("exceeds maximum size (32 bytes)"));
+
+            let response = fetch("/chunked.json").await.unwrap();
+            let body = HttpClient::read_body_limited("chunked.json", response, 64)
+                .await
+                .unwrap();
+            assert_eq!(body.len(), 64);
+
+            sized.assert_async().await;
+            chunked.assert_async().await;
+        });
+    }
+
+    #[test]
+    fn test_check_download_length() {
+        assert!(check_download_length(1024, Some(1024)).is_ok());
+        assert!(check_download_length(1024, None).is_ok());
+
+        let err = check_download_length(512, Some(1024)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Network error: download truncated (expected 1024 bytes, got 512)"
+        );
+
+        let err = check_download_length(2048, Some(1024)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Network error: download longer than declared (expected 1024 bytes, got 2048)"
+        );
+    }
+
+    #[test]
+    fn test_next_page_link() {
+        let header = "<https://api.github.com/repositories/1/releases?per_page=100&page=2>; rel=\"next\", \
+                      <https://api.github.com/repositories/1/releases?per_page=100&page=5>; rel=\"last\"";
+        assert_eq!(
+            next_page_link(header).as_deref(),
+            Some("https://api.github.com/repositories/1/releases?per_page=100&page=2")
+        );
+
+        let last_page = "<https://api.github.com/repositories/1/releases?page=4>; rel=\"prev\", \
+                         <https://api.github.com/repositories/1/releases?page=1>; rel=\"first\"";
+        assert_eq!(next_page_link(last_page), None);
+        assert_eq!(next_page_link(""), None);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = chrono::DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+
+        assert_eq!(parse_retry_after("30", now), Some(Duration::from_secs(30)));
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:45 GMT", now),
+            Some(Duration::from_secs(45))
+        );
+        // Dates in the past mean "retry now"
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
     #[test]
     fn test_private_ip_detection() {
         // Private ranges
@@ -362,6 +1100,10 @@ mod tests {
         assert!(HttpClient::is_private_ip(
             &"127.0.0.1".parse().expect("valid IP")
         ));
+        // IPv4-mapped IPv6
+        assert!(HttpClient::is_private_ip(
+            &"::ffff:10.0.0.1".parse().expect("valid IP")
+        ));
 
         // Public IPs
         assert!(!HttpClient::is_private_ip(
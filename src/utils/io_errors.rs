@@ -20,20 +20,18 @@ pub fn read_file_user_friendly(path: &Path) -> Result<Option<String>> {
             let path_display = path.display();
 
             match e.kind() {
-                io::ErrorKind::PermissionDenied => {
-                    anyhow::bail!(
-                        "❌ Permission denied reading: {}\n\
+                io::ErrorKind::PermissionDenied => Err(e).context(format!(
+                    "❌ Permission denied reading: {}\n\
                          \n\
                          Fix this by running:\n\
                          ├─ chmod 644 {}\n\
                          └─ Or check parent directory permissions: ls -ld {}\n\
                          \n\
                          If this is a system-wide config, you may need sudo.",
-                        path_display,
-                        path_display,
-                        path.parent().unwrap_or(path).display()
-                    );
-                }
+                    path_display,
+                    path_display,
+                    path.parent().unwrap_or(path).display()
+                )),
                 io::ErrorKind::NotFound => {
                     // File doesn't exist - this is OK for configs (use defaults)
                     Ok(None)
@@ -52,20 +50,18 @@ pub async fn read_file_user_friendly_async(path: &Path) -> Result<Option<String>
             let path_display = path.display();
 
             match e.kind() {
-                io::ErrorKind::PermissionDenied => {
-                    anyhow::bail!(
-                        "❌ Permission denied reading: {}\n\
+                io::ErrorKind::PermissionDenied => Err(e).context(format!(
+                    "❌ Permission denied reading: {}\n\
                          \n\
                          Fix this by running:\n\
                          ├─ chmod 644 {}\n\
                          └─ Or check parent directory permissions: ls -ld {}\n\
                          \n\
                          If this is a system-wide config, you may need sudo.",
-                        path_display,
-                        path_display,
-                        path.parent().unwrap_or(path).display()
-                    );
-                }
+                    path_display,
+                    path_display,
+                    path.parent().unwrap_or(path).display()
+                )),
                 io::ErrorKind::NotFound => {
                     // File doesn't exist - this is OK for configs (use defaults)
                     Ok(None)
@@ -91,7 +87,7 @@ pub fn write_file_user_friendly(path: &Path, content: &str) -> Result<()> {
 
             match e.kind() {
                 io::ErrorKind::PermissionDenied => {
-                    anyhow::bail!(
+                    Err(e).context(format!(
                         "❌ Permission denied writing: {}\n\
                          \n\
                          Fix this by running:\n\
@@ -103,7 +99,7 @@ pub fn write_file_user_friendly(path: &Path, content: &str) -> Result<()> {
                         path_display,
                         path_display,
                         path.parent().unwrap_or(path).display()
-                    );
+                    ))
                 }
                 _ => {
                     // Check for specific raw OS errors
@@ -149,7 +145,7 @@ pub async fn write_file_user_friendly_async(path: &Path, content: &[u8]) -> Resu
 
             match e.kind() {
                 io::ErrorKind::PermissionDenied => {
-                    anyhow::bail!(
+                    Err(e).context(format!(
                         "❌ Permission denied writing: {}\n\
                          \n\
                          Fix this by running:\n\
@@ -161,7 +157,7 @@ pub async fn write_file_user_friendly_async(path: &Path, content: &[u8]) -> Resu
                         path_display,
                         path_display,
                         path.parent().unwrap_or(path).display()
-                    );
+                    ))
                 }
                 _ => {
                     // Check for specific raw OS errors
@@ -206,9 +202,8 @@ pub fn create_dir_all_user_friendly(path: &Path) -> Result<()> {
             let path_display = path.display();
 
             match e.kind() {
-                io::ErrorKind::PermissionDenied => {
-                    anyhow::bail!(
-                        "❌ Permission denied creating directory: {}\n\
+                io::ErrorKind::PermissionDenied => Err(e).context(format!(
+                    "❌ Permission denied creating directory: {}\n\
                          \n\
                          Fix this by running:\n\
                          ├─ chmod 755 {} (parent directory)\n\
@@ -218,10 +213,9 @@ pub fn create_dir_all_user_friendly(path: &Path) -> Result<()> {
                          ├─ ORA_CONFIG_DIR for config directory\n\
                          ├─ ORA_DATA_DIR for data directory\n\
                          └─ ORA_CACHE_DIR for cache directory",
-                        path_display,
-                        path.parent().unwrap_or(path).display()
-                    );
-                }
+                    path_display,
+                    path.parent().unwrap_or(path).display()
+                )),
                 _ => {
                     match e.raw_os_error() {
                         Some(28) => {
@@ -0,0 +1,62 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+use std::io::Write;
+
+/// Output format of log records (`--log-format` / `ORA_LOG_FORMAT`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum LogFormat {
+    /// Human-readable lines (default)
+    Human,
+    /// One JSON object per record, for log aggregation
+    Json,
+}
+
+/// Makes `builder` emit one JSON object per log record
+pub fn use_json_format(builder: &mut env_logger::Builder) {
+    builder.format(|buf, record| {
+        writeln!(
+            buf,
+            "{}",
+            json_record(
+                Utc::now(),
+                record.level(),
+                record.target(),
+                &record.args().to_string()
+            )
+        )
+    });
+}
+
+/// Serializes a log record as a single-line JSON object
+fn json_record(timestamp: DateTime<Utc>, level: log::Level, target: &str, message: &str) -> String {
+    serde_json::json!({
+        "timestamp": timestamp.to_rfc3339_opts(SecondsFormat::Millis, true),
+        "level": level.as_str(),
+        "target": target,
+        "message": message,
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_json_record() {
+        let timestamp = Utc.with_ymd_and_hms(2025, 1, 2, 3, 4, 5).unwrap();
+        let line = json_record(
+            timestamp,
+            log::Level::Warn,
+            "ora::installer",
+            "multi\nline \"quoted\"",
+        );
+
+        assert!(!line.contains('\n'));
+        let value: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(value["timestamp"], "2025-01-02T03:04:05.000Z");
+        assert_eq!(value["level"], "WARN");
+        assert_eq!(value["target"], "ora::installer");
+        assert_eq!(value["message"], "multi\nline \"quoted\"");
+    }
+}
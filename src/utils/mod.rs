@@ -1,6 +1,9 @@
 pub mod http;
 pub mod io_errors;
+pub mod logging;
 pub mod platform;
 pub mod regex;
+pub mod shutdown;
+pub mod table;
 pub mod templating;
 pub mod ui;
@@ -1,5 +1,10 @@
 use std::collections::HashMap;
 
+/// GNU C library (glibc), as in `x86_64-unknown-linux-gnu`
+pub const LIBC_GNU: &str = "gnu";
+/// musl C library, as in `x86_64-unknown-linux-musl`
+pub const LIBC_MUSL: &str = "musl";
+
 pub struct Platform {
     pub os: String,
     pub arch: String,
@@ -13,18 +18,87 @@ impl Platform {
         }
     }
 
-    pub fn map_os(&self, mapping: &HashMap<String, String>) -> String {
-        mapping
-            .get(&self.os)
-            .cloned()
-            .unwrap_or_else(|| self.os.clone())
+    /// Detects the host platform, replacing the OS and/or arch with explicit overrides
+    ///
+    /// Overrides use the same names as detection (e.g. `linux`, `macos`, `x86_64`,
+    /// `aarch64`) so they go through the OS/arch mappings like detected values.
+    pub fn with_overrides(os: Option<&str>, arch: Option<&str>) -> Self {
+        let detected = Self::detect();
+        Self {
+            os: os.map(str::to_string).unwrap_or(detected.os),
+            arch: arch.map(str::to_string).unwrap_or(detected.arch),
+        }
+    }
+
+    /// Whether this platform is the one Ora is running on
+    pub fn is_host(&self) -> bool {
+        self.os == std::env::consts::OS && self.arch == std::env::consts::ARCH
+    }
+
+    /// C library flavor (`gnu` or `musl`) on Linux, `None` on other systems
+    ///
+    /// When it can't be determined (including installs for another platform),
+    /// musl is assumed: musl builds are usually static and run on either.
+    pub fn libc(&self) -> Option<&'static str> {
+        if self.os != "linux" {
+            return None;
+        }
+
+        let detected = if self.is_host() {
+            detect_host_libc()
+        } else {
+            None
+        };
+
+        match detected {
+            Some(libc) => {
+                log::debug!("Detected C library: {}", libc);
+                Some(libc)
+            }
+            None => {
+                log::info!("Could not determine the C library, preferring musl builds");
+                Some(LIBC_MUSL)
+            }
+        }
+    }
+
+    /// Candidate OS names for this platform, in order of preference: the
+    /// mapped names, the detected name, then `fallbacks`
+    pub fn map_os(
+        &self,
+        mapping: &HashMap<String, Vec<String>>,
+        fallbacks: &[String],
+    ) -> Vec<String> {
+        Self::candidates(&self.os, mapping, fallbacks)
+    }
+
+    /// Candidate arch names for this platform, in order of preference: the
+    /// mapped names, the detected name, then `fallbacks`
+    pub fn map_arch(
+        &self,
+        mapping: &HashMap<String, Vec<String>>,
+        fallbacks: &[String],
+    ) -> Vec<String> {
+        Self::candidates(&self.arch, mapping, fallbacks)
     }
 
-    pub fn map_arch(&self, mapping: &HashMap<String, String>) -> String {
-        mapping
-            .get(&self.arch)
-            .cloned()
-            .unwrap_or_else(|| self.arch.clone())
+    fn candidates(
+        detected: &str,
+        mapping: &HashMap<String, Vec<String>>,
+        fallbacks: &[String],
+    ) -> Vec<String> {
+        let mapped = mapping.get(detected).into_iter().flatten();
+        let mut candidates: Vec<String> = Vec::new();
+        for name in mapped
+            .map(String::as_str)
+            .chain(std::iter::once(detected))
+            .chain(fallbacks.iter().map(String::as_str))
+        {
+            if !candidates.iter().any(|c| c == name) {
+                candidates.push(name.to_string());
+            }
+        }
+        candidates
     }
 
     /// Reserved for future use when platform key is needed.
@@ -43,17 +117,141 @@ impl Platform {
     }
 }
 
+/// Probes the running system for its C library
+fn detect_host_libc() -> Option<&'static str> {
+    // The musl dynamic loader is installed as /lib/ld-musl-<arch>.so.1
+    let has_musl_loader = glob::glob("/lib/ld-musl-*.so.1")
+        .map(|mut paths| paths.any(|p| p.is_ok()))
+        .unwrap_or(false);
+    if has_musl_loader {
+        return Some(LIBC_MUSL);
+    }
+
+    // musl's ldd prints its banner to stderr and exits non-zero, so check both streams
+    let output = std::process::Command::new("ldd")
+        .arg("--version")
+        .output()
+        .ok()?;
+    classify_ldd_output(&format!(
+        "{}{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    ))
+}
+
+/// Identifies the C library from `ldd --version` output
+fn classify_ldd_output(output: &str) -> Option<&'static str> {
+    let output = output.to_lowercase();
+    if output.contains("musl") {
+        Some(LIBC_MUSL)
+    } else if output.contains("glibc")
+        || output.contains("gnu libc")
+        || output.contains("gnu c library")
+    {
+        Some(LIBC_GNU)
+    } else {
+        None
+    }
+}
+
 // Default mappings for common cases
-pub fn default_os_mapping() -> HashMap<String, String> {
+pub fn default_os_mapping() -> HashMap<String, Vec<String>> {
     let mut map = HashMap::new();
-    map.insert("macos".to_string(), "darwin".to_string());
-    map.insert("linux".to_string(), "linux".to_string());
+    map.insert("macos".to_string(), vec!["darwin".to_string()]);
+    map.insert("linux".to_string(), vec!["linux".to_string()]);
     map
 }
 
-pub fn default_arch_mapping() -> HashMap<String, String> {
+pub fn default_arch_mapping() -> HashMap<String, Vec<String>> {
     let mut map = HashMap::new();
-    map.insert("x86_64".to_string(), "amd64".to_string());
-    map.insert("aarch64".to_string(), "arm64".to_string());
+    map.insert("x86_64".to_string(), vec!["amd64".to_string()]);
+    map.insert("aarch64".to_string(), vec!["arm64".to_string()]);
     map
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_overrides() {
+        let host = Platform::with_overrides(None, None);
+        assert!(host.is_host());
+
+        let foreign = Platform::with_overrides(Some("plan9"), Some("aarch64"));
+        assert_eq!(foreign.os, "plan9");
+        assert_eq!(foreign.arch, "aarch64");
+        assert!(!foreign.is_host());
+
+        let arch_only = Platform::with_overrides(None, Some("riscv64"));
+        assert_eq!(arch_only.os, std::env::consts::OS);
+        assert_eq!(arch_only.arch, "riscv64");
+    }
+
+    #[test]
+    fn test_map_arch_candidates() {
+        let platform = Platform {
+            os: "linux".to_string(),
+            arch: "x86_64".to_string(),
+        };
+
+        let mut mapping = HashMap::new();
+        mapping.insert(
+            "x86_64".to_string(),
+            vec!["amd64".to_string(), "x64".to_string()],
+        );
+        assert_eq!(
+            platform.map_arch(&mapping, &[]),
+            vec!["amd64", "x64", "x86_64"]
+        );
+
+        // Unmapped values fall back to the detected name
+        assert_eq!(platform.map_os(&HashMap::new(), &[]), vec!["linux"]);
+    }
+
+    #[test]
+    fn test_platform_fallbacks() {
+        let platform = Platform {
+            os: "freebsd".to_string(),
+            arch: "riscv64".to_string(),
+        };
+
+        let fallbacks = vec!["linux".to_string(), "freebsd".to_string()];
+        assert_eq!(
+            platform.map_os(&HashMap::new(), &fallbacks),
+            vec!["freebsd", "linux"]
+        );
+
+        let mut mapping = HashMap::new();
+        mapping.insert("riscv64".to_string(), vec!["riscv64gc".to_string()]);
+        assert_eq!(
+            platform.map_arch(&mapping, &["x86_64".to_string()]),
+            vec!["riscv64gc", "riscv64", "x86_64"]
+        );
+    }
+
+    #[test]
+    fn test_classify_ldd_output() {
+        assert_eq!(
+            classify_ldd_output("ldd (Ubuntu GLIBC 2.35-0ubuntu3) 2.35\nCopyright (C) 2022"),
+            Some(LIBC_GNU)
+        );
+        assert_eq!(
+            classify_ldd_output("musl libc (x86_64)\nVersion 1.2.4\nDynamic Program Loader"),
+            Some(LIBC_MUSL)
+        );
+        assert_eq!(classify_ldd_output("ldd: command not found"), None);
+    }
+
+    #[test]
+    fn test_libc_only_on_linux() {
+        let macos = Platform::with_overrides(Some("macos"), Some("aarch64"));
+        assert_eq!(macos.libc(), None);
+
+        // Foreign Linux targets can't be probed and fall back to musl
+        let foreign = Platform::with_overrides(Some("linux"), Some("riscv64"));
+        if !foreign.is_host() {
+            assert_eq!(foreign.libc(), Some(LIBC_MUSL));
+        }
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+use crate::error::OraError;
+
+/// Global shutdown flag, set on Ctrl+C / SIGTERM
+static SHUTDOWN_REQUESTED: AtomicBool = AtomicBool::new(false);
+
+/// Ask long-running operations to stop at their next checkpoint
+pub fn request_shutdown() {
+    SHUTDOWN_REQUESTED.store(true, Ordering::SeqCst);
+}
+
+/// Check if shutdown has been requested
+pub fn is_shutdown_requested() -> bool {
+    SHUTDOWN_REQUESTED.load(Ordering::Relaxed)
+}
+
+/// Fails with [`OraError::Interrupted`] once shutdown has been requested
+///
+/// Called once per item by loops over archive entries or files.
+pub fn check_shutdown() -> Result<(), OraError> {
+    if is_shutdown_requested() {
+        Err(OraError::Interrupted)
+    } else {
+        Ok(())
+    }
+}
+
+/// Filesystem change to undo if an operation doesn't complete
+#[derive(Debug)]
+enum UndoStep {
+    /// Delete a file, symlink or directory tree that was created
+    Remove(PathBuf),
+    /// Point a symlink back at its previous target, or delete it if it had none
+    RestoreLink {
+        link: PathBuf,
+        target: Option<PathBuf>,
+    },
+}
+
+impl UndoStep {
+    fn undo(&self) {
+        let result = match self {
+            Self::Remove(path) => {
+                if path.is_dir() && !path.is_symlink() {
+                    std::fs::remove_dir_all(path)
+                } else if path.exists() || path.is_symlink() {
+                    std::fs::remove_file(path)
+                } else {
+                    Ok(())
+                }
+            }
+            Self::RestoreLink { link, target } => {
+                let removed = if link.is_symlink() {
+                    std::fs::remove_file(link)
+                } else {
+                    Ok(())
+                };
+                match target {
+                    Some(target) => removed.and_then(|_| symlink(target, link)),
+                    None => removed,
+                }
+            }
+        };
+        if let Err(e) = result {
+            log::warn!("Failed to roll back {:?}: {}", self, e);
+        }
+    }
+}
+
+#[cfg(unix)]
+fn symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(target, link)
+}
+
+/// Binary links point at files, `current` links at directories
+#[cfg(windows)]
+fn symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    let resolved = match link.parent() {
+        Some(parent) => parent.join(target),
+        None => target.to_path_buf(),
+    };
+    if resolved.is_dir() {
+        std::os::windows::fs::symlink_dir(target, link)
+    } else {
+        std::os::windows::fs::symlink_file(target, link)
+    }
+}
+
+/// Undo steps of every operation in progress, by guard id
+static PENDING_UNDO: Mutex<BTreeMap<u64, Vec<UndoStep>>> = Mutex::new(BTreeMap::new());
+static NEXT_GUARD_ID: AtomicU64 = AtomicU64::new(0);
+
+fn pending_undo() -> MutexGuard<'static, BTreeMap<u64, Vec<UndoStep>>> {
+    PENDING_UNDO.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Records the filesystem changes of an operation so they can be rolled back
+///
+/// Changes are rolled back when the guard is dropped without [`commit`](Self::commit)
+/// (an error return), and by [`rollback_pending`], which the Ctrl+C / SIGTERM
+/// handler calls before exiting without unwinding.
+pub struct CleanupGuard {
+    id: u64,
+}
+
+impl CleanupGuard {
+    pub fn new() -> Self {
+        let id = NEXT_GUARD_ID.fetch_add(1, Ordering::Relaxed);
+        pending_undo().insert(id, Vec::new());
+        Self { id }
+    }
+
+    /// Deletes `path` (recursively for directories) on rollback
+    pub fn remove_on_abort(&self, path: impl Into<PathBuf>) {
+        self.push(UndoStep::Remove(path.into()));
+    }
+
+    /// Points `link` back at `target` on rollback, or deletes it when `None`
+    pub fn restore_link_on_abort(&self, link: impl Into<PathBuf>, target: Option<PathBuf>) {
+        self.push(UndoStep::RestoreLink {
+            link: link.into(),
+            target,
+        });
+    }
+
+    /// Keeps the changes: the operation completed
+    pub fn commit(self) {
+        pending_undo().remove(&self.id);
+    }
+
+    fn push(&self, step: UndoStep) {
+        if let Some(steps) = pending_undo().get_mut(&self.id) {
+            steps.push(step);
+        }
+    }
+}
+
+impl Default for CleanupGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for CleanupGuard {
+    fn drop(&mut self) {
+        let steps = pending_undo().remove(&self.id);
+        for step in steps.into_iter().flatten().rev() {
+            step.undo();
+        }
+    }
+}
+
+/// Rolls back every operation still in progress, newest change first
+pub fn rollback_pending() {
+    let pending = std::mem::take(&mut *pending_undo());
+    for step in pending.into_values().flatten().rev() {
+        step.undo();
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cleanup_guard_rolls_back_unless_committed() {
+        let dir = tempfile::tempdir().unwrap();
+        let version_dir = dir.path().join("2.0.0");
+        let current = dir.path().join("current");
+        std::os::unix::fs::symlink("1.0.0", &current).unwrap();
+
+        {
+            let cleanup = CleanupGuard::new();
+            cleanup.remove_on_abort(&version_dir);
+            std::fs::create_dir_all(version_dir.join("bin")).unwrap();
+            std::fs::write(version_dir.join("bin/tool"), "").unwrap();
+
+            cleanup.restore_link_on_abort(&current, Some(PathBuf::from("1.0.0")));
+            std::fs::remove_file(&current).unwrap();
+            std::os::unix::fs::symlink("2.0.0", &current).unwrap();
+        }
+        assert!(!version_dir.exists());
+        assert_eq!(
+            std::fs::read_link(&current).unwrap(),
+            PathBuf::from("1.0.0")
+        );
+
+        let cleanup = CleanupGuard::new();
+        cleanup.remove_on_abort(&version_dir);
+        std::fs::create_dir(&version_dir).unwrap();
+        cleanup.commit();
+        assert!(version_dir.exists());
+    }
+}
@@ -0,0 +1,159 @@
+//! Aligned column output for the human-readable views of `list` and `search`
+
+use console::{measure_text_width, pad_str, style, Alignment, Term};
+
+use crate::utils::ui::plain_output;
+
+/// Spaces between two columns
+const COLUMN_GAP: usize = 2;
+
+/// Columns are never shrunk below this many characters (or their header)
+const MIN_COLUMN_WIDTH: usize = 8;
+
+/// Rows of text cells rendered as left-aligned columns under a bold header
+pub struct Table {
+    headers: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl Table {
+    pub fn new(headers: &[&str]) -> Self {
+        Self {
+            headers: headers.iter().map(|header| header.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Adds a row; missing cells are left empty and extra cells are ignored
+    pub fn add_row(&mut self, mut cells: Vec<String>) {
+        cells.resize(self.headers.len(), String::new());
+        self.rows.push(cells);
+    }
+
+    /// Prints the table, fitted to the terminal when stdout is one
+    pub fn print(&self) {
+        let width = Term::stdout()
+            .size_checked()
+            .map(|(_, columns)| columns as usize);
+        for line in self.render(width) {
+            println!("{}", line);
+        }
+    }
+
+    /// Renders the header and rows, truncating cells with an ellipsis so each
+    /// line fits in `max_width` (no limit when `None`)
+    pub fn render(&self, max_width: Option<usize>) -> Vec<String> {
+        let widths = self.column_widths(max_width);
+        let ellipsis = if plain_output() { "..." } else { "…" };
+
+        let render_line = |cells: &[String], header: bool| {
+            let last = cells.len().saturating_sub(1);
+            let line = cells
+                .iter()
+                .zip(&widths)
+                .enumerate()
+                .map(|(i, (cell, &width))| {
+                    // The last column isn't padded, so lines have no trailing spaces
+                    let cell = if i == last && measure_text_width(cell) <= width {
+                        cell.to_string()
+                    } else {
+                        pad_str(cell, width, Alignment::Left, Some(ellipsis)).into_owned()
+                    };
+                    if header {
+                        style(cell).bold().to_string()
+                    } else {
+                        cell
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(&" ".repeat(COLUMN_GAP));
+            line.trim_end().to_string()
+        };
+
+        let mut lines = vec![render_line(&self.headers, true)];
+        lines.extend(self.rows.iter().map(|row| render_line(row, false)));
+        lines
+    }
+
+    /// Natural column widths, with the widest columns shrunk until the line fits
+    fn column_widths(&self, max_width: Option<usize>) -> Vec<usize> {
+        let mut widths: Vec<usize> = self
+            .headers
+            .iter()
+            .enumerate()
+            .map(|(i, header)| {
+                self.rows
+                    .iter()
+                    .map(|row| measure_text_width(&row[i]))
+                    .chain([measure_text_width(header)])
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let Some(max_width) = max_width else {
+            return widths;
+        };
+
+        let gaps = COLUMN_GAP * widths.len().saturating_sub(1);
+        let minimums: Vec<usize> = self
+            .headers
+            .iter()
+            .zip(&widths)
+            .map(|(header, &width)| width.min(MIN_COLUMN_WIDTH.max(measure_text_width(header))))
+            .collect();
+        while widths.iter().sum::<usize>() + gaps > max_width {
+            let Some(widest) = (0..widths.len())
+                .filter(|&i| widths[i] > minimums[i])
+                .max_by_key(|&i| widths[i])
+            else {
+                break;
+            };
+            widths[widest] -= 1;
+        }
+        widths
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table() -> Table {
+        console::set_colors_enabled(false);
+        let mut table = Table::new(&["NAME", "VERSION", "REGISTRY"]);
+        table.add_row(vec![
+            "ripgrep".to_string(),
+            "14.1.0".to_string(),
+            "official".to_string(),
+        ]);
+        table.add_row(vec![
+            "a-package-with-a-very-long-name".to_string(),
+            "1.0.0-beta.1".to_string(),
+        ]);
+        table
+    }
+
+    #[test]
+    fn test_render_aligns_columns() {
+        assert_eq!(
+            table().render(None),
+            vec![
+                "NAME                             VERSION       REGISTRY",
+                "ripgrep                          14.1.0        official",
+                "a-package-with-a-very-long-name  1.0.0-beta.1",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_render_truncates_to_width() {
+        assert_eq!(
+            table().render(Some(40)),
+            vec![
+                "NAME              VERSION       REGISTRY",
+                "ripgrep           14.1.0        official",
+                "a-package-with-…  1.0.0-beta.1",
+            ]
+        );
+    }
+}
@@ -1,24 +1,157 @@
+use anyhow::{Context, Result};
 use console::style;
 use indicatif::{ProgressBar, ProgressStyle};
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Global plain-output flag (set from `--plain`); when enabled, emoji and
+/// box-drawing characters are replaced with ASCII
+static PLAIN_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Enable or disable plain ASCII output for the whole process
+pub fn set_plain_output(enabled: bool) {
+    PLAIN_OUTPUT.store(enabled, Ordering::SeqCst);
+}
+
+/// Check if plain ASCII output is enabled
+pub fn plain_output() -> bool {
+    PLAIN_OUTPUT.load(Ordering::Relaxed)
+}
+
+/// Global assume-yes flag (set from `--yes`); when enabled, every
+/// confirmation is accepted without prompting
+static ASSUME_YES: AtomicBool = AtomicBool::new(false);
+
+/// Accept (or stop accepting) every confirmation for the whole process
+pub fn set_assume_yes(enabled: bool) {
+    ASSUME_YES.store(enabled, Ordering::SeqCst);
+}
+
+/// Check if confirmations are answered with yes automatically
+pub fn assume_yes() -> bool {
+    ASSUME_YES.load(Ordering::Relaxed)
+}
+
+/// Asks a yes/no question on stdin; anything but "y"/"yes" (including EOF) is no
+///
+/// With `--yes` the answer is yes without reading stdin. Without a terminal on
+/// stdin the answer is no, so unattended runs never hang on a prompt.
+pub fn confirm(question: &str) -> Result<bool> {
+    println!("{}", question);
+    if assume_yes() {
+        println!("yes (--yes)");
+        return Ok(true);
+    }
+    if !std::io::stdin().is_terminal() {
+        println!("no (not a terminal; pass --yes to accept)");
+        return Ok(false);
+    }
+
+    let mut input = String::new();
+    std::io::stdin()
+        .read_line(&mut input)
+        .context("Failed to read user input")?;
+    let answer = input.trim();
+    Ok(answer.eq_ignore_ascii_case("y") || answer.eq_ignore_ascii_case("yes"))
+}
+
+/// Status symbols and box-drawing characters with their plain equivalents
+const PLAIN_REPLACEMENTS: &[(&str, &str)] = &[
+    ("✅", "[OK]"),
+    ("✓", "[OK]"),
+    ("❌", "[FAIL]"),
+    ("✗", "[FAIL]"),
+    ("⚠️", "[WARN]"),
+    ("⚠", "[WARN]"),
+    ("ℹ", "[INFO]"),
+    ("├─", "|-"),
+    ("└─", "`-"),
+    ("━", "-"),
+    ("═", "="),
+    ("•", "-"),
+    ("→", "->"),
+];
+
+/// Returns `text` unchanged, or converted to plain ASCII decorations in plain mode
+pub fn decorate(text: &str) -> String {
+    if plain_output() {
+        plain_text(text)
+    } else {
+        text.to_string()
+    }
+}
+
+/// Replaces status symbols with `[OK]`/`[FAIL]`-style tags and drops other pictographs
+fn plain_text(text: &str) -> String {
+    let mut replaced = text.to_string();
+    for (from, to) in PLAIN_REPLACEMENTS {
+        replaced = replaced.replace(from, to);
+    }
+
+    // Remaining emoji are decorative (e.g. "📋 Ora Configuration"): drop them
+    // along with their variation selector and the following spaces
+    let mut result = String::with_capacity(replaced.len());
+    let mut chars = replaced.chars().peekable();
+    while let Some(c) = chars.next() {
+        if is_pictograph(c) {
+            while chars.next_if_eq(&'\u{FE0F}').is_some() {}
+            while chars.next_if_eq(&' ').is_some() {}
+        } else if c != '\u{FE0F}' {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn is_pictograph(c: char) -> bool {
+    matches!(c as u32, 0x2600..=0x27BF | 0x1F300..=0x1FAFF)
+}
+
+/// `println!` for decorated output, honoring `--plain`
+#[macro_export]
+macro_rules! ui_println {
+    () => {
+        println!()
+    };
+    ($($arg:tt)*) => {
+        println!("{}", $crate::utils::ui::decorate(&format!($($arg)*)))
+    };
+}
+
+/// Formats a byte count with a binary unit, e.g. `1.50 MB`
+pub fn format_size(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = 1024 * KB;
+    const GB: u64 = 1024 * MB;
+
+    if bytes >= GB {
+        format!("{:.2} GB", bytes as f64 / GB as f64)
+    } else if bytes >= MB {
+        format!("{:.2} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.2} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{} bytes", bytes)
+    }
+}
 
 // UI helper functions - planned for future CLI improvements
 #[allow(dead_code)]
 pub fn success(msg: &str) {
-    println!("{} {}", style("✅").green().bold(), msg);
+    println!("{} {}", style(decorate("✅")).green().bold(), msg);
 }
 
 pub fn error(msg: &str) {
-    eprintln!("{} {}", style("❌").red().bold(), msg);
+    eprintln!("{} {}", style(decorate("❌")).red().bold(), msg);
 }
 
-#[allow(dead_code)]
 pub fn warning(msg: &str) {
-    println!("{} {}", style("⚠️").yellow().bold(), msg);
+    println!("{} {}", style(decorate("⚠️")).yellow().bold(), msg);
 }
 
 #[allow(dead_code)]
 pub fn info(msg: &str) {
-    println!("{} {}", style("ℹ").blue().bold(), msg);
+    println!("{} {}", style(decorate("ℹ")).blue().bold(), msg);
 }
 
 #[allow(dead_code)]
@@ -35,6 +168,21 @@ pub fn create_progress_bar(len: u64) -> ProgressBar {
     pb
 }
 
+/// Progress bar for git object transfers; the message shows the bytes received
+pub fn create_transfer_progress_bar(prefix: &str) -> ProgressBar {
+    let pb = ProgressBar::new(0);
+    pb.set_style(
+        ProgressStyle::default_bar()
+            .template("{prefix} [{bar:40.cyan/blue}] {pos}/{len} objects ({msg})")
+            // NOTE: This expect() is acceptable because the template string is a compile-time constant.
+            // If the template is invalid, it's a programmer error and should fail fast at startup.
+            .expect("BUG: Invalid hardcoded progress bar template")
+            .progress_chars("#>-"),
+    );
+    pb.set_prefix(prefix.to_string());
+    pb
+}
+
 #[allow(dead_code)]
 pub fn create_spinner(msg: &str) -> ProgressBar {
     let pb = ProgressBar::new_spinner();
@@ -48,3 +196,39 @@ pub fn create_spinner(msg: &str) -> ProgressBar {
     pb.set_message(msg.to_string());
     pb
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 bytes");
+        assert_eq!(format_size(1536), "1.50 KB");
+        assert_eq!(format_size(10 * 1024 * 1024), "10.00 MB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.00 GB");
+    }
+
+    #[test]
+    fn test_plain_text() {
+        assert_eq!(
+            plain_text("📋 Ora Configuration Status"),
+            "Ora Configuration Status"
+        );
+        assert_eq!(
+            plain_text("   ✅ Global Config: Loaded successfully"),
+            "   [OK] Global Config: Loaded successfully"
+        );
+        assert_eq!(
+            plain_text("     └─ Error: ❌ broken"),
+            "     `- Error: [FAIL] broken"
+        );
+        assert_eq!(plain_text("⚠️  Warning"), "[WARN]  Warning");
+        assert_eq!(
+            plain_text("\n🗃️  Registry Security:"),
+            "\nRegistry Security:"
+        );
+        assert_eq!(plain_text("━━━"), "---");
+        assert!(plain_text("⚙️  Resource Limits:").is_ascii());
+    }
+}
@@ -107,6 +107,42 @@ impl MockRegistry {
         Ok(())
     }
 
+    /// Add a .repo file on a separate branch, leaving HEAD untouched
+    #[allow(dead_code)]
+    pub fn add_repo_file_on_branch(&self, branch: &str, name: &str, content: &str) -> Result<()> {
+        let repo = git2::Repository::open(&self.repo_path)?;
+        let head_commit = repo.head()?.peel_to_commit()?;
+
+        // Build a tree containing the new file on top of HEAD's tree
+        let blob_id = repo.blob(content.as_bytes())?;
+        let head_tree = head_commit.tree()?;
+        let registry_entry = head_tree.get_name("ora-registry");
+        let registry_tree = match registry_entry {
+            Some(entry) => Some(repo.find_tree(entry.id())?),
+            None => None,
+        };
+        let mut registry_builder = repo.treebuilder(registry_tree.as_ref())?;
+        registry_builder.insert(format!("{}.repo", name), blob_id, 0o100644)?;
+        let registry_tree_id = registry_builder.write()?;
+
+        let mut root_builder = repo.treebuilder(Some(&head_tree))?;
+        root_builder.insert("ora-registry", registry_tree_id, 0o040000)?;
+        let tree = repo.find_tree(root_builder.write()?)?;
+
+        let signature = git2::Signature::now("Ora Test", "test@example.com")?;
+        let refname = format!("refs/heads/{}", branch);
+        repo.commit(
+            Some(&refname),
+            &signature,
+            &signature,
+            &format!("Add {} package on {}", name, branch),
+            &tree,
+            &[&head_commit],
+        )?;
+
+        Ok(())
+    }
+
     /// List all packages in the registry
     pub fn list_packages(&self) -> Result<Vec<String>> {
         let packages_dir = self.repo_path.join("ora-registry");
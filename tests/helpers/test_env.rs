@@ -97,6 +97,7 @@ max_extracted_size = 21474836480  # 20GB
         std::env::set_var("ORA_CONFIG_DIR", &self.config_dir);
         std::env::set_var("ORA_DATA_DIR", &self.data_dir);
         std::env::set_var("ORA_CACHE_DIR", &self.cache_dir);
+        std::env::set_var("ORA_BIN_DIR", &self.bin_dir);
     }
 
     /// Unset environment variables
@@ -104,6 +105,7 @@ max_extracted_size = 21474836480  # 20GB
         std::env::remove_var("ORA_CONFIG_DIR");
         std::env::remove_var("ORA_DATA_DIR");
         std::env::remove_var("ORA_CACHE_DIR");
+        std::env::remove_var("ORA_BIN_DIR");
     }
 
     /// Check if a package is installed
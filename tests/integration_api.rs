@@ -0,0 +1,49 @@
+mod helpers;
+use helpers::TestEnvironment;
+use ora::{InstallSpec, Ora};
+
+// Library calls read ORA_*_DIR from the process environment, so everything that
+// depends on it runs in a single test to avoid racing other tests.
+#[tokio::test]
+async fn test_library_api_without_installed_packages() {
+    let env = TestEnvironment::new().unwrap();
+    env.set_env_vars();
+
+    let ora = Ora::new();
+
+    assert!(ora.list().await.unwrap().is_empty());
+
+    let err = ora
+        .uninstall("nonexistent-package", None)
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("not installed"));
+
+    let spec = InstallSpec {
+        local: Some(env.cache_dir().join("missing.tar.gz").display().to_string()),
+        ..InstallSpec::new("local-package")
+    };
+    assert!(ora.install(&spec).await.is_err());
+
+    let spec = InstallSpec {
+        verify_only: true,
+        ..spec
+    };
+    let err = ora.install(&spec).await.unwrap_err();
+    assert!(err.to_string().contains("local archive"));
+
+    env.cleanup();
+}
+
+#[test]
+fn test_install_spec_defaults_to_latest_in_default_mode() {
+    let spec = InstallSpec::new("windman");
+
+    assert_eq!(spec.package, "windman");
+    assert!(spec.version.is_none());
+    assert!(spec.mode.is_none());
+    assert!(!spec.allow_insecure);
+    assert!(spec.local.is_none());
+    assert!(!spec.verify_only);
+    assert!(!spec.skip_checksum);
+}
@@ -0,0 +1,106 @@
+mod helpers;
+use assert_cmd::prelude::*;
+use helpers::TestEnvironment;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Writes a tar.gz containing an executable `hello` and its local-install metadata
+fn create_local_package(dir: &Path) -> (PathBuf, PathBuf) {
+    let archive_path = dir.join("hello.tar.gz");
+    let file = std::fs::File::create(&archive_path).unwrap();
+    let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+    let mut builder = tar::Builder::new(encoder);
+
+    let content = b"#!/bin/sh\necho hello\n";
+    let mut header = tar::Header::new_gnu();
+    header.set_size(content.len() as u64);
+    header.set_mode(0o755);
+    header.set_cksum();
+    builder
+        .append_data(&mut header, "hello", &content[..])
+        .unwrap();
+    builder.into_inner().unwrap().finish().unwrap();
+
+    let metadata_path = dir.join("hello.toml");
+    std::fs::write(
+        &metadata_path,
+        "name = \"hello\"\nversion = \"1.0.0\"\nbinaries = [\"hello\"]\n",
+    )
+    .unwrap();
+
+    (archive_path, metadata_path)
+}
+
+fn ora(env: &TestEnvironment) -> Command {
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .env("HOME", env.base_dir());
+    cmd
+}
+
+#[test]
+fn test_hooks_run_after_install_and_uninstall() {
+    let env = TestEnvironment::new().unwrap();
+    let (archive, metadata) = create_local_package(env.base_dir());
+    let log_file = env.base_dir().join("hooks.log");
+
+    std::fs::write(
+        env.config_dir().join("config.toml"),
+        format!(
+            "[hooks]\n\
+             post_install = 'echo \"install $1 $2 $ORA_HOOK_EVENT\" >> {log}'\n\
+             post_uninstall = 'echo \"uninstall $1 $2\" >> {log}'\n",
+            log = log_file.display()
+        ),
+    )
+    .unwrap();
+
+    ora(&env)
+        .arg("install")
+        .arg("hello")
+        .arg("--local")
+        .arg(&archive)
+        .arg("--metadata")
+        .arg(&metadata)
+        .assert()
+        .success();
+
+    ora(&env).arg("uninstall").arg("hello").assert().success();
+
+    let log = std::fs::read_to_string(&log_file).unwrap();
+    assert_eq!(
+        log,
+        "install hello 1.0.0 post_install\nuninstall hello 1.0.0\n"
+    );
+}
+
+#[test]
+fn test_no_hooks_flag_skips_hooks() {
+    let env = TestEnvironment::new().unwrap();
+    let (archive, metadata) = create_local_package(env.base_dir());
+    let log_file = env.base_dir().join("hooks.log");
+
+    std::fs::write(
+        env.config_dir().join("config.toml"),
+        format!(
+            "[hooks]\npost_install = 'echo \"$1\" >> {}'\n",
+            log_file.display()
+        ),
+    )
+    .unwrap();
+
+    ora(&env)
+        .arg("--no-hooks")
+        .arg("install")
+        .arg("hello")
+        .arg("--local")
+        .arg(&archive)
+        .arg("--metadata")
+        .arg(&metadata)
+        .assert()
+        .success();
+
+    assert!(!log_file.exists());
+}
@@ -125,6 +125,218 @@ fn test_install_with_version() {
     env.cleanup();
 }
 
+#[test]
+fn test_install_remote_repo_requires_https() {
+    let env = TestEnvironment::new().unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("install")
+        .arg("--repo")
+        .arg("http://example.com/windman.repo")
+        .arg("windman");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("must be fetched over HTTPS"));
+
+    env.cleanup();
+}
+
+#[test]
+fn test_install_output_dir_requires_download_only() {
+    let env = TestEnvironment::new().unwrap();
+    let output_dir = env.base_dir().join("artifacts");
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("install")
+        .arg("--output-dir")
+        .arg(&output_dir)
+        .arg("windman");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("--download-only"));
+    assert!(!output_dir.exists());
+
+    env.cleanup();
+}
+
+#[test]
+fn test_validate_check_urls_needs_network() {
+    let env = TestEnvironment::new().unwrap();
+    let repo_file = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/repo_files/ripgrep.repo");
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("--offline")
+        .arg("validate")
+        .arg("--check-urls")
+        .arg(&repo_file);
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("needs network access"));
+
+    env.cleanup();
+}
+
+#[test]
+fn test_concurrency_must_be_positive_and_is_capped() {
+    let env = TestEnvironment::new().unwrap();
+    let ora = |concurrency: &str| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+        cmd.env("ORA_CONFIG_DIR", env.config_dir())
+            .env("ORA_CACHE_DIR", env.cache_dir())
+            .env("ORA_DATA_DIR", env.data_dir())
+            .args(["--concurrency", concurrency, "list"]);
+        cmd
+    };
+
+    ora("0").assert().failure();
+    ora("99")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "--concurrency 99 is above the maximum; using 16",
+        ));
+
+    env.cleanup();
+}
+
+#[test]
+fn test_failed_command_keeps_cached_archives() {
+    let env = TestEnvironment::new().unwrap();
+    let downloads = env.cache_dir().join("downloads");
+    let cached = downloads.join("by-checksum/abcdef01/tool.tar.gz");
+    let extract = downloads.join("tool_extract");
+    std::fs::create_dir_all(cached.parent().unwrap()).unwrap();
+    std::fs::create_dir_all(&extract).unwrap();
+    std::fs::write(&cached, "archive").unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .args(["uninstall", "nonexistent-package"]);
+    cmd.assert().failure();
+
+    assert!(cached.exists());
+    assert!(!extract.exists());
+
+    env.cleanup();
+}
+
+#[test]
+fn test_platform_shows_repo_mapping() {
+    let env = TestEnvironment::new().unwrap();
+    let repo_file = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/repo_files/ripgrep.repo");
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("platform")
+        .arg("--os")
+        .arg("macos")
+        .arg("--arch")
+        .arg("x86_64")
+        .arg("--repo")
+        .arg(&repo_file);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("OS:    macos"))
+        .stdout(predicate::str::contains("x86_64 → amd64"))
+        .stdout(predicate::str::contains("macos_x86_64"));
+
+    env.cleanup();
+}
+
+#[test]
+fn test_install_locked_requires_lockfile_entry() {
+    let env = TestEnvironment::new().unwrap();
+    let lockfile = env.base_dir().join("ora.lock");
+
+    // Nothing installed: the lockfile is written empty
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("lock")
+        .arg("--output")
+        .arg(&lockfile);
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Locked 0 package(s)"));
+    assert!(lockfile.exists());
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("install")
+        .arg("--locked")
+        .arg("--lockfile")
+        .arg(&lockfile)
+        .arg("windman");
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("not in the lockfile"));
+
+    env.cleanup();
+}
+
+#[test]
+fn test_alias_resolves_package_name() {
+    let env = TestEnvironment::new().unwrap();
+    let ora = || {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+        cmd.env("ORA_CONFIG_DIR", env.config_dir())
+            .env("ORA_CACHE_DIR", env.cache_dir())
+            .env("ORA_DATA_DIR", env.data_dir());
+        cmd
+    };
+
+    ora()
+        .args(["alias", "add", "rg", "ripgrep"])
+        .assert()
+        .success();
+    ora()
+        .args(["alias", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("rg → ripgrep"));
+
+    // The lockfile lookup sees the resolved name
+    let lockfile = env.base_dir().join("ora.lock");
+    std::fs::write(&lockfile, "packages = []\n").unwrap();
+    ora()
+        .args(["install", "--locked", "--lockfile"])
+        .arg(&lockfile)
+        .arg("rg")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Package 'ripgrep' is not in the lockfile",
+        ));
+
+    ora().args(["alias", "remove", "rg"]).assert().success();
+    ora()
+        .args(["alias", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("No aliases defined"));
+
+    env.cleanup();
+}
+
 #[test]
 fn test_list_empty() {
     let env = TestEnvironment::new().unwrap();
@@ -184,6 +396,35 @@ fn test_download_path_valid_filename() {
     assert!(path.to_string_lossy().ends_with("test-package.tar.gz"));
 }
 
+#[test]
+fn test_checksum_download_path_is_keyed_by_hash() {
+    let path = Cache::checksum_download_path("ABCDEF01", "tool.tar.gz").unwrap();
+    assert!(path.ends_with("downloads/by-checksum/abcdef01/tool.tar.gz"));
+
+    // Same content, same cache entry, whatever the hash's case
+    assert_eq!(
+        path,
+        Cache::checksum_download_path("abcdef01", "tool.tar.gz").unwrap()
+    );
+    assert_ne!(
+        path,
+        Cache::checksum_download_path("abcdef02", "tool.tar.gz").unwrap()
+    );
+    assert_ne!(path, Cache::download_path("tool.tar.gz").unwrap());
+}
+
+#[test]
+fn test_checksum_download_path_rejects_unsafe_keys() {
+    for hash in ["", "xyz", "../abcdef", "ab/cd"] {
+        let err = Cache::checksum_download_path(hash, "tool.tar.gz").unwrap_err();
+        assert!(err.to_string().contains("Invalid checksum"), "{}", hash);
+    }
+    for filename in ["", "..", "../tool.tar.gz", "dir/tool.tar.gz"] {
+        let err = Cache::checksum_download_path("abcdef01", filename).unwrap_err();
+        assert!(err.to_string().contains("Invalid filename"), "{}", filename);
+    }
+}
+
 #[test]
 fn test_url_filename_extraction_with_trailing_slash() {
     // Simulate the bug fix: trailing slash handling (Issue #4)
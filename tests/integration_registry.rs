@@ -162,6 +162,43 @@ fn test_registry_sync_specific() {
         .stdout(predicate::str::contains("synced successfully"));
 }
 
+#[test]
+fn test_registry_repo_file_over_max_toml_size_is_refused() {
+    let env = TestEnvironment::new().unwrap();
+    let registry = MockRegistry::new().unwrap();
+
+    let url = registry.url();
+    for args in [
+        vec!["registry", "add", "test-registry", url.as_str()],
+        vec!["registry", "sync", "test-registry"],
+    ] {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+        cmd.env("ORA_CONFIG_DIR", env.config_dir())
+            .env("ORA_CACHE_DIR", env.cache_dir())
+            .env("ORA_DATA_DIR", env.data_dir())
+            .args(args);
+        cmd.assert().success();
+    }
+
+    std::fs::write(
+        env.config_dir().join("security.toml"),
+        "[validation]\nmax_toml_size = 16\n",
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("install")
+        .arg("windman");
+
+    cmd.assert()
+        .failure()
+        .stderr(predicate::str::contains("is too large"))
+        .stderr(predicate::str::contains("maximum 16 bytes"));
+}
+
 #[test]
 fn test_registry_sync_nonexistent() {
     let env = TestEnvironment::new().unwrap();
@@ -239,6 +276,38 @@ fn test_registry_verify_valid() {
         .stdout(predicate::str::contains("verification complete"));
 }
 
+#[test]
+fn test_registry_verify_plain_output() {
+    let env = TestEnvironment::new().unwrap();
+    let registry = MockRegistry::new().unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("registry")
+        .arg("add")
+        .arg("test-registry")
+        .arg(registry.url());
+    cmd.assert().success();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("--plain")
+        .arg("registry")
+        .arg("verify")
+        .arg("test-registry");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains(
+            "[OK] Registry found in configuration",
+        ))
+        .stdout(predicate::str::contains("✅").not());
+}
+
 #[test]
 fn test_registry_verify_nonexistent() {
     let env = TestEnvironment::new().unwrap();
@@ -322,6 +391,42 @@ fn test_registry_verify_shows_package_count() {
         .stdout(predicate::str::contains("Found").and(predicate::str::contains("package")));
 }
 
+#[test]
+fn test_registry_info_shows_sync_time_and_packages() {
+    let env = TestEnvironment::new().unwrap();
+    let registry = MockRegistry::new().unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("registry")
+        .arg("add")
+        .arg("test-registry")
+        .arg(registry.url());
+    cmd.assert().success();
+
+    let package_count = registry.list_packages().unwrap().len();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("registry")
+        .arg("info")
+        .arg("test-registry");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Registry: test-registry"))
+        .stdout(predicate::str::contains("(0 days ago)"))
+        .stdout(predicate::str::contains(format!(
+            "Packages: {}",
+            package_count
+        )))
+        .stdout(predicate::str::contains("Examples: "));
+}
+
 #[test]
 fn test_registry_list_with_verbose() {
     let env = TestEnvironment::new().unwrap();
@@ -413,3 +518,378 @@ fn test_registry_add_with_branch() {
         "Config should contain branch = \"master\""
     );
 }
+
+#[test]
+fn test_registry_sync_uses_configured_branch() {
+    let env = TestEnvironment::new().unwrap();
+    let registry = MockRegistry::new().unwrap();
+    registry
+        .add_repo_file_on_branch("staging", "staging-only", "name = \"staging-only\"\n")
+        .unwrap();
+
+    // Add the registry pinned to the non-default branch
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("registry")
+        .arg("add")
+        .arg("test-registry")
+        .arg(registry.url())
+        .arg("--branch")
+        .arg("staging");
+
+    cmd.assert().success();
+
+    // The clone must contain the branch-only file
+    let clone_file = env
+        .cache_dir()
+        .join("registries")
+        .join("test-registry")
+        .join("ora-registry")
+        .join("staging-only.repo");
+    assert!(
+        clone_file.exists(),
+        "Registry clone should be checked out on the configured branch"
+    );
+
+    // Syncing again must stay on the configured branch
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("registry")
+        .arg("sync")
+        .arg("test-registry");
+
+    cmd.assert().success();
+    assert!(clone_file.exists());
+}
+
+#[test]
+fn test_registry_sync_reports_changed_repo_files() {
+    let env = TestEnvironment::new().unwrap();
+    let registry = MockRegistry::new().unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("registry")
+        .arg("add")
+        .arg("test-registry")
+        .arg(registry.url());
+    cmd.assert().success();
+
+    // Nothing changed upstream since the initial clone
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("registry")
+        .arg("sync")
+        .arg("test-registry");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("(0 changed since last sync)"));
+
+    registry
+        .add_repo_file("new-package", "name = \"new-package\"\n")
+        .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("registry")
+        .arg("sync")
+        .arg("test-registry");
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("(1 changed since last sync)"));
+}
+
+#[test]
+fn test_registry_verify_uses_shared_registry_cache() {
+    let env = TestEnvironment::new().unwrap();
+    let registry = MockRegistry::new().unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("registry")
+        .arg("add")
+        .arg("test-registry")
+        .arg(registry.url());
+    cmd.assert().success();
+
+    // Move the clone into a shared cache, leaving the user's cache empty
+    let shared_dir = env.data_dir().join("shared-registries");
+    std::fs::create_dir_all(&shared_dir).unwrap();
+    std::fs::rename(
+        env.cache_dir().join("registries").join("test-registry"),
+        shared_dir.join("test-registry"),
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .env("ORA_REGISTRY_CACHE_DIRS", &shared_dir)
+        .arg("registry")
+        .arg("verify")
+        .arg("test-registry");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("✅ Registry synced locally"))
+        .stdout(predicate::str::contains("shared-registries"));
+}
+
+#[test]
+fn test_config_flag_overrides_config_dir() {
+    let env = TestEnvironment::new().unwrap();
+    let alt_config = env.base_dir().join("alt-config.toml");
+    std::fs::write(
+        &alt_config,
+        r#"
+[[registries]]
+name = "alt-registry"
+url = "https://example.com/alt-registry.git"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("--config")
+        .arg(&alt_config)
+        .arg("registry")
+        .arg("list");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("alt-registry"));
+
+    // Without the flag, the config directory's own (empty) config is used
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("registry")
+        .arg("list");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("alt-registry").not());
+}
+
+#[test]
+fn test_config_verify_json_reports_unsynced_registry() {
+    let env = TestEnvironment::new().unwrap();
+    std::fs::write(
+        env.config_dir().join("config.toml"),
+        r#"
+[[registries]]
+name = "unsynced"
+url = "https://example.com/unsynced.git"
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("config")
+        .arg("verify")
+        .arg("--json");
+
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let report: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert!(report["errors"].as_array().unwrap().is_empty());
+    let checks = report["checks"].as_array().unwrap();
+    let status = |name: &str| {
+        checks
+            .iter()
+            .find(|c| c["name"] == name)
+            .map(|c| c["status"].as_str().unwrap().to_string())
+    };
+    assert_eq!(status("global_config").as_deref(), Some("pass"));
+    assert_eq!(status("registry:unsynced").as_deref(), Some("warn"));
+}
+
+#[test]
+fn test_registry_list_json() {
+    let env = TestEnvironment::new().unwrap();
+    std::fs::write(
+        env.config_dir().join("config.toml"),
+        r#"
+[[registries]]
+name = "unsynced"
+url = "https://example.com/unsynced.git"
+trust_level = "private"
+priority = 2
+
+[[registries]]
+name = "single"
+url = "https://example.com/tool.repo"
+enabled = false
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("registry")
+        .arg("list")
+        .arg("--json");
+
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let registries: serde_json::Value = serde_json::from_slice(&output).unwrap();
+
+    assert_eq!(registries[0]["name"], "unsynced");
+    assert_eq!(registries[0]["trust_level"], "private");
+    assert_eq!(registries[0]["priority"], 2);
+    assert!(registries[0]["last_synced_at"].is_null());
+    assert!(registries[0]["package_count"].is_null());
+
+    assert_eq!(registries[1]["name"], "single");
+    assert_eq!(registries[1]["enabled"], false);
+    assert_eq!(registries[1]["package_count"], 1);
+}
+
+#[test]
+fn test_registry_set_url_and_trust() {
+    let env = TestEnvironment::new().unwrap();
+    std::fs::write(
+        env.config_dir().join("config.toml"),
+        r#"
+[[registries]]
+name = "moving"
+url = "https://example.com/old.git"
+trust_level = "public"
+index_checksum = "sha256:3b1f"
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        env.config_dir().join("trusted_keys.toml"),
+        r#"
+[keys.moving]
+fingerprint = "43FA23D18744084E658ADF547725D6577EEB7A95"
+trusted_at = "2024-01-01T00:00:00Z"
+"#,
+    )
+    .unwrap();
+    let ora = |args: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+        cmd.env("ORA_CONFIG_DIR", env.config_dir())
+            .env("ORA_CACHE_DIR", env.cache_dir())
+            .env("ORA_DATA_DIR", env.data_dir())
+            .arg("registry")
+            .args(args);
+        cmd
+    };
+
+    // An index downloaded by an earlier `sync --index-only`
+    let index_file = env.cache_dir().join("registries/moving.index.json");
+    std::fs::create_dir_all(index_file.parent().unwrap()).unwrap();
+    std::fs::write(&index_file, r#"{"packages": []}"#).unwrap();
+
+    ora(&["set-url", "moving", "https://example.org/new.git"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("was https://example.com/old.git"))
+        .stdout(predicate::str::contains("Cleared index_checksum"))
+        .stdout(predicate::str::contains("Forgot the trusted signing key"));
+    assert!(!index_file.exists(), "index of the old URL was kept");
+    let trusted_keys = std::fs::read_to_string(env.config_dir().join("trusted_keys.toml")).unwrap();
+    assert!(!trusted_keys.contains("moving"), "{}", trusted_keys);
+    ora(&["set-trust", "moving", "private"]).assert().success();
+    ora(&["set-trust", "moving", "trusted"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid trust level"));
+    ora(&["set-url", "moving", "ftp://example.org/new.git"])
+        .assert()
+        .failure();
+    ora(&["set-url", "missing", "https://example.org/new.git"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("not found"));
+
+    let output = ora(&["list", "--json"])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let registries: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(registries[0]["url"], "https://example.org/new.git");
+    assert_eq!(registries[0]["trust_level"], "private");
+    let config = std::fs::read_to_string(env.config_dir().join("config.toml")).unwrap();
+    assert!(!config.contains("index_checksum"), "{}", config);
+
+    std::fs::write(&index_file, r#"{"packages": []}"#).unwrap();
+    ora(&["remove", "moving"]).assert().success();
+    assert!(!index_file.exists(), "index of a removed registry was kept");
+}
+
+#[test]
+fn test_frozen_config_refuses_changes() {
+    let env = TestEnvironment::new().unwrap();
+    let config_file = env.config_dir().join("config.toml");
+    std::fs::write(
+        &config_file,
+        r#"
+frozen = true
+
+[[registries]]
+name = "managed"
+url = "https://example.com/managed.git"
+"#,
+    )
+    .unwrap();
+    let ora = |args: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+        cmd.env("ORA_CONFIG_DIR", env.config_dir())
+            .env("ORA_CACHE_DIR", env.cache_dir())
+            .env("ORA_DATA_DIR", env.data_dir())
+            .env_remove("ORA_FROZEN_CONFIG")
+            .args(args);
+        cmd
+    };
+
+    ora(&["registry", "remove", "managed"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("frozen"));
+    ora(&["alias", "add", "k", "kubectl"])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("frozen"));
+    ora(&["registry", "list"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("managed"));
+    assert!(std::fs::read_to_string(&config_file)
+        .unwrap()
+        .contains("managed"));
+
+    // The environment variable freezes a config that doesn't set the flag
+    std::fs::write(&config_file, "").unwrap();
+    ora(&["alias", "add", "k", "kubectl"])
+        .env("ORA_FROZEN_CONFIG", "1")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("frozen"));
+    ora(&["alias", "add", "k", "kubectl"]).assert().success();
+}
@@ -31,6 +31,223 @@ fn test_uninstall_installed_package() {
     env.cleanup();
 }
 
+/// Writes an installed.toml entry for a fake package with one binary and one extra file
+fn write_fake_install(env: &TestEnvironment) -> (std::path::PathBuf, Vec<std::path::PathBuf>) {
+    let version_dir = env.install_dir().join("fake-tool").join("1.0.0");
+    std::fs::create_dir_all(version_dir.join("bin")).unwrap();
+
+    let binary = version_dir.join("bin").join("fake-tool");
+    let readme = version_dir.join("README.md");
+    std::fs::write(&binary, "#!/bin/sh\n").unwrap();
+    std::fs::write(&readme, "readme").unwrap();
+
+    let link = env.bin_dir().join("fake-tool");
+    #[cfg(unix)]
+    std::os::unix::fs::symlink(&binary, &link).unwrap();
+
+    let installed = format!(
+        r#"
+[packages.fake-tool]
+name = "fake-tool"
+version = "1.0.0"
+installed_at = "2024-01-01T00:00:00Z"
+install_mode = "userland"
+install_dir = "{}"
+files = ["{}", "{}"]
+symlinks = ["{}"]
+registry_source = "test-registry"
+"#,
+        version_dir.display(),
+        binary.display(),
+        readme.display(),
+        link.display()
+    );
+    std::fs::write(env.config_dir().join("installed.toml"), installed).unwrap();
+
+    (version_dir, vec![binary, readme])
+}
+
+#[test]
+fn test_uninstall_dry_run_keeps_files() {
+    let env = TestEnvironment::new().unwrap();
+    let (version_dir, files) = write_fake_install(&env);
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("uninstall")
+        .arg("fake-tool")
+        .arg("--dry-run");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("dry run"))
+        .stdout(predicate::str::contains(
+            files[0].to_string_lossy().as_ref(),
+        ))
+        .stdout(predicate::str::contains(
+            files[1].to_string_lossy().as_ref(),
+        ));
+
+    // Nothing must have been removed
+    assert!(version_dir.exists());
+    assert!(files.iter().all(|f| f.exists()));
+    let installed = std::fs::read_to_string(env.config_dir().join("installed.toml")).unwrap();
+    assert!(installed.contains("fake-tool"));
+}
+
+#[test]
+fn test_uninstall_tolerates_missing_files() {
+    let env = TestEnvironment::new().unwrap();
+    let (version_dir, files) = write_fake_install(&env);
+
+    // Simulate a file deleted by hand after install
+    std::fs::remove_file(&files[1]).unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("uninstall")
+        .arg("fake-tool");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Successfully uninstalled"));
+
+    assert!(!version_dir.exists());
+    assert!(!env.bin_dir().join("fake-tool").exists());
+}
+
+#[test]
+fn test_uninstall_only_removes_paths_of_the_package() {
+    let env = TestEnvironment::new().unwrap();
+    let (version_dir, _) = write_fake_install(&env);
+
+    // A tampered database listing a file that belongs to someone else
+    let outside = env.base_dir().join("outside.txt");
+    std::fs::write(&outside, "not ours").unwrap();
+    let db_path = env.config_dir().join("installed.toml");
+    let db = std::fs::read_to_string(&db_path).unwrap();
+    std::fs::write(
+        &db_path,
+        db.replace(
+            "files = [",
+            &format!("files = [\"{}\", ", outside.display()),
+        ),
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("uninstall")
+        .arg("fake-tool");
+
+    cmd.assert().success();
+    assert!(outside.exists());
+    assert!(!version_dir.exists());
+}
+
+#[test]
+fn test_uninstall_refuses_foreign_install_dir() {
+    let env = TestEnvironment::new().unwrap();
+    let (version_dir, _) = write_fake_install(&env);
+
+    let db_path = env.config_dir().join("installed.toml");
+    let db = std::fs::read_to_string(&db_path).unwrap();
+    let foreign = env.base_dir().join("home");
+    std::fs::create_dir_all(&foreign).unwrap();
+    std::fs::write(
+        &db_path,
+        db.replace(
+            &format!("install_dir = \"{}\"", version_dir.display()),
+            &format!("install_dir = \"{}\"", foreign.display()),
+        ),
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("uninstall")
+        .arg("fake-tool");
+
+    cmd.assert().failure().stderr(predicate::str::contains(
+        "is not a 'fake-tool/1.0.0' install directory",
+    ));
+    assert!(foreign.exists());
+}
+
+#[test]
+fn test_uninstall_many_continues_past_failures() {
+    let env = TestEnvironment::new().unwrap();
+    let (version_dir, _) = write_fake_install(&env);
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("uninstall")
+        .arg("nonexistent-package")
+        .arg("fake-tool");
+
+    cmd.assert()
+        .failure()
+        .stdout(predicate::str::contains("Uninstalled 1 of 2 packages"))
+        .stderr(predicate::str::contains(
+            "Failed to uninstall: nonexistent-package",
+        ));
+
+    assert!(!version_dir.exists());
+}
+
+#[test]
+fn test_uninstall_all() {
+    let env = TestEnvironment::new().unwrap();
+    let (version_dir, _) = write_fake_install(&env);
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("uninstall")
+        .arg("--all")
+        .arg("--yes");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("Uninstalled 1 of 1 package"));
+
+    assert!(!version_dir.exists());
+    let installed = std::fs::read_to_string(env.config_dir().join("installed.toml")).unwrap();
+    assert!(!installed.contains("fake-tool"));
+}
+
+#[test]
+fn test_uninstall_all_declines_without_terminal() {
+    let env = TestEnvironment::new().unwrap();
+    let (version_dir, _) = write_fake_install(&env);
+
+    // No --yes and stdin is not a terminal: the prompt answers no instead of hanging
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .arg("uninstall")
+        .arg("--all");
+
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("pass --yes to accept"))
+        .stdout(predicate::str::contains("Uninstall cancelled"));
+
+    assert!(version_dir.exists());
+}
+
 #[test]
 fn test_update_no_packages() {
     let env = TestEnvironment::new().unwrap();
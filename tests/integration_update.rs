@@ -79,3 +79,94 @@ allow_insecure = false
 
     env.cleanup();
 }
+
+#[test]
+fn test_update_all_continue_skips_completed_packages() {
+    let env = TestEnvironment::new().unwrap();
+    let package = |name: &str| {
+        format!(
+            r#"
+[packages.{name}]
+schema_version = "0.1"
+name = "{name}"
+version = "1.0.0"
+installed_at = "2024-01-01T00:00:00Z"
+install_mode = "userland"
+install_dir = "/fake/{name}"
+files = []
+symlinks = []
+registry_source = "file:/fake/{name}.repo"
+allow_insecure = false
+"#
+        )
+    };
+    std::fs::write(
+        env.config_dir().join("installed.toml"),
+        package("alpha") + &package("beta"),
+    )
+    .unwrap();
+    let progress_file = env.data_dir().join("update-progress.toml");
+    std::fs::create_dir_all(env.data_dir()).unwrap();
+    std::fs::write(&progress_file, "completed = [\"alpha\"]\n").unwrap();
+    let ora = |args: &[&str]| {
+        let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+        cmd.env("ORA_CONFIG_DIR", env.config_dir())
+            .env("ORA_CACHE_DIR", env.cache_dir())
+            .env("ORA_DATA_DIR", env.data_dir())
+            .arg("update")
+            .args(args);
+        cmd
+    };
+
+    ora(&["alpha", "--continue"]).assert().failure();
+    ora(&["--all", "--continue"])
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("Skipping alpha: already updated"))
+        .stdout(predicate::str::contains("Skipping beta").not());
+
+    // A finished batch leaves no progress behind
+    assert!(!progress_file.exists());
+}
+
+#[test]
+fn test_update_all_continue_keeps_package_uninstalled_by_failed_update() {
+    let env = TestEnvironment::new().unwrap();
+    // gamma's old version was uninstalled, then its new version failed to install
+    let progress_file = env.data_dir().join("update-progress.toml");
+    std::fs::create_dir_all(env.data_dir()).unwrap();
+    std::fs::write(
+        &progress_file,
+        r#"
+packages = ["alpha", "gamma"]
+completed = ["alpha"]
+
+[uninstalled.gamma]
+schema_version = "0.1"
+name = "gamma"
+version = "1.0.0"
+installed_at = "2024-01-01T00:00:00Z"
+install_mode = "userland"
+install_dir = "/fake/gamma"
+files = []
+symlinks = []
+registry_source = "file:/fake/gamma.repo"
+allow_insecure = false
+"#,
+    )
+    .unwrap();
+
+    let mut cmd = Command::new(assert_cmd::cargo::cargo_bin!("ora"));
+    cmd.env("ORA_CONFIG_DIR", env.config_dir())
+        .env("ORA_CACHE_DIR", env.cache_dir())
+        .env("ORA_DATA_DIR", env.data_dir())
+        .args(["update", "--all", "--continue"]);
+
+    // The .repo file is gone, so gamma can't be installed again yet; it must
+    // stay in the batch rather than silently drop out of it
+    cmd.assert()
+        .success()
+        .stdout(predicate::str::contains("gamma is still uninstalled"));
+    let progress = std::fs::read_to_string(&progress_file).unwrap();
+    assert!(progress.contains("[uninstalled.gamma]"), "{}", progress);
+}